@@ -3,23 +3,34 @@
 //! Downloads map metadata, SVG files, and tile pyramids, then generates a local
 //! `maps.ron` file for the viewer application.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::Arc;
-
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
 use clap::Parser;
 use image::{ImageBuffer, RgbaImage};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use resvg::tiny_skia::Pixmap;
 use resvg::usvg::{Options, Transform, Tree};
 use ron::ser::PrettyConfig;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::fs as async_fs;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
-use tarkov_map::{Extent, ExtentBound, Extract, Label, Layer, Map, Spawn, TarkovMaps};
+use tarkov_map::{
+    BossSpawn, BossSpawnLocation, ContainerCategory, DATA_MANIFEST_FILE, DataManifest, Extent,
+    ExtentBound, Extract, ExtractItem, Hazard, HazardType, Label, Layer, Lock, LockKey,
+    LootContainer, MAPS_SCHEMA_VERSION, ManifestFile, Map, MapsFile, Position2, Position3,
+    SPAWN_CLUSTER_RADIUS, Spawn, SpawnCategory, SpawnSide, StationaryWeapon, Switch,
+    SwitchOperation, SwitchTarget, TarkovMaps, Task, TaskObjective, TaskZone, TilePyramid, Transit,
+    Translations, diff::diff,
+};
 
 /// Errors that can occur during the fetch_maps process.
 #[derive(Error, Debug)]
@@ -48,6 +59,9 @@ pub enum FetchError {
     #[error("failed to save PNG: {0}")]
     PngSave(String),
 
+    #[error("failed to optimize PNG: {0}")]
+    PngOptimize(String),
+
     #[error("image error: {0}")]
     Image(#[from] image::ImageError),
 
@@ -72,15 +86,209 @@ pub enum FetchError {
     #[error("map '{name}' has no svgPath or tilePath")]
     MissingMapSource { name: String },
 
+    #[error("SVG has no renderable group '{id}' for a layer")]
+    MissingSvgLayer { id: String },
+
     #[error("map '{name}' is missing minZoom")]
     MissingMinZoom { name: String },
 
     #[error("map '{name}' is missing maxZoom")]
     MissingMaxZoom { name: String },
+
+    #[error("tarkov.dev schema has {0} incompatible change(s)")]
+    SchemaDrift(usize),
+
+    #[error("no map group matches {0}")]
+    UnknownMaps(String),
+
+    #[error("failed to parse {path}: {message}")]
+    InvalidMapsFile { path: String, message: String },
+
+    #[error("map data has {0} problem(s)")]
+    VerifyFailed(usize),
+
+    #[error("invalid config {path}: {message}")]
+    Config { path: String, message: String },
+}
+
+impl FetchError {
+    /// Whether retrying the request may succeed: connection problems,
+    /// rate limiting and server errors.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Http(err) => !err.is_builder() && !err.is_redirect() && !err.is_decode(),
+            Self::HttpStatus { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+/// Delay before retry number `attempt` (starting at 1): exponential backoff
+/// from [`RETRY_BASE_DELAY`] up to [`RETRY_MAX_DELAY`], with up to 50% jitter
+/// so concurrent tiles don't retry in lockstep.
+fn retry_delay(attempt: u32) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    let backoff = RETRY_BASE_DELAY
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(RETRY_MAX_DELAY);
+    // RandomState is seeded differently on every call, which is random enough for jitter
+    let random = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    let jitter = (random % 1000) as f64 / 1000.0 * 0.5;
+    backoff.mul_f64(1.0 + jitter)
 }
 
 /// Result of downloading a single tile.
-type TileResult = Result<(u32, u32, Vec<u8>), FetchError>;
+type TileResult = Result<(u32, u32, Fetched), FetchError>;
+
+/// Which existing images are rendered again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Refresh {
+    /// Only images that don't exist yet.
+    Missing,
+    /// Also images whose upstream source changed (`--incremental`).
+    Changed,
+    /// Every image (`--force`).
+    All,
+}
+
+impl Refresh {
+    fn from_args(args: &Args) -> Self {
+        if args.force {
+            Self::All
+        } else if args.incremental {
+            Self::Changed
+        } else {
+            Self::Missing
+        }
+    }
+}
+
+/// Body of a conditional GET.
+enum Fetched {
+    /// The source still matches the validators of the last download.
+    NotModified,
+    Modified(Vec<u8>),
+}
+
+/// HTTP validators of a downloaded source.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// ETags and Last-Modified dates of the sources of the last run, for `--incremental`.
+///
+/// Keyed by the image a source was rendered into as well as the URL, since the
+/// same SVG is rendered once per layer.
+struct SourceCache {
+    /// Directory the cache is stored in, if the platform has a cache directory.
+    dir: Option<PathBuf>,
+    sources: Mutex<BTreeMap<String, Validators>>,
+}
+
+impl SourceCache {
+    const FILE: &str = "sources.ron";
+    /// Copy of the last `maps.json`, read when it is unchanged.
+    const MAPS_JSON_FILE: &str = "maps.json";
+
+    fn load() -> Self {
+        let dir = dirs::cache_dir().map(|dir| dir.join("tarkov-map").join("fetch_maps"));
+        let sources = dir
+            .as_ref()
+            .and_then(|dir| std::fs::read_to_string(dir.join(Self::FILE)).ok())
+            .and_then(|text| ron::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            dir,
+            sources: Mutex::new(sources),
+        }
+    }
+
+    fn save(&self) -> Result<(), FetchError> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(dir)?;
+        let sources = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+        let text = ron::ser::to_string_pretty(&*sources, PrettyConfig::default())?;
+        std::fs::write(dir.join(Self::FILE), text)?;
+        Ok(())
+    }
+
+    /// Path of a file kept next to the cache, if there is a cache directory.
+    fn file(&self, name: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(name))
+    }
+
+    /// Downloads `url` for the image `key`, recording its validators.
+    ///
+    /// With `conditional`, the request carries the validators of the last
+    /// download and may come back [`Fetched::NotModified`].
+    async fn get(
+        &self,
+        client: &reqwest::Client,
+        key: &str,
+        url: &str,
+        conditional: bool,
+        resource: &str,
+    ) -> Result<Fetched, FetchError> {
+        use reqwest::header::{
+            ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, USER_AGENT as USER_AGENT_HEADER,
+        };
+
+        let cache_key = format!("{key} {url}");
+        let mut request = client.get(url).header(USER_AGENT_HEADER, USER_AGENT);
+        if conditional {
+            let known = self
+                .sources
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&cache_key)
+                .cloned();
+            if let Some(known) = known {
+                if let Some(etag) = known.etag {
+                    request = request.header(IF_NONE_MATCH, etag);
+                }
+                if let Some(last_modified) = known.last_modified {
+                    request = request.header(IF_MODIFIED_SINCE, last_modified);
+                }
+            }
+        }
+
+        let response = request.send().await?;
+        if conditional && response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Fetched::NotModified);
+        }
+        if !response.status().is_success() {
+            return Err(FetchError::HttpStatus {
+                resource: resource.into(),
+                status: response.status().as_u16(),
+            });
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &reqwest::header::HeaderValue| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let validators = Validators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+        let bytes = response.bytes().await?.to_vec();
+        self.sources
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(cache_key, validators);
+        Ok(Fetched::Modified(bytes))
+    }
+}
 
 #[cynic::schema("tarkov")]
 pub mod schema {}
@@ -153,301 +361,1630 @@ struct MapExtractFragment {
     name: Option<String>,
     faction: Option<String>,
     position: Option<MapPositionFragment>,
+    #[cynic(flatten)]
+    outline: Vec<MapPositionFragment>,
+    top: Option<f64>,
+    bottom: Option<f64>,
+    transfer_item: Option<ContainedItemFragment>,
+    #[cynic(flatten)]
+    switches: Vec<MapSwitchNameFragment>,
 }
 
-/// Fetch Tarkov map assets from tarkov-dev
-#[derive(Parser, Debug)]
-#[command(name = "fetch_maps", version, about)]
-struct Args {
-    /// Force re-download of all assets, ignoring cached files
-    #[arg(short, long)]
-    force: bool,
-
-    /// Reduce tile map zoom level from max (0 = max quality, higher = smaller files)
-    #[arg(long, default_value = "2")]
-    tile_zoom_offset: i32,
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+struct MapHazardsQuery {
+    #[cynic(flatten)]
+    maps: Vec<MapHazardsFragment>,
 }
 
-const MAPS_JSON_URL: &str =
-    "https://raw.githubusercontent.com/the-hideout/tarkov-dev/main/src/data/maps.json";
-const TARKOV_DEV_GRAPHQL_URL: &str = "https://api.tarkov.dev/graphql";
-const USER_AGENT: &str = "tarkov-map";
-const MAPS_RON_PATH: &str = "assets/maps.ron";
-/// Physical directory for storing map images on disk
-const MAPS_DIR: &str = "assets/maps";
-/// Path prefix for maps.ron (relative to assets/ for rust-embed)
-const MAPS_PATH_PREFIX: &str = "maps";
-const TILE_DOWNLOAD_CONCURRENCY: usize = 32;
-const SVG_RENDER_SCALE: f32 = 2.0;
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct FetchedMapGroup {
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Map")]
+struct MapHazardsFragment {
     normalized_name: String,
-    maps: Vec<FetchedMap>,
+    #[cynic(flatten)]
+    hazards: Vec<MapHazardFragment>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct FetchedMap {
-    #[serde(default)]
-    alt_maps: Option<Vec<String>>,
-    projection: String,
-    #[serde(default)]
-    author: Option<String>,
-    #[serde(default)]
-    author_link: Option<String>,
-    #[serde(default)]
-    tile_size: Option<i32>,
-    #[serde(default)]
-    min_zoom: Option<i32>,
-    #[serde(default)]
-    max_zoom: Option<i32>,
-    #[serde(default)]
-    transform: Option<[f64; 4]>,
-    #[serde(default)]
-    coordinate_rotation: Option<f64>,
-    #[serde(default)]
-    bounds: Option<[[f64; 2]; 2]>,
-    #[serde(default)]
-    svg_path: Option<String>,
-    #[serde(default)]
-    tile_path: Option<String>,
-    #[serde(default)]
-    height_range: Option<[f64; 2]>,
-    #[serde(default)]
-    layers: Option<Vec<FetchedLayer>>,
-    #[serde(default)]
-    labels: Option<Vec<FetchedLabel>>,
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapHazard")]
+struct MapHazardFragment {
+    hazard_type: Option<String>,
+    name: Option<String>,
+    position: Option<MapPositionFragment>,
+    #[cynic(flatten)]
+    outline: Vec<MapPositionFragment>,
+    top: Option<f64>,
+    bottom: Option<f64>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct FetchedLayer {
-    name: String,
-    #[serde(default)]
-    svg_layer: Option<String>,
-    #[serde(default)]
-    tile_path: Option<String>,
-    #[serde(default)]
-    show: bool,
-    #[serde(default)]
-    extents: Vec<FetchedExtent>,
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+struct MapLocksQuery {
+    #[cynic(flatten)]
+    maps: Vec<MapLocksFragment>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct FetchedExtent {
-    height: [f64; 2],
-    #[serde(default)]
-    bounds: Option<Vec<FetchedExtentBound>>,
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Map")]
+struct MapLocksFragment {
+    normalized_name: String,
+    #[cynic(flatten)]
+    locks: Vec<MapLockFragment>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(from = "Vec<serde_json::Value>")]
-struct FetchedExtentBound {
-    point1: [f64; 2],
-    point2: [f64; 2],
-    name: String,
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Lock")]
+struct MapLockFragment {
+    lock_type: Option<String>,
+    key: Option<KeyItemFragment>,
+    needs_power: Option<bool>,
+    position: Option<MapPositionFragment>,
 }
 
-impl From<Vec<serde_json::Value>> for FetchedExtentBound {
-    fn from(values: Vec<serde_json::Value>) -> Self {
-        let parse_point = |idx: usize| -> [f64; 2] {
-            values
-                .get(idx)
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    [
-                        arr.first().and_then(|v| v.as_f64()).unwrap_or(0.0),
-                        arr.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0),
-                    ]
-                })
-                .unwrap_or([0.0, 0.0])
-        };
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Item")]
+struct KeyItemFragment {
+    id: cynic::Id,
+    name: Option<String>,
+    short_name: Option<String>,
+}
 
-        Self {
-            point1: parse_point(0),
-            point2: parse_point(1),
-            name: values
-                .get(2)
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_owned(),
-        }
-    }
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+struct MapLootContainersQuery {
+    #[cynic(flatten)]
+    maps: Vec<MapLootContainersFragment>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct FetchedLabel {
-    position: [f64; 2],
-    text: String,
-    #[serde(default, deserialize_with = "deserialize_rotation")]
-    rotation: Option<f64>,
-    #[serde(default)]
-    size: Option<i32>,
-    #[serde(default)]
-    top: Option<f64>,
-    #[serde(default)]
-    bottom: Option<f64>,
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Map")]
+struct MapLootContainersFragment {
+    normalized_name: String,
+    #[cynic(flatten)]
+    loot_containers: Vec<LootContainerPositionFragment>,
 }
 
-fn deserialize_rotation<'de, D>(deserializer: D) -> std::result::Result<Option<f64>, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    use serde::de::Error;
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "LootContainerPosition")]
+struct LootContainerPositionFragment {
+    loot_container: Option<LootContainerFragment>,
+    position: Option<MapPositionFragment>,
+}
 
-    match Option::<serde_json::Value>::deserialize(deserializer)? {
-        None => Ok(None),
-        Some(serde_json::Value::Number(n)) => Ok(n.as_f64()),
-        Some(serde_json::Value::String(s)) => s
-            .parse()
-            .map(Some)
-            .map_err(|_| D::Error::custom(format!("invalid rotation string: {s}"))),
-        Some(other) => Err(D::Error::custom(format!(
-            "expected number or string for rotation, got: {other:?}"
-        ))),
-    }
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "LootContainer")]
+struct LootContainerFragment {
+    name: String,
+    normalized_name: String,
 }
 
-impl From<FetchedLayer> for Layer {
-    fn from(f: FetchedLayer) -> Self {
-        Self {
-            name: f.name,
-            svg_layer: f.svg_layer,
-            tile_path: f.tile_path,
-            show: f.show,
-            extents: f.extents.into_iter().map(Into::into).collect(),
-        }
-    }
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+struct MapBossesQuery {
+    #[cynic(flatten)]
+    maps: Vec<MapBossesFragment>,
 }
 
-impl From<FetchedExtent> for Extent {
-    fn from(f: FetchedExtent) -> Self {
-        Self {
-            height: f.height,
-            bounds: f.bounds.map(|b| b.into_iter().map(Into::into).collect()),
-        }
-    }
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Map")]
+struct MapBossesFragment {
+    normalized_name: String,
+    #[cynic(flatten)]
+    bosses: Vec<BossSpawnFragment>,
+    #[cynic(flatten)]
+    spawns: Vec<SpawnZoneFragment>,
 }
 
-impl From<FetchedExtentBound> for ExtentBound {
-    fn from(f: FetchedExtentBound) -> Self {
-        Self {
-            point1: f.point1,
-            point2: f.point2,
-            name: f.name,
-        }
-    }
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "BossSpawn")]
+struct BossSpawnFragment {
+    boss: MobInfoFragment,
+    spawn_chance: f64,
+    #[cynic(flatten)]
+    spawn_locations: Vec<BossSpawnLocationFragment>,
 }
 
-impl From<FetchedLabel> for Label {
-    fn from(f: FetchedLabel) -> Self {
-        Self {
-            position: f.position,
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MobInfo")]
+struct MobInfoFragment {
+    name: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "BossSpawnLocation")]
+struct BossSpawnLocationFragment {
+    spawn_key: String,
+    name: String,
+    chance: f64,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapSpawn")]
+struct SpawnZoneFragment {
+    zone_name: Option<String>,
+    position: MapPositionFragment,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+struct MapStationaryWeaponsQuery {
+    #[cynic(flatten)]
+    maps: Vec<MapStationaryWeaponsFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Map")]
+struct MapStationaryWeaponsFragment {
+    normalized_name: String,
+    #[cynic(flatten)]
+    stationary_weapons: Vec<StationaryWeaponPositionFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "StationaryWeaponPosition")]
+struct StationaryWeaponPositionFragment {
+    stationary_weapon: Option<StationaryWeaponFragment>,
+    position: Option<MapPositionFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "StationaryWeapon")]
+struct StationaryWeaponFragment {
+    name: Option<String>,
+    short_name: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+struct MapTransitsQuery {
+    #[cynic(flatten)]
+    maps: Vec<MapTransitsFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Map")]
+struct MapTransitsFragment {
+    normalized_name: String,
+    #[cynic(flatten)]
+    transits: Vec<MapTransitFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapTransit")]
+struct MapTransitFragment {
+    description: Option<String>,
+    conditions: Option<String>,
+    map: Option<MapNameFragment>,
+    position: Option<MapPositionFragment>,
+    #[cynic(flatten)]
+    outline: Vec<MapPositionFragment>,
+    top: Option<f64>,
+    bottom: Option<f64>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+struct TasksQuery {
+    #[cynic(flatten)]
+    tasks: Vec<TaskFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Task")]
+struct TaskFragment {
+    id: Option<cynic::Id>,
+    name: String,
+    trader: TraderNameFragment,
+    #[cynic(flatten)]
+    objectives: Vec<TaskObjectiveFragment>,
+    #[cynic(flatten)]
+    task_requirements: Vec<TaskRequirementFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "TaskStatusRequirement")]
+struct TaskRequirementFragment {
+    task: TaskIdFragment,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Task")]
+struct TaskIdFragment {
+    id: Option<cynic::Id>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Trader")]
+struct TraderNameFragment {
+    name: String,
+}
+
+/// Objective types that can have zones on a map.
+#[derive(cynic::InlineFragments, Debug)]
+#[cynic(graphql_type = "TaskObjective")]
+enum TaskObjectiveFragment {
+    TaskObjectiveBasic(TaskObjectiveBasicFragment),
+    TaskObjectiveMark(TaskObjectiveMarkFragment),
+    TaskObjectiveQuestItem(TaskObjectiveQuestItemFragment),
+    TaskObjectiveShoot(TaskObjectiveShootFragment),
+    TaskObjectiveUseItem(TaskObjectiveUseItemFragment),
+    #[cynic(fallback)]
+    Other,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "TaskObjectiveBasic")]
+struct TaskObjectiveBasicFragment {
+    id: Option<cynic::Id>,
+    description: String,
+    optional: bool,
+    #[cynic(flatten)]
+    zones: Vec<TaskZoneFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "TaskObjectiveMark")]
+struct TaskObjectiveMarkFragment {
+    id: Option<cynic::Id>,
+    description: String,
+    optional: bool,
+    #[cynic(flatten)]
+    zones: Vec<TaskZoneFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "TaskObjectiveQuestItem")]
+struct TaskObjectiveQuestItemFragment {
+    id: Option<cynic::Id>,
+    description: String,
+    optional: bool,
+    #[cynic(flatten)]
+    zones: Vec<TaskZoneFragment>,
+    #[cynic(flatten)]
+    possible_locations: Vec<MapWithPositionFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "TaskObjectiveShoot")]
+struct TaskObjectiveShootFragment {
+    id: Option<cynic::Id>,
+    description: String,
+    optional: bool,
+    #[cynic(flatten)]
+    zones: Vec<TaskZoneFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "TaskObjectiveUseItem")]
+struct TaskObjectiveUseItemFragment {
+    id: Option<cynic::Id>,
+    description: String,
+    optional: bool,
+    #[cynic(flatten)]
+    zones: Vec<TaskZoneFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "TaskZone")]
+struct TaskZoneFragment {
+    map: Option<MapNameFragment>,
+    position: Option<MapPositionFragment>,
+    #[cynic(flatten)]
+    outline: Vec<MapPositionFragment>,
+    top: Option<f64>,
+    bottom: Option<f64>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapWithPosition")]
+struct MapWithPositionFragment {
+    map: Option<MapNameFragment>,
+    #[cynic(flatten)]
+    positions: Vec<MapPositionFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+struct MapSwitchesQuery {
+    #[cynic(flatten)]
+    maps: Vec<MapSwitchesFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Map")]
+struct MapSwitchesFragment {
+    normalized_name: String,
+    #[cynic(flatten)]
+    switches: Vec<MapSwitchFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapSwitch")]
+struct MapSwitchFragment {
+    name: Option<String>,
+    switch_type: Option<String>,
+    position: Option<MapPositionFragment>,
+    activated_by: Option<MapSwitchNameFragment>,
+    #[cynic(flatten)]
+    activates: Vec<MapSwitchOperationFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapSwitchOperation")]
+struct MapSwitchOperationFragment {
+    operation: Option<String>,
+    target: Option<MapSwitchTargetFragment>,
+}
+
+#[derive(cynic::InlineFragments, Debug)]
+#[cynic(graphql_type = "MapSwitchTarget")]
+enum MapSwitchTargetFragment {
+    MapSwitch(MapSwitchNameFragment),
+    MapExtract(MapExtractNameFragment),
+    #[cynic(fallback)]
+    Unknown,
+}
+
+/// Languages tarkov.dev translates names into.
+#[derive(cynic::Enum, clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[cynic(graphql_type = "LanguageCode", rename_all = "lowercase")]
+enum LanguageCode {
+    Cs,
+    De,
+    /// The language the data is written in, so never asked for with `--lang`.
+    #[value(skip)]
+    En,
+    Es,
+    Fr,
+    Hu,
+    It,
+    Ja,
+    Ko,
+    Pl,
+    Pt,
+    Ro,
+    Ru,
+    Sk,
+    Tr,
+    Zh,
+}
+
+impl LanguageCode {
+    /// Languages other than English, which the data is written in.
+    const TRANSLATED: [LanguageCode; 15] = [
+        LanguageCode::Cs,
+        LanguageCode::De,
+        LanguageCode::Es,
+        LanguageCode::Fr,
+        LanguageCode::Hu,
+        LanguageCode::It,
+        LanguageCode::Ja,
+        LanguageCode::Ko,
+        LanguageCode::Pl,
+        LanguageCode::Pt,
+        LanguageCode::Ro,
+        LanguageCode::Ru,
+        LanguageCode::Sk,
+        LanguageCode::Tr,
+        LanguageCode::Zh,
+    ];
+
+    fn code(self) -> &'static str {
+        match self {
+            LanguageCode::Cs => "cs",
+            LanguageCode::De => "de",
+            LanguageCode::En => "en",
+            LanguageCode::Es => "es",
+            LanguageCode::Fr => "fr",
+            LanguageCode::Hu => "hu",
+            LanguageCode::It => "it",
+            LanguageCode::Ja => "ja",
+            LanguageCode::Ko => "ko",
+            LanguageCode::Pl => "pl",
+            LanguageCode::Pt => "pt",
+            LanguageCode::Ro => "ro",
+            LanguageCode::Ru => "ru",
+            LanguageCode::Sk => "sk",
+            LanguageCode::Tr => "tr",
+            LanguageCode::Zh => "zh",
+        }
+    }
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+struct LanguageVariables {
+    lang: LanguageCode,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "LanguageVariables")]
+struct MapTranslationsQuery {
+    #[arguments(lang: $lang)]
+    #[cynic(flatten)]
+    maps: Vec<MapTranslationFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Map")]
+struct MapTranslationFragment {
+    normalized_name: String,
+    name: String,
+    #[cynic(flatten)]
+    extracts: Vec<ExtractTranslationFragment>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapExtract")]
+struct ExtractTranslationFragment {
+    id: cynic::Id,
+    name: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "ContainedItem")]
+struct ContainedItemFragment {
+    item: ItemNameFragment,
+    count: f64,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Item")]
+struct ItemNameFragment {
+    name: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapSwitch")]
+struct MapSwitchNameFragment {
+    name: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "MapExtract")]
+struct MapExtractNameFragment {
+    name: Option<String>,
+}
+
+/// Fetch Tarkov map assets from tarkov-dev
+#[derive(Parser, Debug)]
+#[command(name = "fetch_maps", version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Config file with defaults for the options below [default:
+    /// fetch_maps.toml in the working directory, if it exists]
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Force re-download of all assets, ignoring cached files
+    #[arg(short, long)]
+    force: bool,
+
+    /// Re-render existing images only when their upstream SVG or tiles changed,
+    /// checked with conditional requests against the last run's ETags
+    #[arg(long, conflicts_with = "force")]
+    incremental: bool,
+
+    /// How often a failed tile download is retried, with exponential backoff
+    #[arg(long, default_value = "5")]
+    max_retries: u32,
+
+    /// Reduce tile map zoom level from max (0 = max quality, higher = smaller
+    /// files) [default: 2]
+    #[arg(long)]
+    tile_zoom_offset: Option<i32>,
+
+    /// Skip checking the live GraphQL schema for fields used by the queries
+    #[arg(long)]
+    skip_schema_check: bool,
+
+    /// Directory to write maps.ron to [default: the repository's assets folder]
+    #[arg(long, visible_alias = "out-dir")]
+    output_dir: Option<PathBuf>,
+
+    /// Directory to write map images to [default: <OUTPUT_DIR>/maps]
+    #[arg(long)]
+    assets_dir: Option<PathBuf>,
+
+    /// Also write the tile pyramid of tile maps, with a tiles.ron manifest,
+    /// for viewers that stream only the visible tiles
+    #[arg(long)]
+    keep_tiles: bool,
+
+    /// Format of the generated map images. Both formats are always encoded
+    /// losslessly; there is no lossy WebP or AVIF output [default: png]
+    #[arg(long, value_enum)]
+    image_format: Option<ImageFormat>,
+
+    /// WebP near-lossless level (1-100). The encoding stays lossless, but below
+    /// 100 the low bits of each color are dropped first for smaller files
+    /// [default: 100]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    quality: Option<u8>,
+
+    /// Losslessly optimize generated PNGs with oxipng at this level (0-6, higher = slower)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=6))]
+    optimize: Option<u8>,
+
+    /// Only process these map groups, by normalized name or alt_maps key (e.g.
+    /// customs,streets-of-tarkov). Other maps are kept from the existing maps.ron
+    #[arg(long, value_delimiter = ',')]
+    maps: Vec<String>,
+
+    /// Only fetch names in these languages (e.g. ru,de,fr) [default: every
+    /// language tarkov.dev translates into]
+    #[arg(long, value_enum, value_delimiter = ',')]
+    lang: Vec<LanguageCode>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Check the existing maps.ron and its images without fetching anything,
+    /// exiting with an error if any problems are found
+    Verify,
+}
+
+/// GitHub repository holding tarkov-dev's `maps.json`.
+const TARKOV_DEV_REPO: &str = "the-hideout/tarkov-dev";
+/// Branch of [`TARKOV_DEV_REPO`] that `maps.json` is read from.
+const TARKOV_DEV_BRANCH: &str = "main";
+const TARKOV_DEV_GRAPHQL_URL: &str = "https://api.tarkov.dev/graphql";
+/// Config file read from the working directory when `--config` isn't given.
+const CONFIG_FILE: &str = "fetch_maps.toml";
+const DEFAULT_TILE_ZOOM_OFFSET: i32 = 2;
+const DEFAULT_QUALITY: u8 = 100;
+const USER_AGENT: &str = "tarkov-map";
+/// Default output directory, relative to the repository root (embedded by rust-embed)
+const DEFAULT_OUTPUT_DIR: &str = "assets";
+const MAPS_RON_FILE: &str = "maps.ron";
+/// Default image directory name inside the output directory
+const MAPS_DIR_NAME: &str = "maps";
+/// Directory under the map images that `--keep-tiles` writes tile pyramids to.
+const TILES_DIR_NAME: &str = "tiles";
+const TILE_MANIFEST_FILE: &str = "tiles.ron";
+const TILE_DOWNLOAD_CONCURRENCY: usize = 32;
+/// Length of the abbreviated tarkov-dev commit in data versions.
+const SHORT_COMMIT_LEN: usize = 7;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+/// Relative aspect ratio difference `verify` allows between an image and its
+/// recorded size, for rounding when rendering at a different scale.
+const IMAGE_RATIO_TOLERANCE: f32 = 0.01;
+/// GraphQL fields selected by the query fragments, checked against the live schema
+const QUERIED_FIELDS: &[(&str, &[&str])] = &[
+    ("Query", &["maps", "tasks"]),
+    (
+        "Map",
+        &[
+            "normalizedName",
+            "name",
+            "spawns",
+            "extracts",
+            "hazards",
+            "locks",
+            "lootContainers",
+            "bosses",
+            "stationaryWeapons",
+            "switches",
+            "transits",
+        ],
+    ),
+    ("MapSpawn", &["zoneName", "position", "sides", "categories"]),
+    ("MapPosition", &["x", "y", "z"]),
+    (
+        "MapExtract",
+        &[
+            "id",
+            "name",
+            "faction",
+            "position",
+            "outline",
+            "top",
+            "bottom",
+            "transferItem",
+            "switches",
+        ],
+    ),
+    (
+        "MapHazard",
+        &["hazardType", "name", "position", "outline", "top", "bottom"],
+    ),
+    ("Lock", &["lockType", "key", "needsPower", "position"]),
+    ("LootContainerPosition", &["lootContainer", "position"]),
+    ("LootContainer", &["name", "normalizedName"]),
+    ("BossSpawn", &["boss", "spawnChance", "spawnLocations"]),
+    ("MobInfo", &["name"]),
+    ("BossSpawnLocation", &["spawnKey", "name", "chance"]),
+    (
+        "StationaryWeaponPosition",
+        &["stationaryWeapon", "position"],
+    ),
+    ("StationaryWeapon", &["name", "shortName"]),
+    ("ContainedItem", &["item", "count"]),
+    ("Item", &["id", "name", "shortName"]),
+    (
+        "MapSwitch",
+        &["name", "switchType", "position", "activatedBy", "activates"],
+    ),
+    ("MapSwitchOperation", &["operation", "target"]),
+    (
+        "Task",
+        &["id", "name", "trader", "objectives", "taskRequirements"],
+    ),
+    ("TaskStatusRequirement", &["task"]),
+    ("Trader", &["name"]),
+    (
+        "TaskObjectiveBasic",
+        &["id", "description", "optional", "zones"],
+    ),
+    (
+        "TaskObjectiveMark",
+        &["id", "description", "optional", "zones"],
+    ),
+    (
+        "TaskObjectiveQuestItem",
+        &[
+            "id",
+            "description",
+            "optional",
+            "zones",
+            "possibleLocations",
+        ],
+    ),
+    (
+        "TaskObjectiveShoot",
+        &["id", "description", "optional", "zones"],
+    ),
+    (
+        "TaskObjectiveUseItem",
+        &["id", "description", "optional", "zones"],
+    ),
+    ("TaskZone", &["map", "position", "outline", "top", "bottom"]),
+    ("MapWithPosition", &["map", "positions"]),
+    (
+        "MapTransit",
+        &[
+            "description",
+            "conditions",
+            "map",
+            "position",
+            "outline",
+            "top",
+            "bottom",
+        ],
+    ),
+];
+/// tarkov.dev container types of hidden stashes, listed among the loot containers.
+const STASH_CONTAINER_TYPES: &[&str] = &["buried-barrel-cache", "ground-cache"];
+/// Containers of the same type closer than this, in meters, are one container.
+const LOOT_CONTAINER_DUPLICATE_DISTANCE: f64 = 0.5;
+const SVG_RENDER_SCALE: f32 = 2.0;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FetchedMapGroup {
+    normalized_name: String,
+    maps: Vec<FetchedMap>,
+}
+
+impl FetchedMapGroup {
+    /// Returns `true` if `name` is the group's normalized name or one of its alt_maps keys.
+    fn matches(&self, name: &str) -> bool {
+        self.normalized_name == name
+            || self
+                .maps
+                .iter()
+                .flat_map(|map| map.alt_maps.iter().flatten())
+                .any(|key| key == name)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FetchedMap {
+    #[serde(default)]
+    alt_maps: Option<Vec<String>>,
+    projection: String,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    author_link: Option<String>,
+    #[serde(default)]
+    tile_size: Option<i32>,
+    #[serde(default)]
+    min_zoom: Option<i32>,
+    #[serde(default)]
+    max_zoom: Option<i32>,
+    #[serde(default)]
+    transform: Option<[f64; 4]>,
+    #[serde(default)]
+    coordinate_rotation: Option<f64>,
+    #[serde(default)]
+    bounds: Option<[[f64; 2]; 2]>,
+    #[serde(default)]
+    svg_path: Option<String>,
+    #[serde(default)]
+    tile_path: Option<String>,
+    #[serde(default)]
+    height_range: Option<[f64; 2]>,
+    #[serde(default)]
+    layers: Option<Vec<FetchedLayer>>,
+    #[serde(default)]
+    labels: Option<Vec<FetchedLabel>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FetchedLayer {
+    name: String,
+    #[serde(default)]
+    svg_layer: Option<String>,
+    #[serde(default)]
+    tile_path: Option<String>,
+    #[serde(default)]
+    show: bool,
+    #[serde(default)]
+    extents: Vec<FetchedExtent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FetchedExtent {
+    height: [f64; 2],
+    #[serde(default)]
+    bounds: Option<Vec<FetchedExtentBound>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(from = "Vec<serde_json::Value>")]
+struct FetchedExtentBound {
+    point1: [f64; 2],
+    point2: [f64; 2],
+    name: String,
+}
+
+impl From<Vec<serde_json::Value>> for FetchedExtentBound {
+    fn from(values: Vec<serde_json::Value>) -> Self {
+        let parse_point = |idx: usize| -> [f64; 2] {
+            values
+                .get(idx)
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    [
+                        arr.first().and_then(|v| v.as_f64()).unwrap_or(0.0),
+                        arr.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0),
+                    ]
+                })
+                .unwrap_or([0.0, 0.0])
+        };
+
+        Self {
+            point1: parse_point(0),
+            point2: parse_point(1),
+            name: values
+                .get(2)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FetchedLabel {
+    position: [f64; 2],
+    text: String,
+    #[serde(default, deserialize_with = "deserialize_rotation")]
+    rotation: Option<f64>,
+    #[serde(default)]
+    size: Option<i32>,
+    #[serde(default)]
+    top: Option<f64>,
+    #[serde(default)]
+    bottom: Option<f64>,
+}
+
+fn deserialize_rotation<'de, D>(deserializer: D) -> std::result::Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(serde_json::Value::Number(n)) => Ok(n.as_f64()),
+        Some(serde_json::Value::String(s)) => s
+            .parse()
+            .map(Some)
+            .map_err(|_| D::Error::custom(format!("invalid rotation string: {s}"))),
+        Some(other) => Err(D::Error::custom(format!(
+            "expected number or string for rotation, got: {other:?}"
+        ))),
+    }
+}
+
+impl From<FetchedLayer> for Layer {
+    fn from(f: FetchedLayer) -> Self {
+        Self {
+            name: f.name,
+            svg_layer: f.svg_layer,
+            tile_path: f.tile_path,
+            image_path: None,
+            tiles: None,
+            show: f.show,
+            extents: f.extents.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<FetchedExtent> for Extent {
+    fn from(f: FetchedExtent) -> Self {
+        Self {
+            height: f.height,
+            bounds: f.bounds.map(|b| b.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<FetchedExtentBound> for ExtentBound {
+    fn from(f: FetchedExtentBound) -> Self {
+        Self {
+            point1: f.point1,
+            point2: f.point2,
+            name: f.name,
+        }
+    }
+}
+
+impl From<FetchedLabel> for Label {
+    fn from(f: FetchedLabel) -> Self {
+        Self {
+            position: f.position.into(),
             text: f.text,
             rotation: f.rotation,
             size: f.size,
             top: f.top,
             bottom: f.bottom,
+            translations: None,
+        }
+    }
+}
+
+/// HTTP client sending queries to a GraphQL endpoint.
+struct GraphQlClient {
+    http: reqwest::Client,
+    url: String,
+}
+
+async fn fetch_graphql<Q, V, T>(
+    client: &GraphQlClient,
+    operation: cynic::Operation<Q, V>,
+) -> Result<T, FetchError>
+where
+    Q: serde::de::DeserializeOwned,
+    V: serde::Serialize,
+    T: From<Q>,
+{
+    let response: cynic::GraphQlResponse<Q> = client
+        .http
+        .post(&client.url)
+        .header(reqwest::header::USER_AGENT, USER_AGENT)
+        .json(&operation)
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if let Some(errors) = response.errors.filter(|e| !e.is_empty()) {
+        let messages: Vec<_> = errors.into_iter().map(|e| e.message).collect();
+        return Err(FetchError::GraphQL(messages.join("; ")));
+    }
+
+    response
+        .data
+        .map(Into::into)
+        .ok_or(FetchError::GraphQLMissingData)
+}
+
+#[derive(Debug, Deserialize)]
+struct IntrospectedType {
+    fields: Option<Vec<IntrospectedField>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IntrospectedField {
+    name: String,
+}
+
+/// Checks that every field in [`QUERIED_FIELDS`] still exists in the live schema.
+///
+/// Returns one message per missing type or field, suggesting likely renames.
+async fn check_schema(client: &GraphQlClient) -> Result<Vec<String>, FetchError> {
+    let selections: Vec<String> = QUERIED_FIELDS
+        .iter()
+        .enumerate()
+        .map(|(idx, (ty, _))| format!("t{idx}: __type(name: \"{ty}\") {{ fields {{ name }} }}"))
+        .collect();
+    let query = format!("{{ {} }}", selections.join(" "));
+
+    let response: serde_json::Value = client
+        .http
+        .post(&client.url)
+        .header(reqwest::header::USER_AGENT, USER_AGENT)
+        .json(&serde_json::json!({ "query": query }))
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if let Some(errors) = response.get("errors").and_then(|e| e.as_array())
+        && !errors.is_empty()
+    {
+        let messages: Vec<_> = errors
+            .iter()
+            .filter_map(|e| e.get("message")?.as_str())
+            .collect();
+        return Err(FetchError::GraphQL(messages.join("; ")));
+    }
+    let data = response.get("data").ok_or(FetchError::GraphQLMissingData)?;
+
+    let mut problems = Vec::new();
+    for (idx, (ty, fields)) in QUERIED_FIELDS.iter().enumerate() {
+        let introspected: Option<IntrospectedType> =
+            serde_json::from_value(data[format!("t{idx}")].clone())?;
+        let Some(live_fields) = introspected.and_then(|t| t.fields) else {
+            problems.push(format!("  type `{ty}` no longer exists"));
+            continue;
+        };
+        let live_names: Vec<&str> = live_fields.iter().map(|f| f.name.as_str()).collect();
+
+        for field in fields.iter().filter(|f| !live_names.contains(f)) {
+            let candidates: Vec<&str> = live_names
+                .iter()
+                .copied()
+                .filter(|name| {
+                    let (name, field) = (name.to_lowercase(), field.to_lowercase());
+                    name.contains(&field) || field.contains(&name)
+                })
+                .collect();
+            if candidates.is_empty() {
+                problems.push(format!("  field `{ty}.{field}` is missing"));
+            } else {
+                problems.push(format!(
+                    "  field `{ty}.{field}` is missing (renamed to {}?)",
+                    candidates.join(", ")
+                ));
+            }
         }
     }
+
+    Ok(problems)
+}
+
+async fn fetch_map_names(client: &GraphQlClient) -> Result<HashMap<String, String>, FetchError> {
+    use cynic::QueryBuilder;
+
+    let data: MapNamesQuery = fetch_graphql(client, MapNamesQuery::build(())).await?;
+
+    Ok(data
+        .maps
+        .into_iter()
+        .map(|m| (m.normalized_name, m.name))
+        .collect())
+}
+
+async fn fetch_map_spawns(
+    client: &GraphQlClient,
+) -> Result<HashMap<String, Vec<Spawn>>, FetchError> {
+    use cynic::QueryBuilder;
+
+    let data: MapSpawnsQuery = fetch_graphql(client, MapSpawnsQuery::build(())).await?;
+
+    Ok(data
+        .maps
+        .into_iter()
+        .map(|map| {
+            let spawns = map
+                .spawns
+                .into_iter()
+                .map(|s| Spawn {
+                    position: Position3::new(s.position.x, s.position.y, s.position.z),
+                    sides: s.sides.into_iter().map(SpawnSide::from).collect(),
+                    categories: s.categories.into_iter().map(SpawnCategory::from).collect(),
+                })
+                .collect();
+            (map.normalized_name, spawns)
+        })
+        .collect())
+}
+
+async fn fetch_map_extracts(
+    client: &GraphQlClient,
+) -> Result<HashMap<String, Vec<Extract>>, FetchError> {
+    use cynic::QueryBuilder;
+
+    let data: MapExtractsQuery = fetch_graphql(client, MapExtractsQuery::build(())).await?;
+
+    Ok(data
+        .maps
+        .into_iter()
+        .map(|map| {
+            let extracts = map
+                .extracts
+                .into_iter()
+                .filter_map(|e| {
+                    let name = e.name?;
+                    // tarkov.dev has no co-op field, but names co-op extracts as such
+                    let coop = name.to_lowercase().contains("co-op");
+                    Some(Extract {
+                        name,
+                        faction: e.faction?.into(),
+                        position: e.position.map(|p| Position3::new(p.x, p.y, p.z)),
+                        outline: Some(
+                            e.outline
+                                .into_iter()
+                                .map(|p| Position2::new(p.x, p.z))
+                                .collect(),
+                        )
+                        .filter(|outline: &Vec<Position2>| outline.len() >= 3),
+                        top: e.top,
+                        bottom: e.bottom,
+                        required_item: e.transfer_item.and_then(|t| {
+                            Some(ExtractItem {
+                                name: t.item.name?,
+                                count: t.count.round() as u32,
+                            })
+                        }),
+                        switches: Some(e.switches.into_iter().filter_map(|s| s.name).collect())
+                            .filter(|names: &Vec<String>| !names.is_empty()),
+                        coop,
+                        translations: None,
+                    })
+                })
+                .collect();
+            (map.normalized_name, extracts)
+        })
+        .collect())
 }
 
-async fn fetch_graphql<Q, T>(
-    client: &reqwest::Client,
-    operation: cynic::Operation<Q, ()>,
-) -> Result<T, FetchError>
-where
-    Q: serde::de::DeserializeOwned,
-    T: From<Q>,
-{
-    let response: cynic::GraphQlResponse<Q> = client
-        .post(TARKOV_DEV_GRAPHQL_URL)
-        .header(reqwest::header::USER_AGENT, USER_AGENT)
-        .json(&operation)
-        .send()
-        .await?
-        .json()
-        .await?;
+async fn fetch_map_hazards(
+    client: &GraphQlClient,
+) -> Result<HashMap<String, Vec<Hazard>>, FetchError> {
+    use cynic::QueryBuilder;
 
-    if let Some(errors) = response.errors.filter(|e| !e.is_empty()) {
-        let messages: Vec<_> = errors.into_iter().map(|e| e.message).collect();
-        return Err(FetchError::GraphQL(messages.join("; ")));
-    }
+    let data: MapHazardsQuery = fetch_graphql(client, MapHazardsQuery::build(())).await?;
 
-    response
-        .data
-        .map(Into::into)
-        .ok_or(FetchError::GraphQLMissingData)
+    Ok(data
+        .maps
+        .into_iter()
+        .map(|map| {
+            let hazards = map
+                .hazards
+                .into_iter()
+                .filter_map(|h| {
+                    let hazard_type = HazardType::from(h.hazard_type?);
+                    Some(Hazard {
+                        name: h.name.unwrap_or_else(|| hazard_type.to_string()),
+                        hazard_type,
+                        position: h.position.map(|p| Position3::new(p.x, p.y, p.z)),
+                        outline: Some(
+                            h.outline
+                                .into_iter()
+                                .map(|p| Position2::new(p.x, p.z))
+                                .collect(),
+                        )
+                        .filter(|outline: &Vec<Position2>| outline.len() >= 3),
+                        top: h.top,
+                        bottom: h.bottom,
+                    })
+                })
+                .filter(|h| h.position.is_some() || h.outline.is_some())
+                .collect();
+            (map.normalized_name, hazards)
+        })
+        .collect())
 }
 
-async fn fetch_map_names(client: &reqwest::Client) -> Result<HashMap<String, String>, FetchError> {
+async fn fetch_map_locks(client: &GraphQlClient) -> Result<HashMap<String, Vec<Lock>>, FetchError> {
     use cynic::QueryBuilder;
 
-    let data: MapNamesQuery = fetch_graphql(client, MapNamesQuery::build(())).await?;
+    let data: MapLocksQuery = fetch_graphql(client, MapLocksQuery::build(())).await?;
 
     Ok(data
         .maps
         .into_iter()
-        .map(|m| (m.normalized_name, m.name))
+        .map(|map| {
+            let locks = map
+                .locks
+                .into_iter()
+                .filter_map(|l| {
+                    let p = l.position?;
+                    Some(Lock {
+                        lock_type: l.lock_type?.into(),
+                        key: l.key.and_then(|key| {
+                            // Some keys only come with a short name
+                            let name = key.name.or_else(|| key.short_name.clone())?;
+                            Some(LockKey {
+                                id: key.id.into_inner(),
+                                name,
+                                short_name: key.short_name,
+                            })
+                        }),
+                        position: Position3::new(p.x, p.y, p.z),
+                        needs_power: l.needs_power.unwrap_or(false),
+                    })
+                })
+                .collect();
+            (map.normalized_name, locks)
+        })
         .collect())
 }
 
-async fn fetch_map_spawns(
-    client: &reqwest::Client,
-) -> Result<HashMap<String, Vec<Spawn>>, FetchError> {
+async fn fetch_map_loot_containers(
+    client: &GraphQlClient,
+) -> Result<HashMap<String, Vec<LootContainer>>, FetchError> {
     use cynic::QueryBuilder;
 
-    let data: MapSpawnsQuery = fetch_graphql(client, MapSpawnsQuery::build(())).await?;
+    let data: MapLootContainersQuery =
+        fetch_graphql(client, MapLootContainersQuery::build(())).await?;
 
     Ok(data
         .maps
         .into_iter()
         .map(|map| {
-            let spawns = map
-                .spawns
+            let containers = map
+                .loot_containers
                 .into_iter()
-                .filter(|s| {
-                    s.sides.iter().any(|side| side == "pmc" || side == "all")
-                        && s.categories.iter().any(|cat| cat == "player")
+                .filter_map(|c| {
+                    let container = c.loot_container?;
+                    let p = c.position?;
+                    let category = if STASH_CONTAINER_TYPES.contains(&&*container.normalized_name) {
+                        ContainerCategory::Stash
+                    } else {
+                        ContainerCategory::Loot
+                    };
+                    Some(LootContainer {
+                        container_type: container.normalized_name,
+                        name: container.name,
+                        position: Position3::new(p.x, p.y, p.z),
+                        category,
+                    })
                 })
-                .map(|s| Spawn {
-                    position: [s.position.x, s.position.y, s.position.z],
-                    sides: s.sides,
-                    categories: s.categories,
+                .collect();
+            (map.normalized_name, bucket_loot_containers(containers))
+        })
+        .collect())
+}
+
+/// Groups loot containers by type and drops duplicates of the same type
+/// within [`LOOT_CONTAINER_DUPLICATE_DISTANCE`] of each other.
+///
+/// Sorting keeps `maps.ron` stable between runs, since the API returns
+/// containers in no particular order.
+fn bucket_loot_containers(mut containers: Vec<LootContainer>) -> Vec<LootContainer> {
+    containers.sort_by(|a, b| {
+        a.container_type
+            .cmp(&b.container_type)
+            .then(a.position.x.total_cmp(&b.position.x))
+            .then(a.position.z.total_cmp(&b.position.z))
+            .then(a.position.y.total_cmp(&b.position.y))
+    });
+
+    let mut bucketed: Vec<LootContainer> = Vec::with_capacity(containers.len());
+    for container in containers {
+        let duplicate = bucketed
+            .iter()
+            .rev()
+            .take_while(|kept| kept.container_type == container.container_type)
+            .any(|kept| {
+                kept.position.distance(container.position) < LOOT_CONTAINER_DUPLICATE_DISTANCE
+            });
+        if !duplicate {
+            bucketed.push(container);
+        }
+    }
+    bucketed
+}
+
+/// Fetches each map's bosses, placed at the spawns whose zone matches a boss spawn location.
+async fn fetch_map_bosses(
+    client: &GraphQlClient,
+) -> Result<HashMap<String, Vec<BossSpawn>>, FetchError> {
+    use cynic::QueryBuilder;
+
+    let data: MapBossesQuery = fetch_graphql(client, MapBossesQuery::build(())).await?;
+
+    Ok(data
+        .maps
+        .into_iter()
+        .map(|map| {
+            let spawns = &map.spawns;
+            let bosses = map
+                .bosses
+                .into_iter()
+                .filter_map(|b| {
+                    let mut positions: Vec<Position3> = Vec::new();
+                    for location in &b.spawn_locations {
+                        let zone = spawns
+                            .iter()
+                            .filter(|s| s.zone_name.as_deref() == Some(&location.spawn_key))
+                            .map(|s| Position3::new(s.position.x, s.position.y, s.position.z));
+                        for position in zone {
+                            if !positions.contains(&position) {
+                                positions.push(position);
+                            }
+                        }
+                    }
+                    // Bosses without a known spawn zone can't be placed
+                    (!positions.is_empty()).then(|| BossSpawn {
+                        name: b.boss.name,
+                        spawn_chance: b.spawn_chance,
+                        positions,
+                        locations: b
+                            .spawn_locations
+                            .into_iter()
+                            .map(|location| BossSpawnLocation {
+                                name: location.name,
+                                chance: location.chance,
+                            })
+                            .collect(),
+                    })
                 })
                 .collect();
-            (map.normalized_name, spawns)
+            (map.normalized_name, bosses)
         })
         .collect())
 }
 
-async fn fetch_map_extracts(
-    client: &reqwest::Client,
-) -> Result<HashMap<String, Vec<Extract>>, FetchError> {
+async fn fetch_map_stationary_weapons(
+    client: &GraphQlClient,
+) -> Result<HashMap<String, Vec<StationaryWeapon>>, FetchError> {
     use cynic::QueryBuilder;
 
-    let data: MapExtractsQuery = fetch_graphql(client, MapExtractsQuery::build(())).await?;
+    let data: MapStationaryWeaponsQuery =
+        fetch_graphql(client, MapStationaryWeaponsQuery::build(())).await?;
+
+    Ok(data
+        .maps
+        .into_iter()
+        .map(|map| {
+            let weapons = map
+                .stationary_weapons
+                .into_iter()
+                .filter_map(|w| {
+                    let weapon = w.stationary_weapon?;
+                    let p = w.position?;
+                    Some(StationaryWeapon {
+                        name: weapon.name?,
+                        short_name: weapon.short_name,
+                        position: Position3::new(p.x, p.y, p.z),
+                    })
+                })
+                .collect();
+            (map.normalized_name, weapons)
+        })
+        .collect())
+}
+
+async fn fetch_map_transits(
+    client: &GraphQlClient,
+) -> Result<HashMap<String, Vec<Transit>>, FetchError> {
+    use cynic::QueryBuilder;
+
+    let data: MapTransitsQuery = fetch_graphql(client, MapTransitsQuery::build(())).await?;
 
     Ok(data
+        .maps
+        .into_iter()
+        .map(|map| {
+            let transits = map
+                .transits
+                .into_iter()
+                .filter_map(|t| {
+                    Some(Transit {
+                        destination: t.map?.normalized_name,
+                        description: t.description,
+                        conditions: t.conditions.filter(|c| !c.is_empty()),
+                        position: t.position.map(|p| Position3::new(p.x, p.y, p.z)),
+                        outline: Some(
+                            t.outline
+                                .into_iter()
+                                .map(|p| Position2::new(p.x, p.z))
+                                .collect(),
+                        )
+                        .filter(|outline: &Vec<Position2>| outline.len() >= 3),
+                        top: t.top,
+                        bottom: t.bottom,
+                    })
+                })
+                .filter(|t| t.position.is_some() || t.outline.is_some())
+                .collect();
+            (map.normalized_name, transits)
+        })
+        .collect())
+}
+
+/// Fetches tasks with objectives that have zones or item positions, grouped by
+/// map. A task spanning several maps is listed on each with its objectives there.
+async fn fetch_map_tasks(client: &GraphQlClient) -> Result<HashMap<String, Vec<Task>>, FetchError> {
+    use cynic::QueryBuilder;
+
+    let data: TasksQuery = fetch_graphql(client, TasksQuery::build(())).await?;
+    let prerequisites = task_prerequisites(&data.tasks);
+
+    let mut map_tasks: HashMap<String, Vec<Task>> = HashMap::new();
+    for task in data.tasks {
+        let Some(id) = task.id else {
+            continue;
+        };
+        let id = id.into_inner();
+        for objective in task.objectives {
+            let (objective_id, description, optional, zones, locations) = match objective {
+                TaskObjectiveFragment::TaskObjectiveBasic(o) => {
+                    (o.id, o.description, o.optional, o.zones, Vec::new())
+                }
+                TaskObjectiveFragment::TaskObjectiveMark(o) => {
+                    (o.id, o.description, o.optional, o.zones, Vec::new())
+                }
+                TaskObjectiveFragment::TaskObjectiveQuestItem(o) => (
+                    o.id,
+                    o.description,
+                    o.optional,
+                    o.zones,
+                    o.possible_locations,
+                ),
+                TaskObjectiveFragment::TaskObjectiveShoot(o) => {
+                    (o.id, o.description, o.optional, o.zones, Vec::new())
+                }
+                TaskObjectiveFragment::TaskObjectiveUseItem(o) => {
+                    (o.id, o.description, o.optional, o.zones, Vec::new())
+                }
+                TaskObjectiveFragment::Other => continue,
+            };
+
+            // Map -> zones of this objective on it
+            let mut zones_by_map: HashMap<String, Vec<TaskZone>> = HashMap::new();
+            for zone in zones {
+                let Some(map) = zone.map else {
+                    continue;
+                };
+                let zone = TaskZone {
+                    position: zone.position.map(|p| Position3::new(p.x, p.y, p.z)),
+                    outline: Some(
+                        zone.outline
+                            .into_iter()
+                            .map(|p| Position2::new(p.x, p.z))
+                            .collect(),
+                    )
+                    .filter(|outline: &Vec<Position2>| outline.len() >= 3),
+                    top: zone.top,
+                    bottom: zone.bottom,
+                };
+                if zone.position.is_some() || zone.outline.is_some() {
+                    zones_by_map
+                        .entry(map.normalized_name)
+                        .or_default()
+                        .push(zone);
+                }
+            }
+            for location in locations {
+                let Some(map) = location.map else {
+                    continue;
+                };
+                zones_by_map.entry(map.normalized_name).or_default().extend(
+                    location.positions.into_iter().map(|p| TaskZone {
+                        position: Some(Position3::new(p.x, p.y, p.z)),
+                        outline: None,
+                        top: None,
+                        bottom: None,
+                    }),
+                );
+            }
+
+            for (map, zones) in zones_by_map {
+                let tasks = map_tasks.entry(map).or_default();
+                let index = match tasks.iter().position(|t| t.id == id) {
+                    Some(index) => index,
+                    None => {
+                        tasks.push(Task {
+                            id: id.clone(),
+                            name: task.name.clone(),
+                            trader: Some(task.trader.name.clone()),
+                            objectives: Vec::new(),
+                            prerequisites: prerequisites.get(&id).cloned().unwrap_or_default(),
+                        });
+                        tasks.len() - 1
+                    }
+                };
+                tasks[index].objectives.push(TaskObjective {
+                    id: objective_id.clone().map(cynic::Id::into_inner),
+                    description: description.clone(),
+                    optional,
+                    zones,
+                });
+            }
+        }
+    }
+
+    for tasks in map_tasks.values_mut() {
+        tasks.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    Ok(map_tasks)
+}
+
+/// Returns each task's prerequisites by task ID, following the requirements of
+/// required tasks so chains through tasks on other maps stay connected.
+fn task_prerequisites(tasks: &[TaskFragment]) -> HashMap<String, Vec<String>> {
+    let task_id = |id: &Option<cynic::Id>| id.as_ref().map(|id| id.inner().to_owned());
+    let direct: HashMap<String, Vec<String>> = tasks
+        .iter()
+        .filter_map(|task| {
+            let required = task
+                .task_requirements
+                .iter()
+                .filter_map(|requirement| task_id(&requirement.task.id))
+                .collect();
+            Some((task_id(&task.id)?, required))
+        })
+        .collect();
+
+    direct
+        .keys()
+        .map(|id| {
+            let mut all = BTreeSet::new();
+            let mut pending: Vec<&String> = direct[id].iter().collect();
+            while let Some(required) = pending.pop() {
+                if all.insert(required.clone()) {
+                    pending.extend(direct.get(required).into_iter().flatten());
+                }
+            }
+            (id.clone(), all.into_iter().collect())
+        })
+        .collect()
+}
+
+/// Names of a map and its extracts in other languages.
+#[derive(Default)]
+struct MapTranslations {
+    name: Translations,
+    /// Extract name translations by English extract name.
+    extracts: HashMap<String, Translations>,
+}
+
+/// Fetches map and extract names in each of `languages`.
+///
+/// The English names are fetched first, then the same query is repeated with
+/// each language's `lang` argument. Extracts are matched to their English
+/// names by ID. Translations that are the same as the English name are left
+/// out.
+async fn fetch_map_translations(
+    client: &GraphQlClient,
+    languages: &[LanguageCode],
+) -> Result<HashMap<String, MapTranslations>, FetchError> {
+    use cynic::QueryBuilder;
+
+    let english: MapTranslationsQuery = fetch_graphql(
+        client,
+        MapTranslationsQuery::build(LanguageVariables {
+            lang: LanguageCode::En,
+        }),
+    )
+    .await?;
+    // English map names, and extract names by extract ID
+    let english: HashMap<String, (String, HashMap<String, String>)> = english
         .maps
         .into_iter()
         .map(|map| {
             let extracts = map
                 .extracts
                 .into_iter()
-                .filter_map(|e| {
-                    Some(Extract {
-                        name: e.name?,
-                        faction: e.faction?,
-                        position: e.position.map(|p| [p.x, p.y, p.z]),
+                .filter_map(|e| Some((e.id.into_inner(), e.name?)))
+                .collect();
+            (map.normalized_name, (map.name, extracts))
+        })
+        .collect();
+
+    let mut translations: HashMap<String, MapTranslations> = HashMap::new();
+    for &lang in languages {
+        let data: MapTranslationsQuery = fetch_graphql(
+            client,
+            MapTranslationsQuery::build(LanguageVariables { lang }),
+        )
+        .await?;
+        for map in data.maps {
+            let Some((english_name, english_extracts)) = english.get(&map.normalized_name) else {
+                continue;
+            };
+            let entry = translations.entry(map.normalized_name).or_default();
+            if map.name != *english_name {
+                entry.name.insert(lang.code().to_owned(), map.name);
+            }
+            for extract in map.extracts {
+                let Some(name) = extract.name else {
+                    continue;
+                };
+                let Some(english_name) = english_extracts.get(extract.id.inner()) else {
+                    continue;
+                };
+                if name != *english_name {
+                    entry
+                        .extracts
+                        .entry(english_name.clone())
+                        .or_default()
+                        .insert(lang.code().to_owned(), name);
+                }
+            }
+        }
+    }
+    Ok(translations)
+}
+
+/// Fetches each map's switches, linked to the extracts that list them.
+async fn fetch_map_switches(
+    client: &GraphQlClient,
+    map_extracts: &HashMap<String, Vec<Extract>>,
+) -> Result<HashMap<String, Vec<Switch>>, FetchError> {
+    use cynic::QueryBuilder;
+
+    let data: MapSwitchesQuery = fetch_graphql(client, MapSwitchesQuery::build(())).await?;
+
+    Ok(data
+        .maps
+        .into_iter()
+        .map(|map| {
+            let extracts = map_extracts.get(&map.normalized_name);
+            let switches = map
+                .switches
+                .into_iter()
+                .filter_map(|s| {
+                    let name = s.name?;
+                    let p = s.position?;
+                    let operations: Vec<SwitchOperation> = s
+                        .activates
+                        .into_iter()
+                        .filter_map(|op| {
+                            let target = match op.target? {
+                                MapSwitchTargetFragment::MapSwitch(target) => {
+                                    SwitchTarget::Switch(target.name?)
+                                }
+                                MapSwitchTargetFragment::MapExtract(target) => {
+                                    SwitchTarget::Extract(target.name?)
+                                }
+                                MapSwitchTargetFragment::Unknown => return None,
+                            };
+                            Some(SwitchOperation {
+                                operation: op.operation,
+                                target,
+                            })
+                        })
+                        .collect();
+                    // Extracts list the switches they need, switches list the extracts they open
+                    let mut linked: Vec<String> = extracts
+                        .into_iter()
+                        .flatten()
+                        .filter(|e| e.switches.iter().flatten().any(|n| *n == name))
+                        .map(|e| e.name.clone())
+                        .collect();
+                    for operation in &operations {
+                        if let SwitchTarget::Extract(extract) = &operation.target
+                            && !linked.contains(extract)
+                        {
+                            linked.push(extract.clone());
+                        }
+                    }
+                    Some(Switch {
+                        name,
+                        switch_type: s.switch_type,
+                        position: Position3::new(p.x, p.y, p.z),
+                        extracts: linked,
+                        activated_by: s.activated_by.and_then(|by| by.name),
+                        operations,
                     })
                 })
                 .collect();
-            (map.normalized_name, extracts)
+            (map.normalized_name, switches)
         })
         .collect())
 }
@@ -456,46 +1993,417 @@ fn repo_path(path: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path)
 }
 
-struct ImageResult {
-    image_path: String,
-    image_size: [f32; 2],
+/// Where the map data is fetched from: tarkov-dev unless a config file points
+/// elsewhere, e.g. at a fork or a self-hosted API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct Sources {
+    /// GitHub repository holding `src/data/maps.json`.
+    tarkov_dev_repo: String,
+    /// Branch of the repository that `maps.json` is read from.
+    tarkov_dev_branch: String,
+    graphql_url: String,
+}
+
+impl Default for Sources {
+    fn default() -> Self {
+        Self {
+            tarkov_dev_repo: TARKOV_DEV_REPO.to_owned(),
+            tarkov_dev_branch: TARKOV_DEV_BRANCH.to_owned(),
+            graphql_url: TARKOV_DEV_GRAPHQL_URL.to_owned(),
+        }
+    }
+}
+
+/// Defaults from `fetch_maps.toml`, so packagers can generate assets outside
+/// the source tree without repeating flags. Command-line flags take precedence.
+///
+/// ```toml
+/// output-dir = "dist/assets"
+/// image-format = "webp"
+/// quality = 90
+/// tile-zoom-offset = 2
+///
+/// [map-tile-zoom-offsets]
+/// the-lab = 0
+///
+/// [sources]
+/// tarkov-dev-branch = "main"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// Relative to the config file, like `assets-dir`.
+    output_dir: Option<PathBuf>,
+    assets_dir: Option<PathBuf>,
+    image_format: Option<ImageFormat>,
+    quality: Option<u8>,
+    optimize: Option<u8>,
+    tile_zoom_offset: Option<i32>,
+    /// Zoom offsets of individual map groups, used instead of the global one.
+    /// `--tile-zoom-offset` still overrides them.
+    map_tile_zoom_offsets: BTreeMap<String, i32>,
+    sources: Sources,
+}
+
+impl Config {
+    /// Reads the config file at `path`, or [`CONFIG_FILE`] from the working
+    /// directory if it exists.
+    fn load(path: Option<&Path>) -> Result<Self, FetchError> {
+        let path = match path {
+            Some(path) => path.to_owned(),
+            None if Path::new(CONFIG_FILE).is_file() => PathBuf::from(CONFIG_FILE),
+            None => return Ok(Self::default()),
+        };
+        let invalid = |message: String| FetchError::Config {
+            path: path.display().to_string(),
+            message,
+        };
+
+        let text = std::fs::read_to_string(&path)?;
+        let mut config: Self = toml::from_str(&text).map_err(|err| invalid(err.to_string()))?;
+        if config
+            .quality
+            .is_some_and(|quality| !(1..=100).contains(&quality))
+        {
+            return Err(invalid("quality must be between 1 and 100".to_owned()));
+        }
+        if config.optimize.is_some_and(|level| level > 6) {
+            return Err(invalid("optimize must be between 0 and 6".to_owned()));
+        }
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        config.output_dir = config.output_dir.map(|dir| base.join(dir));
+        config.assets_dir = config.assets_dir.map(|dir| base.join(dir));
+        println!("Using config {}", path.display());
+        Ok(config)
+    }
+
+    /// Fills in the options that weren't given on the command line.
+    fn apply_to(&self, args: &mut Args) {
+        args.output_dir = args.output_dir.take().or_else(|| self.output_dir.clone());
+        args.assets_dir = args.assets_dir.take().or_else(|| self.assets_dir.clone());
+        args.image_format = args.image_format.or(self.image_format);
+        args.quality = args.quality.or(self.quality);
+        args.optimize = args.optimize.or(self.optimize);
+    }
+
+    /// Tile zoom offset of the map group `name`: `--tile-zoom-offset`, then
+    /// the group's own offset, then the global one.
+    fn tile_zoom_offset(&self, args: &Args, name: &str) -> i32 {
+        args.tile_zoom_offset
+            .or_else(|| self.map_tile_zoom_offsets.get(name).copied())
+            .or(self.tile_zoom_offset)
+            .unwrap_or(DEFAULT_TILE_ZOOM_OFFSET)
+    }
+}
+
+/// Output format of map images (`--image-format`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ImageFormat {
+    #[default]
+    Png,
+    /// Lossless WebP, usually well under half the size of the PNG.
+    Webp,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+/// Locations and formats generated files are written in.
+struct OutputPaths {
+    /// Directory containing `maps.ron`. Image paths are recorded relative to it.
+    output_dir: PathBuf,
+    /// Directory map images are written to.
+    assets_dir: PathBuf,
+    image_format: ImageFormat,
+    /// WebP quality from 1 to 100, see [`near_lossless`].
+    quality: u8,
+    /// Whether tile maps also keep their tile pyramid (`--keep-tiles`).
+    keep_tiles: bool,
+}
+
+impl OutputPaths {
+    fn from_args(args: &Args) -> Result<Self, FetchError> {
+        let output_dir = match &args.output_dir {
+            Some(dir) => std::path::absolute(dir)?,
+            None => repo_path(DEFAULT_OUTPUT_DIR),
+        };
+        let assets_dir = match &args.assets_dir {
+            Some(dir) => std::path::absolute(dir)?,
+            None => output_dir.join(MAPS_DIR_NAME),
+        };
+        Ok(Self {
+            output_dir,
+            assets_dir,
+            image_format: args.image_format.unwrap_or_default(),
+            quality: args.quality.unwrap_or(DEFAULT_QUALITY),
+            keep_tiles: args.keep_tiles,
+        })
+    }
+
+    fn maps_ron(&self) -> PathBuf {
+        self.output_dir.join(MAPS_RON_FILE)
+    }
+
+    fn image_disk_path(&self, normalized_name: &str) -> PathBuf {
+        let extension = self.image_format.extension();
+        self.assets_dir
+            .join(format!("{normalized_name}.{extension}"))
+    }
+
+    /// Path images are rendered to as PNG before [`finish_image`] converts them.
+    fn render_disk_path(&self, normalized_name: &str) -> PathBuf {
+        self.assets_dir.join(format!("{normalized_name}.png"))
+    }
+
+    /// Directory the tile pyramid of an image is written to with `--keep-tiles`.
+    fn tiles_dir(&self, normalized_name: &str) -> PathBuf {
+        self.assets_dir.join(TILES_DIR_NAME).join(normalized_name)
+    }
+
+    /// Image path as recorded in `maps.ron`.
+    fn image_path(&self, normalized_name: &str) -> String {
+        self.relative_path(&self.image_disk_path(normalized_name))
+    }
+
+    /// Path of a generated file as recorded in `maps.ron`.
+    ///
+    /// Relative to the output directory when the file lives inside it,
+    /// otherwise the absolute path.
+    fn relative_path(&self, disk_path: &Path) -> String {
+        match disk_path.strip_prefix(&self.output_dir) {
+            Ok(relative) => relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            Err(_) => disk_path.display().to_string(),
+        }
+    }
+}
+
+/// Converts the PNG rendered for `image_name` into the output format.
+async fn finish_image(
+    paths: &OutputPaths,
+    image_name: &str,
+    optimize: Option<u8>,
+) -> Result<(), FetchError> {
+    let png_path = paths.render_disk_path(image_name);
+    match paths.image_format {
+        ImageFormat::Png => {
+            if let Some(level) = optimize {
+                optimize_png(png_path, level).await?;
+            }
+        }
+        ImageFormat::Webp => {
+            let webp_path = paths.image_disk_path(image_name);
+            let quality = paths.quality;
+            tokio::task::spawn_blocking(move || encode_webp(&png_path, &webp_path, quality))
+                .await??;
+        }
+    }
+    Ok(())
+}
+
+/// Re-encodes a PNG as lossless WebP and removes the PNG.
+fn encode_webp(png_path: &Path, webp_path: &Path, quality: u8) -> Result<(), FetchError> {
+    let before = std::fs::metadata(png_path)?.len();
+
+    let mut img = image::open(png_path)?.to_rgba8();
+    near_lossless(&mut img, quality);
+    img.save_with_format(webp_path, image::ImageFormat::WebP)?;
+    std::fs::remove_file(png_path)?;
+
+    let after = std::fs::metadata(webp_path)?.len();
+    log::info!(
+        "Encoded {}: {} KiB -> {} KiB",
+        webp_path.display(),
+        before / 1024,
+        after / 1024
+    );
+    Ok(())
+}
+
+/// Rounds away the low bits of each color channel so the lossless encoder
+/// compresses better, like libwebp's near-lossless mode.
+///
+/// Quality 100 keeps the image exact; every 20 below drops one more bit, up
+/// to 4. Alpha is kept exact so layer outlines don't fray.
+fn near_lossless(img: &mut RgbaImage, quality: u8) {
+    let bits = (u32::from(100 - quality.min(100)) / 20).min(4);
+    if bits == 0 {
+        return;
+    }
+    let half = 1u16 << (bits - 1);
+    for pixel in img.pixels_mut() {
+        for channel in &mut pixel.0[..3] {
+            let rounded = ((u16::from(*channel) + half) >> bits) << bits;
+            *channel = rounded.min(255) as u8;
+        }
+    }
+}
+
+/// Losslessly recompresses a PNG in place using the given oxipng preset level.
+async fn optimize_png(path: PathBuf, level: u8) -> Result<(), FetchError> {
+    let before = async_fs::metadata(&path).await?.len();
+
+    let optimized_path = path.clone();
+    tokio::task::spawn_blocking(move || {
+        oxipng::optimize(
+            &oxipng::InFile::Path(optimized_path.clone()),
+            &oxipng::OutFile::from_path(optimized_path),
+            &oxipng::Options::from_preset(level),
+        )
+    })
+    .await?
+    .map_err(|e| FetchError::PngOptimize(e.to_string()))?;
+
+    let after = async_fs::metadata(&path).await?.len();
+    log::info!(
+        "Optimized {}: {} KiB -> {} KiB",
+        path.display(),
+        before / 1024,
+        after / 1024
+    );
+    Ok(())
+}
+
+struct ImageResult {
+    image_path: String,
+    image_size: [f32; 2],
+    tiles: Option<TilePyramid>,
+}
+
+/// URLs of all tiles `(x, y, url)` at zoom level `z` of a tile template.
+fn tile_urls_at(remote_template: &str, z: u32) -> Vec<(u32, u32, String)> {
+    let tiles_per_axis = TilePyramid::tiles_per_axis(z);
+    (0..tiles_per_axis)
+        .flat_map(|x| (0..tiles_per_axis).map(move |y| (x, y)))
+        .map(|(x, y)| {
+            let url = remote_template
+                .replace("{z}", &z.to_string())
+                .replace("{x}", &x.to_string())
+                .replace("{y}", &y.to_string());
+            (x, y, url)
+        })
+        .collect()
+}
+
+/// Writes the tiles `(x, y, bytes)` of zoom level `z` to `{dir}/{z}/{x}/{y}.{extension}`.
+async fn write_tiles(
+    dir: &Path,
+    extension: &str,
+    z: u32,
+    tiles: &[(u32, u32, Vec<u8>)],
+) -> Result<(), FetchError> {
+    for (x, y, bytes) in tiles {
+        let column_dir = dir.join(z.to_string()).join(x.to_string());
+        async_fs::create_dir_all(&column_dir).await?;
+        async_fs::write(column_dir.join(format!("{y}.{extension}")), bytes).await?;
+    }
+    Ok(())
+}
+
+/// Reads the manifest of the tile pyramid in `dir`, if there is a valid one.
+fn read_tile_manifest(dir: &Path) -> Option<TilePyramid> {
+    let text = std::fs::read_to_string(dir.join(TILE_MANIFEST_FILE)).ok()?;
+    ron::from_str(&text).ok()
+}
+
+fn write_tile_manifest(dir: &Path, pyramid: &TilePyramid) -> Result<(), FetchError> {
+    let text = ron::ser::to_string_pretty(pyramid, PrettyConfig::default())?;
+    std::fs::write(dir.join(TILE_MANIFEST_FILE), text)?;
+    Ok(())
+}
+
+/// Image name of a map layer, e.g. "labs-basement" for Labs' "Basement" layer.
+fn layer_image_name(normalized_name: &str, layer_name: &str) -> String {
+    let slug: String = layer_name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+    format!("{normalized_name}-{}", slug.join("-"))
+}
+
+/// Transform that renders `node` with [`resvg::render_node`] where it sits in
+/// the full SVG, scaled by `scale`.
+///
+/// `render_node` moves the node's bounding box to the pixmap origin and only
+/// applies the node's own transform, so both are undone here.
+fn node_render_transform(node: &resvg::usvg::Node, scale: f32) -> Option<Transform> {
+    let resvg::usvg::Node::Group(group) = node else {
+        return None;
+    };
+    let bbox = node.abs_layer_bounding_box()?;
+    let parent = group
+        .abs_transform()
+        .pre_concat(group.transform().invert()?);
+    Some(
+        Transform::from_scale(scale, scale)
+            .pre_concat(parent)
+            .pre_translate(bbox.x(), bbox.y()),
+    )
 }
 
+/// Renders an SVG map to `{image_name}.png`.
+///
+/// With `svg_layer`, only the group with that ID is rendered, on a transparent
+/// image the size of the whole map.
+#[allow(clippy::too_many_arguments)]
 async fn process_svg_map(
     client: &reqwest::Client,
-    normalized_name: &str,
+    image_name: &str,
     svg_url: &str,
-    force: bool,
+    svg_layer: Option<&str>,
+    paths: &OutputPaths,
+    refresh: Refresh,
+    cache: &SourceCache,
+    optimize: Option<u8>,
 ) -> Result<ImageResult, FetchError> {
-    let image_relative = format!("{MAPS_PATH_PREFIX}/{normalized_name}.png");
-    let image_disk_path = repo_path(&format!("{MAPS_DIR}/{normalized_name}.png"));
-
-    if !force && image_disk_path.exists() {
-        let img = image::open(&image_disk_path)?;
-        let source_size = [
-            img.width() as f32 / SVG_RENDER_SCALE,
-            img.height() as f32 / SVG_RENDER_SCALE,
-        ];
-        return Ok(ImageResult {
-            image_path: image_relative,
-            image_size: source_size,
-        });
-    }
-
-    let response = client
-        .get(svg_url)
-        .header(reqwest::header::USER_AGENT, USER_AGENT)
-        .send()
-        .await?;
+    let image_relative = paths.image_path(image_name);
+    let image_disk_path = paths.image_disk_path(image_name);
+
+    let existing = |path: &Path| -> Result<ImageResult, FetchError> {
+        let img = image::open(path)?;
+        Ok(ImageResult {
+            image_path: image_relative.clone(),
+            image_size: [
+                img.width() as f32 / SVG_RENDER_SCALE,
+                img.height() as f32 / SVG_RENDER_SCALE,
+            ],
+            tiles: None,
+        })
+    };
 
-    if !response.status().is_success() {
-        return Err(FetchError::HttpStatus {
-            resource: "SVG".into(),
-            status: response.status().as_u16(),
-        });
+    let exists = image_disk_path.exists();
+    if exists && refresh == Refresh::Missing {
+        return existing(&image_disk_path);
     }
 
-    let svg_bytes = response.bytes().await?;
+    let conditional = exists && refresh == Refresh::Changed;
+    let svg_bytes = match cache
+        .get(client, image_name, svg_url, conditional, "SVG")
+        .await?
+    {
+        Fetched::NotModified => {
+            log::info!("{image_name}: SVG unchanged, keeping the existing image");
+            return existing(&image_disk_path);
+        }
+        Fetched::Modified(bytes) => bytes,
+    };
     let tree = Tree::from_data(&svg_bytes, &Options::default())
         .map_err(|e| FetchError::SvgParse(e.to_string()))?;
 
@@ -505,102 +2413,230 @@ async fn process_svg_map(
 
     let mut pixmap = Pixmap::new(render_w, render_h).ok_or(FetchError::PixmapCreation)?;
 
-    resvg::render(
-        &tree,
-        Transform::from_scale(SVG_RENDER_SCALE, SVG_RENDER_SCALE),
-        &mut pixmap.as_mut(),
-    );
+    match svg_layer {
+        Some(id) => {
+            let node = tree
+                .node_by_id(id)
+                .ok_or_else(|| FetchError::MissingSvgLayer { id: id.to_owned() })?;
+            let transform = node_render_transform(node, SVG_RENDER_SCALE)
+                .ok_or_else(|| FetchError::MissingSvgLayer { id: id.to_owned() })?;
+            resvg::render_node(node, transform, &mut pixmap.as_mut());
+        }
+        None => resvg::render(
+            &tree,
+            Transform::from_scale(SVG_RENDER_SCALE, SVG_RENDER_SCALE),
+            &mut pixmap.as_mut(),
+        ),
+    }
 
     if let Some(parent) = image_disk_path.parent() {
         async_fs::create_dir_all(parent).await?;
     }
     pixmap
-        .save_png(&image_disk_path)
+        .save_png(paths.render_disk_path(image_name))
         .map_err(|e| FetchError::PngSave(e.to_string()))?;
+    finish_image(paths, image_name, optimize).await?;
 
     Ok(ImageResult {
         image_path: image_relative,
         image_size: source_size,
+        tiles: None,
     })
 }
 
+/// Downloads the tiles `(x, y, url)` of the image `image_name` concurrently.
+async fn download_tiles(
+    client: &reqwest::Client,
+    cache: &Arc<SourceCache>,
+    image_name: &str,
+    tile_urls: Vec<(u32, u32, String)>,
+    conditional: bool,
+    max_retries: u32,
+    multi_progress: &MultiProgress,
+) -> Result<Vec<(u32, u32, Fetched)>, FetchError> {
+    let tile_pb = multi_progress.add(ProgressBar::new(tile_urls.len() as u64));
+    tile_pb.set_style(
+        ProgressStyle::default_bar()
+            .template("    {spinner:.green} [{bar:30.cyan/blue}] {pos}/{len} tiles ({eta})")?
+            .progress_chars("=>-"),
+    );
+
+    let semaphore = Arc::new(Semaphore::new(TILE_DOWNLOAD_CONCURRENCY));
+    let tile_pb = Arc::new(tile_pb);
+    let mut join_set: JoinSet<TileResult> = JoinSet::new();
+
+    for (x, y, remote_url) in tile_urls {
+        let client = client.clone();
+        let cache = cache.clone();
+        let image_name = image_name.to_owned();
+        let semaphore = semaphore.clone();
+        let tile_pb = tile_pb.clone();
+
+        join_set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await?;
+            let mut attempt = 0;
+            let fetched = loop {
+                match cache
+                    .get(&client, &image_name, &remote_url, conditional, "tile")
+                    .await
+                {
+                    Ok(fetched) => break fetched,
+                    Err(err) if attempt < max_retries && err.is_transient() => {
+                        attempt += 1;
+                        let delay = retry_delay(attempt);
+                        log::warn!(
+                            "{remote_url}: {err}, retrying in {delay:?} \
+                             ({attempt}/{max_retries})"
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    Err(err) => return Err(err),
+                }
+            };
+            tile_pb.inc(1);
+            Ok((x, y, fetched))
+        });
+    }
+
+    let mut tiles = Vec::new();
+    while let Some(result) = join_set.join_next().await {
+        tiles.push(result??);
+    }
+    tile_pb.finish_and_clear();
+    Ok(tiles)
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn process_tile_map(
     client: &reqwest::Client,
-    normalized_name: &str,
+    image_name: &str,
     remote_template: &str,
     tile_size: i32,
     min_zoom: i32,
     max_zoom: i32,
     zoom_offset: i32,
     multi_progress: &MultiProgress,
-    force: bool,
+    paths: &OutputPaths,
+    refresh: Refresh,
+    cache: &Arc<SourceCache>,
+    optimize: Option<u8>,
+    max_retries: u32,
 ) -> Result<ImageResult, FetchError> {
-    let image_relative = format!("{MAPS_PATH_PREFIX}/{normalized_name}.png");
-    let image_disk_path = repo_path(&format!("{MAPS_DIR}/{normalized_name}.png"));
+    let image_relative = paths.image_path(image_name);
+    let image_disk_path = paths.image_disk_path(image_name);
 
     let zoom = (max_zoom - zoom_offset).max(min_zoom);
     let tiles_per_axis = 1u32 << zoom;
     let full_size = tiles_per_axis * tile_size as u32;
     let source_size = [tile_size as f32, tile_size as f32];
 
-    if !force && image_disk_path.exists() {
+    // With --keep-tiles, an image without a tile pyramid counts as missing
+    let tiles_dir = paths.tiles_dir(image_name);
+    let kept_tiles = paths.keep_tiles.then(|| read_tile_manifest(&tiles_dir));
+    let exists = image_disk_path.exists() && kept_tiles.as_ref().is_none_or(Option::is_some);
+    let kept_tiles = kept_tiles.flatten();
+    if exists && refresh == Refresh::Missing {
         return Ok(ImageResult {
             image_path: image_relative,
             image_size: source_size,
+            tiles: kept_tiles,
         });
     }
 
-    let tile_pb = multi_progress.add(ProgressBar::new((tiles_per_axis * tiles_per_axis) as u64));
-    tile_pb.set_style(
-        ProgressStyle::default_bar()
-            .template("    {spinner:.green} [{bar:30.cyan/blue}] {pos}/{len} tiles ({eta})")?
-            .progress_chars("=>-"),
-    );
-
-    let semaphore = Arc::new(Semaphore::new(TILE_DOWNLOAD_CONCURRENCY));
-    let tile_pb = Arc::new(tile_pb);
-    let mut join_set: JoinSet<TileResult> = JoinSet::new();
-
-    for x in 0..tiles_per_axis {
-        for y in 0..tiles_per_axis {
-            let remote_url = remote_template
-                .replace("{z}", &zoom.to_string())
-                .replace("{x}", &x.to_string())
-                .replace("{y}", &y.to_string());
-
-            let client = client.clone();
-            let semaphore = semaphore.clone();
-            let tile_pb = tile_pb.clone();
-
-            join_set.spawn(async move {
-                let _permit = semaphore.acquire_owned().await?;
+    let tile_urls = tile_urls_at(remote_template, zoom as u32);
 
-                let response = client
-                    .get(&remote_url)
-                    .header(reqwest::header::USER_AGENT, USER_AGENT)
-                    .send()
-                    .await?;
-
-                if !response.status().is_success() {
-                    return Err(FetchError::HttpStatus {
-                        resource: "tile".into(),
-                        status: response.status().as_u16(),
-                    });
-                }
+    let conditional = exists && refresh == Refresh::Changed;
+    let fetched = download_tiles(
+        client,
+        cache,
+        image_name,
+        tile_urls.clone(),
+        conditional,
+        max_retries,
+        multi_progress,
+    )
+    .await?;
 
-                let bytes = response.bytes().await?.to_vec();
-                tile_pb.inc(1);
-                Ok((x, y, bytes))
-            });
+    let mut tiles = Vec::new();
+    let mut unchanged = Vec::new();
+    for (x, y, fetched) in fetched {
+        match fetched {
+            Fetched::Modified(bytes) => tiles.push((x, y, bytes)),
+            Fetched::NotModified => unchanged.push((x, y)),
         }
     }
-
-    let mut tiles = Vec::new();
-    while let Some(result) = join_set.join_next().await {
-        tiles.push(result??);
+    if tiles.is_empty() && !unchanged.is_empty() {
+        log::info!("{image_name}: tiles unchanged, keeping the existing image");
+        return Ok(ImageResult {
+            image_path: image_relative,
+            image_size: source_size,
+            tiles: kept_tiles,
+        });
     }
-    tile_pb.finish_and_clear();
+    // Some tiles changed, so the unchanged ones are needed to compose the image again
+    if !unchanged.is_empty() {
+        let refetch = tile_urls
+            .into_iter()
+            .filter(|(x, y, _)| unchanged.contains(&(*x, *y)))
+            .collect();
+        for (x, y, fetched) in download_tiles(
+            client,
+            cache,
+            image_name,
+            refetch,
+            false,
+            max_retries,
+            multi_progress,
+        )
+        .await?
+        {
+            if let Fetched::Modified(bytes) = fetched {
+                tiles.push((x, y, bytes));
+            }
+        }
+    }
+
+    let pyramid = if paths.keep_tiles {
+        let extension = Path::new(remote_template)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("png");
+        write_tiles(&tiles_dir, extension, zoom as u32, &tiles).await?;
+        // The coarser levels aren't needed for the stitched image, only for the pyramid
+        for z in min_zoom..zoom {
+            let level_urls = tile_urls_at(remote_template, z as u32);
+            let level = download_tiles(
+                client,
+                cache,
+                image_name,
+                level_urls,
+                conditional,
+                max_retries,
+                multi_progress,
+            )
+            .await?
+            .into_iter()
+            .filter_map(|(x, y, fetched)| match fetched {
+                Fetched::Modified(bytes) => Some((x, y, bytes)),
+                Fetched::NotModified => None,
+            })
+            .collect::<Vec<_>>();
+            write_tiles(&tiles_dir, extension, z as u32, &level).await?;
+        }
+        let pyramid = TilePyramid {
+            path_template: format!(
+                "{}/{{z}}/{{x}}/{{y}}.{extension}",
+                paths.relative_path(&tiles_dir)
+            ),
+            tile_size: tile_size as u32,
+            min_zoom: min_zoom.min(zoom) as u32,
+            max_zoom: zoom as u32,
+        };
+        write_tile_manifest(&tiles_dir, &pyramid)?;
+        Some(pyramid)
+    } else {
+        None
+    };
 
     let compose_pb = multi_progress.add(ProgressBar::new(tiles.len() as u64));
     compose_pb.set_style(
@@ -609,35 +2645,51 @@ async fn process_tile_map(
             .progress_chars("=>-"),
     );
 
-    let mut full_image: RgbaImage = ImageBuffer::new(full_size, full_size);
-
+    // Compose one row of tiles at a time and stream it into the PNG encoder,
+    // so only a single band of the decoded map is ever held in memory.
+    let mut rows: Vec<Vec<(u32, Vec<u8>)>> = vec![Vec::new(); tiles_per_axis as usize];
     for (x, y, bytes) in tiles {
-        if let Ok(tile) = image::load_from_memory(&bytes) {
-            let tile_rgba = tile.to_rgba8();
-            let offset_x = x * tile_size as u32;
-            let offset_y = y * tile_size as u32;
-
-            for (tx, ty, pixel) in tile_rgba.enumerate_pixels() {
-                let fx = offset_x + tx;
-                let fy = offset_y + ty;
-                if fx < full_size && fy < full_size {
-                    full_image.put_pixel(fx, fy, *pixel);
-                }
+        rows[y as usize].push((x, bytes));
+    }
+
+    if let Some(parent) = image_disk_path.parent() {
+        async_fs::create_dir_all(parent).await?;
+    }
+    let png_error = |e: png::EncodingError| FetchError::PngSave(e.to_string());
+    let file = BufWriter::new(File::create(paths.render_disk_path(image_name))?);
+    let mut encoder = png::Encoder::new(file, full_size, full_size);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut stream = encoder
+        .write_header()
+        .map_err(png_error)?
+        .into_stream_writer()
+        .map_err(png_error)?;
+
+    let tile_size = tile_size as u32;
+    let mut band: RgbaImage = ImageBuffer::new(full_size, tile_size);
+
+    for row in rows {
+        band.fill(0);
+        for (x, bytes) in row {
+            if let Ok(tile) = image::load_from_memory(&bytes) {
+                let offset_x = (x * tile_size) as i64;
+                image::imageops::replace(&mut band, &tile.to_rgba8(), offset_x, 0);
             }
+            compose_pb.inc(1);
         }
-        compose_pb.inc(1);
+        stream.write_all(band.as_raw())?;
     }
 
+    stream.finish().map_err(png_error)?;
     compose_pb.finish_and_clear();
 
-    if let Some(parent) = image_disk_path.parent() {
-        async_fs::create_dir_all(parent).await?;
-    }
-    full_image.save(&image_disk_path)?;
+    finish_image(paths, image_name, optimize).await?;
 
     Ok(ImageResult {
         image_path: image_relative,
         image_size: source_size,
+        tiles: pyramid,
     })
 }
 
@@ -646,18 +2698,31 @@ async fn convert_group(
     client: &reqwest::Client,
     fetched: FetchedMapGroup,
     map_names: &HashMap<String, String>,
+    map_translations: &HashMap<String, MapTranslations>,
     map_spawns: &HashMap<String, Vec<Spawn>>,
     map_extracts: &HashMap<String, Vec<Extract>>,
+    map_hazards: &HashMap<String, Vec<Hazard>>,
+    map_locks: &HashMap<String, Vec<Lock>>,
+    map_loot_containers: &HashMap<String, Vec<LootContainer>>,
+    map_bosses: &HashMap<String, Vec<BossSpawn>>,
+    map_stationary_weapons: &HashMap<String, Vec<StationaryWeapon>>,
+    map_switches: &HashMap<String, Vec<Switch>>,
+    map_transits: &HashMap<String, Vec<Transit>>,
+    map_tasks: &HashMap<String, Vec<Task>>,
     multi_progress: &MultiProgress,
-    force: bool,
+    paths: &OutputPaths,
+    refresh: Refresh,
+    cache: &Arc<SourceCache>,
+    optimize: Option<u8>,
     tile_zoom_offset: i32,
+    max_retries: u32,
 ) -> Result<Option<Map>, FetchError> {
     let FetchedMapGroup {
         normalized_name,
         maps,
     } = fetched;
 
-    let Some(interactive) = maps.into_iter().find(|m| m.projection == "interactive") else {
+    let Some(mut interactive) = maps.into_iter().find(|m| m.projection == "interactive") else {
         return Ok(None);
     };
 
@@ -670,7 +2735,19 @@ async fn convert_group(
             })?;
 
     let result = match (&interactive.svg_path, &interactive.tile_path) {
-        (Some(svg_url), _) => process_svg_map(client, &normalized_name, svg_url, force).await?,
+        (Some(svg_url), _) => {
+            process_svg_map(
+                client,
+                &normalized_name,
+                svg_url,
+                None,
+                paths,
+                refresh,
+                cache,
+                optimize,
+            )
+            .await?
+        }
         (_, Some(tile_template)) => {
             let min_zoom = interactive
                 .min_zoom
@@ -693,7 +2770,11 @@ async fn convert_group(
                 max_zoom,
                 tile_zoom_offset,
                 multi_progress,
-                force,
+                paths,
+                refresh,
+                cache,
+                optimize,
+                max_retries,
             )
             .await?
         }
@@ -704,6 +2785,68 @@ async fn convert_group(
         }
     };
 
+    let mut layers = Vec::new();
+    for fetched_layer in interactive.layers.take().into_iter().flatten() {
+        let image_name = layer_image_name(&normalized_name, &fetched_layer.name);
+        let rendered = match (&interactive.svg_path, &fetched_layer.svg_layer) {
+            (Some(svg_url), Some(svg_layer)) => Some(
+                process_svg_map(
+                    client,
+                    &image_name,
+                    svg_url,
+                    Some(svg_layer),
+                    paths,
+                    refresh,
+                    cache,
+                    optimize,
+                )
+                .await,
+            ),
+            _ => match (
+                &fetched_layer.tile_path,
+                interactive.min_zoom,
+                interactive.max_zoom,
+            ) {
+                (Some(tile_template), Some(min_zoom), Some(max_zoom)) => Some(
+                    process_tile_map(
+                        client,
+                        &image_name,
+                        tile_template,
+                        interactive.tile_size.unwrap_or(256),
+                        min_zoom,
+                        max_zoom,
+                        tile_zoom_offset,
+                        multi_progress,
+                        paths,
+                        refresh,
+                        cache,
+                        optimize,
+                        max_retries,
+                    )
+                    .await,
+                ),
+                _ => None,
+            },
+        };
+
+        let layer_name = fetched_layer.name.clone();
+        let mut layer = Layer::from(fetched_layer);
+        match rendered {
+            Some(Ok(image)) => {
+                layer.image_path = Some(image.image_path);
+                layer.tiles = image.tiles;
+            }
+            // A broken layer shouldn't cost the whole map
+            Some(Err(err)) => {
+                multi_progress.println(format!(
+                    "  Warning: layer '{layer_name}' of {normalized_name} not rendered: {err}"
+                ))?;
+            }
+            None => {}
+        }
+        layers.push(layer);
+    }
+
     let logical_size = interactive
         .bounds
         .map(|bounds| {
@@ -713,11 +2856,16 @@ async fn convert_group(
         })
         .unwrap_or(result.image_size);
 
-    Ok(Some(Map {
+    let mut map = Map {
         normalized_name: normalized_name.clone(),
         name,
+        translations: map_translations
+            .get(&normalized_name)
+            .map(|translations| translations.name.clone())
+            .filter(|names| !names.is_empty()),
         image_path: result.image_path,
         image_size: result.image_size,
+        tiles: result.tiles,
         logical_size,
         alt_maps: interactive.alt_maps,
         author: interactive.author,
@@ -725,64 +2873,515 @@ async fn convert_group(
         transform: interactive.transform,
         coordinate_rotation: interactive.coordinate_rotation,
         bounds: interactive.bounds,
+        default_view: None,
         height_range: interactive.height_range,
-        layers: interactive
-            .layers
-            .map(|l| l.into_iter().map(Into::into).collect()),
+        layers: (!layers.is_empty()).then_some(layers),
         labels: interactive
             .labels
             .map(|l| l.into_iter().map(Into::into).collect()),
         spawns: map_spawns.get(&normalized_name).cloned(),
         extracts: map_extracts.get(&normalized_name).cloned(),
-    }))
+        spawn_clusters: None,
+        hazards: map_hazards
+            .get(&normalized_name)
+            .filter(|hazards| !hazards.is_empty())
+            .cloned(),
+        locks: map_locks
+            .get(&normalized_name)
+            .filter(|locks| !locks.is_empty())
+            .cloned(),
+        loot_containers: map_loot_containers
+            .get(&normalized_name)
+            .filter(|containers| !containers.is_empty())
+            .cloned(),
+        bosses: map_bosses
+            .get(&normalized_name)
+            .filter(|bosses| !bosses.is_empty())
+            .cloned(),
+        stationary_weapons: map_stationary_weapons
+            .get(&normalized_name)
+            .filter(|weapons| !weapons.is_empty())
+            .cloned(),
+        switches: map_switches
+            .get(&normalized_name)
+            .filter(|switches| !switches.is_empty())
+            .cloned(),
+        transits: map_transits
+            .get(&normalized_name)
+            .filter(|transits| !transits.is_empty())
+            .cloned(),
+        tasks: map_tasks.get(&normalized_name).cloned(),
+    };
+
+    map.default_view = map.compute_default_view();
+    let clusters = map.compute_spawn_clusters(SPAWN_CLUSTER_RADIUS);
+    map.spawn_clusters = (!clusters.is_empty()).then_some(clusters);
+
+    Ok(Some(map))
+}
+
+/// Fetches the commit at the head of tarkov-dev's branch, so the map data
+/// records exactly which `maps.json` it was built from.
+async fn fetch_source_commit(
+    client: &reqwest::Client,
+    sources: &Sources,
+) -> Result<String, FetchError> {
+    let Sources {
+        tarkov_dev_repo: repo,
+        tarkov_dev_branch: branch,
+        ..
+    } = sources;
+    let response = client
+        .get(format!(
+            "https://api.github.com/repos/{repo}/commits/{branch}"
+        ))
+        .header(reqwest::header::USER_AGENT, USER_AGENT)
+        .header(reqwest::header::ACCEPT, "application/vnd.github.sha")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(FetchError::HttpStatus {
+            resource: "tarkov-dev commit".into(),
+            status: response.status().as_u16(),
+        });
+    }
+
+    Ok(response.text().await?.trim().to_owned())
+}
+
+/// Replaces the maps in `previous` that were processed again, keeping the rest in place.
+///
+/// Processed maps that weren't in `previous` are appended.
+fn merge_processed_maps(previous: &[Map], processed: TarkovMaps) -> TarkovMaps {
+    let mut merged: TarkovMaps = previous
+        .iter()
+        .map(|old| {
+            processed
+                .iter()
+                .find(|new| new.normalized_name == old.normalized_name)
+                .unwrap_or(old)
+                .clone()
+        })
+        .collect();
+    merged.extend(processed.into_iter().filter(|new| {
+        !previous
+            .iter()
+            .any(|old| old.normalized_name == new.normalized_name)
+    }));
+    merged
+}
+
+/// Paths of `maps.ron` and the images and kept tiles `maps` refer to,
+/// relative to the directory of `maps.ron`.
+fn data_files(maps: &[Map]) -> BTreeSet<String> {
+    let mut files = BTreeSet::from([MAPS_RON_FILE.to_owned()]);
+    let add_tiles = |files: &mut BTreeSet<String>, tiles: &TilePyramid| {
+        for z in tiles.min_zoom..=tiles.max_zoom {
+            let count = TilePyramid::tiles_per_axis(z);
+            for x in 0..count {
+                files.extend((0..count).map(|y| tiles.tile_path(z, x, y)));
+            }
+        }
+    };
+
+    for map in maps {
+        if !map.image_path.is_empty() {
+            files.insert(map.image_path.clone());
+        }
+        if let Some(tiles) = &map.tiles {
+            add_tiles(&mut files, tiles);
+        }
+        for layer in map.layers.iter().flatten() {
+            files.extend(layer.image_path.clone());
+            if let Some(tiles) = &layer.tiles {
+                add_tiles(&mut files, tiles);
+            }
+        }
+    }
+    files
+}
+
+/// Writes the [`DataManifest`] of `file` and the images it refers to.
+///
+/// Files that aren't on disk are left out with a warning.
+fn write_data_manifest(paths: &OutputPaths, file: &MapsFile) -> Result<DataManifest, FetchError> {
+    use sha2::{Digest, Sha256};
+
+    let mut files = Vec::new();
+    for path in data_files(&file.maps) {
+        let bytes = match std::fs::read(paths.output_dir.join(&path)) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                eprintln!("Warning: {path} is missing, leaving it out of the manifest");
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let sha256 = Sha256::digest(&bytes)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        files.push(ManifestFile {
+            path,
+            size: bytes.len() as u64,
+            sha256,
+        });
+    }
+
+    let date = file.fetched_at.unwrap_or_else(Utc::now).format("%Y-%m-%d");
+    let version = match &file.source_commit {
+        Some(commit) => format!(
+            "{date}+{}",
+            commit.get(..SHORT_COMMIT_LEN).unwrap_or(commit)
+        ),
+        None => date.to_string(),
+    };
+    let manifest = DataManifest {
+        version,
+        fetched_at: file.fetched_at,
+        source_commit: file.source_commit.clone(),
+        files,
+    };
+
+    let pretty_config = PrettyConfig::new()
+        .indentor("  ".to_owned())
+        .struct_names(true);
+    let ron_string = ron::ser::to_string_pretty(&manifest, pretty_config)?;
+    std::fs::write(paths.output_dir.join(DATA_MANIFEST_FILE), ron_string)?;
+    Ok(manifest)
+}
+
+/// Checks that `path`, relative to the directory of `maps.ron`, is an image
+/// with the proportions of `expected`, returning the problem otherwise.
+///
+/// Images are rendered at a multiple of the recorded size, so only the aspect
+/// ratio has to match.
+fn check_image(paths: &OutputPaths, path: &str, expected: [f32; 2]) -> Option<String> {
+    let disk_path = paths.output_dir.join(path);
+    if !disk_path.is_file() {
+        return Some(format!("image {path} does not exist"));
+    }
+    match image::image_dimensions(&disk_path) {
+        Ok((width, height)) => {
+            let [expected_width, expected_height] = expected;
+            let ratio = width as f32 / height as f32;
+            let expected_ratio = expected_width / expected_height;
+            let matches = (ratio / expected_ratio - 1.0).abs() <= IMAGE_RATIO_TOLERANCE;
+            (!matches).then(|| {
+                format!(
+                    "image {path} is {width} × {height}, \
+                     not proportional to the recorded {expected_width} × {expected_height}"
+                )
+            })
+        }
+        Err(err) => Some(format!("image {path} is unreadable: {err}")),
+    }
+}
+
+/// Checks that the coarsest zoom level of `tiles` is on disk.
+fn check_tiles(paths: &OutputPaths, tiles: &TilePyramid) -> Option<String> {
+    let z = tiles.min_zoom;
+    let count = TilePyramid::tiles_per_axis(z);
+    let missing = (0..count)
+        .flat_map(|x| (0..count).map(move |y| (x, y)))
+        .filter(|&(x, y)| !paths.output_dir.join(tiles.tile_path(z, x, y)).is_file())
+        .count();
+    (missing > 0).then(|| {
+        format!(
+            "{missing} of {} tiles of {} missing at zoom {z}",
+            count * count,
+            tiles.path_template
+        )
+    })
+}
+
+/// Problems with `map` that would only show up in the viewer: the
+/// [`Map::validate`] warnings, and images or tiles that are missing or don't
+/// match the recorded size.
+fn verify_map(paths: &OutputPaths, map: &Map) -> Vec<String> {
+    let mut problems: Vec<String> = map.validate().iter().map(ToString::to_string).collect();
+
+    // Data packs that only add markers to another map have no image
+    if !map.image_path.is_empty() {
+        problems.extend(check_image(paths, &map.image_path, map.image_size));
+    }
+    problems.extend(
+        map.tiles
+            .as_ref()
+            .and_then(|tiles| check_tiles(paths, tiles)),
+    );
+
+    for layer in map.layers.iter().flatten() {
+        if let Some(image_path) = &layer.image_path {
+            problems.extend(
+                check_image(paths, image_path, map.image_size)
+                    .map(|problem| format!("layer '{}': {problem}", layer.name)),
+            );
+        }
+        problems.extend(
+            layer
+                .tiles
+                .as_ref()
+                .and_then(|tiles| check_tiles(paths, tiles))
+                .map(|problem| format!("layer '{}': {problem}", layer.name)),
+        );
+    }
+
+    problems
+}
+
+/// Checks the existing `maps.ron` and the images it refers to (`verify`).
+fn verify(paths: &OutputPaths) -> Result<(), FetchError> {
+    let path = paths.maps_ron();
+    println!("Verifying {}", path.display());
+    let text = std::fs::read_to_string(&path)?;
+    let file: MapsFile = ron::from_str(&text).map_err(|source| FetchError::InvalidMapsFile {
+        path: path.display().to_string(),
+        message: source.to_string(),
+    })?;
+
+    let mut problems = 0usize;
+    if file.schema_version != MAPS_SCHEMA_VERSION {
+        println!(
+            "Schema version {} doesn't match the current version {MAPS_SCHEMA_VERSION}",
+            file.schema_version
+        );
+        problems += 1;
+    }
+
+    for map in &file.maps {
+        let map_problems = verify_map(paths, map);
+        if map_problems.is_empty() {
+            println!("  ok  {}", map.normalized_name);
+            continue;
+        }
+        println!(
+            "  {} ({} problem(s)):",
+            map.normalized_name,
+            map_problems.len()
+        );
+        for problem in &map_problems {
+            println!("    - {problem}");
+        }
+        problems += map_problems.len();
+    }
+
+    if problems > 0 {
+        eprintln!("\nFound {problems} problem(s) in {}", path.display());
+        return Err(FetchError::VerifyFailed(problems));
+    }
+    println!("\n{} maps verified, no problems found", file.maps.len());
+    Ok(())
 }
 
 #[tokio::main]
 async fn main() -> Result<(), FetchError> {
     env_logger::init();
 
-    let args = Args::parse();
+    let mut args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+    config.apply_to(&mut args);
+
+    if let Some(Command::Verify) = args.command {
+        return verify(&OutputPaths::from_args(&args)?);
+    }
 
     if args.force {
         println!("Force mode enabled - re-processing all assets");
     }
+    if args.incremental {
+        println!("Incremental mode enabled - re-processing changed assets");
+    }
+    let refresh = Refresh::from_args(&args);
+    let cache = Arc::new(SourceCache::load());
+
+    let paths = OutputPaths::from_args(&args)?;
+    println!("Writing maps.ron to {}", paths.output_dir.display());
+    println!("Writing map images to {}", paths.assets_dir.display());
 
     let client = reqwest::Client::new();
+    let graphql = GraphQlClient {
+        http: client.clone(),
+        url: config.sources.graphql_url.clone(),
+    };
+
+    if !args.skip_schema_check {
+        println!("Checking tarkov.dev schema...");
+        let problems = check_schema(&graphql).await?;
+        if !problems.is_empty() {
+            eprintln!("The tarkov.dev schema no longer matches the queries:");
+            for problem in &problems {
+                eprintln!("{problem}");
+            }
+            eprintln!(
+                "Update schema.graphql and the query fragments in fetch_maps.rs, \
+                 or rerun with --skip-schema-check"
+            );
+            return Err(FetchError::SchemaDrift(problems.len()));
+        }
+    }
 
     println!("Fetching map data from tarkov.dev...");
-    let map_names = fetch_map_names(&client).await?;
+    let map_names = fetch_map_names(&graphql).await?;
     println!("Fetched {} map names", map_names.len());
 
-    println!("Fetching PMC spawns from tarkov.dev...");
-    let map_spawns = fetch_map_spawns(&client).await?;
+    println!("Fetching spawns from tarkov.dev...");
+    let map_spawns = fetch_map_spawns(&graphql).await?;
     let total_spawns: usize = map_spawns.values().map(Vec::len).sum();
-    println!("Fetched {total_spawns} PMC spawns");
+    println!("Fetched {total_spawns} spawns");
 
     println!("Fetching extracts from tarkov.dev...");
-    let map_extracts = fetch_map_extracts(&client).await?;
+    let mut map_extracts = fetch_map_extracts(&graphql).await?;
     let total_extracts: usize = map_extracts.values().map(Vec::len).sum();
     println!("Fetched {total_extracts} extracts");
 
+    println!("Fetching translations from tarkov.dev...");
+    let languages = if args.lang.is_empty() {
+        LanguageCode::TRANSLATED.to_vec()
+    } else {
+        args.lang.clone()
+    };
+    let map_translations = fetch_map_translations(&graphql, &languages).await?;
+    for (map, extracts) in &mut map_extracts {
+        let Some(translations) = map_translations.get(map) else {
+            continue;
+        };
+        for extract in extracts {
+            extract.translations = translations.extracts.get(&extract.name).cloned();
+        }
+    }
+    let codes: Vec<&str> = languages.iter().map(|lang| lang.code()).collect();
+    println!(
+        "Fetched names in {} languages ({})",
+        languages.len(),
+        codes.join(", ")
+    );
+
+    println!("Fetching hazards from tarkov.dev...");
+    let map_hazards = fetch_map_hazards(&graphql).await?;
+    let total_hazards: usize = map_hazards.values().map(Vec::len).sum();
+    println!("Fetched {total_hazards} hazards");
+
+    println!("Fetching locks from tarkov.dev...");
+    let map_locks = fetch_map_locks(&graphql).await?;
+    let total_locks: usize = map_locks.values().map(Vec::len).sum();
+    println!("Fetched {total_locks} locks");
+
+    println!("Fetching loot containers from tarkov.dev...");
+    let map_loot_containers = fetch_map_loot_containers(&graphql).await?;
+    let total_containers: usize = map_loot_containers.values().map(Vec::len).sum();
+    let total_stashes = map_loot_containers
+        .values()
+        .flatten()
+        .filter(|container| container.category == ContainerCategory::Stash)
+        .count();
+    println!("Fetched {total_containers} loot containers ({total_stashes} hidden stashes)");
+
+    println!("Fetching bosses from tarkov.dev...");
+    let map_bosses = fetch_map_bosses(&graphql).await?;
+    let total_bosses: usize = map_bosses.values().map(Vec::len).sum();
+    println!("Fetched {total_bosses} boss spawns");
+
+    println!("Fetching stationary weapons from tarkov.dev...");
+    let map_stationary_weapons = fetch_map_stationary_weapons(&graphql).await?;
+    let total_weapons: usize = map_stationary_weapons.values().map(Vec::len).sum();
+    println!("Fetched {total_weapons} stationary weapons");
+
+    println!("Fetching switches from tarkov.dev...");
+    let map_switches = fetch_map_switches(&graphql, &map_extracts).await?;
+    let total_switches: usize = map_switches.values().map(Vec::len).sum();
+    println!("Fetched {total_switches} switches");
+
+    println!("Fetching transits from tarkov.dev...");
+    let map_transits = fetch_map_transits(&graphql).await?;
+    let total_transits: usize = map_transits.values().map(Vec::len).sum();
+    println!("Fetched {total_transits} transits");
+
+    println!("Fetching task objectives from tarkov.dev...");
+    let map_tasks = fetch_map_tasks(&graphql).await?;
+    let total_objectives: usize = map_tasks
+        .values()
+        .flatten()
+        .map(|task| task.objectives.len())
+        .sum();
+    println!("Fetched {total_objectives} task objectives");
+
+    let sources = &config.sources;
+    let branch = &sources.tarkov_dev_branch;
+    println!("Resolving tarkov-dev's {branch} branch...");
+    let source_commit = match fetch_source_commit(&client, sources).await {
+        Ok(commit) => {
+            println!("Using commit {commit}");
+            Some(commit)
+        }
+        Err(err) => {
+            eprintln!("Warning: couldn't resolve the commit ({err}), using {branch}");
+            None
+        }
+    };
+
     println!("Fetching maps from tarkov-dev...");
 
-    let response = client
-        .get(MAPS_JSON_URL)
-        .header(reqwest::header::USER_AGENT, USER_AGENT)
-        .send()
+    let revision = source_commit.as_deref().unwrap_or(branch);
+    let maps_json_url = format!(
+        "https://raw.githubusercontent.com/{}/{revision}/src/data/maps.json",
+        sources.tarkov_dev_repo
+    );
+    let cached_json = cache
+        .file(SourceCache::MAPS_JSON_FILE)
+        .filter(|_| args.incremental)
+        .and_then(|path| std::fs::read_to_string(path).ok());
+    let fetched = cache
+        .get(
+            &client,
+            SourceCache::MAPS_JSON_FILE,
+            &maps_json_url,
+            cached_json.is_some(),
+            "maps.json",
+        )
         .await?;
+    let json_text = match (fetched, cached_json) {
+        (Fetched::NotModified, Some(cached)) => {
+            println!("maps.json is unchanged, using the cached copy");
+            cached
+        }
+        (Fetched::Modified(bytes), _) => {
+            let text = String::from_utf8_lossy(&bytes).into_owned();
+            println!("Fetched {} bytes of JSON", text.len());
+            if let Some(path) = cache.file(SourceCache::MAPS_JSON_FILE) {
+                std::fs::create_dir_all(path.parent().unwrap_or(&path))?;
+                std::fs::write(path, &text)?;
+            }
+            text
+        }
+        (Fetched::NotModified, None) => unreachable!("only conditional requests are not modified"),
+    };
 
-    if !response.status().is_success() {
-        return Err(FetchError::HttpStatus {
-            resource: "maps.json".into(),
-            status: response.status().as_u16(),
-        });
+    let mut fetched_maps: Vec<FetchedMapGroup> = serde_json::from_str(&json_text)?;
+    println!("Parsed {} map groups", fetched_maps.len());
+
+    if !args.maps.is_empty() {
+        let unknown: Vec<&str> = args
+            .maps
+            .iter()
+            .filter(|name| !fetched_maps.iter().any(|group| group.matches(name)))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(FetchError::UnknownMaps(unknown.join(", ")));
+        }
+        fetched_maps.retain(|group| args.maps.iter().any(|name| group.matches(name)));
+        println!(
+            "Only processing {}",
+            fetched_maps
+                .iter()
+                .map(|group| group.normalized_name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
     }
-
-    let json_text = response.text().await?;
-    println!("Fetched {} bytes of JSON", json_text.len());
-
-    let fetched_maps: Vec<FetchedMapGroup> = serde_json::from_str(&json_text)?;
-    println!("Parsed {} map groups\n", fetched_maps.len());
+    println!();
 
     let multi_progress = MultiProgress::new();
     let maps_pb = multi_progress.add(ProgressBar::new(fetched_maps.len() as u64));
@@ -803,11 +3402,24 @@ async fn main() -> Result<(), FetchError> {
             &client,
             group,
             &map_names,
+            &map_translations,
             &map_spawns,
             &map_extracts,
+            &map_hazards,
+            &map_locks,
+            &map_loot_containers,
+            &map_bosses,
+            &map_stationary_weapons,
+            &map_switches,
+            &map_transits,
+            &map_tasks,
             &multi_progress,
-            args.force,
-            args.tile_zoom_offset,
+            &paths,
+            refresh,
+            &cache,
+            args.optimize,
+            config.tile_zoom_offset(&args, &group_name),
+            args.max_retries,
         )
         .await?
         {
@@ -825,25 +3437,95 @@ async fn main() -> Result<(), FetchError> {
         maps.len()
     );
 
+    let output_path = paths.maps_ron();
+    let previous = std::fs::read_to_string(&output_path)
+        .ok()
+        .and_then(|text| ron::from_str::<MapsFile>(&text).ok());
+
+    // With --maps, the maps that weren't processed are kept from the previous file
+    if !args.maps.is_empty() {
+        match &previous {
+            Some(previous) => maps = merge_processed_maps(&previous.maps, maps),
+            None => eprintln!(
+                "Warning: no readable {MAPS_RON_FILE} to keep other maps from, \
+                 writing only the processed maps"
+            ),
+        }
+    }
+
+    let mut warnings = 0usize;
+    for map in &maps {
+        for warning in map.validate() {
+            if warnings == 0 {
+                println!("\nData warnings:");
+            }
+            println!("  - {}: {warning}", map.normalized_name);
+            warnings += 1;
+        }
+    }
+
     let pretty_config = PrettyConfig::new()
         .depth_limit(10)
         .indentor("  ".to_owned())
         .struct_names(true)
         .enumerate_arrays(false);
 
-    let ron_string = ron::ser::to_string_pretty(&maps, pretty_config)?;
+    let file = MapsFile {
+        schema_version: MAPS_SCHEMA_VERSION,
+        fetched_at: Some(Utc::now()),
+        source_commit,
+        maps,
+    };
+    let ron_string = ron::ser::to_string_pretty(&file, pretty_config)?;
     println!("Serialized to {} bytes of RON", ron_string.len());
 
-    std::fs::create_dir_all(repo_path(MAPS_DIR))?;
+    std::fs::create_dir_all(&paths.output_dir)?;
 
-    let output_path = repo_path(MAPS_RON_PATH);
     std::fs::write(&output_path, &ron_string)?;
     println!("Wrote maps to {}", output_path.display());
+    let manifest = write_data_manifest(&paths, &file)?;
+    println!(
+        "Wrote data manifest {} ({} files)",
+        manifest.version,
+        manifest.files.len()
+    );
+    cache.save()?;
 
     println!("\nMaps:");
-    for map in &maps {
+    for map in &file.maps {
         println!("  - {} ({})", map.name, map.normalized_name);
     }
 
+    if let Some(previous) = previous {
+        let changes = diff(&previous.maps, &file.maps);
+        if changes.is_empty() {
+            println!("\nNo changes since the previous {MAPS_RON_FILE}");
+        } else {
+            println!("\nChanges since the previous {MAPS_RON_FILE}:");
+            for line in changes.to_string().lines() {
+                println!("  - {line}");
+            }
+        }
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_tile_zoom_offset_overrides_map_offsets() {
+        let config: Config =
+            toml::from_str("tile-zoom-offset = 1\n[map-tile-zoom-offsets]\ncustoms = 3\n").unwrap();
+
+        let args = Args::parse_from(["fetch_maps"]);
+        assert_eq!(config.tile_zoom_offset(&args, "customs"), 3);
+        assert_eq!(config.tile_zoom_offset(&args, "woods"), 1);
+
+        let args = Args::parse_from(["fetch_maps", "--tile-zoom-offset", "0"]);
+        assert_eq!(config.tile_zoom_offset(&args, "customs"), 0);
+        assert_eq!(config.tile_zoom_offset(&args, "woods"), 0);
+    }
+}
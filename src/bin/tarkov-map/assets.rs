@@ -1,15 +1,25 @@
 //! Asset embedding and loading utilities.
 
+use chrono::{DateTime, Utc};
 use rust_embed::RustEmbed;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
 use std::sync::mpsc;
-use tarkov_map::TarkovMaps;
+use tarkov_map::{
+    DATA_MANIFEST_FILE, DataManifest, MAPS_SCHEMA_VERSION, MapsFile, MapsJson, TarkovMaps,
+};
 use thiserror::Error;
 
 /// Embeds all assets from the assets/ directory into the binary.
 /// In debug mode, assets are loaded from the filesystem for faster iteration.
 /// In release mode, assets are compressed and embedded in the binary.
+/// Tile pyramids from `fetch_maps --keep-tiles` are left out, the viewer draws
+/// the full images.
 #[derive(RustEmbed)]
 #[folder = "assets/"]
+#[exclude = "maps/tiles/*"]
 pub struct Assets;
 
 /// Errors that can occur when loading map data.
@@ -19,8 +29,23 @@ pub enum MapLoadError {
     MapsNotFound,
     #[error("invalid UTF-8 in maps.ron: {0}")]
     InvalidUtf8(#[from] std::str::Utf8Error),
-    #[error("failed to parse maps.ron: {0}")]
-    ParseError(#[from] ron::de::SpannedError),
+    #[error("invalid maps.ron: {0}")]
+    Format(#[from] MapsFormatError),
+}
+
+/// Errors that can occur when parsing data in the `maps.ron` format.
+#[derive(Error, Debug)]
+pub enum MapsFormatError {
+    #[error("{0}")]
+    Parse(#[from] ron::de::SpannedError),
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+    #[error(
+        "schema version {found} is newer than the supported version {MAPS_SCHEMA_VERSION}; update the app"
+    )]
+    Newer { found: u32 },
+    #[error("schema version {found} is no longer supported; regenerate the assets with fetch_maps")]
+    Outdated { found: u32 },
 }
 
 /// Errors that can occur when loading and decoding images.
@@ -28,6 +53,11 @@ pub enum MapLoadError {
 pub enum ImageLoadError {
     #[error("asset not found: {0}")]
     AssetNotFound(String),
+    #[error("failed to read image '{path}': {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
     #[error("failed to decode image '{path}': {source}")]
     DecodeError {
         path: String,
@@ -52,15 +82,32 @@ pub enum AssetLoadState {
     Error(String),
 }
 
-/// Loads and decodes an image from embedded assets.
+/// Reads the encoded bytes of an image from embedded assets.
+///
+/// Absolute paths, as used by custom maps, are read from disk instead.
+pub fn read_image_bytes(path: &str) -> Result<Cow<'static, [u8]>, ImageLoadError> {
+    if Path::new(path).is_absolute() {
+        Ok(Cow::Owned(fs::read(path).map_err(|source| {
+            ImageLoadError::Read {
+                path: path.to_string(),
+                source,
+            }
+        })?))
+    } else {
+        Ok(Assets::get(path)
+            .ok_or_else(|| ImageLoadError::AssetNotFound(path.to_string()))?
+            .data)
+    }
+}
+
+/// Loads and decodes a PNG, JPEG or WebP image from embedded assets or disk.
 pub fn load_and_decode_image(path: &str) -> Result<DecodedImage, ImageLoadError> {
-    let file = Assets::get(path).ok_or_else(|| ImageLoadError::AssetNotFound(path.to_string()))?;
+    let data = read_image_bytes(path)?;
 
-    let img =
-        image::load_from_memory(&file.data).map_err(|source| ImageLoadError::DecodeError {
-            path: path.to_string(),
-            source,
-        })?;
+    let img = image::load_from_memory(&data).map_err(|source| ImageLoadError::DecodeError {
+        path: path.to_string(),
+        source,
+    })?;
     let rgba = img.to_rgba8();
     let (width, height) = rgba.dimensions();
 
@@ -71,9 +118,97 @@ pub fn load_and_decode_image(path: &str) -> Result<DecodedImage, ImageLoadError>
     })
 }
 
+/// Just the version of a [`MapsFile`], read before parsing the rest.
+#[derive(Deserialize)]
+#[serde(rename = "MapsFile", rename_all = "camelCase")]
+struct SchemaVersion {
+    schema_version: u32,
+}
+
+/// File extensions of map data files: `maps.ron` and its JSON equivalent.
+pub const MAP_DATA_EXTENSIONS: [&str; 2] = ["ron", "json"];
+
+/// Returns `true` if `path` has one of the [`MAP_DATA_EXTENSIONS`].
+pub fn is_map_data_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| MAP_DATA_EXTENSIONS.contains(&ext))
+}
+
+/// Returns `true` if `text` starts like JSON rather than RON.
+///
+/// A RON file opens with a struct `(` or a list of structs `[(`, JSON with
+/// an object `{` or a list of objects `[{`.
+fn looks_like_json(text: &str) -> bool {
+    let mut chars = text.chars().filter(|c| !c.is_whitespace());
+    match chars.next() {
+        Some('{') => true,
+        Some('[') => matches!(chars.next(), Some('{' | ']')),
+        _ => false,
+    }
+}
+
+/// Fails for schema versions other than [`MAPS_SCHEMA_VERSION`].
+fn check_schema_version(found: u32) -> Result<(), MapsFormatError> {
+    match found {
+        MAPS_SCHEMA_VERSION => Ok(()),
+        found if found > MAPS_SCHEMA_VERSION => Err(MapsFormatError::Newer { found }),
+        found => Err(MapsFormatError::Outdated { found }),
+    }
+}
+
+/// Parses data in the `maps.ron` format or its JSON equivalent, migrating
+/// older schema versions.
+pub fn parse_maps(text: &str) -> Result<TarkovMaps, MapsFormatError> {
+    if looks_like_json(text) {
+        let Ok(SchemaVersion { schema_version }) = serde_json::from_str(text) else {
+            return Ok(TarkovMaps::from_json_str(text)?);
+        };
+        check_schema_version(schema_version)?;
+        return Ok(serde_json::from_str::<MapsFile>(text)?.maps);
+    }
+
+    let Ok(SchemaVersion { schema_version }) = ron::from_str(text) else {
+        // Version 0: a bare list of maps, still readable by the current model
+        return Ok(ron::from_str(text)?);
+    };
+    check_schema_version(schema_version)?;
+    Ok(ron::from_str::<MapsFile>(text)?.maps)
+}
+
+/// When and from which tarkov-dev commit a [`MapsFile`] was fetched, read without the maps.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename = "MapsFile", rename_all = "camelCase")]
+pub struct DataProvenance {
+    #[serde(default)]
+    pub fetched_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub source_commit: Option<String>,
+    /// Version and file count from the [`DataManifest`], if the data has one.
+    #[serde(skip)]
+    pub manifest: Option<(String, usize)>,
+}
+
+/// Reads the provenance of the embedded map data. Data written before it was
+/// recorded has none.
+pub fn load_data_provenance() -> DataProvenance {
+    let read_text = |name: &str| {
+        let file = Assets::get(name)?;
+        let text = std::str::from_utf8(&file.data).ok()?.to_owned();
+        Some(text)
+    };
+    let mut provenance: DataProvenance = read_text("maps.ron")
+        .and_then(|text| ron::from_str(&text).ok())
+        .unwrap_or_default();
+    provenance.manifest = read_text(DATA_MANIFEST_FILE)
+        .and_then(|text| ron::from_str::<DataManifest>(&text).ok())
+        .map(|manifest| (manifest.version, manifest.files.len()));
+    provenance
+}
+
 /// Loads the map data from embedded assets.
 pub fn load_maps() -> Result<TarkovMaps, MapLoadError> {
     let file = Assets::get("maps.ron").ok_or(MapLoadError::MapsNotFound)?;
     let ron_string = std::str::from_utf8(&file.data)?;
-    Ok(ron::from_str(ron_string)?)
+    Ok(parse_maps(ron_string)?)
 }
@@ -0,0 +1,130 @@
+//! Named view bookmarks that can be recalled from the sidebar or with hotkeys.
+//!
+//! A bookmark restores the map, the view and the selected floor.
+
+use crate::TarkovMapApp;
+use crate::usage_stats::Feature;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use tarkov_map::MapView;
+
+/// Number keys bound to the first bookmarks (1-9).
+const BOOKMARK_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
+/// A saved view of a map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    /// Normalized name of the bookmarked map.
+    pub map: String,
+    pub view: MapView,
+    /// Name of the selected floor, or `None` for the base map.
+    #[serde(default)]
+    pub layer: Option<String>,
+}
+
+impl TarkovMapApp {
+    /// Switches to the bookmarked map and view.
+    fn jump_to_bookmark(&mut self, index: usize) {
+        let Some(bookmark) = self.bookmarks.get(index) else {
+            return;
+        };
+        let Some(map_idx) = self
+            .maps
+            .iter()
+            .position(|map| map.normalized_name == bookmark.map)
+        else {
+            return;
+        };
+
+        let view = bookmark.view;
+        let layer = bookmark.layer.clone();
+        self.usage.feature_used(Feature::Bookmark);
+        self.select_map(map_idx);
+        self.select_layer_by_name(layer.as_deref());
+        self.pending_view = Some(view);
+    }
+
+    /// Jumps to bookmarks bound to number keys 1-9.
+    pub fn handle_bookmark_keys(&mut self, input: &egui::InputState) {
+        if let Some(index) = BOOKMARK_KEYS.iter().position(|key| input.key_pressed(*key)) {
+            self.jump_to_bookmark(index);
+        }
+    }
+
+    /// Renders the bookmark list and the "add bookmark" row.
+    pub fn show_bookmarks(&mut self, ui: &mut egui::Ui) {
+        let mut jump_to = None;
+        let mut remove = None;
+
+        for (idx, bookmark) in self.bookmarks.iter().enumerate() {
+            let map_name = self
+                .maps
+                .iter()
+                .find(|map| map.normalized_name == bookmark.map)
+                .map_or(bookmark.map.as_str(), |map| map.name.as_str());
+
+            ui.horizontal(|ui| {
+                let hotkey = if idx < BOOKMARK_KEYS.len() {
+                    format!("{}", idx + 1)
+                } else {
+                    " ".to_owned()
+                };
+                ui.weak(hotkey);
+                if ui
+                    .selectable_label(false, &bookmark.name)
+                    .on_hover_text(map_name)
+                    .clicked()
+                {
+                    jump_to = Some(idx);
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                        remove = Some(idx);
+                    }
+                });
+            });
+        }
+
+        if let Some(idx) = jump_to {
+            self.jump_to_bookmark(idx);
+        }
+        if let Some(idx) = remove {
+            self.bookmarks.remove(idx);
+        }
+
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.new_bookmark_name)
+                    .hint_text("Name")
+                    .desired_width(110.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let name = self.new_bookmark_name.trim().to_owned();
+
+            if (ui.button("Add").clicked() || submitted)
+                && !name.is_empty()
+                && let Some(map) = self.selected_map()
+            {
+                let bookmark = Bookmark {
+                    name,
+                    map: map.normalized_name.clone(),
+                    view: self.current_view(),
+                    layer: self.selected_layer_name(),
+                };
+                self.bookmarks.push(bookmark);
+                self.new_bookmark_name.clear();
+            }
+        });
+    }
+}
@@ -0,0 +1,53 @@
+//! Hover details for boss spawn markers.
+
+use crate::TarkovMapApp;
+use crate::marker_picking::{MarkerHit, MarkerKind};
+use eframe::egui;
+use tarkov_map::{BossSpawn, Map};
+
+impl TarkovMapApp {
+    /// Shows a tooltip with the name, spawn chance and spawn zones of the picked boss at
+    /// `index`, and of the bosses sharing its spawn positions.
+    pub fn show_boss_tooltip(&self, response: &egui::Response, map: &Map, index: usize) {
+        let bosses: Vec<&BossSpawn> = map.bosses.iter().flatten().collect();
+        let Some(picked) = bosses.get(index) else {
+            return;
+        };
+
+        // Several bosses (e.g. the Goons) can share a spawn position
+        let hovered: Vec<&BossSpawn> = bosses
+            .iter()
+            .copied()
+            .filter(|boss| {
+                boss.positions
+                    .iter()
+                    .any(|position| picked.positions.contains(position))
+            })
+            .collect();
+
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            for boss in hovered {
+                ui.horizontal(|ui| {
+                    ui.strong(&boss.name);
+                    ui.label(format!("{:.0}%", boss.spawn_chance * 100.0));
+                });
+                // A single zone always has a 100% chance, so only list a choice of zones
+                if boss.locations.len() > 1 {
+                    for location in &boss.locations {
+                        ui.label(format!(
+                            "  {} {:.0}%",
+                            location.name,
+                            location.chance * 100.0
+                        ));
+                    }
+                }
+            }
+            let hit = MarkerHit {
+                kind: MarkerKind::Boss,
+                index,
+            };
+            self.show_marker_note(ui, map, hit);
+            self.marker_picking.cycle_hint(ui);
+        });
+    }
+}
@@ -0,0 +1,114 @@
+//! Extract filter by what the player is carrying.
+//!
+//! Flare, car and co-op extracts each need something brought into the raid.
+//! With the filter on, extracts whose requirements aren't ticked off are dimmed
+//! (or hidden) on the map and in the extract list.
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tarkov_map::Extract;
+
+/// Opacity of extracts that can't be used with what is carried.
+pub const UNUSABLE_EXTRACT_OPACITY: f32 = 0.3;
+
+/// Item names paid at car extracts.
+const CURRENCIES: &[&str] = &["Roubles", "Dollars", "Euros"];
+
+/// Something an extract needs to be brought along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Requirement {
+    GreenFlare,
+    CarPayment,
+    ScavBuddy,
+}
+
+impl Requirement {
+    pub const ALL: [Requirement; 3] = [
+        Requirement::GreenFlare,
+        Requirement::CarPayment,
+        Requirement::ScavBuddy,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Requirement::GreenFlare => "Green flare",
+            Requirement::CarPayment => "Car payment",
+            Requirement::ScavBuddy => "Scav buddy",
+        }
+    }
+
+    /// Returns `true` if `extract` needs this requirement.
+    ///
+    /// Uses the extract's required item where the map data has one, and its
+    /// name ("(Flare)", "V-Ex") otherwise.
+    fn applies_to(self, extract: &Extract) -> bool {
+        let item = extract
+            .required_item
+            .as_ref()
+            .map(|item| item.name.as_str());
+        match self {
+            Requirement::GreenFlare => {
+                item.is_some_and(|name| {
+                    let name = name.to_lowercase();
+                    name.contains("flare") || name.contains("signal cartridge")
+                }) || extract.name.contains("(Flare)")
+            }
+            Requirement::CarPayment => {
+                item.is_some_and(|name| CURRENCIES.contains(&name)) || extract.name.contains("V-Ex")
+            }
+            Requirement::ScavBuddy => extract.coop,
+        }
+    }
+}
+
+/// Which requirements the player has ticked off, and whether extracts are filtered by them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CarriedItems {
+    pub enabled: bool,
+    /// Hide unusable extracts instead of dimming them.
+    pub hide: bool,
+    pub carried: BTreeSet<Requirement>,
+}
+
+impl CarriedItems {
+    /// Returns `true` if `extract` can be used with what is carried, or the filter is off.
+    pub fn can_use(&self, extract: &Extract) -> bool {
+        !self.enabled
+            || Requirement::ALL
+                .iter()
+                .all(|req| !req.applies_to(extract) || self.carried.contains(req))
+    }
+
+    /// Opacity to draw `extract` with, or `None` to skip it.
+    pub fn extract_opacity(&self, extract: &Extract) -> Option<f32> {
+        match (self.can_use(extract), self.hide) {
+            (true, _) => Some(1.0),
+            (false, false) => Some(UNUSABLE_EXTRACT_OPACITY),
+            (false, true) => None,
+        }
+    }
+
+    /// Renders the filter toggle and the "carrying" toggles.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, "Filter by what I carry")
+            .on_hover_text("Dim extracts whose requirements you can't meet");
+        if !self.enabled {
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            for req in Requirement::ALL {
+                let carried = self.carried.contains(&req);
+                if ui.selectable_label(carried, req.label()).clicked() {
+                    if carried {
+                        self.carried.remove(&req);
+                    } else {
+                        self.carried.insert(req);
+                    }
+                }
+            }
+        });
+        ui.checkbox(&mut self.hide, "Hide instead of dim");
+    }
+}
@@ -0,0 +1,263 @@
+//! Pre-raid checklists.
+//!
+//! The sidebar lists the requirements of the visible extracts. The Raid
+//! Checklist window holds the user's own tasks, shared across maps or specific
+//! to one, which are unticked again when a new raid is detected.
+
+use crate::TarkovMapApp;
+use crate::overlays::OverlayVisibility;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tarkov_map::{Extract, Map};
+
+/// A single thing to bring or do before using an extract.
+struct ChecklistItem {
+    text: String,
+    extract: String,
+}
+
+/// Collects the requirements of the extracts currently shown on `map`.
+fn checklist_items(map: &Map, overlays: &OverlayVisibility) -> Vec<ChecklistItem> {
+    let visible = map
+        .extracts
+        .iter()
+        .flatten()
+        .filter(|extract| overlays.shows_extract(&extract.faction));
+
+    let mut items: Vec<ChecklistItem> = visible.flat_map(extract_requirements).collect();
+    items.sort_by(|a, b| a.text.cmp(&b.text).then(a.extract.cmp(&b.extract)));
+    items
+}
+
+fn extract_requirements(extract: &Extract) -> Vec<ChecklistItem> {
+    let item = extract.required_item.iter().map(|item| ChecklistItem {
+        text: format!("{} ×{}", item.name, item.count),
+        extract: extract.name.clone(),
+    });
+    let switches = extract
+        .switches
+        .iter()
+        .flatten()
+        .map(|switch| ChecklistItem {
+            text: format!("Activate {switch}"),
+            extract: extract.name.clone(),
+        });
+    let coop = extract.coop.then(|| ChecklistItem {
+        text: "Find a scav to extract with".to_owned(),
+        extract: extract.name.clone(),
+    });
+    item.chain(switches).chain(coop).collect()
+}
+
+/// Formats the checklist as plain text, one requirement per line.
+fn checklist_text(map: &Map, items: &[ChecklistItem]) -> String {
+    let mut text = format!("{} pre-raid checklist\n", map.name);
+    for item in items {
+        text.push_str(&format!("[ ] {} (for {})\n", item.text, item.extract));
+    }
+    text
+}
+
+impl TarkovMapApp {
+    /// Renders the checklist of requirements for the visible extracts on the selected map.
+    pub fn show_checklist(&self, ui: &mut egui::Ui) {
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let items = checklist_items(map, &self.overlays);
+        if items.is_empty() {
+            ui.weak("Visible extracts have no requirements");
+            return;
+        }
+
+        for item in &items {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(&item.text);
+                ui.weak(format!("for {}", item.extract));
+            });
+        }
+
+        if ui
+            .button("Copy")
+            .on_hover_text("Copy checklist as text")
+            .clicked()
+        {
+            ui.ctx().copy_text(checklist_text(map, &items));
+        }
+    }
+}
+
+/// A user-defined pre-raid task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistTask {
+    pub text: String,
+    pub done: bool,
+}
+
+impl ChecklistTask {
+    fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            done: false,
+        }
+    }
+}
+
+/// The user's own pre-raid checklist, persisted with the app settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RaidChecklist {
+    /// Whether the Raid Checklist window is shown.
+    pub open: bool,
+    /// Tasks shown on every map.
+    pub common: Vec<ChecklistTask>,
+    /// Tasks for a single map, keyed by map normalized name.
+    pub per_map: HashMap<String, Vec<ChecklistTask>>,
+    /// Untick all tasks when a new raid is detected.
+    pub reset_on_raid: bool,
+    /// Text typed into the "add task" field.
+    #[serde(skip)]
+    new_task: String,
+    /// Whether tasks can be removed.
+    #[serde(skip)]
+    editing: bool,
+}
+
+impl Default for RaidChecklist {
+    fn default() -> Self {
+        Self {
+            open: false,
+            common: vec![
+                ChecklistTask::new("Bring keys"),
+                ChecklistTask::new("Insure weapon"),
+                ChecklistTask::new("Check extracts"),
+            ],
+            per_map: HashMap::new(),
+            reset_on_raid: true,
+            new_task: String::new(),
+            editing: false,
+        }
+    }
+}
+
+impl RaidChecklist {
+    /// Unticks every task on every map.
+    pub fn reset(&mut self) {
+        let tasks = self
+            .common
+            .iter_mut()
+            .chain(self.per_map.values_mut().flatten());
+        for task in tasks {
+            task.done = false;
+        }
+    }
+
+    /// Renders a list of tasks. Returns the index of a task to remove, if any.
+    fn tasks_ui(ui: &mut egui::Ui, tasks: &mut [ChecklistTask], editing: bool) -> Option<usize> {
+        let mut remove = None;
+        for (index, task) in tasks.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut task.done, &task.text);
+                if editing && ui.small_button("×").on_hover_text("Remove").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        remove
+    }
+
+    /// Renders the checklist for `map`, with controls for editing it.
+    fn ui(&mut self, ui: &mut egui::Ui, map: Option<&Map>) {
+        if let Some(index) = Self::tasks_ui(ui, &mut self.common, self.editing) {
+            self.common.remove(index);
+        }
+
+        if let Some(map) = map {
+            let tasks = self.per_map.entry(map.normalized_name.clone()).or_default();
+            if !tasks.is_empty() || self.editing {
+                ui.separator();
+                ui.weak(&map.name);
+            }
+            if let Some(index) = Self::tasks_ui(ui, tasks, self.editing) {
+                tasks.remove(index);
+            }
+            if tasks.is_empty() {
+                self.per_map.remove(&map.normalized_name);
+            }
+        }
+
+        if self.editing {
+            ui.separator();
+            ui.text_edit_singleline(&mut self.new_task);
+            let text = self.new_task.trim().to_owned();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!text.is_empty(), egui::Button::new("Add for all maps"))
+                    .clicked()
+                {
+                    self.common.push(ChecklistTask::new(&text));
+                    self.new_task.clear();
+                }
+                if let Some(map) = map
+                    && ui
+                        .add_enabled(!text.is_empty(), egui::Button::new("Add for this map"))
+                        .clicked()
+                {
+                    self.per_map
+                        .entry(map.normalized_name.clone())
+                        .or_default()
+                        .push(ChecklistTask::new(&text));
+                    self.new_task.clear();
+                }
+            });
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui
+                .button("Reset")
+                .on_hover_text("Untick all tasks")
+                .clicked()
+            {
+                self.reset();
+            }
+            ui.toggle_value(&mut self.editing, "Edit");
+        });
+        ui.checkbox(&mut self.reset_on_raid, "Reset when a raid starts")
+            .on_hover_text("A raid is detected from screenshot positions");
+    }
+}
+
+impl TarkovMapApp {
+    /// Renders the Raid Checklist window.
+    pub fn show_raid_checklist_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.raid_checklist.open;
+        let map = self.maps.get(self.selected_map);
+        egui::Window::new("Raid Checklist")
+            .open(&mut open)
+            .default_width(200.0)
+            .resizable(false)
+            .show(ctx, |ui| {
+                self.raid_checklist.ui(ui, map);
+            });
+        self.raid_checklist.open = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coop_extract_needs_a_scav() {
+        let extract: Extract = ron::from_str(
+            r#"(name: "Scav Lands", faction: "shared", coop: true, position: Some((0.0, 0.0, 0.0)))"#,
+        )
+        .unwrap();
+        let items = extract_requirements(&extract);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].text, "Find a scav to extract with");
+        assert_eq!(items[0].extract, "Scav Lands");
+    }
+}
@@ -0,0 +1,112 @@
+//! Optional clipboard watcher that picks up Tarkov coordinate strings.
+//!
+//! Recognizes `x, y, z` (as in screenshot names) and pairs in the selected
+//! [`CoordinateConvention`], e.g. `-198.89, 22.74, -345.97`, and offers to
+//! place a temporary marker there.
+
+use crate::coordinates::CoordinateConvention;
+use crate::updater::render_action_toast;
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions, Toasts};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+const CLIPBOARD_TOAST_KIND: u32 = 3;
+const POLL_INTERVAL: Duration = Duration::from_millis(750);
+
+/// Registers the "place marker" toast renderer. Clicking the action sends on `place_tx`.
+pub fn configure_toasts(toasts: Toasts, place_tx: Sender<()>) -> Toasts {
+    toasts.custom_contents(CLIPBOARD_TOAST_KIND, move |ui, toast| {
+        render_action_toast(
+            ui,
+            toast,
+            toast.style.info_icon.clone(),
+            "Place marker",
+            (),
+            &place_tx,
+        )
+    })
+}
+
+/// Shows a toast offering to place a marker at `position`.
+pub fn offer_marker_toast(
+    toasts: &mut Toasts,
+    position: [f64; 2],
+    convention: CoordinateConvention,
+) {
+    let [x, z] = position;
+    toasts.add(Toast {
+        kind: ToastKind::Custom(CLIPBOARD_TOAST_KIND),
+        text: format!("Coordinates copied: {}", convention.format(x, None, z)).into(),
+        options: ToastOptions::default().duration_in_seconds(10.0),
+        ..Default::default()
+    });
+}
+
+/// Polls the system clipboard in a background thread for coordinate strings.
+pub struct ClipboardWatcher {
+    position_rx: Receiver<[f64; 2]>,
+    stop: Arc<AtomicBool>,
+}
+
+impl ClipboardWatcher {
+    /// Starts watching the clipboard, reading pairs in `convention`.
+    ///
+    /// Returns `None` if the clipboard is unavailable.
+    pub fn new(ctx: egui::Context, convention: CoordinateConvention) -> Option<Self> {
+        let mut clipboard = match arboard::Clipboard::new() {
+            Ok(clipboard) => clipboard,
+            Err(err) => {
+                log::warn!("Clipboard unavailable: {err}");
+                return None;
+            }
+        };
+
+        let (position_tx, position_rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread_stop = stop.clone();
+        thread::spawn(move || {
+            // Ignore whatever is on the clipboard when watching starts
+            let mut last_text = clipboard.get_text().ok();
+
+            while !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(POLL_INTERVAL);
+
+                let Ok(text) = clipboard.get_text() else {
+                    continue;
+                };
+                if last_text.as_deref() == Some(text.as_str()) {
+                    continue;
+                }
+
+                if let Some(position) = convention.parse(&text) {
+                    log::info!(
+                        "Clipboard coordinates: [{:.2}, {:.2}]",
+                        position[0],
+                        position[1]
+                    );
+                    let _ = position_tx.send(position);
+                    ctx.request_repaint();
+                }
+                last_text = Some(text);
+            }
+        });
+
+        Some(Self { position_rx, stop })
+    }
+
+    /// Returns the most recent coordinates copied since the last poll.
+    pub fn poll(&self) -> Option<[f64; 2]> {
+        self.position_rx.try_iter().last()
+    }
+}
+
+impl Drop for ClipboardWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
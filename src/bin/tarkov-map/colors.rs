@@ -5,6 +5,10 @@ use eframe::egui::Color32;
 // Spawn markers
 pub const SPAWN_FILL: Color32 = Color32::from_rgb(50, 205, 50);
 pub const SPAWN_STROKE: Color32 = Color32::from_rgb(0, 100, 0);
+pub const SCAV_SPAWN_FILL: Color32 = Color32::from_rgb(240, 200, 60);
+pub const SCAV_SPAWN_STROKE: Color32 = Color32::from_rgb(120, 90, 0);
+pub const AI_SPAWN_FILL: Color32 = Color32::from_rgb(170, 170, 170);
+pub const AI_SPAWN_STROKE: Color32 = Color32::from_rgb(60, 60, 60);
 
 // PMC extract markers
 pub const PMC_EXTRACT_FILL: Color32 = Color32::from_rgb(65, 105, 225);
@@ -22,7 +26,68 @@ pub const SHARED_EXTRACT_STROKE: Color32 = Color32::from_rgb(75, 0, 130);
 pub const PLAYER_MARKER_FILL: Color32 = Color32::from_rgb(255, 50, 50);
 pub const PLAYER_MARKER_STROKE: Color32 = Color32::from_rgb(139, 0, 0);
 
+// Temporary marker
+pub const TEMP_MARKER_FILL: Color32 = Color32::from_rgb(0, 229, 255);
+pub const TEMP_MARKER_STROKE: Color32 = Color32::from_rgb(0, 96, 100);
+
+// Hazard zones
+pub const HAZARD_FILL: Color32 = Color32::from_rgba_premultiplied(90, 0, 0, 90);
+pub const HAZARD_STROKE: Color32 = Color32::from_rgb(220, 20, 20);
+
+// Lock markers
+pub const LOCK_KEY: Color32 = Color32::from_rgb(255, 200, 60);
+
+// Switch markers and their links to extracts
+pub const SWITCH: Color32 = Color32::from_rgb(120, 220, 255);
+
+// Transit markers and zones
+pub const TRANSIT: Color32 = Color32::from_rgb(80, 220, 140);
+
+// Hidden stash markers
+pub const HIDDEN_STASH: Color32 = Color32::from_rgb(190, 150, 90);
+
+// Task objective markers and zones
+pub const TASK_OBJECTIVE: Color32 = Color32::from_rgb(255, 230, 90);
+
+// Stationary weapon markers
+pub const STATIONARY_WEAPON: Color32 = Color32::from_rgb(255, 140, 0);
+
+// Boss spawn markers
+pub const BOSS_FILL: Color32 = Color32::from_rgb(200, 40, 40);
+pub const BOSS_STROKE: Color32 = Color32::from_rgb(20, 20, 20);
+
+// Loot container markers
+pub const LOOT_CONTAINER: Color32 = Color32::from_rgb(170, 140, 255);
+
+// Measure tool
+pub const MEASURE_LINE: Color32 = Color32::from_rgb(255, 215, 0);
+
+// Replayed raid session path
+pub const REPLAY_PATH: Color32 = Color32::from_rgb(255, 105, 180);
+
+// Tint of the map image while a layer is drawn over it
+pub const MAP_UNDER_LAYER: Color32 = Color32::from_gray(110);
+
+// Data comparison highlights
+pub const CHANGE_ADDED: Color32 = Color32::from_rgb(0, 230, 118);
+pub const CHANGE_REMOVED: Color32 = Color32::from_rgb(255, 23, 68);
+pub const CHANGE_MOVED: Color32 = Color32::from_rgb(255, 234, 0);
+
+// Elevation shading bands, from lowest to highest
+pub const ELEVATION_BANDS: [Color32; 5] = [
+    Color32::from_rgb(49, 54, 149),
+    Color32::from_rgb(69, 117, 180),
+    Color32::from_rgb(254, 224, 144),
+    Color32::from_rgb(244, 109, 67),
+    Color32::from_rgb(215, 48, 39),
+];
+
+// Personal heatmap, from rarely to often visited
+pub const HEATMAP_COLD: Color32 = Color32::from_rgb(40, 120, 220);
+pub const HEATMAP_HOT: Color32 = Color32::from_rgb(255, 70, 40);
+
 // Text colors
 pub const LABEL_TEXT: Color32 = Color32::from_rgba_premultiplied(255, 255, 255, 220);
 pub const LABEL_SHADOW: Color32 = Color32::from_rgba_premultiplied(0, 0, 0, 180);
 pub const EXTRACT_TEXT_SHADOW: Color32 = Color32::from_rgba_premultiplied(0, 0, 0, 200);
+pub const LABEL_BACKGROUND: Color32 = Color32::from_rgba_premultiplied(0, 0, 0, 160);
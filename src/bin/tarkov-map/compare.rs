@@ -0,0 +1,291 @@
+//! "What changed" view comparing the loaded maps against an older `maps.ron`.
+//!
+//! Dropping a `maps.ron` file onto the window loads it as the comparison
+//! baseline. Added, removed, and moved features are highlighted on the map.
+//!
+//! The app also keeps a copy of the built-in map data from its last run, so
+//! after an update that changes the data it opens the comparison by itself.
+
+use crate::assets::{Assets, is_map_data_file, parse_maps};
+use crate::colors;
+use crate::coordinates::game_to_display;
+use crate::usage_stats::Feature;
+use crate::{APP_ID, TarkovMapApp};
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use std::path::{Path, PathBuf};
+use tarkov_map::{Extract, FeatureChange, Label, Map, MapDiff, MapsDiff, Position3, TarkovMaps};
+use thiserror::Error;
+
+/// Errors that can occur when loading a comparison dataset.
+#[derive(Error, Debug)]
+pub enum CompareError {
+    #[error("failed to read '{path}': {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("failed to parse '{path}': {message}")]
+    Parse { path: String, message: String },
+}
+
+/// A loaded comparison between an older dataset and the current one.
+pub struct Comparison {
+    /// File name of the older dataset.
+    pub source: String,
+    pub diff: MapsDiff,
+}
+
+impl Comparison {
+    /// Loads an older `maps.ron` and diffs the current maps against it.
+    pub fn load(path: &Path, current: &TarkovMaps) -> Result<Self, CompareError> {
+        let path_str = path.display().to_string();
+        let text = std::fs::read_to_string(path).map_err(|source| CompareError::Read {
+            path: path_str.clone(),
+            source,
+        })?;
+        let old = parse_maps(&text).map_err(|e| CompareError::Parse {
+            path: path_str.clone(),
+            message: e.to_string(),
+        })?;
+
+        Ok(Self {
+            source: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or(path_str),
+            diff: tarkov_map::diff::diff(&old, current),
+        })
+    }
+}
+
+/// Copy of the built-in `maps.ron` from the last run.
+fn previous_maps_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("previous_maps.ron"))
+}
+
+impl TarkovMapApp {
+    /// Opens a comparison against the built-in map data of the last run, if it changed since.
+    ///
+    /// Saves the current built-in data as the baseline for the next run.
+    pub fn compare_after_data_update(&mut self) {
+        let Some(path) = previous_maps_path() else {
+            return;
+        };
+        let Some(file) = Assets::get("maps.ron") else {
+            return;
+        };
+        let previous = std::fs::read(&path).ok();
+        if previous.as_deref() == Some(&*file.data) {
+            return;
+        }
+
+        if let Some(previous) = previous {
+            let old = std::str::from_utf8(&previous).ok().map(parse_maps);
+            let new = std::str::from_utf8(&file.data).ok().map(parse_maps);
+            match (old, new) {
+                (Some(Ok(old)), Some(Ok(new))) => {
+                    let diff = tarkov_map::diff::diff(&old, &new);
+                    if !diff.is_empty() {
+                        log::info!("Map data changed since the last run:\n{diff}");
+                        self.toasts.add(Toast {
+                            kind: ToastKind::Info,
+                            text: format!(
+                                "Map data updated: {} changes. See Changes in the sidebar.",
+                                diff.counts().total()
+                            )
+                            .into(),
+                            options: ToastOptions::default().duration_in_seconds(8.0),
+                            ..Default::default()
+                        });
+                        self.comparison = Some(Comparison {
+                            source: "previous map data".to_owned(),
+                            diff,
+                        });
+                    }
+                }
+                _ => log::warn!("Could not compare against the previous map data"),
+            }
+        }
+
+        let saved = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, &file.data));
+        if let Err(err) = saved {
+            log::warn!("Failed to save map data for comparison: {err}");
+        }
+    }
+
+    /// Loads a `.ron` or `.json` map data file dropped onto the window as the comparison baseline.
+    pub fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .find(|path| is_map_data_file(path))
+        });
+        let Some(path) = dropped else {
+            return;
+        };
+
+        match Comparison::load(&path, &self.maps) {
+            Ok(comparison) => {
+                self.toasts.add(Toast {
+                    kind: ToastKind::Info,
+                    text: format!("Comparing against {}", comparison.source).into(),
+                    options: ToastOptions::default().duration_in_seconds(4.0),
+                    ..Default::default()
+                });
+                self.usage.feature_used(Feature::Comparison);
+                self.comparison = Some(comparison);
+            }
+            Err(err) => {
+                self.toasts.add(Toast {
+                    kind: ToastKind::Error,
+                    text: err.to_string().into(),
+                    options: ToastOptions::default()
+                        .duration_in_seconds(8.0)
+                        .show_icon(true),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    /// Renders the comparison summary for the selected map in the sidebar.
+    pub fn show_comparison_summary(&mut self, ui: &mut egui::Ui) {
+        let Some(comparison) = &self.comparison else {
+            return;
+        };
+        let map_name = self.selected_map().map(|map| map.normalized_name.clone());
+
+        ui.add_space(12.0);
+        ui.strong("Changes");
+        ui.separator();
+        ui.label(format!("vs {}", comparison.source));
+
+        match map_name
+            .as_deref()
+            .and_then(|name| comparison.diff.map(name))
+        {
+            Some(diff) => {
+                let counts = diff.counts();
+                ui.colored_label(colors::CHANGE_ADDED, format!("{} added", counts.added));
+                ui.colored_label(
+                    colors::CHANGE_REMOVED,
+                    format!("{} removed", counts.removed),
+                );
+                ui.colored_label(colors::CHANGE_MOVED, format!("{} moved", counts.moved));
+            }
+            None => {
+                ui.weak("No changes on this map");
+            }
+        }
+
+        if ui.button("Clear comparison").clicked() {
+            self.comparison = None;
+        }
+    }
+}
+
+/// Highlights added, removed, and moved features on the map.
+pub fn draw_changes(ui: &mut egui::Ui, map_rect: egui::Rect, map: &Map, diff: &MapDiff) {
+    let painter = ui.painter();
+    let to_display = |p: Position3| game_to_display(map, map_rect, p.xz());
+    let label_to_display = |label: &Label| game_to_display(map, map_rect, label.position);
+    let extract_to_display = |extract: &Extract| {
+        extract
+            .center()
+            .and_then(|center| game_to_display(map, map_rect, center))
+    };
+
+    let ring = |pos: egui::Pos2, color: egui::Color32| {
+        painter.circle_stroke(pos, 10.0, egui::Stroke::new(2.5, color));
+    };
+    let cross = |pos: egui::Pos2| {
+        let stroke = egui::Stroke::new(2.5, colors::CHANGE_REMOVED);
+        let d = 6.0;
+        painter.line_segment([pos + egui::vec2(-d, -d), pos + egui::vec2(d, d)], stroke);
+        painter.line_segment([pos + egui::vec2(d, -d), pos + egui::vec2(-d, d)], stroke);
+    };
+
+    for change in &diff.extracts {
+        match change {
+            FeatureChange::Added(extract) => {
+                if let Some(pos) = extract_to_display(extract) {
+                    ring(pos, colors::CHANGE_ADDED);
+                }
+            }
+            FeatureChange::Removed(extract) => {
+                if let Some(pos) = extract_to_display(extract) {
+                    cross(pos);
+                }
+            }
+            FeatureChange::Moved { old, new } => {
+                let old_pos = extract_to_display(old);
+                let new_pos = extract_to_display(new);
+                if let (Some(from), Some(to)) = (old_pos, new_pos) {
+                    painter.arrow(
+                        from,
+                        to - from,
+                        egui::Stroke::new(2.0, colors::CHANGE_MOVED),
+                    );
+                }
+                if let Some(pos) = new_pos {
+                    ring(pos, colors::CHANGE_MOVED);
+                }
+            }
+        }
+    }
+
+    for change in &diff.spawns {
+        match change {
+            FeatureChange::Added(spawn) => {
+                if let Some(pos) = to_display(spawn.position) {
+                    ring(pos, colors::CHANGE_ADDED);
+                }
+            }
+            FeatureChange::Removed(spawn) => {
+                if let Some(pos) = to_display(spawn.position) {
+                    cross(pos);
+                }
+            }
+            FeatureChange::Moved { new, .. } => {
+                if let Some(pos) = to_display(new.position) {
+                    ring(pos, colors::CHANGE_MOVED);
+                }
+            }
+        }
+    }
+
+    for change in &diff.labels {
+        match change {
+            FeatureChange::Added(label) => {
+                if let Some(pos) = label_to_display(label) {
+                    ring(pos, colors::CHANGE_ADDED);
+                }
+            }
+            FeatureChange::Removed(label) => {
+                if let Some(pos) = label_to_display(label) {
+                    cross(pos);
+                }
+            }
+            FeatureChange::Moved { old, new } => {
+                let old_pos = label_to_display(old);
+                let new_pos = label_to_display(new);
+                if let (Some(from), Some(to)) = (old_pos, new_pos) {
+                    painter.arrow(
+                        from,
+                        to - from,
+                        egui::Stroke::new(2.0, colors::CHANGE_MOVED),
+                    );
+                }
+                if let Some(pos) = new_pos {
+                    ring(pos, colors::CHANGE_MOVED);
+                }
+            }
+        }
+    }
+}
@@ -12,3 +12,33 @@ pub const ZOOM_MAX: f32 = 10.0;
 
 /// Zoom speed multiplier for scroll/keyboard zoom.
 pub const ZOOM_SPEED: f32 = 1.2;
+
+/// Interval between automatic screenshot cleanup runs.
+pub const SCREENSHOT_CLEANUP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Zoom level below which spawn clusters are shown instead of individual spawns.
+pub const SPAWN_CLUSTER_MAX_ZOOM: f32 = 2.5;
+
+/// Zoom level from which loot containers are drawn with their names.
+pub const LOOT_CONTAINER_NAME_MIN_ZOOM: f32 = 4.0;
+
+/// Zoom level from which locks are drawn with the short name of their key.
+pub const LOCK_KEY_NAME_MIN_ZOOM: f32 = 4.0;
+
+/// Duration of the fade when an overlay is shown or hidden, in seconds.
+pub const OVERLAY_FADE_SECONDS: f32 = 0.15;
+
+/// Default age in days after which the map data is flagged as possibly outdated.
+pub const DATA_STALE_AFTER_DAYS: u32 = 30;
+
+/// Number of decoded map images kept in memory, including the selected map's.
+pub const MAX_LOADED_MAP_IMAGES: usize = 3;
+
+/// How long a map must be hovered in the sidebar before its image is pre-warmed.
+pub const PREWARM_HOVER_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Default scav cooldown in minutes, before Fence reputation and hideout bonuses.
+pub const SCAV_COOLDOWN_MINUTES: u32 = 25;
+
+/// Opacity of the fill of extract activation zones, relative to the marker color.
+pub const EXTRACT_AREA_OPACITY: f32 = 0.25;
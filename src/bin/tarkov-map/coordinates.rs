@@ -1,86 +1,134 @@
-//! Coordinate transformation utilities for converting game coordinates to display positions.
+//! Coordinate transformation utilities for converting game coordinates to display positions,
+//! and the convention coordinates are shown, copied and pasted in.
 
+use crate::TarkovMapApp;
+use crate::colors;
 use eframe::egui;
-use tarkov_map::Map;
-
-/// Rotates a 2D point by the given angle (in degrees).
-pub fn rotate_point(x: f64, y: f64, angle_deg: f64) -> (f64, f64) {
-    if angle_deg == 0.0 {
-        return (x, y);
-    }
-    let angle_rad = angle_deg.to_radians();
-    let (sin, cos) = angle_rad.sin_cos();
-    (x * cos - y * sin, x * sin + y * cos)
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tarkov_map::{Map, Position2};
+
+/// How coordinates are written in the cursor readout, tooltips and copied text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CoordinateConvention {
+    /// Horizontal position `x, z`.
+    #[default]
+    Xz,
+    /// Full game position `x, y, z`, as in screenshot names. `y` is the height.
+    Xyz,
+    /// Leaflet `lat, lng` as used by tarkov.dev, which is `z, x`.
+    LatLng,
 }
 
-/// Converts game coordinates to display position.
-///
-/// The transformation follows the official tarkov-dev implementation:
-/// 1. Apply coordinate rotation (rotate game coords by `coordinateRotation` degrees)
-/// 2. Map the rotated coordinates to the image using the rotated bounds
-pub fn game_to_display(map: &Map, map_rect: egui::Rect, game_pos: [f64; 2]) -> Option<egui::Pos2> {
-    let bounds = map.bounds?;
-    let rotation = map.coordinate_rotation.unwrap_or(0.0);
-
-    let (rotated_x, rotated_y) = rotate_point(game_pos[0], game_pos[1], rotation);
-
-    // For 270° rotation maps with transform, use transform-based approach
-    // (handles SVG padding/margins in maps like Labs and Labyrinth)
-    if rotation == 270.0
-        && let Some(transform) = map.transform
-    {
-        let scale_x = transform[0];
-        let margin_x = transform[1];
-        let scale_y = -transform[2]; // Negated per tarkov-dev convention
-        let margin_y = transform[3];
+impl CoordinateConvention {
+    pub const ALL: [Self; 3] = [Self::Xz, Self::Xyz, Self::LatLng];
 
-        let svg_x = scale_x * rotated_x + margin_x;
-        let svg_y = scale_y * rotated_y + margin_y;
-
-        let frac_x = svg_x / f64::from(map.image_size[0]);
-        let frac_y = svg_y / f64::from(map.image_size[1]);
-
-        let display_x = map_rect.min.x + (frac_x as f32) * map_rect.width();
-        let display_y = map_rect.min.y + (frac_y as f32) * map_rect.height();
-
-        return Some(egui::pos2(display_x, display_y));
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Xz => "x, z",
+            Self::Xyz => "x, y, z",
+            Self::LatLng => "lat, lng (tarkov.dev)",
+        }
     }
 
-    // Rotate bounds corners to find rotated extent
-    let corners = [
-        (bounds[0][0], bounds[0][1]), // (maxX, minY)
-        (bounds[0][0], bounds[1][1]), // (maxX, maxY)
-        (bounds[1][0], bounds[0][1]), // (minX, minY)
-        (bounds[1][0], bounds[1][1]), // (minX, maxY)
-    ];
-
-    let rotated_corners: Vec<_> = corners
-        .iter()
-        .map(|(x, y)| rotate_point(*x, *y, rotation))
-        .collect();
-
-    let (rotated_min_x, rotated_max_x) = rotated_corners
-        .iter()
-        .map(|(x, _)| *x)
-        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
-            (min.min(x), max.max(x))
-        });
-
-    let (rotated_min_y, rotated_max_y) = rotated_corners
-        .iter()
-        .map(|(_, y)| *y)
-        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), y| {
-            (min.min(y), max.max(y))
-        });
+    /// Formats a game position. Without a height, `x, y, z` falls back to `x, z`.
+    pub fn format(self, x: f64, y: Option<f64>, z: f64) -> String {
+        match (self, y) {
+            (Self::Xyz, Some(y)) => format!("{x:.2}, {y:.2}, {z:.2}"),
+            (Self::Xz | Self::Xyz, _) => format!("{x:.2}, {z:.2}"),
+            (Self::LatLng, _) => format!("{z:.2}, {x:.2}"),
+        }
+    }
 
-    let bounds_width = rotated_max_x - rotated_min_x;
-    let bounds_height = rotated_max_y - rotated_min_y;
+    /// Parses a coordinate string into a game position `[x, z]`.
+    ///
+    /// Three numbers are always read as `x, y, z`. Two numbers are read in
+    /// this convention, so `lat, lng` pairs are swapped.
+    pub fn parse(self, text: &str) -> Option<[f64; 2]> {
+        let re = Regex::new(
+            r"^\s*\[?\s*(?<a>-?\d+(?:\.\d+)?)\s*,\s*(?<b>-?\d+(?:\.\d+)?)(?:\s*,\s*(?<c>-?\d+(?:\.\d+)?))?\s*\]?\s*$",
+        )
+        .ok()?;
+        let caps = re.captures(text)?;
+
+        let a: f64 = caps.name("a")?.as_str().parse().ok()?;
+        let b: f64 = caps.name("b")?.as_str().parse().ok()?;
+        match (caps.name("c"), self) {
+            // x, y, z where y is height
+            (Some(c), _) => Some([a, c.as_str().parse().ok()?]),
+            (None, Self::LatLng) => Some([b, a]),
+            (None, _) => Some([a, b]),
+        }
+    }
+}
 
-    let frac_x = (rotated_x - rotated_min_x) / bounds_width;
-    let frac_y = (rotated_max_y - rotated_y) / bounds_height; // Y inverted
+/// Converts game coordinates to display position.
+///
+/// See [`Map::project`] for the transformation.
+pub fn game_to_display(
+    map: &Map,
+    map_rect: egui::Rect,
+    game_pos: impl Into<Position2>,
+) -> Option<egui::Pos2> {
+    let [frac_x, frac_y] = map.project(game_pos)?;
+    Some(egui::pos2(
+        map_rect.min.x + (frac_x as f32) * map_rect.width(),
+        map_rect.min.y + (frac_y as f32) * map_rect.height(),
+    ))
+}
 
-    let display_x = map_rect.min.x + (frac_x as f32) * map_rect.width();
-    let display_y = map_rect.min.y + (frac_y as f32) * map_rect.height();
+/// Converts a display position back to game coordinates.
+///
+/// Inverse of [`game_to_display`]; see [`Map::unproject`].
+pub fn display_to_game(map: &Map, map_rect: egui::Rect, pos: egui::Pos2) -> Option<[f64; 2]> {
+    let frac_x = f64::from((pos.x - map_rect.min.x) / map_rect.width());
+    let frac_y = f64::from((pos.y - map_rect.min.y) / map_rect.height());
+    map.unproject([frac_x, frac_y]).map(Into::into)
+}
 
-    Some(egui::pos2(display_x, display_y))
+impl TarkovMapApp {
+    /// Shows the game coordinates under the pointer in the viewport corner.
+    ///
+    /// Middle-clicking the map copies them.
+    pub fn show_cursor_readout(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        map: &Map,
+        map_rect: egui::Rect,
+        viewport_rect: egui::Rect,
+    ) {
+        let Some([x, z]) = response
+            .hover_pos()
+            .and_then(|pos| display_to_game(map, map_rect, pos))
+        else {
+            return;
+        };
+        let text = self.coordinate_convention.format(x, None, z);
+
+        let painter = ui.painter();
+        let galley = painter.layout_no_wrap(
+            format!("{text}  (middle-click to copy)"),
+            egui::FontId::proportional(12.0),
+            egui::Color32::WHITE,
+        );
+        let pos = viewport_rect.left_bottom() + egui::vec2(8.0, -8.0 - galley.size().y);
+        painter.rect_filled(
+            egui::Rect::from_min_size(pos, galley.size()).expand(4.0),
+            4.0,
+            colors::LABEL_BACKGROUND,
+        );
+        painter.galley(pos, galley, egui::Color32::WHITE);
+
+        if response.middle_clicked() {
+            ui.ctx().copy_text(text.clone());
+            self.toasts.add(Toast {
+                kind: ToastKind::Info,
+                text: format!("Copied {text}").into(),
+                options: ToastOptions::default().duration_in_seconds(2.0),
+                ..Default::default()
+            });
+        }
+    }
 }
@@ -0,0 +1,358 @@
+//! User-provided maps, e.g. SPT modded locations or annotated community renders.
+//!
+//! A custom map is a RON descriptor inside `<data dir>/tarkov-map/custom-maps/`
+//! pointing at an image, with the game-coordinate bounds the image covers:
+//!
+//! ```ron
+//! (
+//!     name: "Sandbox (SPT)",
+//!     image: "sandbox.png",
+//!     bounds: [[310.0, -180.0], [-260.0, 250.0]],
+//!     rotation: 180.0,
+//! )
+//! ```
+//!
+//! Relative image paths are resolved against the descriptor's folder. Custom
+//! maps are listed after the official maps.
+
+use crate::{APP_ID, TarkovMapApp};
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tarkov_map::Map;
+use thiserror::Error;
+
+/// Prefix of the normalized name of custom maps.
+const CUSTOM_MAP_PREFIX: &str = "custom/";
+
+/// Rotations supported by the coordinate transform, in degrees.
+const ROTATIONS: [f64; 4] = [0.0, 90.0, 180.0, 270.0];
+
+/// Errors that can occur when loading or saving a custom map.
+#[derive(Error, Debug)]
+pub enum CustomMapError {
+    #[error("no data directory available")]
+    NoDataDir,
+    #[error("failed to read '{path}': {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("failed to parse '{path}': {message}")]
+    Parse { path: String, message: String },
+    #[error("failed to read image '{path}': {message}")]
+    Image { path: String, message: String },
+    #[error("failed to write '{path}': {source}")]
+    Write {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("failed to serialize descriptor: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+/// On-disk description of a custom map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomMapDescriptor {
+    pub name: String,
+    /// Image file, absolute or relative to the descriptor.
+    pub image: PathBuf,
+    /// Bounds `[[maxX, minY], [minX, maxY]]` in game coordinates, as in `maps.ron`.
+    pub bounds: [[f64; 2]; 2],
+    /// Coordinate rotation in degrees.
+    #[serde(default)]
+    pub rotation: f64,
+    #[serde(default)]
+    pub author: Option<String>,
+}
+
+impl CustomMapDescriptor {
+    /// Converts the descriptor stored at `path` into a map.
+    fn into_map(self, path: &Path) -> Result<Map, CustomMapError> {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let image = path
+            .parent()
+            .map_or_else(|| self.image.clone(), |dir| dir.join(&self.image));
+        let image_error = |message: String| CustomMapError::Image {
+            path: image.display().to_string(),
+            message,
+        };
+        let image = std::path::absolute(&image).map_err(|e| image_error(e.to_string()))?;
+        let (width, height) =
+            image::image_dimensions(&image).map_err(|e| image_error(e.to_string()))?;
+        let image_size = [width as f32, height as f32];
+
+        Ok(Map {
+            normalized_name: format!("{CUSTOM_MAP_PREFIX}{stem}"),
+            name: self.name,
+            translations: None,
+            image_path: image.display().to_string(),
+            image_size,
+            tiles: None,
+            logical_size: image_size,
+            alt_maps: None,
+            author: self.author,
+            author_link: None,
+            transform: None,
+            coordinate_rotation: Some(self.rotation),
+            bounds: Some(self.bounds),
+            default_view: None,
+            height_range: None,
+            layers: None,
+            labels: None,
+            spawns: None,
+            extracts: None,
+            spawn_clusters: None,
+            hazards: None,
+            locks: None,
+            loot_containers: None,
+            bosses: None,
+            stationary_weapons: None,
+            switches: None,
+            transits: None,
+            tasks: None,
+        })
+    }
+}
+
+/// Returns the folder custom map descriptors are stored in.
+pub fn custom_maps_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("custom-maps"))
+}
+
+/// Returns `true` if `map` was loaded from a custom map descriptor.
+pub fn is_custom_map(map: &Map) -> bool {
+    map.normalized_name.starts_with(CUSTOM_MAP_PREFIX)
+}
+
+fn load_descriptor(path: &Path) -> Result<Map, CustomMapError> {
+    let path_str = path.display().to_string();
+    let text = fs::read_to_string(path).map_err(|source| CustomMapError::Read {
+        path: path_str.clone(),
+        source,
+    })?;
+    let descriptor: CustomMapDescriptor =
+        ron::from_str(&text).map_err(|e| CustomMapError::Parse {
+            path: path_str,
+            message: e.to_string(),
+        })?;
+    descriptor.into_map(path)
+}
+
+/// Loads all custom maps, sorted by name, along with the descriptors that failed to load.
+pub fn load_custom_maps() -> (Vec<Map>, Vec<CustomMapError>) {
+    let Some(entries) = custom_maps_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let mut maps = Vec::new();
+    let mut errors = Vec::new();
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.extension().is_none_or(|ext| ext != "ron") {
+            continue;
+        }
+        match load_descriptor(&path) {
+            Ok(map) => maps.push(map),
+            Err(err) => errors.push(err),
+        }
+    }
+    maps.sort_by(|a, b| a.name.cmp(&b.name));
+    (maps, errors)
+}
+
+/// Writes `descriptor` to the custom maps folder and returns its path.
+fn save_descriptor(descriptor: &CustomMapDescriptor) -> Result<PathBuf, CustomMapError> {
+    let dir = custom_maps_dir().ok_or(CustomMapError::NoDataDir)?;
+    let write_error = |path: &Path, source| CustomMapError::Write {
+        path: path.display().to_string(),
+        source,
+    };
+    fs::create_dir_all(&dir).map_err(|source| write_error(&dir, source))?;
+
+    let slug: String = descriptor
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-');
+    let slug = if slug.is_empty() { "map" } else { slug };
+    let path = (1..)
+        .map(|n| match n {
+            1 => dir.join(format!("{slug}.ron")),
+            n => dir.join(format!("{slug}-{n}.ron")),
+        })
+        .find(|path| !path.exists())
+        .expect("unbounded range");
+
+    let text = ron::ser::to_string_pretty(descriptor, PrettyConfig::default())?;
+    fs::write(&path, text).map_err(|source| write_error(&path, source))?;
+    Ok(path)
+}
+
+/// State of the "Add Custom Map" window.
+pub struct CustomMapWizard {
+    name: String,
+    image: String,
+    min: [f64; 2],
+    max: [f64; 2],
+    rotation: f64,
+    error: Option<String>,
+}
+
+impl Default for CustomMapWizard {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            image: String::new(),
+            min: [-500.0, -500.0],
+            max: [500.0, 500.0],
+            rotation: 180.0,
+            error: None,
+        }
+    }
+}
+
+impl CustomMapWizard {
+    fn descriptor(&self) -> CustomMapDescriptor {
+        CustomMapDescriptor {
+            name: self.name.trim().to_owned(),
+            image: std::path::absolute(self.image.trim())
+                .unwrap_or_else(|_| PathBuf::from(self.image.trim())),
+            bounds: [[self.max[0], self.min[1]], [self.min[0], self.max[1]]],
+            rotation: self.rotation,
+            author: None,
+        }
+    }
+}
+
+impl TarkovMapApp {
+    /// Replaces the custom maps in the map list with the ones currently on disk.
+    pub fn reload_custom_maps(&mut self) {
+        let selected_name = self.selected_map().map(|map| map.normalized_name.clone());
+
+        let (custom_maps, errors) = load_custom_maps();
+        for err in errors {
+            log::warn!("Skipping custom map: {err}");
+            self.toasts.add(Toast {
+                kind: ToastKind::Warning,
+                text: err.to_string().into(),
+                options: ToastOptions::default().duration_in_seconds(8.0),
+                ..Default::default()
+            });
+        }
+        for map in &custom_maps {
+            // Always reload, the image may have changed on disk
+            self.forget_map_image(&map.image_path);
+        }
+
+        self.maps.retain(|map| !is_custom_map(map));
+        self.maps.extend(custom_maps);
+        self.selected_map = selected_name
+            .and_then(|name| self.maps.iter().position(|map| map.normalized_name == name))
+            .unwrap_or(0);
+    }
+
+    /// Renders the "Add Custom Map" window while it is open.
+    pub fn show_custom_map_wizard(&mut self, ctx: &egui::Context) {
+        let Some(mut wizard) = self.custom_map_wizard.take() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut save = false;
+        egui::Window::new("Add Custom Map")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("custom_map_wizard")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Name");
+                        ui.text_edit_singleline(&mut wizard.name);
+                        ui.end_row();
+
+                        ui.label("Image");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut wizard.image)
+                                .hint_text("Full path to a PNG or JPEG"),
+                        );
+                        ui.end_row();
+
+                        for (axis, index) in [("X", 0), ("Z", 1)] {
+                            ui.label(format!("{axis} range"));
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut wizard.min[index]).speed(1.0));
+                                ui.label("to");
+                                ui.add(egui::DragValue::new(&mut wizard.max[index]).speed(1.0));
+                            });
+                            ui.end_row();
+                        }
+
+                        ui.label("Rotation");
+                        egui::ComboBox::from_id_salt("custom_map_rotation")
+                            .selected_text(format!("{}°", wizard.rotation))
+                            .show_ui(ui, |ui| {
+                                for rotation in ROTATIONS {
+                                    ui.selectable_value(
+                                        &mut wizard.rotation,
+                                        rotation,
+                                        format!("{rotation}°"),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+                    });
+
+                ui.weak("Ranges are the game coordinates at the image edges.");
+                if let Some(error) = &wizard.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.separator();
+                let valid = !wizard.name.trim().is_empty()
+                    && !wizard.image.trim().is_empty()
+                    && wizard.min[0] < wizard.max[0]
+                    && wizard.min[1] < wizard.max[1];
+                if ui
+                    .add_enabled(valid, egui::Button::new("Add Map"))
+                    .clicked()
+                {
+                    save = true;
+                }
+            });
+
+        if save {
+            let descriptor = wizard.descriptor();
+            let result = descriptor
+                .clone()
+                .into_map(Path::new(""))
+                // Only validates the image, the map is reloaded from disk below
+                .and_then(|_| save_descriptor(&descriptor));
+            match result {
+                Ok(path) => {
+                    log::info!("Saved custom map to {}", path.display());
+                    self.reload_custom_maps();
+                    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                    let name = format!("{CUSTOM_MAP_PREFIX}{stem}");
+                    if let Some(idx) = self.maps.iter().position(|m| m.normalized_name == name) {
+                        self.select_map(idx);
+                    }
+                    return;
+                }
+                Err(err) => wizard.error = Some(err.to_string()),
+            }
+        }
+
+        if open {
+            self.custom_map_wizard = Some(wizard);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Age of the built-in map data.
+//!
+//! `fetch_maps` records when it ran and which tarkov-dev commit it read. The
+//! status bar shows the fetch date, turning amber once the data is older than
+//! the configured number of days as a nudge to update after a wipe. The data
+//! version comes from the manifest `fetch_maps` writes next to `maps.ron`.
+
+use crate::TarkovMapApp;
+use chrono::Utc;
+use eframe::egui;
+
+/// Length of an abbreviated commit hash.
+const SHORT_COMMIT_LEN: usize = 7;
+
+impl TarkovMapApp {
+    /// Renders the "map data from" indicator, with a context menu for the age threshold.
+    pub fn show_data_freshness(&mut self, ui: &mut egui::Ui) {
+        let Some(fetched_at) = self.data_provenance.fetched_at else {
+            return;
+        };
+        let age_days = (Utc::now() - fetched_at).num_days();
+        let stale = age_days > i64::from(self.data_stale_days);
+
+        let text = format!("Map data from {}", fetched_at.format("%Y-%m-%d"));
+        let text = if stale {
+            egui::RichText::new(text).color(ui.visuals().warn_fg_color)
+        } else {
+            egui::RichText::new(text).weak()
+        };
+
+        let mut hover = match age_days {
+            0 => "Fetched today".to_owned(),
+            1 => "Fetched 1 day ago".to_owned(),
+            days => format!("Fetched {days} days ago"),
+        };
+        if let Some(commit) = &self.data_provenance.source_commit {
+            let short = commit.get(..SHORT_COMMIT_LEN).unwrap_or(commit);
+            hover.push_str(&format!(" from tarkov-dev commit {short}"));
+        }
+        if let Some((version, files)) = &self.data_provenance.manifest {
+            hover.push_str(&format!("\nData version {version}, {files} files"));
+        }
+        if stale {
+            hover.push_str(
+                "\nThe map data may predate the last wipe. Update the app for current data.",
+            );
+        }
+        hover.push_str("\nRight-click to change when this turns amber");
+
+        ui.add(egui::Label::new(text).sense(egui::Sense::click()))
+            .on_hover_text(hover)
+            .context_menu(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Warn after");
+                    ui.add(
+                        egui::DragValue::new(&mut self.data_stale_days)
+                            .range(1..=365)
+                            .suffix(" days"),
+                    );
+                });
+            });
+    }
+}
@@ -0,0 +1,314 @@
+//! Data packs: extra map datasets merged on top of the official data.
+//!
+//! A data pack is a `.ron` or `.json` file in `<data dir>/tarkov-map/data-packs/`
+//! using the `maps.ron` format or its JSON equivalent. Packs that only add
+//! markers to existing maps can leave out names and images. Enabled packs are
+//! merged in load order after the official data, so later packs override
+//! earlier ones. Relative image paths are resolved against the data packs
+//! folder.
+
+use crate::assets::{MAP_DATA_EXTENSIONS, is_map_data_file, load_maps, parse_maps};
+use crate::custom_maps::load_custom_maps;
+use crate::{APP_ID, TarkovMapApp};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tarkov_map::merge::merge;
+use tarkov_map::{MergeConflict, SPAWN_CLUSTER_RADIUS, TarkovMaps};
+use thiserror::Error;
+
+/// Errors that can occur when loading a data pack.
+#[derive(Error, Debug)]
+pub enum DataPackError {
+    #[error("no data directory available")]
+    NoDataDir,
+    #[error("failed to read data pack '{path}': {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("failed to parse data pack '{path}': {message}")]
+    Parse { path: String, message: String },
+}
+
+/// A data pack in the load order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataPackEntry {
+    /// File stem of the pack inside the data packs folder.
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// Which data packs are enabled, and in which order they are merged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataPackSettings {
+    /// Loads the official data bundled with the app first.
+    pub official: bool,
+    /// Additional packs, merged in order.
+    pub packs: Vec<DataPackEntry>,
+}
+
+impl Default for DataPackSettings {
+    fn default() -> Self {
+        Self {
+            official: true,
+            packs: Vec::new(),
+        }
+    }
+}
+
+impl DataPackSettings {
+    /// Adds installed packs missing from the load order (disabled) and drops removed ones.
+    fn refresh(&mut self) {
+        let available = available_data_packs();
+        self.packs.retain(|entry| available.contains(&entry.name));
+        for name in available {
+            if !self.packs.iter().any(|entry| entry.name == name) {
+                self.packs.push(DataPackEntry {
+                    name,
+                    enabled: false,
+                });
+            }
+        }
+    }
+}
+
+/// Data in an earlier pack replaced by a later one.
+#[derive(Debug, Clone)]
+pub struct PackConflict {
+    /// Name of the pack that replaced the data.
+    pub pack: String,
+    pub conflict: MergeConflict,
+}
+
+/// Result of merging the official data, data packs and custom maps.
+#[derive(Default)]
+pub struct MapData {
+    pub maps: TarkovMaps,
+    pub conflicts: Vec<PackConflict>,
+    /// Sources that failed to load and were skipped.
+    pub errors: Vec<String>,
+}
+
+/// Returns the folder data packs are installed into.
+pub fn data_packs_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("data-packs"))
+}
+
+/// Lists the names of installed data packs, sorted alphabetically.
+pub fn available_data_packs() -> Vec<String> {
+    let Some(entries) = data_packs_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut packs: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_map_data_file(path))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_owned()))
+        .collect();
+    packs.sort();
+    packs.dedup();
+    packs
+}
+
+fn load_pack(dir: &Path, name: &str) -> Result<TarkovMaps, DataPackError> {
+    // A `.ron` pack wins over a `.json` pack of the same name
+    let path = MAP_DATA_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{name}.{ext}")))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| dir.join(format!("{name}.ron")));
+    let path_str = path.display().to_string();
+    let text = fs::read_to_string(&path).map_err(|source| DataPackError::Read {
+        path: path_str.clone(),
+        source,
+    })?;
+    let mut maps = parse_maps(&text).map_err(|e| DataPackError::Parse {
+        path: path_str,
+        message: e.to_string(),
+    })?;
+
+    for map in &mut maps {
+        if !map.image_path.is_empty() && Path::new(&map.image_path).is_relative() {
+            map.image_path = dir.join(&map.image_path).display().to_string();
+        }
+    }
+    Ok(maps)
+}
+
+/// Loads the official data and enabled data packs in order, followed by custom maps.
+pub fn load_map_data(settings: &DataPackSettings) -> MapData {
+    let mut data = MapData::default();
+
+    if settings.official {
+        match load_maps() {
+            Ok(maps) => data.maps = maps,
+            Err(err) => data.errors.push(err.to_string()),
+        }
+    }
+
+    let enabled: Vec<&str> = settings
+        .packs
+        .iter()
+        .filter(|entry| entry.enabled)
+        .map(|entry| entry.name.as_str())
+        .collect();
+    if !enabled.is_empty() {
+        match data_packs_dir() {
+            Some(dir) => {
+                for name in enabled {
+                    match load_pack(&dir, name) {
+                        Ok(maps) => {
+                            let conflicts = merge(&mut data.maps, maps);
+                            data.conflicts.extend(conflicts.into_iter().map(|conflict| {
+                                PackConflict {
+                                    pack: name.to_owned(),
+                                    conflict,
+                                }
+                            }));
+                        }
+                        Err(err) => data.errors.push(err.to_string()),
+                    }
+                }
+            }
+            None => data.errors.push(DataPackError::NoDataDir.to_string()),
+        }
+    }
+
+    // Packs that only add markers can't be shown without a base map
+    data.maps.retain(|map| !map.image_path.is_empty());
+
+    let (custom_maps, custom_errors) = load_custom_maps();
+    data.maps.extend(custom_maps);
+    data.errors
+        .extend(custom_errors.iter().map(ToString::to_string));
+
+    // The bundled data and most packs ship without clusters
+    for map in data
+        .maps
+        .iter_mut()
+        .filter(|map| map.spawn_clusters.is_none())
+    {
+        let clusters = map.compute_spawn_clusters(SPAWN_CLUSTER_RADIUS);
+        map.spawn_clusters = (!clusters.is_empty()).then_some(clusters);
+    }
+    // Custom maps and packs written by hand rarely set a default view
+    for map in data
+        .maps
+        .iter_mut()
+        .filter(|map| map.default_view.is_none())
+    {
+        map.default_view = map.compute_default_view();
+    }
+
+    data
+}
+
+impl TarkovMapApp {
+    /// Opens the Data Packs window, picking up newly installed packs.
+    pub fn open_data_packs(&mut self) {
+        self.data_packs.refresh();
+        self.data_packs_open = true;
+    }
+
+    /// Reloads map data with the current data pack settings.
+    fn apply_data_packs(&mut self) {
+        let data = load_map_data(&self.data_packs);
+        for err in &data.errors {
+            log::warn!("{err}");
+        }
+        self.data_pack_errors = data.errors;
+        self.data_pack_conflicts = data.conflicts;
+        self.replace_maps(data.maps);
+    }
+
+    /// Renders the Data Packs window while it is open.
+    pub fn show_data_packs_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.data_packs_open;
+        let mut apply = false;
+        egui::Window::new("Data Packs")
+            .open(&mut open)
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                ui.weak("Packs are merged top to bottom; later packs override earlier ones.");
+                ui.add_space(4.0);
+
+                ui.checkbox(&mut self.data_packs.official, "Official (tarkov.dev)");
+
+                let count = self.data_packs.packs.len();
+                let mut swap = None;
+                for (idx, entry) in self.data_packs.packs.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut entry.enabled, &entry.name);
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .add_enabled(idx + 1 < count, egui::Button::new("⏷").small())
+                                .clicked()
+                            {
+                                swap = Some((idx, idx + 1));
+                            }
+                            if ui
+                                .add_enabled(idx > 0, egui::Button::new("⏶").small())
+                                .clicked()
+                            {
+                                swap = Some((idx - 1, idx));
+                            }
+                        });
+                    });
+                }
+                if let Some((a, b)) = swap {
+                    self.data_packs.packs.swap(a, b);
+                }
+                if count == 0 {
+                    ui.weak("No data packs installed");
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Apply").clicked() {
+                        apply = true;
+                    }
+                    if ui.button("Refresh").clicked() {
+                        self.data_packs.refresh();
+                    }
+                    if ui.button("Open Folder").clicked()
+                        && let Some(dir) = data_packs_dir()
+                    {
+                        let _ = fs::create_dir_all(&dir);
+                        let _ = open::that(dir);
+                    }
+                });
+
+                for err in &self.data_pack_errors {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+
+                if !self.data_pack_conflicts.is_empty() {
+                    egui::CollapsingHeader::new(format!(
+                        "{} conflicts",
+                        self.data_pack_conflicts.len()
+                    ))
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(200.0)
+                            .show(ui, |ui| {
+                                for PackConflict { pack, conflict } in &self.data_pack_conflicts {
+                                    ui.label(format!(
+                                        "{pack} replaces {} on {}",
+                                        conflict.feature, conflict.map
+                                    ));
+                                }
+                            });
+                    });
+                }
+            });
+
+        if apply {
+            self.apply_data_packs();
+        }
+        self.data_packs_open = open;
+    }
+}
@@ -0,0 +1,249 @@
+//! Sortable extract list shown in the sidebar.
+
+use crate::TarkovMapApp;
+use crate::carried_items::UNUSABLE_EXTRACT_OPACITY;
+use crate::colors;
+use crate::notes::MarkerNotes;
+use crate::usage_stats::Feature;
+use eframe::egui;
+use tarkov_map::{Extract, Faction, Map, Position3};
+
+/// Column the extract list is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExtractSortColumn {
+    #[default]
+    Name,
+    Faction,
+    Distance,
+}
+
+/// Sort state of the extract list.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtractSort {
+    pub column: ExtractSortColumn,
+    pub descending: bool,
+}
+
+impl ExtractSort {
+    /// Sorts by `column`, toggling the direction if it is already the active column.
+    fn toggle(&mut self, column: ExtractSortColumn) {
+        if self.column == column {
+            self.descending = !self.descending;
+        } else {
+            self.column = column;
+            self.descending = false;
+        }
+    }
+
+    /// Header text for `column`, with an arrow on the active column.
+    fn header(&self, column: ExtractSortColumn, text: &str) -> String {
+        if self.column != column {
+            return text.to_owned();
+        }
+        let arrow = if self.descending { "⏷" } else { "⏶" };
+        format!("{text} {arrow}")
+    }
+}
+
+/// Horizontal distance in meters between a player position and an extract.
+fn extract_distance(player: [f64; 3], extract: &Extract) -> Option<f64> {
+    let player = Position3::from(player).xz();
+    Some(extract.center()?.distance(player))
+}
+
+/// Lists what an extract needs before it can be used.
+fn show_extract_requirements(ui: &mut egui::Ui, extract: &Extract) {
+    if let Some(item) = &extract.required_item {
+        ui.label(format!("Pay {} ×{}", item.name, item.count));
+    }
+    for switch in extract.switches.iter().flatten() {
+        ui.label(format!("Activate {switch}"));
+    }
+    if extract.coop {
+        ui.label("Co-op: a PMC and a scav extract together");
+    }
+}
+
+fn faction_color(faction: &Faction) -> egui::Color32 {
+    match faction {
+        Faction::Pmc => colors::PMC_EXTRACT_FILL,
+        Faction::Scav => colors::SCAV_EXTRACT_FILL,
+        Faction::Shared => colors::SHARED_EXTRACT_FILL,
+        Faction::Other(_) => egui::Color32::GRAY,
+    }
+}
+
+impl TarkovMapApp {
+    /// Renders the sortable list of extracts on the selected map.
+    ///
+    /// Clicking an extract centers the map on its marker and opens its note.
+    pub fn show_extract_list(&mut self, ui: &mut egui::Ui) {
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let map_name = map.normalized_name.clone();
+        let Some(extracts) = map.extracts.clone() else {
+            ui.label("No extracts");
+            return;
+        };
+
+        self.carried_items.ui(ui);
+        ui.add_space(4.0);
+
+        let player = self.player_position.map(|p| p.position);
+
+        let mut rows: Vec<(&Extract, Option<f64>)> = extracts
+            .iter()
+            .filter(|extract| self.carried_items.extract_opacity(extract).is_some())
+            .map(|extract| (extract, player.and_then(|p| extract_distance(p, extract))))
+            .collect();
+
+        let sort = self.extract_sort;
+        let language = self.language.as_deref();
+        rows.sort_by(|(a, a_dist), (b, b_dist)| {
+            let ordering = match sort.column {
+                ExtractSortColumn::Name => {
+                    a.localized_name(language).cmp(b.localized_name(language))
+                }
+                ExtractSortColumn::Faction => a
+                    .faction
+                    .cmp(&b.faction)
+                    .then(a.localized_name(language).cmp(b.localized_name(language))),
+                ExtractSortColumn::Distance => a_dist
+                    .unwrap_or(f64::INFINITY)
+                    .total_cmp(&b_dist.unwrap_or(f64::INFINITY)),
+            };
+            if sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        egui::Grid::new("extract_list")
+            .num_columns(3)
+            .striped(true)
+            .spacing([6.0, 2.0])
+            .show(ui, |ui| {
+                let columns = [
+                    (ExtractSortColumn::Name, "Name"),
+                    (ExtractSortColumn::Faction, "Side"),
+                    (ExtractSortColumn::Distance, "Dist"),
+                ];
+                for (column, text) in columns {
+                    if ui
+                        .selectable_label(sort.column == column, sort.header(column, text))
+                        .clicked()
+                    {
+                        self.extract_sort.toggle(column);
+                    }
+                }
+                ui.end_row();
+
+                for (extract, distance) in rows {
+                    let previous_opacity = ui.opacity();
+                    if !self.carried_items.can_use(extract) {
+                        ui.multiply_opacity(UNUSABLE_EXTRACT_OPACITY);
+                    }
+                    let note = self
+                        .notes
+                        .get(&map_name, &MarkerNotes::extract_key(&extract.name));
+                    let mut response = ui
+                        .add(
+                            egui::Label::new(extract.localized_name(self.language.as_deref()))
+                                .truncate()
+                                .sense(egui::Sense::click()),
+                        )
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    if let Some(note) = note {
+                        response = response.on_hover_text(note);
+                    }
+                    if response.clicked() {
+                        self.usage.feature_used(Feature::ExtractList);
+                        self.selected_extract = Some(extract.name.clone());
+                        if let Some(center) = extract.center() {
+                            self.focus_position = Some(center.into());
+                        }
+                    }
+
+                    ui.colored_label(faction_color(&extract.faction), extract.faction.as_str());
+
+                    match distance {
+                        Some(distance) => ui.label(format!("{distance:.0} m")),
+                        None => ui.weak("-"),
+                    };
+                    ui.set_opacity(previous_opacity);
+                    ui.end_row();
+                }
+            });
+
+        self.show_extract_note_editor(ui, &map_name, &extracts);
+    }
+
+    /// Renders the requirements, note editor and photos for the extract selected in the list.
+    fn show_extract_note_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        map_name: &str,
+        extracts: &[Extract],
+    ) {
+        let Some(name) = self.selected_extract.clone() else {
+            return;
+        };
+        let key = MarkerNotes::extract_key(&name);
+        let mut note = self
+            .notes
+            .get(map_name, &key)
+            .unwrap_or_default()
+            .to_owned();
+
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            ui.label(format!("Note: {name}"));
+            if ui.small_button("✖").on_hover_text("Close").clicked() {
+                self.selected_extract = None;
+            }
+        });
+        if let Some(extract) = extracts.iter().find(|extract| extract.name == name) {
+            show_extract_requirements(ui, extract);
+        }
+        let response = ui.add(
+            egui::TextEdit::multiline(&mut note)
+                .desired_rows(2)
+                .hint_text("e.g. needs green flare"),
+        );
+        if response.changed() {
+            self.notes.set(map_name, &key, note);
+        }
+        self.show_marker_photos(ui, map_name, &key);
+    }
+
+    /// Shows a tooltip with name, faction, requirements and note for the picked extract at `index`.
+    pub fn show_extract_tooltip(&self, response: &egui::Response, map: &Map, index: usize) {
+        let Some(extract) = map.extracts.iter().flatten().nth(index) else {
+            return;
+        };
+
+        let note = self.notes.get(
+            &map.normalized_name,
+            &MarkerNotes::extract_key(&extract.name),
+        );
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            ui.strong(extract.localized_name(self.language.as_deref()));
+            ui.label(format!("Faction: {}", extract.faction));
+            show_extract_requirements(ui, extract);
+            if let Some(center) = extract.center() {
+                let height = extract.position.map(|p| p.y);
+                ui.weak(
+                    self.coordinate_convention
+                        .format(center.x, height, center.z),
+                );
+            }
+            if let Some(note) = note {
+                ui.separator();
+                ui.label(note);
+            }
+            self.marker_picking.cycle_hint(ui);
+        });
+    }
+}
@@ -0,0 +1,96 @@
+//! Nearest usable extracts from the live player position.
+
+use crate::TarkovMapApp;
+use crate::measure::bearing;
+use eframe::egui;
+use tarkov_map::Map;
+
+/// Number of extracts suggested.
+const ROUTE_COUNT: usize = 3;
+
+/// An extract suggestion with straight-line distance and compass bearing.
+struct ExtractRoute<'a> {
+    name: &'a str,
+    /// Extract position in game coordinates `[x, z]`.
+    target: [f64; 2],
+    distance: f64,
+    bearing: f64,
+}
+
+/// Eight-point compass direction for a bearing in degrees.
+fn compass_point(bearing: f64) -> &'static str {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    POINTS[((bearing + 22.5).rem_euclid(360.0) / 45.0) as usize % 8]
+}
+
+impl TarkovMapApp {
+    /// Returns the nearest extracts allowed by the extract and carried-item
+    /// filters, closest first.
+    fn extract_routes<'a>(&self, map: &'a Map, player: [f64; 2]) -> Vec<ExtractRoute<'a>> {
+        let mut routes: Vec<ExtractRoute> = map
+            .extracts
+            .iter()
+            .flatten()
+            .filter(|extract| self.overlays.shows_extract(&extract.faction))
+            .filter(|extract| self.carried_items.extract_opacity(extract).is_some())
+            .filter_map(|extract| {
+                let target = extract.center()?.into();
+                Some(ExtractRoute {
+                    name: &extract.name,
+                    target,
+                    distance: map.meters_between(player, target),
+                    bearing: bearing(map, player, target),
+                })
+            })
+            .collect();
+        routes.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        routes.truncate(ROUTE_COUNT);
+        routes
+    }
+
+    /// Renders the floating panel with the nearest extracts while the player position is known.
+    pub fn show_extract_routes(&mut self, ctx: &egui::Context, panel_rect: egui::Rect, map: &Map) {
+        let Some(player) = self.player_position.map(|p| [p.position[0], p.position[2]]) else {
+            return;
+        };
+        let routes = self.extract_routes(map, player);
+        if routes.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("extract_routes"))
+            .pivot(egui::Align2::LEFT_BOTTOM)
+            .fixed_pos(panel_rect.left_bottom() + egui::vec2(12.0, -12.0))
+            .interactable(true)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style())
+                    .fill(ui.style().visuals.window_fill.gamma_multiply(0.95))
+                    .show(ui, |ui| {
+                        ui.strong("Nearest Extracts");
+                        egui::Grid::new("extract_routes_grid")
+                            .num_columns(3)
+                            .spacing([10.0, 2.0])
+                            .show(ui, |ui| {
+                                for route in &routes {
+                                    let response = ui
+                                        .add(
+                                            egui::Label::new(route.name)
+                                                .sense(egui::Sense::click()),
+                                        )
+                                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                                    if response.clicked() {
+                                        self.focus_position = Some(route.target);
+                                    }
+                                    ui.label(format!("{:.0} m", route.distance));
+                                    let bearing = route.bearing.round() as u32 % 360;
+                                    ui.label(format!(
+                                        "{bearing}° {}",
+                                        compass_point(route.bearing)
+                                    ));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+    }
+}
@@ -0,0 +1,190 @@
+//! Quest progress from the game's logs.
+//!
+//! Tarkov logs every chat notification it receives to a `notifications.log`
+//! file in a per-session folder under `Logs`. Trader messages for started,
+//! failed and completed quests carry the quest ID in their `templateId`. The
+//! newest log is polled in the background and new quest events are reported
+//! as they happen, so completed quests are tracked without any input.
+
+use crate::TarkovMapApp;
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// Default locations of the game's `Logs` folder, for the launcher's install paths.
+const GAME_LOGS_DIRS: &[&str] = &[
+    r"C:\Battlestate Games\Escape from Tarkov\Logs",
+    r"C:\Battlestate Games\EFT\Logs",
+];
+
+/// Time between checks of the newest notifications log.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Log line that precedes each notification's JSON body.
+const CHAT_MESSAGE_MARKER: &str = "Got notification | ChatMessageReceived";
+
+/// Chat message types of quest notifications.
+const MESSAGE_QUEST_STARTED: u64 = 10;
+const MESSAGE_QUEST_FAILED: u64 = 11;
+const MESSAGE_QUEST_COMPLETED: u64 = 12;
+
+/// What happened to a quest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestStatus {
+    Started,
+    Failed,
+    Completed,
+}
+
+/// A quest status change read from the notifications log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestEvent {
+    /// tarkov.dev task ID.
+    pub quest_id: String,
+    pub status: QuestStatus,
+}
+
+/// Returns the game's `Logs` folder, if it is in a default install location.
+pub fn logs_dir() -> Option<PathBuf> {
+    GAME_LOGS_DIRS
+        .iter()
+        .map(PathBuf::from)
+        .find(|dir| dir.is_dir())
+}
+
+/// Parses the quest events in the text of a notifications log, in order.
+pub fn parse_quest_events(log: &str) -> Vec<QuestEvent> {
+    log.match_indices(CHAT_MESSAGE_MARKER)
+        .filter_map(|(idx, _)| {
+            let body = &log[idx + CHAT_MESSAGE_MARKER.len()..];
+            let body = &body[body.find('{')?..];
+            // The body is followed by the next log line, so only read one value
+            let notification: serde_json::Value = serde_json::Deserializer::from_str(body)
+                .into_iter()
+                .next()?
+                .ok()?;
+            let message = notification.get("message")?;
+            let status = match message.get("type")?.as_u64()? {
+                MESSAGE_QUEST_STARTED => QuestStatus::Started,
+                MESSAGE_QUEST_FAILED => QuestStatus::Failed,
+                MESSAGE_QUEST_COMPLETED => QuestStatus::Completed,
+                _ => return None,
+            };
+            // e.g. "5967725e86f774601a446662 successMessageText"
+            let template = message.get("templateId")?.as_str()?;
+            let quest_id = template.split_whitespace().next()?.to_owned();
+            Some(QuestEvent { quest_id, status })
+        })
+        .collect()
+}
+
+/// Returns the newest log whose file name ends with `suffix`, in the newest session folder.
+pub fn newest_session_log(logs_dir: &Path, suffix: &str) -> Option<PathBuf> {
+    let newest = |dir: &Path, keep: &dyn Fn(&Path) -> bool| {
+        fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| keep(&entry.path()))
+            .max_by_key(|entry| entry.metadata().ok().and_then(|m| m.modified().ok()))
+            .map(|entry| entry.path())
+    };
+    let session = newest(logs_dir, &|path| path.is_dir())?;
+    newest(&session, &|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(suffix))
+    })
+}
+
+/// Watches the game's notifications log for quest events.
+pub struct QuestLogWatcher {
+    rx: Receiver<QuestEvent>,
+}
+
+impl QuestLogWatcher {
+    /// Starts polling the game's logs. Returns `None` if the logs folder isn't found.
+    pub fn new(ctx: egui::Context) -> Option<Self> {
+        let logs_dir = logs_dir()?;
+        log::info!(
+            "Watching game logs for quest progress: {}",
+            logs_dir.display()
+        );
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || poll_logs(&logs_dir, &tx, &ctx));
+        Some(Self { rx })
+    }
+
+    /// Returns the quest events logged since the last call.
+    pub fn poll(&self) -> Vec<QuestEvent> {
+        self.rx.try_iter().collect()
+    }
+}
+
+/// Sends the events added to the newest notifications log until the receiver is dropped.
+fn poll_logs(logs_dir: &Path, tx: &Sender<QuestEvent>, ctx: &egui::Context) {
+    let mut current: Option<PathBuf> = None;
+    // Events already in the log when it was first seen are not news
+    let mut seen = 0;
+    let mut first = true;
+    loop {
+        if let Some(path) = newest_session_log(logs_dir, "notifications.log") {
+            let events = fs::read_to_string(&path)
+                .map(|text| parse_quest_events(&text))
+                .unwrap_or_default();
+            if current.as_ref() != Some(&path) {
+                seen = if first { events.len() } else { 0 };
+                current = Some(path);
+            }
+            first = false;
+
+            if events.len() > seen {
+                for event in events.into_iter().skip(seen) {
+                    seen += 1;
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+                ctx.request_repaint();
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+impl TarkovMapApp {
+    /// Records quest events from the game's logs, with a toast for completed quests.
+    pub fn poll_quest_log(&mut self) {
+        let Some(watcher) = &self.quest_log_watcher else {
+            return;
+        };
+        let mut completed = 0;
+        for event in watcher.poll() {
+            log::info!("Quest {} {:?}", event.quest_id, event.status);
+            match event.status {
+                QuestStatus::Completed => {
+                    completed += 1;
+                    self.completed_quests.insert(event.quest_id);
+                }
+                QuestStatus::Failed | QuestStatus::Started => {
+                    self.completed_quests.remove(&event.quest_id);
+                }
+            }
+        }
+        if completed > 0 {
+            let text = match completed {
+                1 => "Quest completed".to_owned(),
+                count => format!("{count} quests completed"),
+            };
+            self.toasts.add(Toast {
+                kind: ToastKind::Success,
+                text: text.into(),
+                options: ToastOptions::default().duration_in_seconds(6.0),
+                ..Default::default()
+            });
+        }
+    }
+}
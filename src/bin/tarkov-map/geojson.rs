@@ -0,0 +1,109 @@
+//! GeoJSON export and import of the selected map's markers.
+//!
+//! File > Export GeoJSON writes the labels, spawns and extracts of the selected
+//! map, with their notes and photo file names, to
+//! `<data dir>/tarkov-map/exports/`. Dropping a `.geojson` file onto the window
+//! replaces the selected map's markers with the ones in the file, e.g. to
+//! preview corrections made in QGIS. Imports last until the maps reload.
+
+use crate::notes::MarkerNotes;
+use crate::{APP_ID, TarkovMapApp};
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use std::fs;
+use std::path::PathBuf;
+use tarkov_map::SPAWN_CLUSTER_RADIUS;
+use tarkov_map::geojson::{Annotations, MapFeatures};
+
+/// Returns the folder exports are written to.
+fn exports_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("exports"))
+}
+
+impl TarkovMapApp {
+    fn geojson_toast(&mut self, kind: ToastKind, text: String) {
+        self.toasts.add(Toast {
+            kind,
+            text: text.into(),
+            options: ToastOptions::default().duration_in_seconds(6.0),
+            ..Default::default()
+        });
+    }
+
+    /// Writes the selected map's markers to a GeoJSON file and opens its folder.
+    pub fn export_geojson(&mut self) {
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let Some(dir) = exports_dir() else {
+            self.geojson_toast(ToastKind::Error, "No data directory available".to_owned());
+            return;
+        };
+
+        let file_name = format!("{}.geojson", map.normalized_name.replace('/', "-"));
+        let path = dir.join(file_name);
+        let json = map.to_geojson_annotated(|marker| {
+            let key = MarkerNotes::feature_key(marker);
+            Annotations {
+                note: self
+                    .notes
+                    .get(&map.normalized_name, &key)
+                    .map(str::to_owned),
+                photos: self.photos.get(&map.normalized_name, &key).to_vec(),
+            }
+        });
+        let result = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, json));
+        match result {
+            Ok(()) => {
+                log::info!("Exported GeoJSON to {}", path.display());
+                let _ = open::that(&dir);
+            }
+            Err(err) => self.geojson_toast(
+                ToastKind::Error,
+                format!("Failed to write {}: {err}", path.display()),
+            ),
+        }
+    }
+
+    /// Replaces the selected map's markers with those of a dropped `.geojson` file.
+    pub fn handle_dropped_geojson(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .find(|path| path.extension().is_some_and(|ext| ext == "geojson"))
+        });
+        let Some(path) = dropped else {
+            return;
+        };
+
+        let features = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| MapFeatures::from_geojson(&text).map_err(|err| err.to_string()));
+        let features = match features {
+            Ok(features) => features,
+            Err(err) => {
+                self.geojson_toast(
+                    ToastKind::Error,
+                    format!("Failed to import {}: {err}", path.display()),
+                );
+                return;
+            }
+        };
+
+        let Some(map) = self.maps.get_mut(self.selected_map) else {
+            return;
+        };
+        let count = features.len();
+        map.labels = Some(features.labels);
+        map.spawns = Some(features.spawns);
+        map.extracts = Some(features.extracts);
+        map.spawn_clusters = Some(map.compute_spawn_clusters(SPAWN_CLUSTER_RADIUS));
+
+        let text = format!("Imported {count} markers into {}", map.name);
+        self.selected_extract = None;
+        self.selected_marker = None;
+        self.geojson_toast(ToastKind::Info, text);
+    }
+}
@@ -0,0 +1,300 @@
+//! Startup health check.
+//!
+//! On launch (and from Help > Health Check) a background pass checks the things
+//! features silently depend on: map data, map images and their size against the
+//! GPU texture limit, the screenshots and game logs folders, and write access
+//! to the app data folder. Problems are listed in one dialog with a fix each.
+
+use crate::APP_ID;
+use crate::TarkovMapApp;
+use crate::assets::{load_maps, read_image_bytes};
+use crate::game_logs;
+use crate::screenshot_watcher::ScreenshotWatcher;
+use eframe::egui;
+use std::fs;
+use std::io::Cursor;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// How badly a problem affects the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// A feature doesn't work at all.
+    Error,
+    /// A feature may not work, or works with reduced quality.
+    Warning,
+}
+
+/// A problem found by the health check, with a suggestion to fix it.
+#[derive(Debug, Clone)]
+pub struct HealthIssue {
+    pub severity: Severity,
+    pub problem: String,
+    pub fix: String,
+}
+
+impl HealthIssue {
+    fn new(severity: Severity, problem: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            severity,
+            problem: problem.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+/// A map image to check, by map name and image path.
+struct ImageCheck {
+    map: String,
+    path: String,
+}
+
+/// State of the health check and its dialog.
+#[derive(Default)]
+pub struct HealthCheck {
+    /// Start a check on the next frame, once the GPU texture limit is known.
+    pending: bool,
+    rx: Option<Receiver<Vec<HealthIssue>>>,
+    /// Open the dialog even if the running check finds nothing.
+    show_empty: bool,
+    /// Issues of the last check while the dialog is open.
+    issues: Option<Vec<HealthIssue>>,
+}
+
+impl HealthCheck {
+    /// Creates the health check state, scheduling a check if `on_startup` is set.
+    pub fn new(on_startup: bool) -> Self {
+        Self {
+            pending: on_startup,
+            ..Default::default()
+        }
+    }
+}
+
+fn check_map_data(issues: &mut Vec<HealthIssue>) {
+    match load_maps() {
+        Ok(maps) if maps.is_empty() => issues.push(HealthIssue::new(
+            Severity::Error,
+            "The built-in map data contains no maps",
+            "Download the latest release of the app.",
+        )),
+        Ok(_) => {}
+        Err(err) => issues.push(HealthIssue::new(
+            Severity::Error,
+            format!("The built-in map data can't be loaded: {err}"),
+            "Download the latest release of the app.",
+        )),
+    }
+}
+
+/// Checks that every map image can be read, and fits in a texture of `max_texture_side`.
+fn check_map_images(images: &[ImageCheck], max_texture_side: usize, issues: &mut Vec<HealthIssue>) {
+    for image in images {
+        let dimensions = read_image_bytes(&image.path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| {
+                image::ImageReader::new(Cursor::new(bytes))
+                    .with_guessed_format()
+                    .map_err(|err| err.to_string())?
+                    .into_dimensions()
+                    .map_err(|err| err.to_string())
+            });
+        match dimensions {
+            Ok((width, height)) if width.max(height) as usize > max_texture_side => {
+                issues.push(HealthIssue::new(
+                    Severity::Warning,
+                    format!(
+                        "The {} image ({width}×{height}) is larger than your graphics card's \
+                         texture limit of {max_texture_side} pixels",
+                        image.map
+                    ),
+                    "Update your graphics drivers. If the map still doesn't show, \
+                     use a data pack with smaller images.",
+                ));
+            }
+            Ok(_) => {}
+            Err(err) => issues.push(HealthIssue::new(
+                Severity::Error,
+                format!("The {} image can't be read: {err}", image.map),
+                "Restore the image file, or remove the data pack or custom map that uses it.",
+            )),
+        }
+    }
+}
+
+fn check_screenshots_folder(issues: &mut Vec<HealthIssue>) {
+    match ScreenshotWatcher::screenshots_path() {
+        Some(path) if path.is_dir() => {}
+        Some(path) => issues.push(HealthIssue::new(
+            Severity::Warning,
+            format!("Screenshots folder not found: {}", path.display()),
+            "Take a screenshot in game so Tarkov creates the folder, then restart the app. \
+             Player position tracking is off until then.",
+        )),
+        None => issues.push(HealthIssue::new(
+            Severity::Warning,
+            "No Documents folder found for Tarkov's screenshots",
+            "Make sure your user account has a Documents folder. \
+             Player position tracking is off until then.",
+        )),
+    }
+}
+
+fn check_logs_folder(issues: &mut Vec<HealthIssue>) {
+    // The game only runs on Windows, where its default install paths apply
+    if !cfg!(windows) || game_logs::logs_dir().is_some() {
+        return;
+    }
+    issues.push(HealthIssue::new(
+        Severity::Warning,
+        "Tarkov's logs folder was not found in the default install location",
+        "Launch the game once to create it. Until then, or if Tarkov is installed \
+         in another folder, completed quests aren't tracked.",
+    ));
+}
+
+/// Checks that files can be created in the app data folder.
+fn check_data_dir(issues: &mut Vec<HealthIssue>) {
+    let Some(dir) = dirs::data_dir().map(|dir| dir.join(APP_ID)) else {
+        issues.push(HealthIssue::new(
+            Severity::Error,
+            "No app data folder is available",
+            "Notes, photos, sessions and exports can't be saved. \
+             Check that your user profile is set up correctly.",
+        ));
+        return;
+    };
+
+    let probe = dir.join(".write-test");
+    let result = fs::create_dir_all(&dir)
+        .and_then(|()| fs::write(&probe, b""))
+        .and_then(|()| fs::remove_file(&probe));
+    if let Err(err) = result {
+        issues.push(HealthIssue::new(
+            Severity::Error,
+            format!("Can't write to {}: {err}", dir.display()),
+            "Notes, photos, sessions and exports can't be saved. Check the folder's \
+             permissions, or allow the app in your antivirus' protected folders.",
+        ));
+    }
+}
+
+fn run_checks(images: &[ImageCheck], max_texture_side: usize) -> Vec<HealthIssue> {
+    let mut issues = Vec::new();
+    check_map_data(&mut issues);
+    check_map_images(images, max_texture_side, &mut issues);
+    check_screenshots_folder(&mut issues);
+    check_logs_folder(&mut issues);
+    check_data_dir(&mut issues);
+    issues.sort_by_key(|issue| issue.severity != Severity::Error);
+    issues
+}
+
+impl TarkovMapApp {
+    /// Runs the health check in the background, opening the dialog even if nothing is found.
+    pub fn start_health_check(&mut self, ctx: &egui::Context) {
+        self.health_check.pending = false;
+        self.health_check.show_empty = true;
+        self.spawn_health_check(ctx);
+    }
+
+    fn spawn_health_check(&mut self, ctx: &egui::Context) {
+        let images: Vec<ImageCheck> = self
+            .maps
+            .iter()
+            .flat_map(|map| {
+                let layers = map.layers.iter().flatten().filter_map(|layer| {
+                    Some(ImageCheck {
+                        map: format!("{} {}", map.name, layer.name),
+                        path: layer.image_path.clone()?,
+                    })
+                });
+                std::iter::once(ImageCheck {
+                    map: map.name.clone(),
+                    path: map.image_path.clone(),
+                })
+                .chain(layers)
+            })
+            .collect();
+        let max_texture_side = ctx.input(|i| i.max_texture_side);
+
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(run_checks(&images, max_texture_side));
+            ctx.request_repaint();
+        });
+        self.health_check.rx = Some(rx);
+    }
+
+    /// Starts the startup check on the first frame and collects finished results.
+    pub fn poll_health_check(&mut self, ctx: &egui::Context) {
+        if self.health_check.pending {
+            self.health_check.pending = false;
+            self.spawn_health_check(ctx);
+        }
+
+        let Some(rx) = &self.health_check.rx else {
+            return;
+        };
+        let Ok(issues) = rx.try_recv() else {
+            return;
+        };
+        self.health_check.rx = None;
+        for issue in &issues {
+            log::warn!("Health check: {}", issue.problem);
+        }
+        if !issues.is_empty() || self.health_check.show_empty {
+            self.health_check.issues = Some(issues);
+        }
+        self.health_check.show_empty = false;
+    }
+
+    /// Renders the health check dialog while it has results to show.
+    pub fn show_health_check_window(&mut self, ctx: &egui::Context) {
+        let Some(issues) = &self.health_check.issues else {
+            return;
+        };
+
+        let mut open = true;
+        let mut close = false;
+        egui::Window::new("Health Check")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(380.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                if issues.is_empty() {
+                    ui.label("No problems found.");
+                }
+                for issue in issues {
+                    let (icon, color) = match issue.severity {
+                        Severity::Error => ("⛔", ui.visuals().error_fg_color),
+                        Severity::Warning => ("⚠", ui.visuals().warn_fg_color),
+                    };
+                    ui.horizontal_top(|ui| {
+                        ui.colored_label(color, icon);
+                        ui.vertical(|ui| {
+                            ui.add(
+                                egui::Label::new(egui::RichText::new(&issue.problem).strong())
+                                    .wrap(),
+                            );
+                            ui.add(egui::Label::new(egui::RichText::new(&issue.fix).weak()).wrap());
+                        });
+                    });
+                    ui.add_space(4.0);
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.health_check_on_startup, "Check on startup");
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if !open || close {
+            self.health_check.issues = None;
+        }
+    }
+}
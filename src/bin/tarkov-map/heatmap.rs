@@ -0,0 +1,200 @@
+//! Personal heatmap of the parts of a map the player's own raids went through.
+//!
+//! The stored session trails on the selected map are rasterized onto a grid
+//! over the map bounds, filling in the gaps between screenshots along a
+//! straight line. Each cell counts a session once, so the heat shows how many
+//! raids passed through it and cells no raid reached stay clear.
+
+use crate::TarkovMapApp;
+use crate::colors;
+use crate::coordinates::game_to_display;
+use crate::sessions::load_sessions;
+use eframe::egui::{self, ColorImage, TextureHandle, TextureOptions};
+use tarkov_map::{GameBounds, Map, Position2, Position3};
+
+/// Smallest grid cell, in meters.
+const CELL_METERS: f64 = 8.0;
+
+/// Largest grid dimension, to bound the texture size on big maps.
+const MAX_CELLS: usize = 512;
+
+/// Opacity of the hottest cells.
+const HEATMAP_OPACITY: f32 = 0.6;
+
+/// The rasterized heatmap of one map.
+pub struct PersonalHeatmap {
+    /// Normalized name of the map the heatmap is for.
+    pub map: String,
+    /// `None` if the map has no bounds or no recorded sessions.
+    texture: Option<(TextureHandle, GameBounds)>,
+}
+
+/// Session counts per grid cell over `bounds`, row by row from `min.z`.
+struct Grid {
+    bounds: GameBounds,
+    cell: f64,
+    size: [usize; 2],
+    counts: Vec<u32>,
+}
+
+impl Grid {
+    fn new(bounds: GameBounds) -> Self {
+        let longest = bounds.width().max(bounds.height());
+        let cell = CELL_METERS.max(longest / MAX_CELLS as f64);
+        let size = [
+            ((bounds.width() / cell).ceil() as usize).max(1),
+            ((bounds.height() / cell).ceil() as usize).max(1),
+        ];
+        Self {
+            bounds,
+            cell,
+            size,
+            counts: vec![0; size[0] * size[1]],
+        }
+    }
+
+    fn cell_index(&self, position: Position2) -> Option<usize> {
+        if !self.bounds.contains(position) {
+            return None;
+        }
+        let col = ((position.x - self.bounds.min.x) / self.cell) as usize;
+        let row = ((position.z - self.bounds.min.z) / self.cell) as usize;
+        Some(row.min(self.size[1] - 1) * self.size[0] + col.min(self.size[0] - 1))
+    }
+
+    /// Adds one to every cell along the trail.
+    fn add_trail(&mut self, trail: &[Position2]) {
+        let mut visited = vec![false; self.counts.len()];
+        let mut visit = |position: Position2| {
+            if let Some(idx) = self.cell_index(position) {
+                visited[idx] = true;
+            }
+        };
+        if let [only] = trail {
+            visit(*only);
+        }
+        for pair in trail.windows(2) {
+            // Half-cell steps so no cell along the line is skipped
+            let steps = (pair[0].distance(pair[1]) / (self.cell / 2.0))
+                .ceil()
+                .max(1.0);
+            for step in 0..=steps as usize {
+                visit(pair[0].lerp(pair[1], step as f64 / steps));
+            }
+        }
+        for (count, visited) in self.counts.iter_mut().zip(visited) {
+            *count += u32::from(visited);
+        }
+    }
+
+    /// Colors cells from cold to hot on a log scale, leaving unvisited cells clear.
+    fn to_image(&self) -> ColorImage {
+        let max = self.counts.iter().copied().max().unwrap_or(0).max(1);
+        let scale = f64::from(max).ln_1p();
+        let pixels = self
+            .counts
+            .iter()
+            .map(|&count| {
+                if count == 0 {
+                    return egui::Color32::TRANSPARENT;
+                }
+                let t = (f64::from(count).ln_1p() / scale) as f32;
+                colors::HEATMAP_COLD
+                    .lerp_to_gamma(colors::HEATMAP_HOT, t)
+                    .gamma_multiply(HEATMAP_OPACITY * (0.5 + t / 2.0))
+            })
+            .collect();
+        ColorImage::new(self.size, pixels)
+    }
+}
+
+impl PersonalHeatmap {
+    /// Rasterizes the stored sessions on `map`.
+    fn build(ctx: &egui::Context, map: &Map) -> Self {
+        let trails: Vec<Vec<Position2>> = load_sessions()
+            .into_iter()
+            .filter(|stored| stored.session.map == map.normalized_name)
+            .map(|stored| {
+                let positions = stored.session.positions.into_iter();
+                positions.map(|p| Position3::from(p).xz()).collect()
+            })
+            .collect();
+
+        let texture = map
+            .game_bounds()
+            .filter(|_| !trails.is_empty())
+            .map(|bounds| {
+                let mut grid = Grid::new(bounds);
+                for trail in &trails {
+                    grid.add_trail(trail);
+                }
+                let texture = ctx.load_texture(
+                    format!("heatmap/{}", map.normalized_name),
+                    grid.to_image(),
+                    TextureOptions::LINEAR,
+                );
+                (texture, bounds)
+            });
+
+        Self {
+            map: map.normalized_name.clone(),
+            texture,
+        }
+    }
+}
+
+/// Draws the heatmap texture stretched over the map bounds.
+pub fn draw_personal_heatmap(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    heatmap: &PersonalHeatmap,
+) {
+    let Some((texture, bounds)) = &heatmap.texture else {
+        return;
+    };
+    let (min, max) = (bounds.min, bounds.max);
+    let corners = [
+        (Position2::new(min.x, min.z), egui::pos2(0.0, 0.0)),
+        (Position2::new(max.x, min.z), egui::pos2(1.0, 0.0)),
+        (Position2::new(max.x, max.z), egui::pos2(1.0, 1.0)),
+        (Position2::new(min.x, max.z), egui::pos2(0.0, 1.0)),
+    ];
+
+    // The map may be rotated on screen, so map the corners rather than a rect
+    let mut mesh = egui::Mesh::with_texture(texture.id());
+    for (position, uv) in corners {
+        let Some(pos) = game_to_display(map, map_rect, position) else {
+            return;
+        };
+        mesh.vertices.push(egui::epaint::Vertex {
+            pos,
+            uv,
+            color: egui::Color32::WHITE,
+        });
+    }
+    mesh.add_triangle(0, 1, 2);
+    mesh.add_triangle(0, 2, 3);
+    ui.painter().add(mesh);
+}
+
+impl TarkovMapApp {
+    /// Rebuilds the heatmap when it is shown for a different map or new positions were recorded.
+    pub fn update_personal_heatmap(&mut self, ctx: &egui::Context) {
+        if !self.overlays.personal_heatmap {
+            return;
+        }
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let current = self
+            .personal_heatmap
+            .as_ref()
+            .is_some_and(|heatmap| heatmap.map == map.normalized_name);
+        if current && !self.personal_heatmap_stale {
+            return;
+        }
+        self.personal_heatmap = Some(PersonalHeatmap::build(ctx, map));
+        self.personal_heatmap_stale = false;
+    }
+}
@@ -0,0 +1,98 @@
+//! Reloads map data when `assets/maps.ron` changes on disk.
+//!
+//! Only useful in debug builds, where rust-embed reads assets from the
+//! filesystem instead of the binary. Lets data contributors rerun
+//! `fetch_maps` or edit `maps.ron` without restarting the viewer.
+
+use crate::TarkovMapApp;
+use crate::data_packs::load_map_data;
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+
+const MAPS_RON_FILE: &str = "maps.ron";
+
+/// Watches the assets folder for changes to `maps.ron`.
+pub struct MapsWatcher {
+    changed_rx: Receiver<()>,
+    /// The watcher must be kept alive for events to fire
+    _watcher: RecommendedWatcher,
+}
+
+impl MapsWatcher {
+    /// Starts watching. Returns `None` in release builds or if watching fails.
+    pub fn new(ctx: egui::Context) -> Option<Self> {
+        if !cfg!(debug_assertions) {
+            return None;
+        }
+
+        let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets");
+        let (changed_tx, changed_rx) = mpsc::channel();
+
+        let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+            let Ok(event) = res else {
+                return;
+            };
+            let is_maps_ron = event
+                .paths
+                .iter()
+                .any(|path| path.file_name().is_some_and(|name| name == MAPS_RON_FILE));
+            if is_maps_ron && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                let _ = changed_tx.send(());
+                ctx.request_repaint();
+            }
+        })
+        .inspect_err(|err| log::warn!("Failed to create maps.ron watcher: {err}"))
+        .ok()?;
+
+        watcher
+            .watch(&assets_dir, RecursiveMode::NonRecursive)
+            .inspect_err(|err| log::warn!("Failed to watch {}: {err}", assets_dir.display()))
+            .ok()?;
+
+        log::info!("Watching {} for map data changes", assets_dir.display());
+        Some(Self {
+            changed_rx,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns `true` if `maps.ron` changed since the last poll.
+    fn poll(&self) -> bool {
+        self.changed_rx.try_iter().count() > 0
+    }
+}
+
+impl TarkovMapApp {
+    /// Reloads map data if `maps.ron` changed, keeping the selected map and view.
+    pub fn poll_maps_reload(&mut self) {
+        if !self.maps_watcher.as_ref().is_some_and(MapsWatcher::poll) {
+            return;
+        }
+
+        let data = load_map_data(&self.data_packs);
+        if let Some(err) = data.errors.first() {
+            // Often a half-written file; the next change event retries
+            log::warn!("Failed to reload map data: {err}");
+            self.toasts.add(Toast {
+                kind: ToastKind::Error,
+                text: format!("Failed to reload map data: {err}").into(),
+                options: ToastOptions::default().duration_in_seconds(5.0),
+                ..Default::default()
+            });
+            return;
+        }
+
+        log::info!("Reloaded {} maps", data.maps.len());
+        self.data_pack_conflicts = data.conflicts;
+        self.replace_maps(data.maps);
+        self.toasts.add(Toast {
+            kind: ToastKind::Info,
+            text: "Reloaded map data".into(),
+            options: ToastOptions::default().duration_in_seconds(3.0),
+            ..Default::default()
+        });
+    }
+}
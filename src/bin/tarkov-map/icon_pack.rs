@@ -0,0 +1,183 @@
+//! User-provided icon packs that override the built-in marker shapes.
+//!
+//! An icon pack is a folder inside `<data dir>/tarkov-map/icon-packs/` containing
+//! PNG or SVG files named after marker categories, e.g. `spawn.png` or
+//! `pmc-extract.svg`. Categories without an icon keep the built-in shape.
+
+use crate::APP_ID;
+use eframe::egui::{self, ColorImage, TextureHandle, TextureOptions};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Pixel size SVG icons are rasterized at.
+const SVG_ICON_SIZE: u32 = 64;
+
+/// Marker categories that can be themed by an icon pack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkerIcon {
+    Spawn,
+    PmcExtract,
+    ScavExtract,
+    SharedExtract,
+    Player,
+}
+
+impl MarkerIcon {
+    pub const ALL: [Self; 5] = [
+        Self::Spawn,
+        Self::PmcExtract,
+        Self::ScavExtract,
+        Self::SharedExtract,
+        Self::Player,
+    ];
+
+    /// File stem the icon is looked up by inside a pack folder.
+    pub fn file_stem(self) -> &'static str {
+        match self {
+            Self::Spawn => "spawn",
+            Self::PmcExtract => "pmc-extract",
+            Self::ScavExtract => "scav-extract",
+            Self::SharedExtract => "shared-extract",
+            Self::Player => "player",
+        }
+    }
+}
+
+/// Errors that can occur when loading an icon pack.
+#[derive(Error, Debug)]
+pub enum IconPackError {
+    #[error("icon pack folder not found: {0}")]
+    NotFound(String),
+    #[error("failed to read icon '{path}': {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("failed to decode icon '{path}': {message}")]
+    Decode { path: String, message: String },
+}
+
+/// Loaded icon textures, keyed by marker category.
+#[derive(Default)]
+pub struct IconPack {
+    textures: HashMap<MarkerIcon, TextureHandle>,
+}
+
+impl IconPack {
+    /// Loads the icon pack with the given folder name.
+    pub fn load(ctx: &egui::Context, name: &str) -> Result<Self, IconPackError> {
+        let dir = icon_packs_dir()
+            .map(|dir| dir.join(name))
+            .filter(|dir| dir.is_dir())
+            .ok_or_else(|| IconPackError::NotFound(name.to_owned()))?;
+
+        let mut textures = HashMap::new();
+        for icon in MarkerIcon::ALL {
+            let Some(path) = find_icon_file(&dir, icon.file_stem()) else {
+                continue;
+            };
+            let image = decode_icon(&path)?;
+            let texture = ctx.load_texture(
+                format!("icon-pack/{name}/{}", icon.file_stem()),
+                image,
+                TextureOptions::LINEAR,
+            );
+            textures.insert(icon, texture);
+        }
+
+        log::info!("Loaded icon pack '{name}' with {} icons", textures.len());
+        Ok(Self { textures })
+    }
+
+    /// Returns the texture overriding `icon`, if the pack provides one.
+    pub fn get(&self, icon: MarkerIcon) -> Option<&TextureHandle> {
+        self.textures.get(&icon)
+    }
+
+    /// Paints `icon` centered on `pos`. Returns `false` if the pack has no such icon.
+    pub fn paint(
+        &self,
+        painter: &egui::Painter,
+        icon: MarkerIcon,
+        pos: egui::Pos2,
+        size: f32,
+    ) -> bool {
+        let Some(texture) = self.get(icon) else {
+            return false;
+        };
+        painter.image(
+            texture.id(),
+            egui::Rect::from_center_size(pos, egui::vec2(size, size)),
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+        true
+    }
+}
+
+/// Returns the folder icon packs are installed into.
+pub fn icon_packs_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("icon-packs"))
+}
+
+/// Lists the names of installed icon packs, sorted alphabetically.
+pub fn available_icon_packs() -> Vec<String> {
+    let Some(entries) = icon_packs_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut packs: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    packs.sort();
+    packs
+}
+
+fn find_icon_file(dir: &Path, stem: &str) -> Option<PathBuf> {
+    ["png", "svg"]
+        .iter()
+        .map(|ext| dir.join(format!("{stem}.{ext}")))
+        .find(|path| path.is_file())
+}
+
+fn decode_icon(path: &Path) -> Result<ColorImage, IconPackError> {
+    let path_str = path.display().to_string();
+    let bytes = fs::read(path).map_err(|source| IconPackError::Read {
+        path: path_str.clone(),
+        source,
+    })?;
+    let decode_error = |message: String| IconPackError::Decode {
+        path: path_str.clone(),
+        message,
+    };
+
+    if path.extension().is_some_and(|ext| ext == "svg") {
+        let tree = resvg::usvg::Tree::from_data(&bytes, &resvg::usvg::Options::default())
+            .map_err(|e| decode_error(e.to_string()))?;
+        let size = tree.size();
+        let scale = SVG_ICON_SIZE as f32 / size.width().max(size.height());
+        let mut pixmap = resvg::tiny_skia::Pixmap::new(SVG_ICON_SIZE, SVG_ICON_SIZE)
+            .ok_or_else(|| decode_error("failed to create pixmap".into()))?;
+        resvg::render(
+            &tree,
+            resvg::tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+        return Ok(ColorImage::from_rgba_premultiplied(
+            [SVG_ICON_SIZE as usize, SVG_ICON_SIZE as usize],
+            pixmap.data(),
+        ));
+    }
+
+    let rgba = image::load_from_memory(&bytes)
+        .map_err(|e| decode_error(e.to_string()))?
+        .to_rgba8();
+    Ok(ColorImage::from_rgba_unmultiplied(
+        [rgba.width() as usize, rgba.height() as usize],
+        &rgba,
+    ))
+}
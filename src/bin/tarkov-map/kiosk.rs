@@ -0,0 +1,210 @@
+//! Borderless fullscreen "kiosk" mode for dedicating a monitor to the map.
+//!
+//! Kiosk mode goes fullscreen on the monitor the window is on and hides all
+//! chrome. The window position is remembered so that starting in kiosk mode
+//! reopens on the same monitor. A monitor can also be picked in the View menu.
+//!
+//! egui can't list monitors, so the app runs on its own winit event loop and
+//! [`MonitorProbe`] lists them from it for the picker.
+
+use crate::TarkovMapApp;
+use crate::usage_stats::Feature;
+use eframe::UserEvent;
+use eframe::egui::{self, ViewportCommand};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use winit::application::ApplicationHandler;
+use winit::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
+use winit::event_loop::ActiveEventLoop;
+use winit::window::WindowId;
+
+/// Persisted kiosk mode preferences.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KioskSettings {
+    /// Enter kiosk mode on startup.
+    pub start_in_kiosk: bool,
+    /// Outer window position when kiosk mode was last entered, identifying the monitor.
+    pub monitor_position: Option<[f32; 2]>,
+    /// Top-left corner of the monitor picked for kiosk mode. `None` uses the
+    /// monitor the window is on.
+    pub monitor: Option<[f32; 2]>,
+}
+
+/// A connected monitor, in logical points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    pub name: String,
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+}
+
+impl Monitor {
+    fn contains(&self, [x, y]: [f32; 2]) -> bool {
+        let [left, top] = self.position;
+        let [width, height] = self.size;
+        (left..left + width).contains(&x) && (top..top + height).contains(&y)
+    }
+}
+
+/// Monitors listed by [`MonitorProbe`], shared with the app.
+pub type Monitors = Arc<Mutex<Vec<Monitor>>>;
+
+/// Wraps the eframe app on the winit event loop, listing the monitors on
+/// startup and whenever the window gains focus or changes scale.
+pub struct MonitorProbe<A> {
+    pub app: A,
+    pub monitors: Monitors,
+}
+
+impl<A> MonitorProbe<A> {
+    fn refresh(&self, event_loop: &ActiveEventLoop) {
+        let monitors = event_loop
+            .available_monitors()
+            .enumerate()
+            .map(|(idx, handle)| {
+                let scale = handle.scale_factor();
+                let position = handle.position().to_logical::<f32>(scale);
+                let size = handle.size().to_logical::<f32>(scale);
+                Monitor {
+                    name: handle
+                        .name()
+                        .unwrap_or_else(|| format!("Monitor {}", idx + 1)),
+                    position: [position.x, position.y],
+                    size: [size.width, size.height],
+                }
+            })
+            .collect();
+        if let Ok(mut shared) = self.monitors.lock() {
+            *shared = monitors;
+        }
+    }
+}
+
+impl<A: ApplicationHandler<UserEvent>> ApplicationHandler<UserEvent> for MonitorProbe<A> {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.refresh(event_loop);
+        self.app.resumed(event_loop);
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        if matches!(
+            event,
+            WindowEvent::Focused(true) | WindowEvent::ScaleFactorChanged { .. }
+        ) {
+            self.refresh(event_loop);
+        }
+        self.app.window_event(event_loop, id, event);
+    }
+
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: UserEvent) {
+        self.app.user_event(event_loop, event);
+    }
+
+    fn device_event(&mut self, event_loop: &ActiveEventLoop, id: DeviceId, event: DeviceEvent) {
+        self.app.device_event(event_loop, id, event);
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+    }
+
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    fn exiting(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+}
+
+impl TarkovMapApp {
+    /// Enters kiosk mode on startup if configured, on the remembered monitor.
+    pub fn apply_kiosk_on_start(&mut self, ctx: &egui::Context) {
+        if !self.kiosk_settings.start_in_kiosk {
+            return;
+        }
+        let position = self
+            .kiosk_settings
+            .monitor
+            .or(self.kiosk_settings.monitor_position);
+        if let Some([x, y]) = position {
+            ctx.send_viewport_cmd(ViewportCommand::OuterPosition(egui::pos2(x, y)));
+        }
+        ctx.send_viewport_cmd(ViewportCommand::Fullscreen(true));
+        self.kiosk = true;
+    }
+
+    /// Toggles kiosk mode on the picked monitor, or the one the window is currently on.
+    pub fn toggle_kiosk(&mut self, ctx: &egui::Context) {
+        self.kiosk = !self.kiosk;
+        if self.kiosk {
+            self.usage.feature_used(Feature::KioskMode);
+        }
+
+        if self.kiosk {
+            if let Some([x, y]) = self.kiosk_settings.monitor {
+                ctx.send_viewport_cmd(ViewportCommand::OuterPosition(egui::pos2(x, y)));
+                self.kiosk_settings.monitor_position = Some([x, y]);
+            } else if let Some(rect) = ctx.input(|i| i.viewport().outer_rect) {
+                self.kiosk_settings.monitor_position = Some([rect.min.x, rect.min.y]);
+            }
+        }
+        ctx.send_viewport_cmd(ViewportCommand::Fullscreen(self.kiosk));
+    }
+
+    /// Renders the combo box picking the monitor kiosk mode opens on.
+    pub fn kiosk_monitor_ui(&mut self, ui: &mut egui::Ui) {
+        let monitors = self
+            .monitors
+            .lock()
+            .map(|monitors| monitors.clone())
+            .unwrap_or_default();
+        let picked = self.kiosk_settings.monitor;
+        let selected_text = match picked {
+            None => "Current monitor".to_owned(),
+            Some(position) => monitors
+                .iter()
+                .find(|monitor| monitor.contains(position))
+                .map_or_else(|| "Disconnected monitor".to_owned(), |m| m.name.clone()),
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Kiosk monitor");
+            egui::ComboBox::from_id_salt("kiosk_monitor")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.kiosk_settings.monitor, None, "Current monitor");
+                    for monitor in &monitors {
+                        let [width, height] = monitor.size;
+                        ui.selectable_value(
+                            &mut self.kiosk_settings.monitor,
+                            Some(monitor.position),
+                            format!("{} ({width:.0}×{height:.0})", monitor.name),
+                        );
+                    }
+                });
+        });
+    }
+
+    /// Renders only the map, without title bar, sidebar, or status bar.
+    pub fn show_kiosk_frame(&mut self, ctx: &egui::Context) {
+        let selected_map = self.selected_map().cloned();
+
+        egui::CentralPanel::default()
+            .frame(egui::Frame::NONE.fill(ctx.style().visuals.window_fill()))
+            .show(ctx, |ui| {
+                if let Some(map) = selected_map {
+                    self.show_map(ui, ctx, &map);
+                }
+            });
+    }
+}
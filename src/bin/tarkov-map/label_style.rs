@@ -0,0 +1,93 @@
+//! Text rendering options for map and extract labels.
+
+use crate::colors;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// How map and extract labels are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LabelStyle {
+    /// Multiplier applied to the zoom-based font size.
+    pub font_scale: f32,
+    /// Outline thickness in pixels. `0.0` draws no outline.
+    pub outline: f32,
+    /// Draws a dark rounded background behind each label.
+    pub background: bool,
+}
+
+impl Default for LabelStyle {
+    fn default() -> Self {
+        Self {
+            font_scale: 1.0,
+            outline: 1.0,
+            background: false,
+        }
+    }
+}
+
+impl LabelStyle {
+    /// Paints `text` anchored at `pos` with this style's outline and background.
+    #[allow(clippy::too_many_arguments)]
+    pub fn paint(
+        &self,
+        painter: &egui::Painter,
+        pos: egui::Pos2,
+        anchor: egui::Align2,
+        text: &str,
+        font_size: f32,
+        color: egui::Color32,
+        outline_color: egui::Color32,
+    ) {
+        let font_id = egui::FontId::proportional(font_size * self.font_scale);
+        let galley = painter.layout_no_wrap(text.to_owned(), font_id, color);
+        let rect = anchor.anchor_size(pos, galley.size());
+
+        if self.background {
+            let pill = rect.expand2(egui::vec2(rect.height() * 0.35, 1.0));
+            painter.rect_filled(pill, pill.height() / 2.0, colors::LABEL_BACKGROUND);
+        }
+
+        if self.outline > 0.0 {
+            let t = self.outline;
+            let offsets = [
+                (-t, -t),
+                (0.0, -t),
+                (t, -t),
+                (-t, 0.0),
+                (t, 0.0),
+                (-t, t),
+                (0.0, t),
+                (t, t),
+            ];
+            for (dx, dy) in offsets {
+                painter.galley_with_override_text_color(
+                    rect.min + egui::vec2(dx, dy),
+                    galley.clone(),
+                    outline_color,
+                );
+            }
+        }
+
+        painter.galley(rect.min, galley, color);
+    }
+
+    /// Renders the label style controls.
+    pub fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add(
+            egui::Slider::new(&mut self.font_scale, 0.5..=2.0)
+                .text("Font size")
+                .fixed_decimals(1),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.outline, 0.0..=3.0)
+                .text("Outline")
+                .suffix(" px")
+                .fixed_decimals(1),
+        );
+        ui.checkbox(&mut self.background, "Background");
+        if ui.button("Reset").clicked() {
+            *self = Self::default();
+        }
+    }
+}
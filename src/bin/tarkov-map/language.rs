@@ -0,0 +1,75 @@
+//! Display language of map, label and extract names.
+//!
+//! `fetch_maps` stores tarkov.dev's translations next to the English names.
+//! Picking a language swaps the names shown in the sidebar and on the map;
+//! anything without a translation stays in English.
+
+use crate::TarkovMapApp;
+use eframe::egui;
+use std::collections::BTreeSet;
+
+/// English name of a tarkov.dev language code.
+fn language_name(code: &str) -> &str {
+    match code {
+        "cs" => "Czech",
+        "de" => "German",
+        "es" => "Spanish",
+        "fr" => "French",
+        "hu" => "Hungarian",
+        "it" => "Italian",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        "pl" => "Polish",
+        "pt" => "Portuguese",
+        "ro" => "Romanian",
+        "ru" => "Russian",
+        "sk" => "Slovak",
+        "tr" => "Turkish",
+        "zh" => "Chinese",
+        other => other,
+    }
+}
+
+impl TarkovMapApp {
+    /// Language codes that any loaded map has names in.
+    fn available_languages(&self) -> BTreeSet<&str> {
+        self.maps
+            .iter()
+            .flat_map(|map| {
+                let map_name = map.translations.iter().flatten();
+                let labels = map
+                    .labels
+                    .iter()
+                    .flatten()
+                    .flat_map(|label| label.translations.iter().flatten());
+                let extracts = map
+                    .extracts
+                    .iter()
+                    .flatten()
+                    .flat_map(|extract| extract.translations.iter().flatten());
+                map_name.chain(labels).chain(extracts)
+            })
+            .map(|(code, _)| code.as_str())
+            .collect()
+    }
+
+    /// Renders the language picker.
+    pub fn language_ui(&mut self, ui: &mut egui::Ui) {
+        let languages: Vec<String> = self
+            .available_languages()
+            .into_iter()
+            .map(str::to_owned)
+            .collect();
+
+        ui.radio_value(&mut self.language, None, "English");
+        if languages.is_empty() {
+            ui.weak("The map data has no translations");
+            return;
+        }
+        for code in languages {
+            let name = language_name(&code).to_owned();
+            ui.radio_value(&mut self.language, Some(code), name);
+        }
+        ui.weak("Used for map, label and extract names");
+    }
+}
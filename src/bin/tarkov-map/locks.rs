@@ -0,0 +1,34 @@
+//! Hover details for lock markers.
+
+use crate::TarkovMapApp;
+use crate::marker_picking::{MarkerHit, MarkerKind};
+use eframe::egui;
+use tarkov_map::Map;
+
+impl TarkovMapApp {
+    /// Shows a tooltip with the key, lock type and power requirement for the picked lock at `index`.
+    pub fn show_lock_tooltip(&self, response: &egui::Response, map: &Map, index: usize) {
+        let Some(lock) = map.locks.iter().flatten().nth(index) else {
+            return;
+        };
+
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            match &lock.key {
+                Some(key) => ui.strong(&key.name),
+                None => ui.strong("Unknown key"),
+            };
+            ui.label(format!("Lock: {}", lock.lock_type));
+            if lock.needs_power {
+                ui.label("Needs power");
+            }
+            let p = lock.position;
+            ui.weak(self.coordinate_convention.format(p.x, Some(p.y), p.z));
+            let hit = MarkerHit {
+                kind: MarkerKind::Lock,
+                index,
+            };
+            self.show_marker_note(ui, map, hit);
+            self.marker_picking.cycle_hint(ui);
+        });
+    }
+}
@@ -1,36 +1,159 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod assets;
+mod bookmarks;
+mod bosses;
+mod carried_items;
+mod checklist;
+mod clipboard;
 mod colors;
+mod compare;
 mod constants;
 mod coordinates;
+mod custom_maps;
+mod data_freshness;
+mod data_packs;
+mod extract_list;
+mod extract_routes;
+mod game_logs;
+mod geojson;
+mod health_check;
+mod heatmap;
+mod hot_reload;
+mod icon_pack;
+mod kiosk;
+mod label_style;
+mod language;
+mod locks;
+mod map_detect;
+mod map_images;
+mod map_layers;
+mod map_stats;
+mod map_variants;
+mod marker_picking;
+mod measure;
+mod notes;
 mod overlays;
+mod pan_clamp;
+mod photos;
+mod print_export;
+mod raid_history;
+mod raid_mode;
+mod raid_plans;
+mod raid_time;
+mod safe_mode;
+mod scav_timer;
 mod screenshot_watcher;
+mod search;
+mod session_browser;
+mod sessions;
+mod squad;
+mod switches;
 mod ui;
 mod updater;
-
-use assets::{AssetLoadState, load_and_decode_image, load_maps};
+mod usage_stats;
+mod zoom;
+
+use assets::{AssetLoadState, DataProvenance, load_and_decode_image, load_data_provenance};
+use bookmarks::Bookmark;
+use carried_items::CarriedItems;
+use checklist::RaidChecklist;
+use clap::Parser;
+use clipboard::ClipboardWatcher;
+use compare::Comparison;
+use constants::{DATA_STALE_AFTER_DAYS, SCREENSHOT_CLEANUP_INTERVAL};
+use coordinates::CoordinateConvention;
+use custom_maps::CustomMapWizard;
+use data_packs::{DataPackSettings, PackConflict, load_map_data};
+use eframe::UserEvent;
 use eframe::egui::{self, ColorImage, TextureHandle, TextureOptions};
 use egui_toast::{Toast, ToastKind, ToastOptions, Toasts};
-use overlays::OverlayVisibility;
-use screenshot_watcher::{PlayerPosition, ScreenshotWatcher};
+use extract_list::ExtractSort;
+use game_logs::QuestLogWatcher;
+use health_check::HealthCheck;
+use heatmap::PersonalHeatmap;
+use hot_reload::MapsWatcher;
+use icon_pack::IconPack;
+use kiosk::{KioskSettings, MonitorProbe, Monitors};
+use label_style::LabelStyle;
+use map_detect::MapDetection;
+use map_images::{ImageLru, MapHover};
+use marker_picking::{MarkerHit, MarkerPicking};
+use measure::Measurement;
+use notes::MarkerNotes;
+use overlays::{Overlay, OverlayVisibility, builtin_overlays};
+use pan_clamp::PanClamp;
+use photos::MarkerPhotos;
+use print_export::PrintExportError;
+use raid_mode::RaidMode;
+use raid_plans::RaidPlans;
+use safe_mode::{SafeMode, clear_running_marker, mark_running};
+use scav_timer::{RaidEndWatcher, ScavTimer};
+use screenshot_watcher::{PlayerPosition, ScreenshotCleanup, ScreenshotPolling, ScreenshotWatcher};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use session_browser::{SessionBrowser, SessionPrune};
+use sessions::{RaidHistory, RaidSession, SessionRecorder};
+use squad::SquadSession;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
 use std::sync::{Arc, mpsc};
 use std::thread;
-use tarkov_map::{Map, TarkovMaps};
+use std::time::Instant;
+use tarkov_map::{Map, MapView, TarkovMaps};
+use usage_stats::UsageTracker;
+use winit::event_loop::EventLoop;
+use zoom::ZoomAnchor;
 
 const APP_ID: &str = "tarkov-map";
 const APP_TITLE: &str = "Tarkov Map";
 const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 const SETTINGS_STORAGE_KEY: &str = "app_settings";
 
+/// Interactive maps for Escape from Tarkov.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Start with default settings and only the last selected map, without
+    /// position tracking, to recover from broken settings or rendering
+    #[arg(long)]
+    safe_mode: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 struct AppSettings {
     schema_version: u32,
     selected_map_normalized_name: Option<String>,
     overlays: OverlayVisibility,
+    icon_pack: Option<String>,
+    notes: MarkerNotes,
+    photos: MarkerPhotos,
+    screenshot_cleanup: ScreenshotCleanup,
+    screenshot_polling: ScreenshotPolling,
+    watch_clipboard: bool,
+    bookmarks: Vec<Bookmark>,
+    kiosk: KioskSettings,
+    map_detection: MapDetection,
+    session_prune: SessionPrune,
+    label_style: LabelStyle,
+    usage_stats: bool,
+    data_packs: DataPackSettings,
+    pan_clamp: PanClamp,
+    zoom_anchor: ZoomAnchor,
+    raid_checklist: RaidChecklist,
+    coordinate_convention: CoordinateConvention,
+    health_check_on_startup: bool,
+    squad_address: String,
+    squad_bind_address: String,
+    completed_quests: BTreeSet<String>,
+    raid_plans: RaidPlans,
+    marker_picking: MarkerPicking,
+    data_stale_days: u32,
+    language: Option<String>,
+    scav_timer: ScavTimer,
+    carried_items: CarriedItems,
+    /// Select the layer and hide labels by the player's height.
+    follow_player_height: bool,
 }
 
 impl Default for AppSettings {
@@ -39,6 +162,34 @@ impl Default for AppSettings {
             schema_version: 1,
             selected_map_normalized_name: None,
             overlays: OverlayVisibility::default(),
+            icon_pack: None,
+            notes: MarkerNotes::default(),
+            photos: MarkerPhotos::default(),
+            screenshot_cleanup: ScreenshotCleanup::default(),
+            screenshot_polling: ScreenshotPolling::default(),
+            watch_clipboard: false,
+            bookmarks: Vec::new(),
+            kiosk: KioskSettings::default(),
+            map_detection: MapDetection::default(),
+            session_prune: SessionPrune::default(),
+            label_style: LabelStyle::default(),
+            usage_stats: false,
+            data_packs: DataPackSettings::default(),
+            pan_clamp: PanClamp::default(),
+            zoom_anchor: ZoomAnchor::default(),
+            raid_checklist: RaidChecklist::default(),
+            coordinate_convention: CoordinateConvention::default(),
+            health_check_on_startup: true,
+            squad_address: String::new(),
+            squad_bind_address: String::new(),
+            completed_quests: BTreeSet::new(),
+            raid_plans: RaidPlans::default(),
+            marker_picking: MarkerPicking::default(),
+            data_stale_days: DATA_STALE_AFTER_DAYS,
+            language: None,
+            scav_timer: ScavTimer::default(),
+            carried_items: CarriedItems::default(),
+            follow_player_height: true,
         }
     }
 }
@@ -50,47 +201,199 @@ pub struct TarkovMapApp {
     zoom: f32,
     prev_zoom: f32,
     pan_offset: egui::Vec2,
+    pan_clamp: PanClamp,
+    zoom_anchor: ZoomAnchor,
+    raid_checklist: RaidChecklist,
+    coordinate_convention: CoordinateConvention,
     overlays: OverlayVisibility,
+    label_style: LabelStyle,
+    /// Overlays drawn over the map, bottom to top.
+    registered_overlays: Vec<Box<dyn Overlay>>,
+    /// Reloads `maps.ron` on change (debug builds only).
+    maps_watcher: Option<MapsWatcher>,
     asset_cache: HashMap<String, AssetLoadState>,
     texture_cache: HashMap<String, TextureHandle>,
+    /// Recency of loaded map images, for unloading within the budget.
+    image_lru: ImageLru,
+    /// Map hovered in the sidebar, pre-warmed after a short delay.
+    map_hover: Option<MapHover>,
     toasts: Toasts,
     updater: updater::Updater,
     screenshot_watcher: Option<ScreenshotWatcher>,
+    screenshot_polling: ScreenshotPolling,
     player_position: Option<PlayerPosition>,
+    /// View to apply once the viewport size is known (default view or bookmark).
+    pending_view: Option<MapView>,
+    /// Displayed map size in pixels during the last frame.
+    last_display_size: egui::Vec2,
+    /// Game position `[x, z]` to center the view on during the next frame.
+    focus_position: Option<[f64; 2]>,
+    extract_sort: ExtractSort,
+    measurement: Measurement,
+    icon_pack: IconPack,
+    icon_pack_name: Option<String>,
+    notes: MarkerNotes,
+    photos: MarkerPhotos,
+    /// Extract selected in the extract list, for editing its note.
+    selected_extract: Option<String>,
+    /// Marker clicked on the map, for editing its note.
+    selected_marker: Option<MarkerHit>,
+    /// Index of the layer drawn over the selected map, if any.
+    selected_layer: Option<usize>,
+    /// Older dataset the current maps are compared against, if any.
+    comparison: Option<Comparison>,
+    screenshot_cleanup: ScreenshotCleanup,
+    /// When screenshot cleanup last ran (`None` = not yet this session).
+    last_screenshot_cleanup: Option<Instant>,
+    clipboard_watcher: Option<ClipboardWatcher>,
+    /// Coordinates last offered from the clipboard, placed when the toast action is clicked.
+    clipboard_offer: Option<[f64; 2]>,
+    clipboard_place_rx: mpsc::Receiver<()>,
+    /// Temporary marker `[x, z]` placed from clipboard coordinates.
+    temp_marker: Option<[f64; 2]>,
+    bookmarks: Vec<Bookmark>,
+    /// Name typed into the "add bookmark" field.
+    new_bookmark_name: String,
+    /// Borderless fullscreen with no chrome.
+    kiosk: bool,
+    kiosk_settings: KioskSettings,
+    /// Connected monitors, for picking the kiosk monitor.
+    monitors: Monitors,
+    /// Text typed into the sidebar search field.
+    search_query: String,
+    /// Raid type for this session, selected in the title bar.
+    raid_mode: RaidMode,
+    map_detection: MapDetection,
+    /// Map offered by the last "switch map" toast.
+    map_switch_offer: Option<usize>,
+    map_switch_rx: mpsc::Receiver<()>,
+    session_recorder: SessionRecorder,
+    raid_history: RaidHistory,
+    raid_history_open: bool,
+    /// Open "Add Custom Map" window, if any.
+    custom_map_wizard: Option<CustomMapWizard>,
+    usage: UsageTracker,
+    usage_stats_open: bool,
+    data_packs: DataPackSettings,
+    data_packs_open: bool,
+    /// Data replaced by later data packs in the last load.
+    data_pack_conflicts: Vec<PackConflict>,
+    /// Data packs that failed to load in the last load.
+    data_pack_errors: Vec<String>,
+    /// Open Sessions window, if any.
+    session_browser: Option<SessionBrowser>,
+    session_prune: SessionPrune,
+    /// Session whose path is drawn on the map.
+    replay: Option<RaidSession>,
+    /// Print export rendering in the background, if any.
+    print_export_rx: Option<mpsc::Receiver<Result<PathBuf, PrintExportError>>>,
+    health_check: HealthCheck,
+    health_check_on_startup: bool,
+    squad: Option<SquadSession>,
+    squad_open: bool,
+    /// Leader address typed into the Squad View window.
+    squad_address: String,
+    /// Address to lead from; empty for this computer's LAN address.
+    squad_bind_address: String,
+    quest_log_watcher: Option<QuestLogWatcher>,
+    /// IDs of quests completed according to the game's logs.
+    completed_quests: BTreeSet<String>,
+    raid_plans: RaidPlans,
+    marker_picking: MarkerPicking,
+    /// When and where the built-in map data was fetched.
+    data_provenance: DataProvenance,
+    data_stale_days: u32,
+    /// Language code names are shown in, or `None` for English.
+    language: Option<String>,
+    scav_timer: ScavTimer,
+    raid_end_watcher: Option<RaidEndWatcher>,
+    carried_items: CarriedItems,
+    follow_player_height: bool,
+    personal_heatmap: Option<PersonalHeatmap>,
+    /// Set when sessions changed since the heatmap was built.
+    personal_heatmap_stale: bool,
+    safe_mode: SafeMode,
 
     /// Flag to clear settings on app close (triggered by File -> Clear Settings).
     pub clear_settings_on_close: bool,
 }
 
+/// Starts decoding a map image in a background thread.
+fn spawn_image_load(ctx: &egui::Context, path: &str) -> AssetLoadState {
+    let (tx, rx) = mpsc::channel();
+    let ctx = ctx.clone();
+    let asset_path = path.to_owned();
+
+    thread::spawn(move || {
+        let result = load_and_decode_image(&asset_path);
+        let _ = tx.send(result);
+        ctx.request_repaint();
+    });
+
+    AssetLoadState::Loading(rx)
+}
+
 impl TarkovMapApp {
-    fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        let settings: AppSettings = cc
+    fn new(cc: &eframe::CreationContext<'_>, safe_mode: bool, monitors: Monitors) -> Self {
+        let crashed = mark_running();
+        let stored_settings = cc
             .storage
-            .and_then(|storage| eframe::get_value(storage, SETTINGS_STORAGE_KEY))
-            .unwrap_or_default();
+            .and_then(|storage| storage.get_string(SETTINGS_STORAGE_KEY));
+        let stored: Option<AppSettings> = stored_settings
+            .as_deref()
+            .and_then(|text| ron::from_str(text).ok());
+        let settings = if safe_mode {
+            log::info!("Starting in safe mode");
+            AppSettings {
+                selected_map_normalized_name: stored
+                    .and_then(|settings| settings.selected_map_normalized_name),
+                ..Default::default()
+            }
+        } else {
+            stored.unwrap_or_default()
+        };
+
+        egui_extras::install_image_loaders(&cc.egui_ctx);
 
         let updater = updater::Updater::new(cc.egui_ctx.clone());
 
-        let mut toasts = updater.configure_toasts(
-            Toasts::new()
-                .anchor(egui::Align2::RIGHT_TOP, (-10.0, 10.0))
-                .direction(egui::Direction::TopDown),
+        let (clipboard_place_tx, clipboard_place_rx) = mpsc::channel();
+        let (map_switch_tx, map_switch_rx) = mpsc::channel();
+        let mut toasts = map_detect::configure_toasts(
+            clipboard::configure_toasts(
+                updater.configure_toasts(
+                    Toasts::new()
+                        .anchor(egui::Align2::RIGHT_TOP, (-10.0, 10.0))
+                        .direction(egui::Direction::TopDown),
+                ),
+                clipboard_place_tx,
+            ),
+            map_switch_tx,
         );
 
-        let maps = match load_maps() {
-            Ok(maps) => maps,
-            Err(err) => {
-                toasts.add(Toast {
-                    kind: ToastKind::Error,
-                    text: err.to_string().into(),
-                    options: ToastOptions::default()
-                        .duration_in_seconds(10.0)
-                        .show_icon(true),
-                    ..Default::default()
-                });
-                Vec::new()
+        let map_data = load_map_data(&settings.data_packs);
+        for err in &map_data.errors {
+            log::warn!("{err}");
+            toasts.add(Toast {
+                kind: ToastKind::Error,
+                text: err.clone().into(),
+                options: ToastOptions::default()
+                    .duration_in_seconds(10.0)
+                    .show_icon(true),
+                ..Default::default()
+            });
+        }
+        let mut maps = map_data.maps;
+        if safe_mode {
+            let keep = settings
+                .selected_map_normalized_name
+                .as_deref()
+                .and_then(|name| maps.iter().position(|map| map.normalized_name == name))
+                .unwrap_or(0);
+            if keep < maps.len() {
+                maps = vec![maps.swap_remove(keep)];
             }
-        };
+        }
 
         let selected_map = settings
             .selected_map_normalized_name
@@ -101,47 +404,128 @@ impl TarkovMapApp {
             })
             .unwrap_or(0);
 
-        let mut asset_cache = HashMap::new();
-
-        // Preload all map images in background threads
-        for map in &maps {
-            let (tx, rx) = mpsc::channel();
-            let ctx = cc.egui_ctx.clone();
-            let asset_path = map.image_path.clone();
-
-            thread::spawn(move || {
-                let result = load_and_decode_image(&asset_path);
-                let _ = tx.send(result);
-                ctx.request_repaint();
-            });
-
-            asset_cache.insert(map.image_path.clone(), AssetLoadState::Loading(rx));
-        }
+        let pending_view = maps.get(selected_map).and_then(|map| map.default_view);
 
         // Initialize screenshot watcher for player position tracking
-        let mut screenshot_watcher = ScreenshotWatcher::new(cc.egui_ctx.clone());
+        let screenshot_watcher = if safe_mode {
+            None
+        } else {
+            ScreenshotWatcher::new(cc.egui_ctx.clone(), settings.screenshot_polling)
+        };
         // Get initial position from the newest screenshot
-        let player_position = screenshot_watcher.as_mut().and_then(|w| w.poll());
+        let player_position = screenshot_watcher
+            .as_ref()
+            .and_then(ScreenshotWatcher::current);
 
         if screenshot_watcher.is_none() {
             log::info!("Screenshot watcher not available - player position tracking disabled");
         }
 
-        Self {
+        let mut app = Self {
             maps,
             selected_map,
             zoom: 1.0,
             prev_zoom: 1.0,
             pan_offset: egui::Vec2::ZERO,
+            pan_clamp: settings.pan_clamp,
+            zoom_anchor: settings.zoom_anchor,
+            raid_checklist: settings.raid_checklist,
+            coordinate_convention: settings.coordinate_convention,
             overlays: settings.overlays,
-            asset_cache,
+            label_style: settings.label_style,
+            registered_overlays: builtin_overlays(),
+            maps_watcher: MapsWatcher::new(cc.egui_ctx.clone()),
+            asset_cache: HashMap::new(),
             texture_cache: HashMap::new(),
+            image_lru: ImageLru::default(),
+            map_hover: None,
             toasts,
             updater,
             screenshot_watcher,
+            screenshot_polling: settings.screenshot_polling,
             player_position,
+            pending_view,
+            last_display_size: egui::Vec2::ZERO,
+            focus_position: None,
+            extract_sort: ExtractSort::default(),
+            measurement: Measurement::default(),
+            icon_pack: IconPack::default(),
+            icon_pack_name: None,
+            notes: settings.notes,
+            photos: settings.photos,
+            selected_extract: None,
+            selected_marker: None,
+            selected_layer: None,
+            comparison: None,
+            screenshot_cleanup: settings.screenshot_cleanup,
+            last_screenshot_cleanup: None,
+            clipboard_watcher: None,
+            clipboard_offer: None,
+            clipboard_place_rx,
+            temp_marker: None,
+            bookmarks: settings.bookmarks,
+            new_bookmark_name: String::new(),
+            kiosk: false,
+            kiosk_settings: settings.kiosk,
+            monitors,
+            search_query: String::new(),
+            raid_mode: RaidMode::default(),
+            map_detection: settings.map_detection,
+            map_switch_offer: None,
+            map_switch_rx,
+            session_recorder: SessionRecorder::default(),
+            raid_history: RaidHistory::load(),
+            raid_history_open: false,
+            custom_map_wizard: None,
+            usage: UsageTracker::new(settings.usage_stats),
+            usage_stats_open: false,
+            data_packs: settings.data_packs,
+            data_packs_open: false,
+            data_pack_conflicts: map_data.conflicts,
+            data_pack_errors: map_data.errors,
+            session_browser: None,
+            session_prune: settings.session_prune,
+            replay: None,
+            print_export_rx: None,
+            health_check: HealthCheck::new(settings.health_check_on_startup && !safe_mode),
+            health_check_on_startup: settings.health_check_on_startup,
+            squad: None,
+            squad_open: false,
+            squad_address: settings.squad_address,
+            squad_bind_address: settings.squad_bind_address,
+            quest_log_watcher: if safe_mode {
+                None
+            } else {
+                QuestLogWatcher::new(cc.egui_ctx.clone())
+            },
+            completed_quests: settings.completed_quests,
+            raid_plans: settings.raid_plans,
+            marker_picking: settings.marker_picking,
+            data_provenance: load_data_provenance(),
+            data_stale_days: settings.data_stale_days,
+            language: settings.language,
+            scav_timer: settings.scav_timer,
+            raid_end_watcher: if safe_mode {
+                None
+            } else {
+                RaidEndWatcher::new(cc.egui_ctx.clone())
+            },
+            carried_items: settings.carried_items,
+            follow_player_height: settings.follow_player_height,
+            personal_heatmap: None,
+            personal_heatmap_stale: false,
+            safe_mode: SafeMode::new(safe_mode, crashed, stored_settings),
             clear_settings_on_close: false,
+        };
+
+        app.set_icon_pack(&cc.egui_ctx, settings.icon_pack);
+        app.set_watch_clipboard(&cc.egui_ctx, settings.watch_clipboard);
+        app.apply_kiosk_on_start(&cc.egui_ctx);
+        app.session_prune.run();
+        if !safe_mode {
+            app.compare_after_data_update();
         }
+        app
     }
 
     fn selected_map(&self) -> Option<&Map> {
@@ -219,15 +603,140 @@ impl TarkovMapApp {
 
     fn reset_view(&mut self) {
         self.zoom = 1.0;
+        self.prev_zoom = 1.0;
         self.pan_offset = egui::Vec2::ZERO;
     }
 
+    /// Selects the map at `idx`, showing its default view and clearing per-map state.
+    fn select_map(&mut self, idx: usize) {
+        if self.selected_map == idx {
+            return;
+        }
+        self.selected_map = idx;
+        if let Some(map) = self.maps.get(idx) {
+            self.usage.map_opened(&map.normalized_name);
+        }
+        self.show_default_view();
+        self.measurement.clear();
+        self.selected_extract = None;
+        self.selected_marker = None;
+        self.selected_layer = None;
+    }
+
+    /// Replaces the loaded maps, keeping the selected map.
+    fn replace_maps(&mut self, maps: TarkovMaps) {
+        let selected_name = self.selected_map().map(|map| map.normalized_name.clone());
+        self.selected_map = selected_name
+            .and_then(|name| maps.iter().position(|map| map.normalized_name == name))
+            .unwrap_or(0);
+        self.maps = maps;
+    }
+
+    /// Resets the view and requests the selected map's default view on the next frame.
+    fn show_default_view(&mut self) {
+        self.reset_view();
+        self.pending_view = self.selected_map().and_then(|map| map.default_view);
+    }
+
+    /// Returns the current view, as last displayed.
+    fn current_view(&self) -> MapView {
+        let size = self.last_display_size;
+        let center = if size.x > 0.0 && size.y > 0.0 {
+            [
+                0.5 - self.pan_offset.x / size.x,
+                0.5 - self.pan_offset.y / size.y,
+            ]
+        } else {
+            [0.5, 0.5]
+        };
+        MapView {
+            center,
+            zoom: self.zoom,
+        }
+    }
+
+    /// Switches to the named icon pack, or the built-in markers for `None`.
+    fn set_icon_pack(&mut self, ctx: &egui::Context, name: Option<String>) {
+        let pack = match name.as_deref().map(|name| IconPack::load(ctx, name)) {
+            None => Ok(IconPack::default()),
+            Some(result) => result,
+        };
+
+        match pack {
+            Ok(pack) => {
+                self.icon_pack = pack;
+                self.icon_pack_name = name;
+            }
+            Err(err) => {
+                self.toasts.add(Toast {
+                    kind: ToastKind::Error,
+                    text: err.to_string().into(),
+                    options: ToastOptions::default()
+                        .duration_in_seconds(8.0)
+                        .show_icon(true),
+                    ..Default::default()
+                });
+                self.icon_pack = IconPack::default();
+                self.icon_pack_name = None;
+            }
+        }
+    }
+
+    /// Starts or stops watching the clipboard for coordinates.
+    fn set_watch_clipboard(&mut self, ctx: &egui::Context, enabled: bool) {
+        self.clipboard_watcher = if enabled {
+            ClipboardWatcher::new(ctx.clone(), self.coordinate_convention)
+        } else {
+            None
+        };
+    }
+
+    /// Offers copied coordinates and places the temporary marker when accepted.
+    fn poll_clipboard(&mut self) {
+        if let Some(position) = self.clipboard_watcher.as_ref().and_then(|w| w.poll()) {
+            self.clipboard_offer = Some(position);
+            clipboard::offer_marker_toast(&mut self.toasts, position, self.coordinate_convention);
+        }
+
+        if self.clipboard_place_rx.try_iter().count() > 0
+            && let Some(position) = self.clipboard_offer.take()
+        {
+            self.temp_marker = Some(position);
+            self.focus_position = Some(position);
+        }
+    }
+
+    /// Runs screenshot cleanup in the background when it is due.
+    fn poll_screenshot_cleanup(&mut self) {
+        let due = self
+            .last_screenshot_cleanup
+            .is_none_or(|last| last.elapsed() >= SCREENSHOT_CLEANUP_INTERVAL);
+        if !due {
+            return;
+        }
+
+        self.last_screenshot_cleanup = Some(Instant::now());
+        let cleanup = self.screenshot_cleanup;
+        thread::spawn(move || cleanup.run());
+    }
+
+    /// Recreates the screenshot watcher after the polling settings changed.
+    fn restart_screenshot_watcher(&mut self, ctx: &egui::Context) {
+        // Drop the old watcher first so a polling thread stops before the next starts
+        self.screenshot_watcher = None;
+        self.screenshot_watcher = ScreenshotWatcher::new(ctx.clone(), self.screenshot_polling);
+    }
+
     /// Polls the screenshot watcher for player position updates.
     fn poll_player_position(&mut self) {
-        if let Some(watcher) = &mut self.screenshot_watcher
-            && let Some(position) = watcher.poll()
-        {
+        let Some(watcher) = &mut self.screenshot_watcher else {
+            return;
+        };
+        for position in watcher.poll() {
             self.player_position = Some(position);
+            self.detect_map_from_position([position.position[0], position.position[2]]);
+            self.select_layer_for_player();
+            self.record_session_position(position.position);
         }
     }
 }
@@ -238,13 +747,42 @@ impl eframe::App for TarkovMapApp {
     }
 
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_maps_reload();
+        self.load_selected_map_image(ctx);
         self.poll_all_assets(ctx);
         self.poll_player_position();
+        self.poll_screenshot_cleanup();
+        self.poll_clipboard();
+        self.poll_map_switch();
+        self.poll_print_export();
+        self.poll_health_check(ctx);
+        self.poll_squad(ctx);
+        self.poll_quest_log();
+        self.poll_scav_timer(ctx);
+        self.update_personal_heatmap(ctx);
         self.handle_keyboard_input(ctx);
+        self.handle_dropped_files(ctx);
+        self.handle_dropped_geojson(ctx);
+        self.handle_dropped_photos(ctx);
         self.updater.poll(ctx, &mut self.toasts);
+        self.track_usage();
 
-        // Render custom window frame with title bar
-        self.show_custom_frame(ctx);
+        // Render custom window frame with title bar (or just the map in kiosk mode)
+        if self.kiosk {
+            self.show_kiosk_frame(ctx);
+        } else {
+            self.show_custom_frame(ctx);
+        }
+        self.show_raid_history_window(ctx);
+        self.show_raid_checklist_window(ctx);
+        self.show_session_browser(ctx);
+        self.show_custom_map_wizard(ctx);
+        self.show_usage_stats_window(ctx);
+        self.show_data_packs_window(ctx);
+        self.show_health_check_window(ctx);
+        self.show_squad_window(ctx);
+        self.show_safe_mode_window(ctx);
+        self.show_marker_note_window(ctx);
 
         self.prev_zoom = self.zoom;
 
@@ -253,6 +791,14 @@ impl eframe::App for TarkovMapApp {
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // Safe mode runs on defaults, so only store settings repaired in the Safe Mode window
+        if self.safe_mode.active {
+            if let Some(text) = &self.safe_mode.repaired {
+                storage.set_string(SETTINGS_STORAGE_KEY, text.clone());
+            }
+            return;
+        }
+
         // If clear settings was requested, save default settings
         if self.clear_settings_on_close {
             eframe::set_value(storage, SETTINGS_STORAGE_KEY, &AppSettings::default());
@@ -267,11 +813,47 @@ impl eframe::App for TarkovMapApp {
         let settings = AppSettings {
             selected_map_normalized_name,
             overlays: self.overlays,
+            icon_pack: self.icon_pack_name.clone(),
+            notes: self.notes.clone(),
+            photos: self.photos.clone(),
+            screenshot_cleanup: self.screenshot_cleanup,
+            screenshot_polling: self.screenshot_polling,
+            watch_clipboard: self.clipboard_watcher.is_some(),
+            bookmarks: self.bookmarks.clone(),
+            kiosk: self.kiosk_settings,
+            map_detection: self.map_detection,
+            session_prune: self.session_prune,
+            label_style: self.label_style,
+            usage_stats: self.usage.enabled,
+            data_packs: self.data_packs.clone(),
+            pan_clamp: self.pan_clamp,
+            zoom_anchor: self.zoom_anchor,
+            raid_checklist: self.raid_checklist.clone(),
+            coordinate_convention: self.coordinate_convention,
+            health_check_on_startup: self.health_check_on_startup,
+            squad_address: self.squad_address.clone(),
+            squad_bind_address: self.squad_bind_address.clone(),
+            completed_quests: self.completed_quests.clone(),
+            raid_plans: self.raid_plans.clone(),
+            marker_picking: self.marker_picking.clone(),
+            data_stale_days: self.data_stale_days,
+            language: self.language.clone(),
+            scav_timer: self.scav_timer.clone(),
+            carried_items: self.carried_items.clone(),
+            follow_player_height: self.follow_player_height,
             ..Default::default()
         };
 
+        if self.usage.enabled {
+            self.usage.stats.save();
+        }
+
         eframe::set_value(storage, SETTINGS_STORAGE_KEY, &settings);
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        clear_running_marker();
+    }
 }
 
 fn load_icon() -> egui::IconData {
@@ -289,21 +871,32 @@ fn load_icon() -> egui::IconData {
 
 fn main() -> eframe::Result {
     env_logger::init();
+    let cli = Cli::parse();
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_title(APP_TITLE)
             .with_decorations(false) // Hide OS window decorations for custom title bar
-            .with_transparent(true) // Enable transparency for rounded corners
+            // Enable transparency for rounded corners, except where it may be the problem
+            .with_transparent(!cli.safe_mode)
             .with_inner_size([1280.0, 720.0])
             .with_min_inner_size([800.0, 600.0])
             .with_icon(Arc::new(load_icon())),
+        // A broken window position or size shouldn't survive into safe mode
+        persist_window: !cli.safe_mode,
         ..Default::default()
     };
 
-    eframe::run_native(
+    // Our own event loop, so that MonitorProbe can list the monitors
+    let event_loop = EventLoop::<UserEvent>::with_user_event().build()?;
+    let monitors = Monitors::default();
+    let app_monitors = monitors.clone();
+    let app = eframe::create_native(
         APP_ID,
         options,
-        Box::new(|cc| Ok(Box::new(TarkovMapApp::new(cc)))),
-    )
+        Box::new(move |cc| Ok(Box::new(TarkovMapApp::new(cc, cli.safe_mode, app_monitors)))),
+        &event_loop,
+    );
+    event_loop.run_app(&mut MonitorProbe { app, monitors })?;
+    Ok(())
 }
@@ -0,0 +1,107 @@
+//! Detects which map a screenshot position belongs to by comparing map bounds.
+//!
+//! Used as a fallback when the current map can't be detected from game logs:
+//! a position far outside the selected map but inside another map's bounds
+//! suggests the player is in a raid on that other map.
+
+use crate::TarkovMapApp;
+use crate::updater::render_action_toast;
+use egui_toast::{Toast, ToastKind, ToastOptions, Toasts};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::Sender;
+use tarkov_map::Map;
+
+const MAP_SWITCH_TOAST_KIND: u32 = 4;
+
+/// Positions within this many meters outside the selected map's bounds still count as on it.
+const BOUNDS_MARGIN: f64 = 50.0;
+
+/// What to do when a position belongs to a different map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MapDetection {
+    Off,
+    /// Show a toast offering to switch.
+    #[default]
+    Ask,
+    /// Switch right away.
+    Auto,
+}
+
+/// Returns `true` if the game position `[x, z]` lies within `margin` meters of the map bounds.
+fn within_bounds(map: &Map, position: [f64; 2], margin: f64) -> bool {
+    map.game_bounds()
+        .is_some_and(|bounds| bounds.expand(margin).contains(position))
+}
+
+fn bounds_area(map: &Map) -> f64 {
+    map.game_bounds()
+        .map_or(f64::INFINITY, |bounds| bounds.area())
+}
+
+/// Finds the map a position most likely belongs to, if it is not the selected one.
+///
+/// When several maps contain the position, the one with the smallest bounds wins.
+pub fn detect_map(maps: &[Map], selected: usize, position: [f64; 2]) -> Option<usize> {
+    let current = maps.get(selected)?;
+    if current.bounds.is_none() || within_bounds(current, position, BOUNDS_MARGIN) {
+        return None;
+    }
+
+    maps.iter()
+        .enumerate()
+        .filter(|(idx, map)| *idx != selected && map.contains(position))
+        .min_by(|(_, a), (_, b)| bounds_area(a).total_cmp(&bounds_area(b)))
+        .map(|(idx, _)| idx)
+}
+
+/// Registers the "switch map" toast renderer. Clicking the action sends on `switch_tx`.
+pub fn configure_toasts(toasts: Toasts, switch_tx: Sender<()>) -> Toasts {
+    toasts.custom_contents(MAP_SWITCH_TOAST_KIND, move |ui, toast| {
+        render_action_toast(
+            ui,
+            toast,
+            toast.style.info_icon.clone(),
+            "Switch map",
+            (),
+            &switch_tx,
+        )
+    })
+}
+
+impl TarkovMapApp {
+    /// Offers or performs a map switch if the player position belongs to another map.
+    pub fn detect_map_from_position(&mut self, position: [f64; 2]) {
+        if self.map_detection == MapDetection::Off {
+            return;
+        }
+        let Some(idx) = detect_map(&self.maps, self.selected_map, position) else {
+            return;
+        };
+
+        if self.map_detection == MapDetection::Auto {
+            log::info!("Switching to {} from player position", self.maps[idx].name);
+            self.select_map(idx);
+            return;
+        }
+
+        if self.map_switch_offer == Some(idx) {
+            return;
+        }
+        self.map_switch_offer = Some(idx);
+        self.toasts.add(Toast {
+            kind: ToastKind::Custom(MAP_SWITCH_TOAST_KIND),
+            text: format!("Position looks like {}", self.maps[idx].name).into(),
+            options: ToastOptions::default().duration_in_seconds(15.0),
+            ..Default::default()
+        });
+    }
+
+    /// Switches to the offered map when the toast action was clicked.
+    pub fn poll_map_switch(&mut self) {
+        if self.map_switch_rx.try_iter().count() > 0
+            && let Some(idx) = self.map_switch_offer.take()
+        {
+            self.select_map(idx);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Loading of map images on demand, within a budget of images kept in memory.
+//!
+//! The selected map's image is loaded when needed, and hovering a map in the
+//! sidebar starts decoding its image early so selecting it is instant. Once
+//! more than [`MAX_LOADED_MAP_IMAGES`] images are loaded, the least recently
+//! used ones are dropped, but never the selected map's or its selected layer's.
+
+use crate::constants::{MAX_LOADED_MAP_IMAGES, PREWARM_HOVER_DELAY};
+use crate::{TarkovMapApp, spawn_image_load};
+use eframe::egui;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Loaded map image paths, least recently used first.
+#[derive(Default)]
+pub struct ImageLru(VecDeque<String>);
+
+impl ImageLru {
+    /// Marks `path` as the most recently used image.
+    fn touch(&mut self, path: &str) {
+        if self.0.back().is_some_and(|last| last == path) {
+            return;
+        }
+        self.remove(path);
+        self.0.push_back(path.to_owned());
+    }
+
+    fn remove(&mut self, path: &str) {
+        self.0.retain(|p| p != path);
+    }
+
+    /// Returns the least recently used image not in `keep`, if over budget.
+    fn eviction_candidate(&self, keep: &[String]) -> Option<String> {
+        if self.0.len() <= MAX_LOADED_MAP_IMAGES {
+            return None;
+        }
+        self.0.iter().find(|p| !keep.contains(p)).cloned()
+    }
+}
+
+/// Sidebar map hovered since the given time, for pre-warming.
+pub struct MapHover {
+    idx: usize,
+    since: Instant,
+}
+
+impl TarkovMapApp {
+    /// Starts loading the image at `path` if it isn't loaded, and marks it as recently used.
+    fn request_map_image(&mut self, ctx: &egui::Context, path: &str) {
+        if !self.asset_cache.contains_key(path) {
+            self.asset_cache
+                .insert(path.to_owned(), spawn_image_load(ctx, path));
+        }
+        self.image_lru.touch(path);
+        self.enforce_image_budget();
+    }
+
+    /// Drops the least recently used images until within budget, keeping the selected map's
+    /// and its selected layer's.
+    fn enforce_image_budget(&mut self) {
+        let active: Vec<String> = self
+            .selected_map()
+            .map(|map| map.image_path.clone())
+            .into_iter()
+            .chain(self.selected_layer_image().map(str::to_owned))
+            .collect();
+        while let Some(path) = self.image_lru.eviction_candidate(&active) {
+            log::debug!("Unloading map image {path}");
+            self.forget_map_image(&path);
+        }
+    }
+
+    /// Drops a loaded image, so it is decoded again the next time it is needed.
+    pub fn forget_map_image(&mut self, path: &str) {
+        self.asset_cache.remove(path);
+        self.texture_cache.remove(path);
+        self.image_lru.remove(path);
+    }
+
+    /// Loads the selected map's image and that of its selected layer. Called once per frame.
+    pub fn load_selected_map_image(&mut self, ctx: &egui::Context) {
+        if let Some(path) = self.selected_map().map(|map| map.image_path.clone()) {
+            self.request_map_image(ctx, &path);
+        }
+        if let Some(path) = self.selected_layer_image().map(str::to_owned) {
+            self.request_map_image(ctx, &path);
+        }
+    }
+
+    /// Pre-warms the image of the map hovered in the sidebar once hovered long enough.
+    pub fn prewarm_hovered_map(&mut self, ctx: &egui::Context, hovered: Option<usize>) {
+        let Some(idx) = hovered.filter(|idx| *idx != self.selected_map) else {
+            self.map_hover = None;
+            return;
+        };
+
+        match &self.map_hover {
+            Some(hover) if hover.idx == idx => {
+                if hover.since.elapsed() >= PREWARM_HOVER_DELAY
+                    && let Some(path) = self.maps.get(idx).map(|map| map.image_path.clone())
+                {
+                    self.request_map_image(ctx, &path);
+                }
+            }
+            _ => {
+                self.map_hover = Some(MapHover {
+                    idx,
+                    since: Instant::now(),
+                });
+                // Fire the pre-warm even if the pointer doesn't move again
+                ctx.request_repaint_after(PREWARM_HOVER_DELAY);
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Floors and underground areas drawn over the map image.
+//!
+//! `fetch_maps` renders each tarkov-dev layer to its own image, the size of the
+//! map image and transparent outside the layer. Selecting a layer from the
+//! floating picker dims the base map and draws the layer on top.
+//!
+//! While following the player height, each new player position selects the
+//! layer whose extents contain it, or the base map when none do.
+
+use crate::TarkovMapApp;
+use eframe::egui;
+use tarkov_map::Map;
+
+impl TarkovMapApp {
+    /// Image path of the selected layer of the selected map, if it has one.
+    pub fn selected_layer_image(&self) -> Option<&str> {
+        let layer = self
+            .selected_map()?
+            .layers
+            .as_ref()?
+            .get(self.selected_layer?)?;
+        layer.image_path.as_deref()
+    }
+
+    /// Name of the selected layer of the selected map, or `None` for the base map.
+    pub fn selected_layer_name(&self) -> Option<String> {
+        let layer = self
+            .selected_map()?
+            .layers
+            .as_ref()?
+            .get(self.selected_layer?)?;
+        Some(layer.name.clone())
+    }
+
+    /// Selects the rendered layer of the selected map called `name`, or the
+    /// base map for `None` or a layer the map doesn't have.
+    pub fn select_layer_by_name(&mut self, name: Option<&str>) {
+        self.selected_layer = name.and_then(|name| {
+            self.selected_map()?
+                .layers
+                .iter()
+                .flatten()
+                .position(|layer| layer.name == name && layer.image_path.is_some())
+        });
+    }
+
+    /// Selects the layer the player is in, when following the player height.
+    pub fn select_layer_for_player(&mut self) {
+        if !self.follow_player_height {
+            return;
+        }
+        let (Some(map), Some(player)) = (self.selected_map(), self.player_position) else {
+            return;
+        };
+        let [x, height, z] = player.position;
+        let layer = map.active_layer(height, [x, z]).filter(|idx| {
+            map.layers
+                .iter()
+                .flatten()
+                .nth(*idx)
+                .is_some_and(|layer| layer.image_path.is_some())
+        });
+        self.selected_layer = layer;
+    }
+
+    /// Renders the floating layer picker for maps with rendered layers.
+    pub fn show_layer_controls(&mut self, ctx: &egui::Context, panel_rect: egui::Rect, map: &Map) {
+        let layers: Vec<(usize, &str)> = map
+            .layers
+            .iter()
+            .flatten()
+            .enumerate()
+            .filter(|(_, layer)| layer.image_path.is_some())
+            .map(|(idx, layer)| (idx, layer.name.as_str()))
+            .collect();
+        if layers.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("layer_controls"))
+            .pivot(egui::Align2::RIGHT_TOP)
+            .fixed_pos(panel_rect.right_top() + egui::vec2(-12.0, 12.0))
+            .interactable(true)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style())
+                    .fill(ui.style().visuals.window_fill.gamma_multiply(0.95))
+                    .show(ui, |ui| {
+                        let selected = layers
+                            .iter()
+                            .find(|(idx, _)| Some(*idx) == self.selected_layer)
+                            .map_or("Base map", |(_, name)| name);
+                        egui::ComboBox::from_id_salt("map_layer")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.selected_layer, None, "Base map");
+                                for (idx, name) in &layers {
+                                    ui.selectable_value(
+                                        &mut self.selected_layer,
+                                        Some(*idx),
+                                        *name,
+                                    );
+                                }
+                            });
+                        if ui
+                            .checkbox(&mut self.follow_player_height, "Follow player height")
+                            .on_hover_text(
+                                "Pick the layer and hide labels of other floors by the player position",
+                            )
+                            .changed()
+                        {
+                            self.select_layer_for_player();
+                        }
+                    });
+            });
+    }
+}
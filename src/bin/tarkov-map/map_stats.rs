@@ -0,0 +1,116 @@
+//! Summary of the marker data available for a map, copyable for bug reports.
+
+use crate::TarkovMapApp;
+use eframe::egui;
+use std::fmt;
+use tarkov_map::{Faction, Map, SpawnCategory, SpawnSide, ValidationWarning};
+
+/// Marker counts and dimensions of a single map.
+#[derive(Debug, Clone)]
+struct MapStats {
+    name: String,
+    pmc_spawns: usize,
+    scav_spawns: usize,
+    pmc_extracts: usize,
+    scav_extracts: usize,
+    shared_extracts: usize,
+    labels: usize,
+    /// Bounds size `[width, height]` in meters.
+    bounds_size: Option<[f64; 2]>,
+    warnings: Vec<ValidationWarning>,
+}
+
+impl MapStats {
+    fn new(map: &Map) -> Self {
+        let spawns_for = |side: SpawnSide| {
+            map.spawns_filtered(Some(&side), Some(&SpawnCategory::Player))
+                .count()
+        };
+        let extracts_for = |faction: Faction| map.extracts_for_faction(&faction).count();
+
+        Self {
+            name: map.name.clone(),
+            pmc_spawns: spawns_for(SpawnSide::Pmc),
+            scav_spawns: spawns_for(SpawnSide::Scav),
+            pmc_extracts: extracts_for(Faction::Pmc),
+            scav_extracts: extracts_for(Faction::Scav),
+            shared_extracts: extracts_for(Faction::Shared),
+            labels: map.labels.as_ref().map_or(0, Vec::len),
+            bounds_size: map
+                .bounds
+                .map(|[a, b]| [(a[0] - b[0]).abs(), (a[1] - b[1]).abs()]),
+            warnings: map.validate(),
+        }
+    }
+
+    /// Label/value rows shown in the panel and in the copied text.
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("PMC spawns", self.pmc_spawns.to_string()),
+            ("Scav spawns", self.scav_spawns.to_string()),
+            ("PMC extracts", self.pmc_extracts.to_string()),
+            ("Scav extracts", self.scav_extracts.to_string()),
+            ("Shared extracts", self.shared_extracts.to_string()),
+            ("Labels", self.labels.to_string()),
+            (
+                "Bounds",
+                match self.bounds_size {
+                    Some([w, h]) => format!("{w:.0} × {h:.0} m"),
+                    None => "unknown".to_owned(),
+                },
+            ),
+        ]
+    }
+}
+
+impl fmt::Display for MapStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.name)?;
+        for (label, value) in self.rows() {
+            writeln!(f, "{label}: {value}")?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "Warning: {warning}")?;
+        }
+        write!(f, "tarkov-map {}", env!("CARGO_PKG_VERSION"))
+    }
+}
+
+impl TarkovMapApp {
+    /// Renders marker statistics for the selected map.
+    pub fn show_map_stats(&self, ui: &mut egui::Ui) {
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let stats = MapStats::new(map);
+
+        egui::Grid::new("map_stats")
+            .num_columns(2)
+            .spacing([12.0, 2.0])
+            .show(ui, |ui| {
+                for (label, value) in stats.rows() {
+                    ui.label(label);
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+
+        if !stats.warnings.is_empty() {
+            egui::CollapsingHeader::new(format!("{} data warnings", stats.warnings.len()))
+                .id_salt("map_stats_warnings")
+                .show(ui, |ui| {
+                    for warning in &stats.warnings {
+                        ui.colored_label(ui.visuals().warn_fg_color, warning.to_string());
+                    }
+                });
+        }
+
+        if ui
+            .button("Copy")
+            .on_hover_text("Copy summary for bug reports")
+            .clicked()
+        {
+            ui.ctx().copy_text(stats.to_string());
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Variants of a map listed in its `alt_maps`, such as Ground Zero for level
+//! 21+ players or Night Factory.
+//!
+//! Variants share the base map's image, so switching between them keeps the
+//! current view. A variant only has its own entry when the map data contains
+//! a map with that name; otherwise it is listed but can't be selected.
+
+use crate::TarkovMapApp;
+use eframe::egui;
+
+impl TarkovMapApp {
+    /// Returns the index of the map that lists the map at `idx` as a variant.
+    fn variant_base(&self, idx: usize) -> Option<usize> {
+        let name = &self.maps.get(idx)?.normalized_name;
+        self.maps
+            .iter()
+            .position(|map| map.alt_maps.iter().flatten().any(|alt| alt == name))
+    }
+
+    /// Returns `true` if the map at `idx` is listed as a variant of another map.
+    pub fn is_map_variant(&self, idx: usize) -> bool {
+        self.variant_base(idx).is_some()
+    }
+
+    /// Renders the variants of the map at `idx` as indented sidebar entries.
+    ///
+    /// Returns the index of the variant that was clicked.
+    pub fn show_map_variants(&self, ui: &mut egui::Ui, idx: usize) -> Option<usize> {
+        let alt_maps = self.maps.get(idx)?.alt_maps.as_ref()?;
+        let mut clicked = None;
+        for alt in alt_maps {
+            let variant = self.maps.iter().position(|map| map.normalized_name == *alt);
+            let name = variant.map_or(alt.as_str(), |variant| {
+                self.maps[variant].localized_name(self.language.as_deref())
+            });
+            ui.horizontal(|ui| {
+                ui.add_space(12.0);
+                let response = ui
+                    .add_enabled(
+                        variant.is_some(),
+                        egui::Button::selectable(
+                            variant == Some(self.selected_map),
+                            format!("↳ {name}"),
+                        ),
+                    )
+                    .on_disabled_hover_text("This variant is not in the map data");
+                if response.clicked() {
+                    clicked = variant;
+                }
+            });
+        }
+        clicked
+    }
+
+    /// Selects the map at `idx`, keeping the current view if it is a variant of the selected map.
+    pub fn select_map_keeping_variant_view(&mut self, idx: usize) {
+        let base = |idx| self.variant_base(idx).unwrap_or(idx);
+        if idx == self.selected_map || base(idx) != base(self.selected_map) {
+            self.select_map(idx);
+            return;
+        }
+        let view = self.current_view();
+        self.select_map(idx);
+        self.pending_view = Some(view);
+    }
+}
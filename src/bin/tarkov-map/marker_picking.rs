@@ -0,0 +1,306 @@
+//! Picking the marker under the pointer when markers overlap.
+//!
+//! Every visible marker within its hit radius of the pointer is a candidate.
+//! Candidates are ordered by the user's marker priority, then by distance, and
+//! the first one gets the hover details. Alt+click steps through the others,
+//! and a plain click opens the picked marker's note.
+//! Locked marker kinds are still drawn but never picked.
+
+use crate::TarkovMapApp;
+use crate::constants::SPAWN_CLUSTER_MAX_ZOOM;
+use crate::coordinates::game_to_display;
+use crate::overlays::SpawnKind;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tarkov_map::{Map, Position2};
+
+/// A kind of marker with hover details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MarkerKind {
+    Extract,
+    Switch,
+    Boss,
+    Lock,
+    Label,
+    Spawn,
+}
+
+impl MarkerKind {
+    pub const ALL: [MarkerKind; 6] = [
+        MarkerKind::Extract,
+        MarkerKind::Switch,
+        MarkerKind::Boss,
+        MarkerKind::Lock,
+        MarkerKind::Label,
+        MarkerKind::Spawn,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MarkerKind::Extract => "Extracts",
+            MarkerKind::Switch => "Switches",
+            MarkerKind::Boss => "Bosses",
+            MarkerKind::Lock => "Locks",
+            MarkerKind::Label => "Labels",
+            MarkerKind::Spawn => "Spawns",
+        }
+    }
+}
+
+/// A picked marker: its kind and index in the map's list of that kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkerHit {
+    pub kind: MarkerKind,
+    pub index: usize,
+}
+
+/// Marker priority and lock settings, plus the candidates under the pointer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkerPicking {
+    /// Marker kinds from highest to lowest priority.
+    pub priority: Vec<MarkerKind>,
+    /// Marker kinds that are drawn but never picked.
+    pub locked: BTreeSet<MarkerKind>,
+    /// Candidates under the pointer, in pick order.
+    #[serde(skip)]
+    candidates: Vec<MarkerHit>,
+    /// Index into `candidates` of the picked marker.
+    #[serde(skip)]
+    cycle: usize,
+}
+
+impl Default for MarkerPicking {
+    fn default() -> Self {
+        Self {
+            priority: MarkerKind::ALL.to_vec(),
+            locked: BTreeSet::new(),
+            candidates: Vec::new(),
+            cycle: 0,
+        }
+    }
+}
+
+impl MarkerPicking {
+    /// Position of `kind` in the priority order. Kinds missing from older settings go last.
+    fn rank(&self, kind: MarkerKind) -> usize {
+        self.priority
+            .iter()
+            .position(|k| *k == kind)
+            .unwrap_or(self.priority.len())
+    }
+
+    /// Replaces the candidates under the pointer, keeping the picked one if the set is unchanged.
+    fn set_candidates(&mut self, candidates: Vec<MarkerHit>) {
+        if candidates != self.candidates {
+            self.candidates = candidates;
+            self.cycle = 0;
+        }
+    }
+
+    /// Picks the next candidate under the pointer.
+    fn cycle_next(&mut self) {
+        if !self.candidates.is_empty() {
+            self.cycle = (self.cycle + 1) % self.candidates.len();
+        }
+    }
+
+    fn picked(&self) -> Option<MarkerHit> {
+        self.candidates.get(self.cycle).copied()
+    }
+
+    /// Shows which of several overlapping markers is picked, for the end of a tooltip.
+    pub fn cycle_hint(&self, ui: &mut egui::Ui) {
+        if self.candidates.len() > 1 {
+            ui.weak(format!(
+                "{} of {} markers here (Alt+click for next)",
+                self.cycle + 1,
+                self.candidates.len()
+            ));
+        }
+    }
+
+    /// Renders the priority order and lock toggles.
+    pub fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        for kind in MarkerKind::ALL {
+            if !self.priority.contains(&kind) {
+                self.priority.push(kind);
+            }
+        }
+
+        ui.weak("Highest priority first");
+        let mut swap = None;
+        let count = self.priority.len();
+        for (idx, kind) in self.priority.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(idx > 0, egui::Button::new("⏶").small())
+                    .on_hover_text("Higher priority")
+                    .clicked()
+                {
+                    swap = Some((idx, idx - 1));
+                }
+                if ui
+                    .add_enabled(idx + 1 < count, egui::Button::new("⏷").small())
+                    .on_hover_text("Lower priority")
+                    .clicked()
+                {
+                    swap = Some((idx, idx + 1));
+                }
+                let mut locked = self.locked.contains(kind);
+                if ui
+                    .checkbox(&mut locked, kind.label())
+                    .on_hover_text("Locked markers are drawn but show no details")
+                    .changed()
+                {
+                    if locked {
+                        self.locked.insert(*kind);
+                    } else {
+                        self.locked.remove(kind);
+                    }
+                }
+            });
+        }
+        if let Some((a, b)) = swap {
+            self.priority.swap(a, b);
+        }
+        ui.weak("Checked kinds are locked");
+    }
+}
+
+impl TarkovMapApp {
+    /// Picks the marker under the pointer, stepping to the next overlapping one on Alt+click.
+    pub fn pick_marker(
+        &mut self,
+        response: &egui::Response,
+        map: &Map,
+        map_rect: egui::Rect,
+    ) -> Option<MarkerHit> {
+        let Some(pointer) = response.hover_pos() else {
+            self.marker_picking.set_candidates(Vec::new());
+            return None;
+        };
+        let distance = |position: Position2| {
+            game_to_display(map, map_rect, position).map(|pos| pos.distance(pointer))
+        };
+        let extract_radius = (8.0 * self.zoom).clamp(6.0, 16.0);
+        let marker_radius = (4.0 * self.zoom).clamp(6.0, 12.0);
+
+        let mut hits: Vec<(MarkerHit, f32)> = Vec::new();
+        let mut add = |kind: MarkerKind, index: usize, dist: Option<f32>, radius: f32| {
+            if let Some(dist) = dist.filter(|d| *d <= radius) {
+                hits.push((MarkerHit { kind, index }, dist));
+            }
+        };
+        let picking = &self.marker_picking;
+        let pickable = |kind: MarkerKind, visible: bool| visible && !picking.locked.contains(&kind);
+
+        if pickable(MarkerKind::Extract, true) {
+            for (index, extract) in map.extracts.iter().flatten().enumerate() {
+                if self.overlays.shows_extract(&extract.faction)
+                    && self.carried_items.extract_opacity(extract).is_some()
+                {
+                    let dist = extract.center().and_then(distance);
+                    add(MarkerKind::Extract, index, dist, extract_radius);
+                }
+            }
+        }
+        if pickable(MarkerKind::Switch, self.overlays.switches) {
+            for (index, switch) in map.switches.iter().flatten().enumerate() {
+                add(
+                    MarkerKind::Switch,
+                    index,
+                    distance(switch.position.xz()),
+                    marker_radius,
+                );
+            }
+        }
+        if pickable(MarkerKind::Boss, self.overlays.bosses) {
+            // Bosses sharing a spawn (e.g. the Goons) are one marker, so only add the nearest
+            let nearest = map
+                .bosses
+                .iter()
+                .flatten()
+                .enumerate()
+                .filter_map(|(index, boss)| {
+                    let dist = boss
+                        .positions
+                        .iter()
+                        .filter_map(|p| distance(p.xz()))
+                        .min_by(f32::total_cmp)?;
+                    Some((index, dist))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((index, dist)) = nearest {
+                add(MarkerKind::Boss, index, Some(dist), marker_radius);
+            }
+        }
+        if pickable(MarkerKind::Lock, self.overlays.locks) {
+            for (index, lock) in map.locks.iter().flatten().enumerate() {
+                add(
+                    MarkerKind::Lock,
+                    index,
+                    distance(lock.position.xz()),
+                    marker_radius,
+                );
+            }
+        }
+        if pickable(MarkerKind::Label, self.overlays.labels) {
+            let player_height = self
+                .player_position
+                .filter(|_| self.follow_player_height)
+                .map(|player| player.position[1]);
+            for (index, label) in map.labels.iter().flatten().enumerate() {
+                if player_height.is_none_or(|height| label.visible_at(height)) {
+                    add(
+                        MarkerKind::Label,
+                        index,
+                        distance(label.position),
+                        marker_radius,
+                    );
+                }
+            }
+        }
+        if pickable(MarkerKind::Spawn, true) {
+            // PMC spawns drawn as clusters have no individual markers to pick
+            let clustered = map.spawn_clusters.is_some() && self.zoom < SPAWN_CLUSTER_MAX_ZOOM;
+            let shown = |kind: SpawnKind| {
+                self.overlays.shows_spawns(kind) && !(kind == SpawnKind::Pmc && clustered)
+            };
+            for (index, spawn) in map.spawns.iter().flatten().enumerate() {
+                if SpawnKind::ALL
+                    .into_iter()
+                    .any(|kind| shown(kind) && kind.matches(spawn))
+                {
+                    add(
+                        MarkerKind::Spawn,
+                        index,
+                        distance(spawn.position.xz()),
+                        marker_radius,
+                    );
+                }
+            }
+        }
+
+        hits.sort_by(|(a, a_dist), (b, b_dist)| {
+            picking
+                .rank(a.kind)
+                .cmp(&picking.rank(b.kind))
+                .then(a_dist.total_cmp(b_dist))
+        });
+        self.marker_picking
+            .set_candidates(hits.into_iter().map(|(hit, _)| hit).collect());
+
+        let alt = response.ctx.input(|i| i.modifiers.alt);
+        if response.clicked() && alt {
+            self.marker_picking.cycle_next();
+        }
+        let picked = self.marker_picking.picked();
+        // A plain click opens the picked marker's note
+        if response.clicked() && !alt && picked.is_some() {
+            self.selected_marker = picked;
+        }
+        picked
+    }
+}
@@ -0,0 +1,141 @@
+//! Two-point bearing and distance tool with copyable callouts.
+
+use crate::TarkovMapApp;
+use crate::colors;
+use crate::coordinates::{display_to_game, game_to_display};
+use crate::usage_stats::Feature;
+use eframe::egui;
+use tarkov_map::{Map, rotate_point};
+
+/// Points picked with the measure tool, in game coordinates `[x, z]`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Measurement {
+    /// Explicit start point. Falls back to the player position when unset.
+    pub start: Option<[f64; 2]>,
+    pub end: Option<[f64; 2]>,
+}
+
+impl Measurement {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Compass bearing in degrees (0° = map north, clockwise) from `from` to `to`.
+pub fn bearing(map: &Map, from: [f64; 2], to: [f64; 2]) -> f64 {
+    let rotation = map.coordinate_rotation.unwrap_or(0.0);
+    let (east, north) = rotate_point(to[0] - from[0], to[1] - from[1], rotation);
+    east.atan2(north).to_degrees().rem_euclid(360.0)
+}
+
+/// Name of the label or extract closest to `position`, used as a callout reference.
+fn nearest_landmark(map: &Map, position: [f64; 2]) -> Option<String> {
+    let labels = map
+        .labels
+        .iter()
+        .flatten()
+        .map(|label| (label.text.replace('\n', " "), label.position));
+    let extracts = map
+        .extracts
+        .iter()
+        .flatten()
+        .filter_map(|extract| Some((extract.name.clone(), extract.center()?)));
+
+    labels
+        .chain(extracts)
+        .map(|(name, p)| (name, map.meters_between(p, position)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(name, _)| name)
+}
+
+/// Formats a callout such as `240°, 310 m from Crossroads`.
+fn callout(map: &Map, from: [f64; 2], to: [f64; 2]) -> String {
+    let bearing = bearing(map, from, to).round() as u32 % 360;
+    let distance = map.meters_between(from, to);
+    match nearest_landmark(map, from) {
+        Some(landmark) => format!("{bearing}°, {distance:.0} m from {landmark}"),
+        None => format!("{bearing}°, {distance:.0} m"),
+    }
+}
+
+impl TarkovMapApp {
+    /// Start point of the measurement: the picked point, or the player position.
+    fn measure_start(&self) -> Option<[f64; 2]> {
+        self.measurement
+            .start
+            .or_else(|| self.player_position.map(|p| [p.position[0], p.position[2]]))
+    }
+
+    /// Picks measure points: right-click sets the target, Shift+right-click the start.
+    pub fn handle_measure_input(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        map: &Map,
+        map_rect: egui::Rect,
+    ) {
+        if !response.secondary_clicked() {
+            return;
+        }
+        let Some(game_pos) = response
+            .interact_pointer_pos()
+            .and_then(|pos| display_to_game(map, map_rect, pos))
+        else {
+            return;
+        };
+
+        self.usage.feature_used(Feature::Measure);
+        if ui.input(|i| i.modifiers.shift) {
+            self.measurement.start = Some(game_pos);
+        } else {
+            self.measurement.end = Some(game_pos);
+        }
+    }
+
+    /// Draws the measurement line and end points.
+    pub fn draw_measurement(&self, ui: &egui::Ui, map: &Map, map_rect: egui::Rect) {
+        let painter = ui.painter();
+        let start = self
+            .measure_start()
+            .and_then(|p| game_to_display(map, map_rect, p));
+        let end = self
+            .measurement
+            .end
+            .and_then(|p| game_to_display(map, map_rect, p));
+
+        let stroke = egui::Stroke::new(2.0, colors::MEASURE_LINE);
+        if let (Some(start), Some(end)) = (start, end) {
+            painter.line_segment([start, end], stroke);
+        }
+        for point in [start, end].into_iter().flatten() {
+            painter.circle(point, 4.0, colors::MEASURE_LINE, stroke);
+        }
+    }
+
+    /// Renders the floating callout panel when a measurement is complete.
+    pub fn show_measure_panel(&mut self, ctx: &egui::Context, panel_rect: egui::Rect, map: &Map) {
+        let (Some(start), Some(end)) = (self.measure_start(), self.measurement.end) else {
+            return;
+        };
+        let text = callout(map, start, end);
+
+        egui::Area::new(egui::Id::new("measure_panel"))
+            .fixed_pos(panel_rect.left_top() + egui::vec2(12.0, 12.0))
+            .interactable(true)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style())
+                    .fill(ui.style().visuals.window_fill.gamma_multiply(0.95))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(&text);
+                            if ui.button("Copy").clicked() {
+                                ui.ctx().copy_text(text.clone());
+                            }
+                            if ui.button("Clear").on_hover_text("Clear (Esc)").clicked() {
+                                self.measurement.clear();
+                            }
+                        });
+                    });
+            });
+    }
+}
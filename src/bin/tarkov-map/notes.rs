@@ -0,0 +1,263 @@
+//! Free-text notes attached to map markers, persisted with the app settings.
+//!
+//! Clicking a picked marker on the map opens its note and photos in the Marker
+//! window.
+//! Notes are also written into GeoJSON exports, as each feature's `note`.
+
+use crate::TarkovMapApp;
+use crate::marker_picking::{MarkerHit, MarkerKind};
+use crate::overlays::SpawnKind;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tarkov_map::geojson::MarkerRef;
+use tarkov_map::{BossSpawn, Label, Lock, Map, Position3, Spawn, Switch};
+
+/// Notes keyed by map normalized name, then by marker key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MarkerNotes(HashMap<String, HashMap<String, String>>);
+
+/// Formats a position rounded to whole meters, for markers without a unique name.
+fn position_key(kind: &str, position: Position3) -> String {
+    format!(
+        "{kind}:{:.0},{:.0},{:.0}",
+        position.x, position.y, position.z
+    )
+}
+
+impl MarkerNotes {
+    /// Key identifying an extract within a map.
+    pub fn extract_key(name: &str) -> String {
+        format!("extract:{name}")
+    }
+
+    /// Key identifying a label within a map. Labels sharing a text are told apart by position.
+    pub fn label_key(label: &Label) -> String {
+        let position = label.position;
+        format!("label:{}@{:.0},{:.0}", label.text, position.x, position.z)
+    }
+
+    /// Key identifying a spawn point within a map.
+    pub fn spawn_key(spawn: &Spawn) -> String {
+        position_key("spawn", spawn.position)
+    }
+
+    /// Key identifying a switch within a map.
+    pub fn switch_key(switch: &Switch) -> String {
+        format!("switch:{}", switch.name)
+    }
+
+    /// Key identifying a boss within a map.
+    pub fn boss_key(boss: &BossSpawn) -> String {
+        format!("boss:{}", boss.name)
+    }
+
+    /// Key identifying a lock within a map.
+    pub fn lock_key(lock: &Lock) -> String {
+        position_key("lock", lock.position)
+    }
+
+    /// Key of an exported GeoJSON feature.
+    pub fn feature_key(marker: MarkerRef) -> String {
+        match marker {
+            MarkerRef::Label(label) => Self::label_key(label),
+            MarkerRef::Spawn(spawn) => Self::spawn_key(spawn),
+            MarkerRef::Extract(extract) => Self::extract_key(&extract.name),
+        }
+    }
+
+    /// Key of a picked marker, or `None` if `map` has no such marker.
+    pub fn hit_key(map: &Map, hit: MarkerHit) -> Option<String> {
+        let MarkerHit { kind, index } = hit;
+        match kind {
+            MarkerKind::Extract => map
+                .extracts
+                .iter()
+                .flatten()
+                .nth(index)
+                .map(|extract| Self::extract_key(&extract.name)),
+            MarkerKind::Switch => map
+                .switches
+                .iter()
+                .flatten()
+                .nth(index)
+                .map(Self::switch_key),
+            MarkerKind::Boss => map.bosses.iter().flatten().nth(index).map(Self::boss_key),
+            MarkerKind::Lock => map.locks.iter().flatten().nth(index).map(Self::lock_key),
+            MarkerKind::Label => map.labels.iter().flatten().nth(index).map(Self::label_key),
+            MarkerKind::Spawn => map.spawns.iter().flatten().nth(index).map(Self::spawn_key),
+        }
+    }
+
+    /// Returns the note for a marker, if any.
+    pub fn get(&self, map: &str, key: &str) -> Option<&str> {
+        self.0.get(map)?.get(key).map(String::as_str)
+    }
+
+    /// Sets the note for a marker. Blank notes are removed.
+    pub fn set(&mut self, map: &str, key: &str, note: String) {
+        if note.trim().is_empty() {
+            if let Some(notes) = self.0.get_mut(map) {
+                notes.remove(key);
+                if notes.is_empty() {
+                    self.0.remove(map);
+                }
+            }
+        } else {
+            self.0
+                .entry(map.to_owned())
+                .or_default()
+                .insert(key.to_owned(), note);
+        }
+    }
+}
+
+/// Name shown for a picked marker in the Marker window.
+fn marker_title(map: &Map, hit: MarkerHit, language: Option<&str>) -> Option<String> {
+    let MarkerHit { kind, index } = hit;
+    let title = match kind {
+        MarkerKind::Extract => map
+            .extracts
+            .iter()
+            .flatten()
+            .nth(index)?
+            .localized_name(language)
+            .to_owned(),
+        MarkerKind::Switch => map.switches.iter().flatten().nth(index)?.name.clone(),
+        MarkerKind::Boss => map.bosses.iter().flatten().nth(index)?.name.clone(),
+        MarkerKind::Lock => {
+            let lock = map.locks.iter().flatten().nth(index)?;
+            match &lock.key {
+                Some(key) => key.name.clone(),
+                None => format!("{} lock", lock.lock_type),
+            }
+        }
+        MarkerKind::Label => map
+            .labels
+            .iter()
+            .flatten()
+            .nth(index)?
+            .localized_text(language)
+            .to_owned(),
+        MarkerKind::Spawn => {
+            let spawn = map.spawns.iter().flatten().nth(index)?;
+            spawn_kind_label(spawn).to_owned()
+        }
+    };
+    Some(title)
+}
+
+/// Name of the first spawn overlay group a spawn point belongs to.
+fn spawn_kind_label(spawn: &Spawn) -> &'static str {
+    match SpawnKind::ALL.into_iter().find(|kind| kind.matches(spawn)) {
+        Some(SpawnKind::Pmc) => "PMC spawn",
+        Some(SpawnKind::Scav) => "Scav spawn",
+        Some(SpawnKind::Ai) => "AI spawn",
+        None => "Spawn",
+    }
+}
+
+impl TarkovMapApp {
+    /// Shows the note of a picked marker at the end of its tooltip.
+    pub fn show_marker_note(&self, ui: &mut egui::Ui, map: &Map, hit: MarkerHit) {
+        let note = MarkerNotes::hit_key(map, hit)
+            .and_then(|key| self.notes.get(&map.normalized_name, &key));
+        match note {
+            Some(note) => ui.label(format!("Note: {note}")),
+            None => ui.weak("Click to add a note"),
+        };
+    }
+
+    /// Shows a tooltip with the text, position and note of the picked label at `index`.
+    pub fn show_label_tooltip(&self, response: &egui::Response, map: &Map, index: usize) {
+        let Some(label) = map.labels.iter().flatten().nth(index) else {
+            return;
+        };
+
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            ui.strong(label.localized_text(self.language.as_deref()));
+            let p = label.position;
+            ui.weak(self.coordinate_convention.format(p.x, None, p.z));
+            let hit = MarkerHit {
+                kind: MarkerKind::Label,
+                index,
+            };
+            self.show_marker_note(ui, map, hit);
+            self.marker_picking.cycle_hint(ui);
+        });
+    }
+
+    /// Shows a tooltip with the sides, categories, position and note of the picked spawn at `index`.
+    pub fn show_spawn_tooltip(&self, response: &egui::Response, map: &Map, index: usize) {
+        let Some(spawn) = map.spawns.iter().flatten().nth(index) else {
+            return;
+        };
+
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            ui.strong(spawn_kind_label(spawn));
+            let sides: Vec<&str> = spawn.sides.iter().map(|side| side.as_str()).collect();
+            ui.label(format!("Sides: {}", sides.join(", ")));
+            let categories: Vec<&str> = spawn
+                .categories
+                .iter()
+                .map(|category| category.as_str())
+                .collect();
+            ui.label(format!("Used for: {}", categories.join(", ")));
+            let p = spawn.position;
+            ui.weak(self.coordinate_convention.format(p.x, Some(p.y), p.z));
+            let hit = MarkerHit {
+                kind: MarkerKind::Spawn,
+                index,
+            };
+            self.show_marker_note(ui, map, hit);
+            self.marker_picking.cycle_hint(ui);
+        });
+    }
+
+    /// Renders the note editor for the marker clicked on the map.
+    pub fn show_marker_note_window(&mut self, ctx: &egui::Context) {
+        let Some(hit) = self.selected_marker else {
+            return;
+        };
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let map_name = map.normalized_name.clone();
+        let (Some(key), Some(title)) = (
+            MarkerNotes::hit_key(map, hit),
+            marker_title(map, hit, self.language.as_deref()),
+        ) else {
+            self.selected_marker = None;
+            return;
+        };
+        let mut note = self
+            .notes
+            .get(&map_name, &key)
+            .unwrap_or_default()
+            .to_owned();
+
+        let mut open = true;
+        egui::Window::new("Marker")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(260.0)
+            .show(ctx, |ui| {
+                ui.strong(&title);
+                let response = ui.add(
+                    egui::TextEdit::multiline(&mut note)
+                        .desired_rows(3)
+                        .hint_text("e.g. camper spot, check the window"),
+                );
+                if response.changed() {
+                    self.notes.set(&map_name, &key, note);
+                }
+                self.show_marker_photos(ui, &map_name, &key);
+                ui.weak("Drop images onto the window to attach photos");
+            });
+        if !open {
+            self.selected_marker = None;
+        }
+    }
+}
@@ -1,22 +1,52 @@
-//! Overlay visibility settings and drawing functions for map markers.
+//! Map overlays: the `Overlay` trait, built-in overlay implementations, and
+//! their drawing functions and visibility settings.
 
+use crate::carried_items::CarriedItems;
 use crate::colors;
+use crate::constants::{
+    EXTRACT_AREA_OPACITY, LOCK_KEY_NAME_MIN_ZOOM, LOOT_CONTAINER_NAME_MIN_ZOOM,
+    OVERLAY_FADE_SECONDS, SPAWN_CLUSTER_MAX_ZOOM,
+};
 use crate::coordinates::game_to_display;
+use crate::heatmap::{PersonalHeatmap, draw_personal_heatmap};
+use crate::icon_pack::{IconPack, MarkerIcon};
+use crate::label_style::LabelStyle;
+use crate::raid_mode::RaidMode;
 use crate::screenshot_watcher::PlayerPosition;
 use eframe::egui;
 use serde::{Deserialize, Serialize};
-use tarkov_map::{Extract, Label, Map, Spawn};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use tarkov_map::rotation::map_yaw;
+use tarkov_map::{
+    BossSpawn, ContainerCategory, Extract, Faction, Hazard, Label, Lock, LootContainer, Map, Spawn,
+    SpawnCategory, SpawnCluster, SpawnSide, StationaryWeapon, Switch, Task, TaskZone, Transit,
+};
 
 /// Controls visibility of different overlay types on the map.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(default)]
 pub struct OverlayVisibility {
     pub labels: bool,
+    /// PMC spawns.
     pub spawns: bool,
+    pub scav_spawns: bool,
+    pub ai_spawns: bool,
     pub pmc_extracts: bool,
     pub scav_extracts: bool,
     pub shared_extracts: bool,
     pub player_marker: bool,
+    pub elevation: bool,
+    pub personal_heatmap: bool,
+    pub hazards: bool,
+    pub locks: bool,
+    pub loot_containers: bool,
+    pub hidden_stashes: bool,
+    pub bosses: bool,
+    pub stationary_weapons: bool,
+    pub switches: bool,
+    pub transits: bool,
+    pub task_objectives: bool,
+    pub task_chains: bool,
 }
 
 impl Default for OverlayVisibility {
@@ -24,14 +54,114 @@ impl Default for OverlayVisibility {
         Self {
             labels: false,
             spawns: true,
+            scav_spawns: false,
+            ai_spawns: false,
             pmc_extracts: true,
             scav_extracts: true,
             shared_extracts: true,
             player_marker: true,
+            elevation: false,
+            personal_heatmap: false,
+            hazards: true,
+            locks: false,
+            loot_containers: false,
+            hidden_stashes: false,
+            bosses: true,
+            stationary_weapons: true,
+            switches: true,
+            transits: true,
+            task_objectives: true,
+            task_chains: false,
         }
     }
 }
 
+impl OverlayVisibility {
+    /// Returns `true` if extracts of `faction` are currently shown.
+    pub fn shows_extract(&self, faction: &Faction) -> bool {
+        match faction {
+            Faction::Pmc => self.pmc_extracts,
+            Faction::Scav => self.scav_extracts,
+            Faction::Shared => self.shared_extracts,
+            Faction::Other(_) => false,
+        }
+    }
+
+    /// Returns `true` if spawns of `kind` are currently shown.
+    pub fn shows_spawns(&self, kind: SpawnKind) -> bool {
+        match kind {
+            SpawnKind::Pmc => self.spawns,
+            SpawnKind::Scav => self.scav_spawns,
+            SpawnKind::Ai => self.ai_spawns,
+        }
+    }
+}
+
+/// A group of spawn points with its own overlay toggle.
+///
+/// The map data keeps every spawn with its sides and categories; these pick
+/// the ones each toggle shows. A spawn for all sides counts as both a PMC and a
+/// Scav spawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnKind {
+    /// Where PMC players start a raid.
+    Pmc,
+    /// Where player Scavs start a raid.
+    Scav,
+    /// Bot, sniper and boss spawns.
+    Ai,
+}
+
+impl SpawnKind {
+    pub const ALL: [SpawnKind; 3] = [SpawnKind::Pmc, SpawnKind::Scav, SpawnKind::Ai];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SpawnKind::Pmc => "PMC Spawns",
+            SpawnKind::Scav => "Scav Spawns",
+            SpawnKind::Ai => "AI Spawns",
+        }
+    }
+
+    /// Returns `true` if `spawn` belongs to this group.
+    pub fn matches(self, spawn: &Spawn) -> bool {
+        match self {
+            SpawnKind::Pmc => {
+                spawn.has_side(&SpawnSide::Pmc) && spawn.has_category(&SpawnCategory::Player)
+            }
+            SpawnKind::Scav => {
+                spawn.has_side(&SpawnSide::Scav) && spawn.has_category(&SpawnCategory::Player)
+            }
+            SpawnKind::Ai => [
+                SpawnCategory::Bot,
+                SpawnCategory::Sniper,
+                SpawnCategory::Boss,
+            ]
+            .iter()
+            .any(|category| spawn.has_category(category)),
+        }
+    }
+
+    /// Marker fill and stroke colors.
+    pub fn colors(self) -> (egui::Color32, egui::Color32) {
+        match self {
+            SpawnKind::Pmc => (colors::SPAWN_FILL, colors::SPAWN_STROKE),
+            SpawnKind::Scav => (colors::SCAV_SPAWN_FILL, colors::SCAV_SPAWN_STROKE),
+            SpawnKind::Ai => (colors::AI_SPAWN_FILL, colors::AI_SPAWN_STROKE),
+        }
+    }
+
+    fn count(self, map: Option<&Map>) -> Option<usize> {
+        map.map(|map| {
+            map.spawns
+                .iter()
+                .flatten()
+                .filter(|s| self.matches(s))
+                .count()
+        })
+    }
+}
+
 /// Draws label overlays on the map.
 pub fn draw_labels(
     ui: &mut egui::Ui,
@@ -39,6 +169,8 @@ pub fn draw_labels(
     map: &Map,
     labels: &[Label],
     zoom: f32,
+    style: &LabelStyle,
+    language: Option<&str>,
 ) {
     let painter = ui.painter();
 
@@ -53,42 +185,1078 @@ pub fn draw_labels(
 
         let base_size = label.size.unwrap_or(40) as f32 * 0.15;
         let font_size = (base_size * zoom).clamp(8.0, 48.0);
-        let font_id = egui::FontId::proportional(font_size);
 
-        // Shadow
-        painter.text(
-            pos + egui::vec2(1.0, 1.0),
+        style.paint(
+            painter,
+            pos,
             egui::Align2::CENTER_CENTER,
-            &label.text,
-            font_id.clone(),
+            label.localized_text(language),
+            font_size,
+            colors::LABEL_TEXT,
             colors::LABEL_SHADOW,
         );
+    }
+}
 
-        // Main text
-        painter.text(
-            pos,
-            egui::Align2::CENTER_CENTER,
-            &label.text,
-            font_id,
-            colors::LABEL_TEXT,
+/// Everything an overlay needs to draw itself for the current frame.
+pub struct OverlayContext<'a> {
+    pub map: &'a Map,
+    /// Screen rect the whole map image is drawn into.
+    pub map_rect: egui::Rect,
+    pub zoom: f32,
+    pub visibility: &'a OverlayVisibility,
+    pub icons: &'a IconPack,
+    pub player: Option<&'a PlayerPosition>,
+    pub raid_mode: RaidMode,
+    pub label_style: LabelStyle,
+    /// Language code names are shown in, or `None` for English.
+    pub language: Option<&'a str>,
+    pub carried_items: &'a CarriedItems,
+    /// Player height that labels are filtered by, when following the player height.
+    pub player_height: Option<f64>,
+    pub personal_heatmap: Option<&'a PersonalHeatmap>,
+    /// IDs of the quests the game's logs report as completed.
+    pub completed_quests: &'a BTreeSet<String>,
+}
+
+/// A layer drawn on top of the map image, with its own sidebar controls.
+///
+/// Overlays are drawn in the order returned by [`builtin_overlays`], so later
+/// overlays appear on top. New overlay types only need an implementation and
+/// an entry in that list.
+pub trait Overlay {
+    /// Renders the overlay's sidebar controls, usually a visibility toggle.
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    );
+
+    /// Draws the overlay onto the map.
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext);
+}
+
+/// Runs `draw` at the opacity of a short fade towards `visible`.
+///
+/// egui keeps the fade state per `id`, so every overlay (or part of one) fades
+/// independently. Nothing is drawn once fully faded out.
+fn draw_faded(ui: &mut egui::Ui, id: &str, visible: bool, draw: impl FnOnce(&mut egui::Ui)) {
+    let opacity = ui.ctx().animate_bool_with_time(
+        egui::Id::new(("overlay_fade", id)),
+        visible,
+        OVERLAY_FADE_SECONDS,
+    );
+    if opacity <= 0.0 {
+        return;
+    }
+    let previous = ui.opacity();
+    ui.multiply_opacity(opacity);
+    draw(ui);
+    ui.set_opacity(previous);
+}
+
+/// Appends a count to an overlay toggle's label, e.g. "Locks (12)".
+///
+/// Counts come from the selected map, so an empty overlay can be told apart
+/// from one that is toggled off. Without a selected map there is no count.
+fn counted(label: &str, count: Option<usize>) -> String {
+    match count {
+        Some(count) => format!("{label} ({count})"),
+        None => label.to_owned(),
+    }
+}
+
+/// Number of entries in one of `map`'s optional lists.
+fn count<T>(map: Option<&Map>, list: fn(&Map) -> &Option<Vec<T>>) -> Option<usize> {
+    map.map(|map| list(map).as_ref().map_or(0, Vec::len))
+}
+
+/// The built-in overlays, bottom to top.
+pub fn builtin_overlays() -> Vec<Box<dyn Overlay>> {
+    vec![
+        Box::new(ElevationOverlay),
+        Box::new(PersonalHeatmapOverlay),
+        Box::new(HazardOverlay),
+        Box::new(LabelOverlay),
+        Box::new(SpawnOverlay),
+        Box::new(TaskObjectiveOverlay::default()),
+        Box::new(LootContainerOverlay::default()),
+        Box::new(HiddenStashOverlay),
+        Box::new(LockOverlay),
+        Box::new(SwitchOverlay),
+        Box::new(StationaryWeaponOverlay),
+        Box::new(BossOverlay),
+        Box::new(TransitOverlay),
+        Box::new(ExtractOverlay),
+        Box::new(PlayerOverlay),
+    ]
+}
+
+struct ElevationOverlay;
+
+impl Overlay for ElevationOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        let hint = map
+            .and_then(elevation_range)
+            .map(|[min, max]| format!("Tint by height, {min:.0} m to {max:.0} m"))
+            .unwrap_or_else(|| "No height data for this map".to_owned());
+        ui.scope(|ui| {
+            toggle_rect(
+                ui,
+                &mut visibility.elevation,
+                "Elevation",
+                colors::ELEVATION_BANDS[3],
+            );
+        })
+        .response
+        .on_hover_text(hint);
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        draw_faded(ui, "elevation", ctx.visibility.elevation, |ui| {
+            draw_elevation(ui, ctx.map_rect, ctx.map, ctx.zoom);
+        });
+    }
+}
+
+struct PersonalHeatmapOverlay;
+
+impl Overlay for PersonalHeatmapOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        _: Option<&Map>,
+    ) {
+        ui.scope(|ui| {
+            toggle_rect(
+                ui,
+                &mut visibility.personal_heatmap,
+                "My Raids",
+                colors::HEATMAP_HOT,
+            );
+        })
+        .response
+        .on_hover_text("Heatmap of where your recorded raids went");
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(heatmap) = ctx.personal_heatmap {
+            draw_faded(
+                ui,
+                "personal_heatmap",
+                ctx.visibility.personal_heatmap,
+                |ui| {
+                    draw_personal_heatmap(ui, ctx.map_rect, ctx.map, heatmap);
+                },
+            );
+        }
+    }
+}
+
+struct HazardOverlay;
+
+impl Overlay for HazardOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        toggle_rect(
+            ui,
+            &mut visibility.hazards,
+            &counted("Hazards", count(map, |map| &map.hazards)),
+            colors::HAZARD_STROKE,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(hazards) = &ctx.map.hazards {
+            draw_faded(ui, "hazards", ctx.visibility.hazards, |ui| {
+                draw_hazards(ui, ctx.map_rect, ctx.map, hazards, ctx.zoom);
+            });
+        }
+    }
+}
+
+struct LabelOverlay;
+
+impl Overlay for LabelOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        toggle_circle(
+            ui,
+            &mut visibility.labels,
+            &counted("Labels", count(map, |map| &map.labels)),
+            egui::Color32::WHITE,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(labels) = &ctx.map.labels {
+            draw_faded(ui, "labels", ctx.visibility.labels, |ui| {
+                let labels: Vec<Label> = match ctx.player_height {
+                    Some(height) => labels
+                        .iter()
+                        .filter(|label| label.visible_at(height))
+                        .cloned()
+                        .collect(),
+                    None => labels.clone(),
+                };
+                draw_labels(
+                    ui,
+                    ctx.map_rect,
+                    ctx.map,
+                    &labels,
+                    ctx.zoom,
+                    &ctx.label_style,
+                    ctx.language,
+                );
+            });
+        }
+    }
+}
+
+struct SpawnOverlay;
+
+impl Overlay for SpawnOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        for kind in SpawnKind::ALL {
+            let value = match kind {
+                SpawnKind::Pmc => &mut visibility.spawns,
+                SpawnKind::Scav => &mut visibility.scav_spawns,
+                SpawnKind::Ai => &mut visibility.ai_spawns,
+            };
+            toggle_circle(
+                ui,
+                value,
+                &counted(kind.label(), kind.count(map)),
+                kind.colors().0,
+            );
+        }
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        let Some(spawns) = &ctx.map.spawns else {
+            return;
+        };
+        // AI spawns are drawn first so player spawns stay on top
+        for kind in [SpawnKind::Ai, SpawnKind::Scav, SpawnKind::Pmc] {
+            let id = format!("spawns_{kind:?}");
+            draw_faded(ui, &id, ctx.visibility.shows_spawns(kind), |ui| {
+                // Clusters are computed from PMC spawns: named clusters at low
+                // zoom, individual points when zoomed in
+                match &ctx.map.spawn_clusters {
+                    Some(clusters)
+                        if kind == SpawnKind::Pmc && ctx.zoom < SPAWN_CLUSTER_MAX_ZOOM =>
+                    {
+                        draw_spawn_clusters(ui, ctx.map_rect, ctx.map, clusters, ctx.zoom);
+                    }
+                    _ => draw_spawns(ui, ctx.map_rect, ctx.map, spawns, kind, ctx.zoom, ctx.icons),
+                }
+            });
+        }
+    }
+}
+
+/// Loot containers, filterable by container type.
+///
+/// The filter lists the container types of the selected map and lasts for the
+/// session; only the overlay toggle itself is persisted.
+#[derive(Default)]
+struct LootContainerOverlay {
+    /// Normalized names of the container types that are not drawn.
+    hidden: HashSet<String>,
+}
+
+impl Overlay for LootContainerOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        let shown = map.map(|map| {
+            loot_containers(map)
+                .filter(|c| !self.hidden.contains(&c.container_type))
+                .count()
+        });
+        toggle_rect(
+            ui,
+            &mut visibility.loot_containers,
+            &counted("Loot Containers", shown),
+            colors::LOOT_CONTAINER,
+        );
+        if !visibility.loot_containers {
+            return;
+        }
+        let Some(map) = map else {
+            return;
+        };
+
+        // Container type -> (display name, count), sorted by type
+        let mut types: BTreeMap<&str, (&str, usize)> = BTreeMap::new();
+        for container in loot_containers(map) {
+            types
+                .entry(&container.container_type)
+                .or_insert((&container.name, 0))
+                .1 += 1;
+        }
+        ui.indent("loot_container_types", |ui| {
+            for (container_type, (name, count)) in types {
+                let mut shown = !self.hidden.contains(container_type);
+                if ui
+                    .checkbox(&mut shown, format!("{name} ({count})"))
+                    .changed()
+                {
+                    if shown {
+                        self.hidden.remove(container_type);
+                    } else {
+                        self.hidden.insert(container_type.to_owned());
+                    }
+                }
+            }
+        });
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        draw_faded(
+            ui,
+            "loot_containers",
+            ctx.visibility.loot_containers,
+            |ui| {
+                let shown: Vec<&LootContainer> = loot_containers(ctx.map)
+                    .filter(|c| !self.hidden.contains(&c.container_type))
+                    .collect();
+                draw_loot_containers(ui, ctx.map_rect, ctx.map, &shown, ctx.zoom);
+            },
+        );
+    }
+}
+
+/// `map`'s loot containers of `category`.
+fn containers_of(map: &Map, category: ContainerCategory) -> impl Iterator<Item = &LootContainer> {
+    map.loot_containers
+        .iter()
+        .flatten()
+        .filter(move |container| container.category == category)
+}
+
+/// `map`'s regular loot containers, without the hidden stashes.
+fn loot_containers(map: &Map) -> impl Iterator<Item = &LootContainer> {
+    containers_of(map, ContainerCategory::Loot)
+}
+
+/// Buried barrels and ground caches, kept apart from the other loot
+/// containers for planning stash farming routes.
+struct HiddenStashOverlay;
+
+impl Overlay for HiddenStashOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        let count = map.map(|map| containers_of(map, ContainerCategory::Stash).count());
+        toggle_circle(
+            ui,
+            &mut visibility.hidden_stashes,
+            &counted("Hidden Stashes", count),
+            colors::HIDDEN_STASH,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        draw_faded(ui, "hidden_stashes", ctx.visibility.hidden_stashes, |ui| {
+            let stashes: Vec<&LootContainer> =
+                containers_of(ctx.map, ContainerCategory::Stash).collect();
+            draw_hidden_stashes(ui, ctx.map_rect, ctx.map, &stashes, ctx.zoom);
+        });
+    }
+}
+
+/// Objectives of the tasks picked in the sidebar.
+///
+/// Tasks are picked per session, and tasks the game's logs report as
+/// completed are not drawn.
+#[derive(Default)]
+struct TaskObjectiveOverlay {
+    /// IDs of the tasks whose objectives are drawn.
+    shown: HashSet<String>,
+}
+
+impl Overlay for TaskObjectiveOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        let tasks = map.and_then(|map| map.tasks.as_ref());
+        let shown = tasks.map(|tasks| {
+            tasks
+                .iter()
+                .filter(|task| self.shown.contains(&task.id))
+                .count()
+        });
+        toggle_circle(
+            ui,
+            &mut visibility.task_objectives,
+            &counted("Task Objectives", shown),
+            colors::TASK_OBJECTIVE,
+        );
+        if !visibility.task_objectives {
+            return;
+        }
+        let Some(tasks) = tasks else {
+            return;
+        };
+
+        egui::CollapsingHeader::new(format!("Tasks ({})", tasks.len()))
+            .id_salt("task_objective_tasks")
+            .show(ui, |ui| {
+                for task in tasks {
+                    let mut shown = self.shown.contains(&task.id);
+                    let label = match &task.trader {
+                        Some(trader) => format!("{} ({trader})", task.name),
+                        None => task.name.clone(),
+                    };
+                    if ui.checkbox(&mut shown, label).changed() {
+                        if shown {
+                            self.shown.insert(task.id.clone());
+                        } else {
+                            self.shown.remove(&task.id);
+                        }
+                    }
+                }
+            });
+        ui.checkbox(&mut visibility.task_chains, "Quest chains")
+            .on_hover_text(
+                "Also show the earlier tasks on this map that the shown tasks require, \
+                 with arrows between objectives in the order they're done",
+            );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(tasks) = &ctx.map.tasks {
+            draw_faded(
+                ui,
+                "task_objectives",
+                ctx.visibility.task_objectives,
+                |ui| {
+                    let pending = |task: &&Task| !ctx.completed_quests.contains(&task.id);
+                    let selected = tasks
+                        .iter()
+                        .filter(|task| self.shown.contains(&task.id))
+                        .filter(pending);
+                    if !ctx.visibility.task_chains {
+                        let shown: Vec<&Task> = selected.collect();
+                        draw_task_objectives(ui, ctx.map_rect, ctx.map, &shown, ctx.zoom);
+                        return;
+                    }
+
+                    let mut shown: Vec<&Task> = Vec::new();
+                    for task in selected {
+                        let chain: Vec<&Task> = ctx
+                            .map
+                            .task_chain(&task.id)
+                            .into_iter()
+                            .filter(pending)
+                            .collect();
+                        draw_task_chain(ui, ctx.map_rect, ctx.map, &chain);
+                        for task in chain {
+                            if !shown.iter().any(|shown| shown.id == task.id) {
+                                shown.push(task);
+                            }
+                        }
+                    }
+                    draw_task_objectives(ui, ctx.map_rect, ctx.map, &shown, ctx.zoom);
+                },
+            );
+        }
+    }
+}
+
+struct LockOverlay;
+
+impl Overlay for LockOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        toggle_circle(
+            ui,
+            &mut visibility.locks,
+            &counted("Locks", count(map, |map| &map.locks)),
+            colors::LOCK_KEY,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(locks) = &ctx.map.locks {
+            draw_faded(ui, "locks", ctx.visibility.locks, |ui| {
+                draw_locks(ui, ctx.map_rect, ctx.map, locks, ctx.zoom);
+            });
+        }
+    }
+}
+
+struct SwitchOverlay;
+
+impl Overlay for SwitchOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        toggle_circle(
+            ui,
+            &mut visibility.switches,
+            &counted("Switches", count(map, |map| &map.switches)),
+            colors::SWITCH,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(switches) = &ctx.map.switches {
+            draw_faded(ui, "switches", ctx.visibility.switches, |ui| {
+                draw_switches(ui, ctx.map_rect, ctx.map, switches, ctx.zoom);
+            });
+        }
+    }
+}
+
+struct StationaryWeaponOverlay;
+
+impl Overlay for StationaryWeaponOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        toggle_circle(
+            ui,
+            &mut visibility.stationary_weapons,
+            &counted(
+                "Stationary Weapons",
+                count(map, |map| &map.stationary_weapons),
+            ),
+            colors::STATIONARY_WEAPON,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(weapons) = &ctx.map.stationary_weapons {
+            draw_faded(
+                ui,
+                "stationary_weapons",
+                ctx.visibility.stationary_weapons,
+                |ui| {
+                    draw_stationary_weapons(ui, ctx.map_rect, ctx.map, weapons, ctx.zoom);
+                },
+            );
+        }
+    }
+}
+
+struct TransitOverlay;
+
+impl Overlay for TransitOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        toggle_rect(
+            ui,
+            &mut visibility.transits,
+            &counted("Transits", count(map, |map| &map.transits)),
+            colors::TRANSIT,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(transits) = &ctx.map.transits {
+            draw_faded(ui, "transits", ctx.visibility.transits, |ui| {
+                draw_transits(ui, ctx.map_rect, ctx.map, transits, ctx.zoom);
+            });
+        }
+    }
+}
+
+struct BossOverlay;
+
+impl Overlay for BossOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        toggle_circle(
+            ui,
+            &mut visibility.bosses,
+            &counted("Bosses", count(map, |map| &map.bosses)),
+            colors::BOSS_FILL,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(bosses) = &ctx.map.bosses {
+            draw_faded(ui, "bosses", ctx.visibility.bosses, |ui| {
+                draw_bosses(ui, ctx.map_rect, ctx.map, bosses, ctx.zoom);
+            });
+        }
+    }
+}
+
+struct ExtractOverlay;
+
+impl Overlay for ExtractOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        map: Option<&Map>,
+    ) {
+        let faction_count = |faction: Faction| {
+            map.map(|map| {
+                map.extracts
+                    .iter()
+                    .flatten()
+                    .filter(|extract| extract.faction == faction)
+                    .count()
+            })
+        };
+        toggle_rect(
+            ui,
+            &mut visibility.pmc_extracts,
+            &counted("PMC Extracts", faction_count(Faction::Pmc)),
+            colors::PMC_EXTRACT_FILL,
+        );
+        toggle_rect(
+            ui,
+            &mut visibility.scav_extracts,
+            &counted("Scav Extracts", faction_count(Faction::Scav)),
+            colors::SCAV_EXTRACT_FILL,
+        );
+        toggle_rect(
+            ui,
+            &mut visibility.shared_extracts,
+            &counted("Shared Extracts", faction_count(Faction::Shared)),
+            colors::SHARED_EXTRACT_FILL,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        let Some(extracts) = &ctx.map.extracts else {
+            return;
+        };
+        // Each faction fades on its own, so draw them one at a time
+        let none = OverlayVisibility {
+            pmc_extracts: false,
+            scav_extracts: false,
+            shared_extracts: false,
+            ..*ctx.visibility
+        };
+        let factions = [
+            (
+                "pmc_extracts",
+                ctx.visibility.pmc_extracts,
+                OverlayVisibility {
+                    pmc_extracts: true,
+                    ..none
+                },
+            ),
+            (
+                "scav_extracts",
+                ctx.visibility.scav_extracts,
+                OverlayVisibility {
+                    scav_extracts: true,
+                    ..none
+                },
+            ),
+            (
+                "shared_extracts",
+                ctx.visibility.shared_extracts,
+                OverlayVisibility {
+                    shared_extracts: true,
+                    ..none
+                },
+            ),
+        ];
+        for (id, visible, only) in factions {
+            draw_faded(ui, id, visible, |ui| {
+                draw_extracts(
+                    ui,
+                    ctx.map_rect,
+                    ctx.map,
+                    extracts,
+                    ctx.zoom,
+                    &only,
+                    ctx.icons,
+                    &ctx.label_style,
+                    ctx.language,
+                    ctx.carried_items,
+                );
+            });
+        }
+    }
+}
+
+struct PlayerOverlay;
+
+impl Overlay for PlayerOverlay {
+    fn settings_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        visibility: &mut OverlayVisibility,
+        _: Option<&Map>,
+    ) {
+        toggle_triangle(
+            ui,
+            &mut visibility.player_marker,
+            "Player Position",
+            colors::PLAYER_MARKER_FILL,
+        );
+    }
+
+    fn draw(&self, ui: &mut egui::Ui, ctx: &OverlayContext) {
+        if let Some(player) = ctx.player {
+            draw_faded(ui, "player_marker", ctx.visibility.player_marker, |ui| {
+                draw_player_marker(
+                    ui,
+                    ctx.map_rect,
+                    ctx.map,
+                    player,
+                    ctx.zoom,
+                    ctx.icons,
+                    ctx.raid_mode.label(),
+                );
+            });
+        }
+    }
+}
+
+/// Renders a triangle-style overlay toggle (for player marker).
+pub fn toggle_triangle(ui: &mut egui::Ui, value: &mut bool, label: &str, color: egui::Color32) {
+    ui.horizontal(|ui| {
+        ui.checkbox(value, "");
+        let (rect, icon_response) =
+            ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::click());
+        let center = rect.center();
+        // Draw a small triangle pointing up
+        let size = 5.0;
+        let points = vec![
+            center + egui::vec2(0.0, -size),
+            center + egui::vec2(-size * 0.7, size * 0.5),
+            center + egui::vec2(size * 0.7, size * 0.5),
+        ];
+        ui.painter().add(egui::Shape::convex_polygon(
+            points,
+            color,
+            egui::Stroke::new(1.0, color.gamma_multiply(0.5)),
+        ));
+        let label_response = ui
+            .label(label)
+            .interact(egui::Sense::click())
+            .on_hover_cursor(egui::CursorIcon::PointingHand);
+        if icon_response.clicked() || label_response.clicked() {
+            *value = !*value;
+        }
+    });
+}
+
+/// Renders a circle-style overlay toggle (for spawns, labels).
+pub fn toggle_circle(ui: &mut egui::Ui, value: &mut bool, label: &str, color: egui::Color32) {
+    ui.horizontal(|ui| {
+        ui.checkbox(value, "");
+        let (rect, icon_response) =
+            ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::click());
+        let center = rect.center();
+        ui.painter().circle_filled(center, 5.0, color);
+        ui.painter()
+            .circle_stroke(center, 5.0, egui::Stroke::new(1.0, egui::Color32::GRAY));
+        let label_response = ui
+            .label(label)
+            .interact(egui::Sense::click())
+            .on_hover_cursor(egui::CursorIcon::PointingHand);
+        if icon_response.clicked() || label_response.clicked() {
+            *value = !*value;
+        }
+    });
+}
+
+/// Renders a rectangle-style overlay toggle (for extracts).
+pub fn toggle_rect(ui: &mut egui::Ui, value: &mut bool, label: &str, color: egui::Color32) {
+    ui.horizontal(|ui| {
+        ui.checkbox(value, "");
+        let (rect, icon_response) =
+            ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::click());
+        ui.painter().rect_filled(rect, 2.0, color);
+        ui.painter().rect_stroke(
+            rect,
+            2.0,
+            egui::Stroke::new(1.0, color.gamma_multiply(0.5)),
+            egui::StrokeKind::Inside,
         );
+        let label_response = ui
+            .label(label)
+            .interact(egui::Sense::click())
+            .on_hover_cursor(egui::CursorIcon::PointingHand);
+        if icon_response.clicked() || label_response.clicked() {
+            *value = !*value;
+        }
+    });
+}
+
+/// Height range `[min, max]` used for elevation shading.
+///
+/// Uses the map's default height range, falling back to the spread of marker heights.
+pub fn elevation_range(map: &Map) -> Option<[f64; 2]> {
+    if let Some(range) = map.height_range.filter(|[min, max]| max > min) {
+        return Some(range);
     }
+
+    let spawns = map.spawns.iter().flatten().map(|s| s.position.y);
+    let extracts = map.extracts.iter().flatten().filter_map(extract_height);
+    let (min, max) = spawns
+        .chain(extracts)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), h| {
+            (min.min(h), max.max(h))
+        });
+    (max > min).then_some([min, max])
 }
 
-/// Draws spawn point markers on the map.
+/// Height of an extract's marker, or the middle of its zone's height bounds.
+fn extract_height(extract: &Extract) -> Option<f64> {
+    match (extract.position, extract.top, extract.bottom) {
+        (Some(position), _, _) => Some(position.y),
+        (None, Some(top), Some(bottom)) => Some((top + bottom) / 2.0),
+        _ => None,
+    }
+}
+
+fn elevation_color(height: f64, [min, max]: [f64; 2]) -> egui::Color32 {
+    let bands = colors::ELEVATION_BANDS.len();
+    let t = ((height - min) / (max - min)).clamp(0.0, 1.0);
+    let band = ((t * bands as f64) as usize).min(bands - 1);
+    colors::ELEVATION_BANDS[band]
+}
+
+/// Tints the map by elevation band.
+///
+/// Layer extent bounds are filled with the color of their height range, and
+/// PMC spawns and extracts get a soft halo colored by their own height.
+pub fn draw_elevation(ui: &mut egui::Ui, map_rect: egui::Rect, map: &Map, zoom: f32) {
+    let Some(range) = elevation_range(map) else {
+        return;
+    };
+    let painter = ui.painter();
+
+    let extents = map.layers.iter().flatten().flat_map(|layer| &layer.extents);
+    for extent in extents {
+        let height = (extent.height[0] + extent.height[1]) / 2.0;
+        let fill = elevation_color(height, range).gamma_multiply(0.35);
+
+        for bound in extent.bounds.iter().flatten() {
+            let [x1, z1] = bound.point1;
+            let [x2, z2] = bound.point2;
+            let corners: Option<Vec<egui::Pos2>> = [[x1, z1], [x2, z1], [x2, z2], [x1, z2]]
+                .into_iter()
+                .map(|p| game_to_display(map, map_rect, p))
+                .collect();
+            if let Some(corners) = corners {
+                painter.add(egui::Shape::convex_polygon(
+                    corners,
+                    fill,
+                    egui::Stroke::NONE,
+                ));
+            }
+        }
+    }
+
+    let spawns = map
+        .spawns
+        .iter()
+        .flatten()
+        .filter(|s| SpawnKind::Pmc.matches(s))
+        .map(|s| (s.position.xz(), s.position.y));
+    let extracts = map
+        .extracts
+        .iter()
+        .flatten()
+        .filter_map(|e| Some((e.center()?, extract_height(e)?)));
+    let radius = (24.0 * zoom).clamp(12.0, 96.0);
+    for (position, height) in spawns.chain(extracts) {
+        let Some(pos) = game_to_display(map, map_rect, position) else {
+            continue;
+        };
+        if !map_rect.expand(radius).contains(pos) {
+            continue;
+        }
+        let color = elevation_color(height, range).gamma_multiply(0.3);
+        painter.circle_filled(pos, radius, color);
+    }
+}
+
+/// Triangulates a simple polygon by ear clipping, returning vertex indices.
+///
+/// Works for concave outlines, which egui can only stroke, not fill.
+fn triangulate(points: &[egui::Pos2]) -> Vec<u32> {
+    let cross =
+        |o: egui::Pos2, a: egui::Pos2, b: egui::Pos2| (a - o).x * (b - o).y - (a - o).y * (b - o).x;
+    // Signed area, to clip ears with the polygon's own winding
+    let area: f32 = (0..points.len())
+        .map(|i| cross(egui::Pos2::ZERO, points[i], points[(i + 1) % points.len()]))
+        .sum();
+
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut indices = Vec::new();
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let [a, b, c] = [
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            ];
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            cross(pa, pb, pc) * area > 0.0
+                && !remaining.iter().any(|&j| {
+                    ![a, b, c].contains(&j)
+                        && cross(pa, pb, points[j]) * area >= 0.0
+                        && cross(pb, pc, points[j]) * area >= 0.0
+                        && cross(pc, pa, points[j]) * area >= 0.0
+                })
+        });
+        // Self-intersecting outlines have no ear left; fill what was clipped
+        let Some(i) = ear else {
+            return indices;
+        };
+        let [a, b, c] = [
+            remaining[(i + n - 1) % n],
+            remaining[i],
+            remaining[(i + 1) % n],
+        ];
+        indices.extend([a as u32, b as u32, c as u32]);
+        remaining.remove(i);
+    }
+    if remaining.len() == 3 {
+        indices.extend(remaining.iter().map(|&i| i as u32));
+    }
+    indices
+}
+
+/// Fills and outlines a polygon in display coordinates, returning its centroid.
+fn draw_area(
+    painter: &egui::Painter,
+    points: Vec<egui::Pos2>,
+    fill: egui::Color32,
+    stroke: egui::Stroke,
+) -> egui::Pos2 {
+    let mut mesh = egui::Mesh::default();
+    for point in &points {
+        mesh.colored_vertex(*point, fill);
+    }
+    mesh.indices = triangulate(&points);
+    painter.add(mesh);
+    let sum = points
+        .iter()
+        .fold(egui::Vec2::ZERO, |sum, p| sum + p.to_vec2());
+    let center = (sum / points.len() as f32).to_pos2();
+    painter.add(egui::Shape::closed_line(points, stroke));
+    center
+}
+
+/// Draws hazard zones as translucent red areas, or circles for hazards without an outline.
+pub fn draw_hazards(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    hazards: &[Hazard],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+    let stroke = egui::Stroke::new(1.5, colors::HAZARD_STROKE);
+
+    for hazard in hazards {
+        let outline: Option<Vec<egui::Pos2>> = hazard.outline.as_ref().and_then(|outline| {
+            outline
+                .iter()
+                .map(|p| game_to_display(map, map_rect, *p))
+                .collect()
+        });
+        let center = match outline {
+            Some(points) => Some(draw_area(painter, points, colors::HAZARD_FILL, stroke)),
+            None => hazard
+                .position
+                .and_then(|position| game_to_display(map, map_rect, position.xz()))
+                .inspect(|pos| {
+                    let radius = (10.0 * zoom).clamp(8.0, 40.0);
+                    painter.circle(*pos, radius, colors::HAZARD_FILL, stroke);
+                }),
+        };
+
+        if let Some(center) = center {
+            let font_id = egui::FontId::proportional((5.0 * zoom).clamp(9.0, 14.0));
+            painter.text(
+                center + egui::vec2(1.0, 1.0),
+                egui::Align2::CENTER_CENTER,
+                &hazard.name,
+                font_id.clone(),
+                colors::LABEL_SHADOW,
+            );
+            painter.text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                &hazard.name,
+                font_id,
+                colors::HAZARD_STROKE,
+            );
+        }
+    }
+}
+
+/// Draws markers for the spawn points of `kind` on the map.
+///
+/// Icon packs only replace PMC spawn markers.
 pub fn draw_spawns(
     ui: &mut egui::Ui,
     map_rect: egui::Rect,
     map: &Map,
     spawns: &[Spawn],
+    kind: SpawnKind,
     zoom: f32,
+    icons: &IconPack,
 ) {
     let painter = ui.painter();
+    let (fill, stroke) = kind.colors();
 
-    for spawn in spawns {
-        // Use x, z for 2D position (y is height)
-        let game_pos = [spawn.position[0], spawn.position[2]];
-        let Some(pos) = game_to_display(map, map_rect, game_pos) else {
+    for spawn in spawns.iter().filter(|spawn| kind.matches(spawn)) {
+        let Some(pos) = game_to_display(map, map_rect, spawn.position.xz()) else {
             continue;
         };
 
@@ -97,16 +1265,457 @@ pub fn draw_spawns(
         }
 
         let radius = (4.0 * zoom).clamp(3.0, 12.0);
+        if kind == SpawnKind::Pmc && icons.paint(painter, MarkerIcon::Spawn, pos, radius * 2.5) {
+            continue;
+        }
+        painter.circle(pos, radius, fill, egui::Stroke::new(1.5, stroke));
+    }
+}
+
+/// Draws lock markers as small key icons on the map, with the key's short name once zoomed in.
+pub fn draw_locks(ui: &mut egui::Ui, map_rect: egui::Rect, map: &Map, locks: &[Lock], zoom: f32) {
+    let painter = ui.painter();
+    let size = (3.0 * zoom).clamp(4.0, 9.0);
+    let stroke = egui::Stroke::new(size * 0.3, colors::LOCK_KEY);
+    let show_names = zoom >= LOCK_KEY_NAME_MIN_ZOOM;
+
+    for lock in locks {
+        let Some(pos) = game_to_display(map, map_rect, lock.position.xz()) else {
+            continue;
+        };
+        if !map_rect.expand(20.0).contains(pos) {
+            continue;
+        }
+
+        painter.circle_filled(pos, size * 1.4, colors::LABEL_BACKGROUND);
+        // Bow on the left, shaft to the right with two teeth
+        painter.circle_stroke(pos - egui::vec2(size * 0.5, 0.0), size * 0.4, stroke);
+        painter.line_segment(
+            [
+                pos - egui::vec2(size * 0.1, 0.0),
+                pos + egui::vec2(size, 0.0),
+            ],
+            stroke,
+        );
+        for tooth in [0.6, 0.9] {
+            let base = pos + egui::vec2(size * tooth, 0.0);
+            painter.line_segment([base, base + egui::vec2(0.0, size * 0.4)], stroke);
+        }
+        if let Some(key) = lock.key.as_ref().filter(|_| show_names) {
+            painter.text(
+                pos + egui::vec2(size * 1.4 + 3.0, 0.0),
+                egui::Align2::LEFT_CENTER,
+                key.label(),
+                egui::FontId::proportional(11.0),
+                colors::LABEL_TEXT,
+            );
+        }
+    }
+}
+
+/// Draws loot containers as small squares, named once zoomed in far enough.
+pub fn draw_loot_containers(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    containers: &[&LootContainer],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+    let size = (2.0 * zoom).clamp(3.0, 7.0);
+    let show_names = zoom >= LOOT_CONTAINER_NAME_MIN_ZOOM;
+
+    for container in containers {
+        let Some(pos) = game_to_display(map, map_rect, container.position.xz()) else {
+            continue;
+        };
+        if !map_rect.expand(20.0).contains(pos) {
+            continue;
+        }
+
+        painter.rect(
+            egui::Rect::from_center_size(pos, egui::vec2(size, size) * 2.0),
+            1.0,
+            colors::LOOT_CONTAINER,
+            egui::Stroke::new(1.0, colors::LABEL_BACKGROUND),
+            egui::StrokeKind::Outside,
+        );
+        if show_names {
+            painter.text(
+                pos + egui::vec2(size + 3.0, 0.0),
+                egui::Align2::LEFT_CENTER,
+                &container.name,
+                egui::FontId::proportional(11.0),
+                colors::LABEL_TEXT,
+            );
+        }
+    }
+}
+
+/// Draws hidden stashes as circles marked with a cross, named once zoomed in far enough.
+pub fn draw_hidden_stashes(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    stashes: &[&LootContainer],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+    let radius = (2.5 * zoom).clamp(4.0, 8.0);
+    let stroke = egui::Stroke::new(1.5, colors::HIDDEN_STASH);
+    let show_names = zoom >= LOOT_CONTAINER_NAME_MIN_ZOOM;
+
+    for stash in stashes {
+        let Some(pos) = game_to_display(map, map_rect, stash.position.xz()) else {
+            continue;
+        };
+        if !map_rect.expand(20.0).contains(pos) {
+            continue;
+        }
+
+        painter.circle_filled(pos, radius + 1.5, colors::LABEL_BACKGROUND);
+        painter.circle_stroke(pos, radius, stroke);
+        let arm = radius * 0.55;
+        painter.line_segment(
+            [pos + egui::vec2(-arm, -arm), pos + egui::vec2(arm, arm)],
+            stroke,
+        );
+        painter.line_segment(
+            [pos + egui::vec2(-arm, arm), pos + egui::vec2(arm, -arm)],
+            stroke,
+        );
+        if show_names {
+            painter.text(
+                pos + egui::vec2(radius + 3.0, 0.0),
+                egui::Align2::LEFT_CENTER,
+                &stash.name,
+                egui::FontId::proportional(11.0),
+                colors::LABEL_TEXT,
+            );
+        }
+    }
+}
+
+/// Draws switches as small lever icons on the map.
+pub fn draw_switches(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    switches: &[Switch],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+    let size = (3.0 * zoom).clamp(4.0, 8.0);
+    let stroke = egui::Stroke::new(size * 0.35, colors::SWITCH);
+
+    for switch in switches {
+        let Some(pos) = game_to_display(map, map_rect, switch.position.xz()) else {
+            continue;
+        };
+        if !map_rect.expand(20.0).contains(pos) {
+            continue;
+        }
+
+        painter.circle_filled(pos, size * 1.4, colors::LABEL_BACKGROUND);
+        // Base plate with a lever tilted to the right
+        let base = pos + egui::vec2(0.0, size * 0.6);
+        painter.line_segment(
+            [
+                base - egui::vec2(size * 0.7, 0.0),
+                base + egui::vec2(size * 0.7, 0.0),
+            ],
+            stroke,
+        );
+        let knob = pos + egui::vec2(size * 0.5, -size * 0.7);
+        painter.line_segment([base, knob], stroke);
+        painter.circle_filled(knob, size * 0.3, colors::SWITCH);
+    }
+}
+
+/// Draws task objective zones as translucent areas and their positions as
+/// stars, labeled with the task name.
+pub fn draw_task_objectives(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    tasks: &[&Task],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+    let size = (3.0 * zoom).clamp(5.0, 10.0);
+    let stroke = egui::Stroke::new(1.5, colors::TASK_OBJECTIVE);
+    let fill = colors::TASK_OBJECTIVE.gamma_multiply(EXTRACT_AREA_OPACITY);
+
+    for task in tasks {
+        for zone in task
+            .objectives
+            .iter()
+            .flat_map(|objective| &objective.zones)
+        {
+            let outline: Option<Vec<egui::Pos2>> = zone.outline.as_ref().and_then(|outline| {
+                outline
+                    .iter()
+                    .map(|p| game_to_display(map, map_rect, *p))
+                    .collect()
+            });
+            let area_center = outline.map(|points| draw_area(painter, points, fill, stroke));
+            let pos = match zone.position {
+                Some(position) => game_to_display(map, map_rect, position.xz()),
+                None => area_center,
+            };
+            let Some(pos) = pos else {
+                continue;
+            };
+            if !map_rect.expand(50.0).contains(pos) {
+                continue;
+            }
+
+            // Five-pointed star
+            let points: Vec<egui::Pos2> = (0..10)
+                .map(|i| {
+                    let angle = std::f32::consts::PI * (i as f32 / 5.0 - 0.5);
+                    let radius = if i % 2 == 0 { size } else { size * 0.45 };
+                    pos + egui::vec2(angle.cos(), angle.sin()) * radius
+                })
+                .collect();
+            // Not convex, so filled as a fan around the center
+            let mut mesh = egui::Mesh::default();
+            mesh.colored_vertex(pos, colors::TASK_OBJECTIVE);
+            for point in &points {
+                mesh.colored_vertex(*point, colors::TASK_OBJECTIVE);
+            }
+            for i in 0..points.len() as u32 {
+                mesh.add_triangle(0, i + 1, (i + 1) % points.len() as u32 + 1);
+            }
+            painter.add(mesh);
+            painter.add(egui::Shape::closed_line(
+                points,
+                egui::Stroke::new(1.0, colors::LABEL_BACKGROUND),
+            ));
+            painter.text(
+                pos + egui::vec2(size + 4.0, 0.0),
+                egui::Align2::LEFT_CENTER,
+                &task.name,
+                egui::FontId::proportional(11.0),
+                colors::TASK_OBJECTIVE,
+            );
+        }
+    }
+}
+
+/// Connects the objectives of a task chain in order, with an arrow halfway
+/// along each leg. Each objective is placed at its first zone.
+fn draw_task_chain(ui: &mut egui::Ui, map_rect: egui::Rect, map: &Map, chain: &[&Task]) {
+    let painter = ui.painter();
+    let stroke = egui::Stroke::new(2.0, colors::TASK_OBJECTIVE.gamma_multiply(0.7));
+
+    let stops: Vec<egui::Pos2> = chain
+        .iter()
+        .flat_map(|task| &task.objectives)
+        .filter_map(|objective| objective.zones.iter().find_map(TaskZone::center))
+        .filter_map(|center| game_to_display(map, map_rect, center))
+        .collect();
+    for leg in stops.windows(2) {
+        let (from, to) = (leg[0], leg[1]);
+        if from.distance(to) < 1.0 {
+            continue;
+        }
+        let direction = (to - from).normalized();
+        painter.line_segment([from, to], stroke);
+        let tip = from + (to - from) * 0.5 + direction * 5.0;
+        let back = tip - direction * 10.0;
+        let side = direction.rot90() * 5.0;
+        painter.add(egui::Shape::convex_polygon(
+            vec![tip, back + side, back - side],
+            stroke.color,
+            egui::Stroke::NONE,
+        ));
+    }
+}
+
+/// Draws transits as their activation zone with an arrow marker, labeled
+/// with their description or destination.
+pub fn draw_transits(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    transits: &[Transit],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+    let size = (4.0 * zoom).clamp(6.0, 12.0);
+    let stroke = egui::Stroke::new(1.5, colors::TRANSIT);
+
+    for transit in transits {
+        let outline: Option<Vec<egui::Pos2>> = transit.outline.as_ref().and_then(|outline| {
+            outline
+                .iter()
+                .map(|p| game_to_display(map, map_rect, *p))
+                .collect()
+        });
+        let area_center = outline.map(|points| {
+            let fill = colors::TRANSIT.gamma_multiply(EXTRACT_AREA_OPACITY);
+            draw_area(painter, points, fill, stroke)
+        });
+        let pos = match transit.position {
+            Some(position) => game_to_display(map, map_rect, position.xz()),
+            None => area_center,
+        };
+        let Some(pos) = pos else {
+            continue;
+        };
+        if !map_rect.expand(50.0).contains(pos) {
+            continue;
+        }
+
+        painter.circle_filled(pos, size + 2.0, colors::LABEL_BACKGROUND);
+        painter.circle_stroke(pos, size, stroke);
+        painter.arrow(
+            pos - egui::vec2(size * 0.6, 0.0),
+            egui::vec2(size * 1.2, 0.0),
+            stroke,
+        );
+        painter.text(
+            pos + egui::vec2(size + 4.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            transit
+                .description
+                .as_deref()
+                .unwrap_or(&transit.destination),
+            egui::FontId::proportional(11.0),
+            colors::TRANSIT,
+        );
+    }
+}
+
+/// Draws stationary weapons as crosshairs labeled with the weapon's short name.
+pub fn draw_stationary_weapons(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    weapons: &[StationaryWeapon],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+    let radius = (3.0 * zoom).clamp(5.0, 9.0);
+    let stroke = egui::Stroke::new(1.5, colors::STATIONARY_WEAPON);
+
+    for weapon in weapons {
+        let Some(pos) = game_to_display(map, map_rect, weapon.position.xz()) else {
+            continue;
+        };
+        if !map_rect.expand(50.0).contains(pos) {
+            continue;
+        }
+
+        painter.circle_filled(pos, radius + 2.0, colors::LABEL_BACKGROUND);
+        painter.circle_stroke(pos, radius, stroke);
+        for dir in [egui::Vec2::X, egui::Vec2::Y, -egui::Vec2::X, -egui::Vec2::Y] {
+            painter.line_segment([pos + dir * radius * 0.4, pos + dir * radius * 1.4], stroke);
+        }
+        painter.text(
+            pos + egui::vec2(radius + 4.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            weapon.short_name.as_deref().unwrap_or(&weapon.name),
+            egui::FontId::proportional(11.0),
+            colors::STATIONARY_WEAPON,
+        );
+    }
+}
+
+/// Draws boss spawn positions as diamonds on the map.
+///
+/// Bosses sharing a spawn position are drawn once.
+pub fn draw_bosses(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    bosses: &[BossSpawn],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+    let size = (4.0 * zoom).clamp(5.0, 10.0);
+    let stroke = egui::Stroke::new(1.5, colors::BOSS_STROKE);
+
+    let mut drawn: Vec<egui::Pos2> = Vec::new();
+    for position in bosses.iter().flat_map(|boss| &boss.positions) {
+        let Some(pos) = game_to_display(map, map_rect, position.xz()) else {
+            continue;
+        };
+        if !map_rect.expand(20.0).contains(pos) || drawn.contains(&pos) {
+            continue;
+        }
+        drawn.push(pos);
+
+        let points = vec![
+            pos - egui::vec2(0.0, size),
+            pos + egui::vec2(size, 0.0),
+            pos + egui::vec2(0.0, size),
+            pos - egui::vec2(size, 0.0),
+        ];
+        painter.add(egui::Shape::convex_polygon(
+            points,
+            colors::BOSS_FILL,
+            stroke,
+        ));
+    }
+}
+
+/// Draws named spawn cluster markers on the map.
+pub fn draw_spawn_clusters(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    clusters: &[SpawnCluster],
+    zoom: f32,
+) {
+    let painter = ui.painter();
+
+    for cluster in clusters {
+        let Some(pos) = game_to_display(map, map_rect, cluster.position) else {
+            continue;
+        };
+
+        if !map_rect.expand(50.0).contains(pos) {
+            continue;
+        }
+
+        let radius = (6.0 * zoom).clamp(7.0, 14.0);
         painter.circle(
             pos,
             radius,
             colors::SPAWN_FILL,
             egui::Stroke::new(1.5, colors::SPAWN_STROKE),
         );
+        painter.text(
+            pos,
+            egui::Align2::CENTER_CENTER,
+            cluster.count.to_string(),
+            egui::FontId::proportional(radius * 1.1),
+            colors::SPAWN_STROKE,
+        );
+
+        let font_id = egui::FontId::proportional((5.0 * zoom).clamp(10.0, 16.0));
+        let text_pos = pos + egui::vec2(0.0, radius + 2.0);
+        painter.text(
+            text_pos + egui::vec2(1.0, 1.0),
+            egui::Align2::CENTER_TOP,
+            &cluster.name,
+            font_id.clone(),
+            colors::LABEL_SHADOW,
+        );
+        painter.text(
+            text_pos,
+            egui::Align2::CENTER_TOP,
+            &cluster.name,
+            font_id,
+            colors::LABEL_TEXT,
+        );
     }
 }
 
 /// Draws extraction point markers on the map.
+#[allow(clippy::too_many_arguments)]
 pub fn draw_extracts(
     ui: &mut egui::Ui,
     map_rect: egui::Rect,
@@ -114,30 +1723,54 @@ pub fn draw_extracts(
     extracts: &[Extract],
     zoom: f32,
     overlays: &OverlayVisibility,
+    icons: &IconPack,
+    style: &LabelStyle,
+    language: Option<&str>,
+    carried_items: &CarriedItems,
 ) {
-    let painter = ui.painter();
-
     for extract in extracts {
-        let faction = extract.faction.to_lowercase();
-        let (fill_color, stroke_color) = match faction.as_str() {
-            "pmc" if overlays.pmc_extracts => {
-                (colors::PMC_EXTRACT_FILL, colors::PMC_EXTRACT_STROKE)
-            }
-            "scav" if overlays.scav_extracts => {
-                (colors::SCAV_EXTRACT_FILL, colors::SCAV_EXTRACT_STROKE)
-            }
-            "shared" if overlays.shared_extracts => {
-                (colors::SHARED_EXTRACT_FILL, colors::SHARED_EXTRACT_STROKE)
-            }
+        let (fill_color, stroke_color, icon) = match extract.faction {
+            Faction::Pmc if overlays.pmc_extracts => (
+                colors::PMC_EXTRACT_FILL,
+                colors::PMC_EXTRACT_STROKE,
+                MarkerIcon::PmcExtract,
+            ),
+            Faction::Scav if overlays.scav_extracts => (
+                colors::SCAV_EXTRACT_FILL,
+                colors::SCAV_EXTRACT_STROKE,
+                MarkerIcon::ScavExtract,
+            ),
+            Faction::Shared if overlays.shared_extracts => (
+                colors::SHARED_EXTRACT_FILL,
+                colors::SHARED_EXTRACT_STROKE,
+                MarkerIcon::SharedExtract,
+            ),
             _ => continue,
         };
 
-        let Some(position) = extract.position else {
+        let Some(opacity) = carried_items.extract_opacity(extract) else {
             continue;
         };
+        let mut painter = ui.painter().clone();
+        painter.multiply_opacity(opacity);
+        let painter = &painter;
 
-        let game_pos = [position[0], position[2]];
-        let Some(pos) = game_to_display(map, map_rect, game_pos) else {
+        // The activation zone goes under the marker, which sits at its center
+        // when the extract has no position of its own (Extract::center)
+        let outline: Option<Vec<egui::Pos2>> = extract.outline.as_ref().and_then(|outline| {
+            outline
+                .iter()
+                .map(|p| game_to_display(map, map_rect, *p))
+                .collect()
+        });
+        if let Some(points) = outline {
+            let fill = fill_color.gamma_multiply(EXTRACT_AREA_OPACITY);
+            draw_area(painter, points, fill, egui::Stroke::new(1.5, fill_color));
+        }
+        let Some(pos) = extract
+            .center()
+            .and_then(|center| game_to_display(map, map_rect, center))
+        else {
             continue;
         };
 
@@ -148,43 +1781,43 @@ pub fn draw_extracts(
         let size = (12.0 * zoom).clamp(8.0, 32.0);
         let rect = egui::Rect::from_center_size(pos, egui::vec2(size, size));
 
-        painter.rect_filled(rect, 2.0, fill_color);
-        painter.rect_stroke(
-            rect,
-            2.0,
-            egui::Stroke::new(2.0, stroke_color),
-            egui::StrokeKind::Outside,
-        );
+        if !icons.paint(painter, icon, pos, size * 1.5) {
+            painter.rect_filled(rect, 2.0, fill_color);
+            painter.rect_stroke(
+                rect,
+                2.0,
+                egui::Stroke::new(2.0, stroke_color),
+                egui::StrokeKind::Outside,
+            );
+        }
 
         // Extract name label
         let font_size = (6.0 * zoom).clamp(9.0, 18.0);
-        let font_id = egui::FontId::proportional(font_size);
         let text_pos = pos + egui::vec2(0.0, -size / 2.0 - 4.0);
 
-        painter.text(
-            text_pos + egui::vec2(1.0, 1.0),
-            egui::Align2::CENTER_BOTTOM,
-            &extract.name,
-            font_id.clone(),
-            colors::EXTRACT_TEXT_SHADOW,
-        );
-        painter.text(
+        style.paint(
+            painter,
             text_pos,
             egui::Align2::CENTER_BOTTOM,
-            &extract.name,
-            font_id,
+            extract.localized_name(language),
+            font_size,
             egui::Color32::WHITE,
+            colors::EXTRACT_TEXT_SHADOW,
         );
     }
 }
 
 /// Draws the player position marker as a circle with a directional triangle on the map.
+///
+/// `label` (e.g. "PMC") is drawn below the marker.
 pub fn draw_player_marker(
     ui: &mut egui::Ui,
     map_rect: egui::Rect,
     map: &Map,
     player: &PlayerPosition,
     zoom: f32,
+    icons: &IconPack,
+    label: &str,
 ) {
     // Use x, z for 2D position (y is height in Tarkov)
     let game_pos = [player.position[0], player.position[2]];
@@ -206,16 +1839,17 @@ pub fn draw_player_marker(
 
     // The yaw from the screenshot represents the player's facing direction.
     // We need to adjust for the map's coordinate rotation to display correctly.
-    let coord_rotation = map.coordinate_rotation.unwrap_or(0.0) as f32;
-    let adjusted_yaw = player.yaw - coord_rotation.to_radians();
+    let adjusted_yaw = map_yaw(player.yaw, map.coordinate_rotation.unwrap_or(0.0));
 
-    // Draw the circle at player position
-    painter.circle(
-        pos,
-        circle_radius,
-        colors::PLAYER_MARKER_FILL,
-        egui::Stroke::new(2.0, colors::PLAYER_MARKER_STROKE),
-    );
+    // Draw the circle (or icon pack sprite) at player position
+    if !icons.paint(painter, MarkerIcon::Player, pos, circle_radius * 2.5) {
+        painter.circle(
+            pos,
+            circle_radius,
+            colors::PLAYER_MARKER_FILL,
+            egui::Stroke::new(2.0, colors::PLAYER_MARKER_STROKE),
+        );
+    }
 
     // Calculate triangle center position (outside the circle, in direction of yaw)
     let triangle_center = pos
@@ -245,4 +1879,50 @@ pub fn draw_player_marker(
         colors::PLAYER_MARKER_FILL,
         egui::Stroke::new(1.5, colors::PLAYER_MARKER_STROKE),
     ));
+
+    let font_id = egui::FontId::proportional((5.0 * zoom).clamp(9.0, 14.0));
+    let text_pos = pos + egui::vec2(0.0, circle_radius + 4.0);
+    painter.text(
+        text_pos + egui::vec2(1.0, 1.0),
+        egui::Align2::CENTER_TOP,
+        label,
+        font_id.clone(),
+        colors::EXTRACT_TEXT_SHADOW,
+    );
+    painter.text(
+        text_pos,
+        egui::Align2::CENTER_TOP,
+        label,
+        font_id,
+        egui::Color32::WHITE,
+    );
+}
+
+/// Draws a temporary pin marker (e.g. from clipboard coordinates).
+pub fn draw_temp_marker(
+    ui: &mut egui::Ui,
+    map_rect: egui::Rect,
+    map: &Map,
+    game_pos: [f64; 2],
+    zoom: f32,
+) {
+    let Some(pos) = game_to_display(map, map_rect, game_pos) else {
+        return;
+    };
+
+    let painter = ui.painter();
+    let radius = (6.0 * zoom).clamp(5.0, 12.0);
+    let tip = pos;
+    let head = pos - egui::vec2(0.0, radius * 2.0);
+
+    painter.line_segment(
+        [tip, head],
+        egui::Stroke::new(2.0, colors::TEMP_MARKER_STROKE),
+    );
+    painter.circle(
+        head,
+        radius,
+        colors::TEMP_MARKER_FILL,
+        egui::Stroke::new(1.5, colors::TEMP_MARKER_STROKE),
+    );
 }
@@ -0,0 +1,93 @@
+//! Keeps part of the map on screen while panning.
+//!
+//! Dragging stops once only the minimum share of the map is visible. When the
+//! viewport shrinks (window resize, sidebar shown) or the map is zoomed out
+//! and it ends up too far off-screen, it glides back into view.
+
+use crate::TarkovMapApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// Speed of the glide back into view; higher is faster.
+const RECENTER_RATE: f32 = 12.0;
+
+/// Pan limit settings.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanClamp {
+    pub enabled: bool,
+    /// Share of the map, in percent, that stays visible along each axis.
+    pub min_visible_percent: u32,
+}
+
+impl Default for PanClamp {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_visible_percent: 25,
+        }
+    }
+}
+
+impl PanClamp {
+    /// Clamps `pan` (the map center's offset from the viewport center) so the
+    /// minimum share of a `display_size` map stays within `viewport_size`.
+    fn clamp(
+        &self,
+        pan: egui::Vec2,
+        viewport_size: egui::Vec2,
+        display_size: egui::Vec2,
+    ) -> egui::Vec2 {
+        let share = self.min_visible_percent.min(100) as f32 / 100.0;
+        let limit = |viewport: f32, display: f32| {
+            ((viewport + display) / 2.0 - share * viewport.min(display)).max(0.0)
+        };
+        let limit = egui::vec2(
+            limit(viewport_size.x, display_size.x),
+            limit(viewport_size.y, display_size.y),
+        );
+        pan.clamp(-limit, limit)
+    }
+
+    /// Renders the pan limit controls.
+    pub fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, "Keep map on screen");
+        ui.add_enabled(
+            self.enabled,
+            egui::Slider::new(&mut self.min_visible_percent, 5..=100)
+                .text("visible")
+                .suffix("%"),
+        );
+    }
+}
+
+impl TarkovMapApp {
+    /// Applies the pan limit: immediately while dragging, smoothly otherwise.
+    pub fn clamp_pan(
+        &mut self,
+        ui: &egui::Ui,
+        dragging: bool,
+        viewport_size: egui::Vec2,
+        display_size: egui::Vec2,
+    ) {
+        if !self.pan_clamp.enabled {
+            return;
+        }
+
+        let target = self
+            .pan_clamp
+            .clamp(self.pan_offset, viewport_size, display_size);
+        if target == self.pan_offset {
+            return;
+        }
+        if dragging || (target - self.pan_offset).length() < 0.5 {
+            self.pan_offset = target;
+            return;
+        }
+
+        let dt = ui.input(|i| i.stable_dt).min(0.1);
+        let t = 1.0 - (-RECENTER_RATE * dt).exp();
+        self.pan_offset += (target - self.pan_offset) * t;
+        ui.ctx().request_repaint();
+    }
+}
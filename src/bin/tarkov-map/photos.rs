@@ -0,0 +1,199 @@
+//! Reference photos attached to map markers, e.g. a picture of an extract entrance.
+//!
+//! Attached images are copied into `<data dir>/tarkov-map/photos/<map>/`, and
+//! their file names are saved with the app settings, keyed like marker notes.
+//! Images dropped onto the window go to the marker open in the Marker window,
+//! or else to the extract selected in the extract list.
+
+use crate::notes::MarkerNotes;
+use crate::usage_stats::Feature;
+use crate::{APP_ID, TarkovMapApp};
+use chrono::Utc;
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File extensions accepted as photos.
+const PHOTO_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+/// Height of photo thumbnails in the detail panel.
+const THUMBNAIL_HEIGHT: f32 = 110.0;
+
+/// Errors that can occur when attaching a photo.
+#[derive(Error, Debug)]
+pub enum PhotoError {
+    #[error("no data directory available")]
+    NoDataDir,
+    #[error("failed to copy photo '{path}': {source}")]
+    Copy {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+/// Photo file names keyed by map normalized name, then by marker key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MarkerPhotos(HashMap<String, HashMap<String, Vec<String>>>);
+
+/// Returns the folder photos for `map` are stored in.
+fn photos_dir(map: &str) -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("photos").join(map))
+}
+
+/// Returns `true` if `path` has an image extension accepted as a photo.
+fn is_photo(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| PHOTO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+impl MarkerPhotos {
+    /// Returns the file names of the photos attached to a marker.
+    pub fn get(&self, map: &str, key: &str) -> &[String] {
+        self.0
+            .get(map)
+            .and_then(|photos| photos.get(key))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the full path of an attached photo.
+    pub fn path(map: &str, file_name: &str) -> Option<PathBuf> {
+        Some(photos_dir(map)?.join(file_name))
+    }
+
+    /// Copies `source` into the photos folder and attaches it to a marker.
+    pub fn attach(&mut self, map: &str, key: &str, source: &Path) -> Result<(), PhotoError> {
+        let dir = photos_dir(map).ok_or(PhotoError::NoDataDir)?;
+        let copy_error = |source_err| PhotoError::Copy {
+            path: source.display().to_string(),
+            source: source_err,
+        };
+        fs::create_dir_all(&dir).map_err(copy_error)?;
+
+        let stem: String = key
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        let extension = source
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("png")
+            .to_lowercase();
+        let file_name = format!(
+            "{stem}-{}.{extension}",
+            Utc::now().format("%Y%m%d-%H%M%S%3f")
+        );
+        fs::copy(source, dir.join(&file_name)).map_err(copy_error)?;
+
+        self.0
+            .entry(map.to_owned())
+            .or_default()
+            .entry(key.to_owned())
+            .or_default()
+            .push(file_name);
+        Ok(())
+    }
+
+    /// Detaches a photo from a marker and deletes its copy.
+    pub fn remove(&mut self, map: &str, key: &str, file_name: &str) {
+        let Some(photos) = self.0.get_mut(map) else {
+            return;
+        };
+        if let Some(files) = photos.get_mut(key) {
+            files.retain(|file| file != file_name);
+            if files.is_empty() {
+                photos.remove(key);
+            }
+        }
+        if photos.is_empty() {
+            self.0.remove(map);
+        }
+
+        if let Some(path) = Self::path(map, file_name)
+            && let Err(err) = fs::remove_file(&path)
+        {
+            log::warn!("Failed to delete photo {}: {err}", path.display());
+        }
+    }
+}
+
+impl TarkovMapApp {
+    /// Attaches images dropped onto the window to the marker open in the Marker
+    /// window, or else to the extract selected in the list.
+    pub fn handle_dropped_photos(&mut self, ctx: &egui::Context) {
+        let dropped: Vec<PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .filter(|path| is_photo(path))
+                .collect()
+        });
+        if dropped.is_empty() {
+            return;
+        }
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let marker_key = self
+            .selected_marker
+            .and_then(|hit| MarkerNotes::hit_key(map, hit));
+        let extract_key = self
+            .selected_extract
+            .as_deref()
+            .map(MarkerNotes::extract_key);
+        let Some(key) = marker_key.or(extract_key) else {
+            return;
+        };
+        let map = map.normalized_name.clone();
+
+        for path in dropped {
+            if let Err(err) = self.photos.attach(&map, &key, &path) {
+                log::warn!("{err}");
+                self.toasts.add(Toast {
+                    kind: ToastKind::Error,
+                    text: err.to_string().into(),
+                    options: ToastOptions::default().duration_in_seconds(6.0),
+                    ..Default::default()
+                });
+            }
+        }
+        self.usage.feature_used(Feature::Photos);
+    }
+
+    /// Renders the photos attached to a marker, with controls to open and remove them.
+    pub fn show_marker_photos(&mut self, ui: &mut egui::Ui, map: &str, key: &str) {
+        let mut removed = None;
+        for file_name in self.photos.get(map, key) {
+            let Some(path) = MarkerPhotos::path(map, file_name) else {
+                continue;
+            };
+            ui.horizontal(|ui| {
+                let response = ui
+                    .add(
+                        egui::Image::new(format!("file://{}", path.display()))
+                            .max_height(THUMBNAIL_HEIGHT)
+                            .max_width(ui.available_width() - 24.0)
+                            .sense(egui::Sense::click()),
+                    )
+                    .on_hover_text("Open")
+                    .on_hover_cursor(egui::CursorIcon::PointingHand);
+                if response.clicked() {
+                    let _ = open::that(&path);
+                }
+                if ui.small_button("✖").on_hover_text("Remove photo").clicked() {
+                    removed = Some(file_name.clone());
+                }
+            });
+        }
+        if let Some(file_name) = removed {
+            self.photos.remove(map, key, &file_name);
+        }
+        ui.weak("Drop an image onto the window to attach it");
+    }
+}
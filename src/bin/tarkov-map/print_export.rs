@@ -0,0 +1,588 @@
+//! Print-resolution export of the whole map.
+//!
+//! File > Print Export renders the selected map with the enabled overlays onto
+//! an A4 or A3 page at 300 DPI, with a title, legend and scale bar, and saves
+//! it as a PNG in `<data dir>/tarkov-map/exports/`. The page is composed as
+//! SVG in millimeters and rasterized with resvg on a background thread.
+//!
+//! Spawns are always drawn individually and elevation shading is left out, as
+//! neither reads well on paper.
+
+use crate::assets::{ImageLoadError, read_image_bytes};
+use crate::colors;
+use crate::constants::EXTRACT_AREA_OPACITY;
+use crate::overlays::{OverlayVisibility, SpawnKind};
+use crate::screenshot_watcher::PlayerPosition;
+use crate::{APP_ID, TarkovMapApp};
+use base64::Engine;
+use eframe::egui::{self, Color32};
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use resvg::tiny_skia::Pixmap;
+use resvg::usvg::{Options, Transform, Tree, fontdb};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, mpsc};
+use std::thread;
+use tarkov_map::rotation::map_yaw;
+use tarkov_map::{Faction, Hazard, Map, Position2};
+use thiserror::Error;
+
+const PRINT_DPI: f32 = 300.0;
+const MM_PER_INCH: f32 = 25.4;
+
+/// Page margin in millimeters.
+const MARGIN: f32 = 10.0;
+/// Height of the title above the map, in millimeters.
+const TITLE_HEIGHT: f32 = 10.0;
+/// Height of the legend and scale bar below the map, in millimeters.
+const FOOTER_HEIGHT: f32 = 14.0;
+/// Longest scale bar, in millimeters.
+const SCALE_BAR_MAX_WIDTH: f32 = 50.0;
+/// Scale bar lengths to pick from, in meters.
+const SCALE_BAR_STEPS: [f64; 9] = [10.0, 20.0, 25.0, 50.0, 100.0, 200.0, 250.0, 500.0, 1000.0];
+
+/// Errors that can occur during a print export.
+#[derive(Error, Debug)]
+pub enum PrintExportError {
+    #[error("no data directory available")]
+    NoDataDir,
+    #[error(transparent)]
+    Image(#[from] ImageLoadError),
+    #[error("failed to compose page: {0}")]
+    Svg(String),
+    #[error("page too large to render")]
+    PixmapCreation,
+    #[error("failed to save PNG: {0}")]
+    Save(String),
+    #[error("failed to create exports folder: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("render thread stopped")]
+    Disconnected,
+}
+
+/// Paper size of a print export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperSize {
+    A4,
+    A3,
+}
+
+impl PaperSize {
+    pub const ALL: [Self; 2] = [Self::A4, Self::A3];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::A4 => "A4",
+            Self::A3 => "A3",
+        }
+    }
+
+    /// Portrait page size in millimeters.
+    fn size_mm(self) -> [f32; 2] {
+        match self {
+            Self::A4 => [210.0, 297.0],
+            Self::A3 => [297.0, 420.0],
+        }
+    }
+}
+
+/// Formats a color as an SVG hex color, ignoring alpha.
+fn hex(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// Escapes text for use in SVG content and attributes.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Page area the map image is drawn into, in millimeters.
+#[derive(Clone, Copy)]
+struct MapArea {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl MapArea {
+    /// Page position of a game position, if the map can project it.
+    fn project(&self, map: &Map, game_pos: impl Into<Position2>) -> Option<(f32, f32)> {
+        let [frac_x, frac_y] = map.project(game_pos)?;
+        Some((
+            self.x + frac_x as f32 * self.width,
+            self.y + frac_y as f32 * self.height,
+        ))
+    }
+
+    /// Page millimeters per game meter, if the map has bounds.
+    fn mm_per_meter(&self, map: &Map) -> Option<f64> {
+        map.pixels_per_meter(self.width)
+    }
+}
+
+/// Text with a dark outline, readable on any map background.
+fn outlined_text(svg: &mut String, x: f32, y: f32, size: f32, fill: Color32, text: &str) {
+    let _ = write!(
+        svg,
+        r#"<text x="{x}" y="{y}" font-size="{size}" fill="{}" stroke="black" stroke-width="{}" paint-order="stroke" text-anchor="middle">{}</text>"#,
+        hex(fill),
+        size * 0.12,
+        escape(text)
+    );
+}
+
+fn square(svg: &mut String, x: f32, y: f32, size: f32, fill: Color32, stroke: Color32) {
+    let _ = write!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{size}" height="{size}" rx="{}" fill="{}" stroke="{}" stroke-width="0.3"/>"#,
+        x - size / 2.0,
+        y - size / 2.0,
+        size * 0.15,
+        hex(fill),
+        hex(stroke)
+    );
+}
+
+fn circle(svg: &mut String, x: f32, y: f32, radius: f32, fill: Color32, stroke: Color32) {
+    let _ = write!(
+        svg,
+        r#"<circle cx="{x}" cy="{y}" r="{radius}" fill="{}" stroke="{}" stroke-width="0.3"/>"#,
+        hex(fill),
+        hex(stroke)
+    );
+}
+
+/// The player marker: a circle with a triangle pointing in the facing direction.
+fn player_marker(svg: &mut String, x: f32, y: f32, degrees: f32) {
+    circle(
+        svg,
+        x,
+        y,
+        1.6,
+        colors::PLAYER_MARKER_FILL,
+        colors::PLAYER_MARKER_STROKE,
+    );
+    let _ = write!(
+        svg,
+        r#"<path d="M 0 -4.2 L -1 -2.4 L 1 -2.4 Z" transform="translate({x} {y}) rotate({degrees})" fill="{}" stroke="{}" stroke-width="0.3"/>"#,
+        hex(colors::PLAYER_MARKER_FILL),
+        hex(colors::PLAYER_MARKER_STROKE)
+    );
+}
+
+fn extract_colors(faction: &Faction, visibility: &OverlayVisibility) -> Option<(Color32, Color32)> {
+    match faction {
+        Faction::Pmc if visibility.pmc_extracts => {
+            Some((colors::PMC_EXTRACT_FILL, colors::PMC_EXTRACT_STROKE))
+        }
+        Faction::Scav if visibility.scav_extracts => {
+            Some((colors::SCAV_EXTRACT_FILL, colors::SCAV_EXTRACT_STROKE))
+        }
+        Faction::Shared if visibility.shared_extracts => {
+            Some((colors::SHARED_EXTRACT_FILL, colors::SHARED_EXTRACT_STROKE))
+        }
+        _ => None,
+    }
+}
+
+/// Everything needed to render a print export, detached from the app.
+struct PrintJob {
+    map: Map,
+    image: Vec<u8>,
+    visibility: OverlayVisibility,
+    player: Option<PlayerPosition>,
+    paper: PaperSize,
+}
+
+impl PrintJob {
+    /// Composes the page as SVG, in millimeters.
+    fn compose(&self) -> (String, [f32; 2]) {
+        let map = &self.map;
+        let [short, long] = self.paper.size_mm();
+        let landscape = map.logical_size[0] > map.logical_size[1];
+        let [page_w, page_h] = if landscape {
+            [long, short]
+        } else {
+            [short, long]
+        };
+
+        // Fit the map between title and footer, keeping its aspect ratio
+        let avail_w = page_w - 2.0 * MARGIN;
+        let avail_h = page_h - 2.0 * MARGIN - TITLE_HEIGHT - FOOTER_HEIGHT;
+        let scale = (avail_w / map.logical_size[0]).min(avail_h / map.logical_size[1]);
+        let area = MapArea {
+            width: map.logical_size[0] * scale,
+            height: map.logical_size[1] * scale,
+            x: (page_w - map.logical_size[0] * scale) / 2.0,
+            y: MARGIN + TITLE_HEIGHT + (avail_h - map.logical_size[1] * scale) / 2.0,
+        };
+
+        let mime = match map.image_path.rsplit('.').next() {
+            Some("jpg" | "jpeg") => "image/jpeg",
+            Some("webp") => "image/webp",
+            _ => "image/png",
+        };
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{page_w}" height="{page_h}" viewBox="0 0 {page_w} {page_h}" font-family="sans-serif">"#
+        );
+        let _ = write!(
+            svg,
+            r#"<rect width="{page_w}" height="{page_h}" fill="white"/><text x="{}" y="{}" font-size="7" font-weight="bold" text-anchor="middle">{}</text>"#,
+            page_w / 2.0,
+            MARGIN + 6.0,
+            escape(&map.name)
+        );
+        let _ = write!(
+            svg,
+            r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none" href="data:{mime};base64,{}"/>"#,
+            area.x,
+            area.y,
+            area.width,
+            area.height,
+            base64::engine::general_purpose::STANDARD.encode(&self.image)
+        );
+
+        self.draw_overlays(&mut svg, area);
+        self.draw_legend(&mut svg, MARGIN, page_h - MARGIN - FOOTER_HEIGHT / 2.0);
+        if let Some(mm_per_meter) = area.mm_per_meter(map) {
+            draw_scale_bar(
+                &mut svg,
+                page_w - MARGIN,
+                page_h - MARGIN - FOOTER_HEIGHT / 2.0,
+                mm_per_meter,
+            );
+        }
+
+        svg.push_str("</svg>");
+        (svg, [page_w, page_h])
+    }
+
+    fn draw_overlays(&self, svg: &mut String, area: MapArea) {
+        let map = &self.map;
+        let visibility = &self.visibility;
+
+        if visibility.hazards {
+            for hazard in map.hazards.iter().flatten() {
+                self.draw_hazard(svg, area, hazard);
+            }
+        }
+
+        if visibility.labels {
+            for label in map.labels.iter().flatten() {
+                let Some((x, y)) = area.project(map, label.position) else {
+                    continue;
+                };
+                let size = (label.size.unwrap_or(40) as f32 * 0.05).clamp(2.0, 5.0);
+                let rotation = label.rotation.unwrap_or(0.0);
+                let _ = write!(svg, r#"<g transform="rotate({rotation} {x} {y})">"#);
+                let lines: Vec<_> = label.text.lines().collect();
+                let top = y - (lines.len() as f32 - 1.0) * size / 2.0 + size * 0.35;
+                for (i, line) in lines.iter().enumerate() {
+                    outlined_text(svg, x, top + i as f32 * size, size, Color32::WHITE, line);
+                }
+                svg.push_str("</g>");
+            }
+        }
+
+        for kind in [SpawnKind::Ai, SpawnKind::Scav, SpawnKind::Pmc] {
+            if !visibility.shows_spawns(kind) {
+                continue;
+            }
+            let (fill, stroke) = kind.colors();
+            for spawn in map.spawns.iter().flatten().filter(|s| kind.matches(s)) {
+                if let Some((x, y)) = area.project(map, spawn.position.xz()) {
+                    circle(svg, x, y, 1.0, fill, stroke);
+                }
+            }
+        }
+
+        for extract in map.extracts.iter().flatten() {
+            let Some((fill, stroke)) = extract_colors(&extract.faction, visibility) else {
+                continue;
+            };
+            let outline: Option<Vec<(f32, f32)>> = extract
+                .outline
+                .as_ref()
+                .and_then(|outline| outline.iter().map(|p| area.project(map, *p)).collect());
+            if let Some(points) = outline {
+                let coords: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+                let _ = write!(
+                    svg,
+                    r#"<polygon points="{}" fill="{1}" fill-opacity="{EXTRACT_AREA_OPACITY}" stroke="{1}" stroke-width="0.3"/>"#,
+                    coords.join(" "),
+                    hex(fill)
+                );
+            }
+            let Some((x, y)) = extract
+                .center()
+                .and_then(|center| area.project(map, center))
+            else {
+                continue;
+            };
+            square(svg, x, y, 2.6, fill, stroke);
+            outlined_text(svg, x, y - 2.2, 2.4, fill, &extract.name);
+        }
+
+        if visibility.player_marker
+            && let Some(player) = &self.player
+            && let Some((x, y)) = area.project(map, [player.position[0], player.position[2]])
+        {
+            let yaw = map_yaw(player.yaw, map.coordinate_rotation.unwrap_or(0.0));
+            player_marker(svg, x, y, yaw.to_degrees());
+        }
+    }
+
+    /// Draws a hazard as a translucent area, or a circle when it has no outline.
+    fn draw_hazard(&self, svg: &mut String, area: MapArea, hazard: &Hazard) {
+        let map = &self.map;
+        let style = format!(
+            r#"fill="{0}" fill-opacity="0.35" stroke="{0}" stroke-width="0.4""#,
+            hex(colors::HAZARD_STROKE)
+        );
+        let outline: Option<Vec<(f32, f32)>> = hazard
+            .outline
+            .as_ref()
+            .and_then(|outline| outline.iter().map(|p| area.project(map, *p)).collect());
+        let center = match outline {
+            Some(points) => {
+                let coords: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+                let _ = write!(svg, r#"<polygon points="{}" {style}/>"#, coords.join(" "));
+                let (sum_x, sum_y) = points
+                    .iter()
+                    .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+                Some((sum_x / points.len() as f32, sum_y / points.len() as f32))
+            }
+            None => hazard
+                .position
+                .and_then(|position| area.project(map, position.xz()))
+                .inspect(|(x, y)| {
+                    let _ = write!(svg, r#"<circle cx="{x}" cy="{y}" r="3" {style}/>"#);
+                }),
+        };
+        if let Some((x, y)) = center {
+            outlined_text(svg, x, y + 0.8, 2.4, colors::HAZARD_STROKE, &hazard.name);
+        }
+    }
+
+    /// Draws a row of legend entries for the enabled marker types, starting at `x`.
+    fn draw_legend(&self, svg: &mut String, mut x: f32, y: f32) {
+        let visibility = &self.visibility;
+        let mut entries: Vec<(&str, Color32, Color32, bool)> = Vec::new();
+        if visibility.hazards && self.map.hazards.as_ref().is_some_and(|h| !h.is_empty()) {
+            entries.push(("Hazard", colors::HAZARD_STROKE, colors::HAZARD_STROKE, true));
+        }
+        let spawn_entries = [
+            (SpawnKind::Pmc, "PMC spawn"),
+            (SpawnKind::Scav, "Scav spawn"),
+            (SpawnKind::Ai, "AI spawn"),
+        ];
+        for (kind, text) in spawn_entries {
+            if visibility.shows_spawns(kind) {
+                let (fill, stroke) = kind.colors();
+                entries.push((text, fill, stroke, false));
+            }
+        }
+        let factions = [
+            (Faction::Pmc, "PMC extract"),
+            (Faction::Scav, "Scav extract"),
+            (Faction::Shared, "Shared extract"),
+        ];
+        for (faction, text) in factions {
+            if let Some((fill, stroke)) = extract_colors(&faction, visibility) {
+                entries.push((text, fill, stroke, true));
+            }
+        }
+        if visibility.player_marker && self.player.is_some() {
+            entries.push((
+                "Player",
+                colors::PLAYER_MARKER_FILL,
+                colors::PLAYER_MARKER_STROKE,
+                false,
+            ));
+        }
+
+        for (text, fill, stroke, is_square) in entries {
+            if is_square {
+                square(svg, x + 1.2, y, 2.4, fill, stroke);
+            } else {
+                circle(svg, x + 1.2, y, 1.1, fill, stroke);
+            }
+            let _ = write!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="3.2">{}</text>"#,
+                x + 3.5,
+                y + 1.1,
+                escape(text)
+            );
+            x += 8.0 + text.len() as f32 * 1.6;
+        }
+    }
+
+    /// Renders the page at print resolution and saves it as a PNG.
+    fn run(self) -> Result<PathBuf, PrintExportError> {
+        let dir = dirs::data_dir()
+            .ok_or(PrintExportError::NoDataDir)?
+            .join(APP_ID)
+            .join("exports");
+        let path = dir.join(format!(
+            "{}-{}.png",
+            self.map.normalized_name.replace('/', "-"),
+            self.paper.label().to_lowercase()
+        ));
+
+        let (svg, [page_w, page_h]) = self.compose();
+        let mut options = Options::default();
+        load_fonts(options.fontdb_mut());
+        let tree =
+            Tree::from_str(&svg, &options).map_err(|e| PrintExportError::Svg(e.to_string()))?;
+
+        let px_per_mm = PRINT_DPI / MM_PER_INCH;
+        let mut pixmap = Pixmap::new(
+            (page_w * px_per_mm).round() as u32,
+            (page_h * px_per_mm).round() as u32,
+        )
+        .ok_or(PrintExportError::PixmapCreation)?;
+        resvg::render(
+            &tree,
+            Transform::from_scale(px_per_mm, px_per_mm),
+            &mut pixmap.as_mut(),
+        );
+
+        fs::create_dir_all(&dir)?;
+        pixmap
+            .save_png(&path)
+            .map_err(|e| PrintExportError::Save(e.to_string()))?;
+        Ok(path)
+    }
+}
+
+/// Loads the fonts egui ships with, so text renders the same everywhere, plus
+/// system fonts for glyphs they lack.
+fn load_fonts(fontdb: &mut fontdb::Database) {
+    fontdb.load_system_fonts();
+    let definitions = egui::FontDefinitions::default();
+    let Some(proportional) = definitions.font_data.get("Ubuntu-Light") else {
+        return;
+    };
+    let ids = fontdb.load_font_source(fontdb::Source::Binary(Arc::new(proportional.font.to_vec())));
+    let family = ids
+        .first()
+        .and_then(|id| fontdb.face(*id))
+        .and_then(|face| face.families.first())
+        .map(|(family, _)| family.clone());
+    if let Some(family) = family {
+        fontdb.set_sans_serif_family(family);
+    }
+}
+
+/// Draws a scale bar ending at `right`, as long as a round number of meters.
+fn draw_scale_bar(svg: &mut String, right: f32, y: f32, mm_per_meter: f64) {
+    let Some(meters) = SCALE_BAR_STEPS
+        .into_iter()
+        .rev()
+        .find(|meters| meters * mm_per_meter <= f64::from(SCALE_BAR_MAX_WIDTH))
+    else {
+        return;
+    };
+    let width = (meters * mm_per_meter) as f32;
+    let left = right - width;
+    let _ = write!(
+        svg,
+        r#"<rect x="{left}" y="{}" width="{}" height="1.5" fill="black"/><rect x="{}" y="{}" width="{}" height="1.5" fill="white" stroke="black" stroke-width="0.3"/>"#,
+        y,
+        width / 2.0,
+        left + width / 2.0,
+        y,
+        width / 2.0
+    );
+    let _ = write!(
+        svg,
+        r#"<text x="{left}" y="{}" font-size="3" text-anchor="middle">0</text><text x="{right}" y="{}" font-size="3" text-anchor="middle">{meters} m</text>"#,
+        y - 1.2,
+        y - 1.2
+    );
+}
+
+impl TarkovMapApp {
+    /// Starts rendering the selected map for printing on a background thread.
+    pub fn start_print_export(&mut self, ctx: &egui::Context, paper: PaperSize) {
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let image = match read_image_bytes(&map.image_path) {
+            Ok(image) => image.into_owned(),
+            Err(err) => {
+                self.toasts.add(Toast {
+                    kind: ToastKind::Error,
+                    text: format!("Print export failed: {err}").into(),
+                    options: ToastOptions::default().duration_in_seconds(6.0),
+                    ..Default::default()
+                });
+                return;
+            }
+        };
+        let job = PrintJob {
+            map: map.clone(),
+            image,
+            visibility: self.overlays,
+            player: self.player_position,
+            paper,
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let _ = tx.send(job.run());
+            ctx.request_repaint();
+        });
+        self.print_export_rx = Some(rx);
+        self.toasts.add(Toast {
+            kind: ToastKind::Info,
+            text: format!("Rendering {} for printing...", paper.label()).into(),
+            options: ToastOptions::default().duration_in_seconds(3.0),
+            ..Default::default()
+        });
+    }
+
+    /// Returns `true` while a print export is rendering.
+    pub fn print_export_running(&self) -> bool {
+        self.print_export_rx.is_some()
+    }
+
+    /// Reports a finished print export and opens its folder.
+    pub fn poll_print_export(&mut self) {
+        let Some(rx) = &self.print_export_rx else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err(PrintExportError::Disconnected),
+        };
+        self.print_export_rx = None;
+
+        match result {
+            Ok(path) => {
+                log::info!("Saved print export to {}", path.display());
+                if let Some(dir) = path.parent() {
+                    let _ = open::that(dir);
+                }
+            }
+            Err(err) => {
+                self.toasts.add(Toast {
+                    kind: ToastKind::Error,
+                    text: format!("Print export failed: {err}").into(),
+                    options: ToastOptions::default().duration_in_seconds(6.0),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Per-map raid counter and history window.
+
+use crate::TarkovMapApp;
+use chrono::Local;
+use eframe::egui;
+
+impl TarkovMapApp {
+    /// Records a player position into the current raid session.
+    pub fn record_session_position(&mut self, position: [f64; 3]) {
+        let Some(map) = self.selected_map().map(|map| map.normalized_name.clone()) else {
+            return;
+        };
+        self.personal_heatmap_stale = true;
+        match self
+            .session_recorder
+            .record(&map, position, &mut self.raid_history)
+        {
+            Ok(true) if self.raid_checklist.reset_on_raid => self.raid_checklist.reset(),
+            Ok(_) => {}
+            Err(err) => log::warn!("Failed to record raid session: {err}"),
+        }
+    }
+
+    /// Renders the raid count for the selected map, with a link to the history.
+    pub fn show_raid_counter(&mut self, ui: &mut egui::Ui) {
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        let count = self.raid_history.count(&map.normalized_name);
+        let last = self
+            .raid_history
+            .visits()
+            .iter()
+            .rfind(|visit| visit.map == map.normalized_name)
+            .map(|visit| visit.started_at.with_timezone(&Local));
+
+        ui.horizontal(|ui| {
+            let text = match last {
+                Some(last) => {
+                    let raids = if count == 1 { "raid" } else { "raids" };
+                    format!("{count} {raids}, last {}", last.format("%Y-%m-%d"))
+                }
+                None => "No raids recorded".to_owned(),
+            };
+            ui.weak(text);
+            if ui.small_button("History").clicked() {
+                self.raid_history_open = true;
+            }
+        });
+    }
+
+    /// Renders the raid history window, newest first.
+    pub fn show_raid_history_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.raid_history_open;
+        egui::Window::new("Raid History")
+            .open(&mut open)
+            .default_width(280.0)
+            .show(ctx, |ui| {
+                let visits = self.raid_history.visits();
+                if visits.is_empty() {
+                    ui.label("Raids are recorded from screenshot positions.");
+                    return;
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("raid_history")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for visit in visits.iter().rev() {
+                                let name = self
+                                    .maps
+                                    .iter()
+                                    .find(|map| map.normalized_name == visit.map)
+                                    .map_or(visit.map.as_str(), |map| map.name.as_str());
+                                ui.label(name);
+                                ui.label(
+                                    visit
+                                        .started_at
+                                        .with_timezone(&Local)
+                                        .format("%Y-%m-%d %H:%M")
+                                        .to_string(),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        self.raid_history_open = open;
+    }
+}
@@ -0,0 +1,72 @@
+//! PMC/Scav raid switch that applies an overlay preset for the raid type.
+
+use crate::TarkovMapApp;
+use crate::overlays::OverlayVisibility;
+use crate::usage_stats::Feature;
+use eframe::egui;
+
+/// Whether the current raid is played as a PMC or a Scav.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RaidMode {
+    #[default]
+    Pmc,
+    Scav,
+}
+
+impl RaidMode {
+    /// Short name, also used to label the player marker.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pmc => "PMC",
+            Self::Scav => "Scav",
+        }
+    }
+
+    fn toggled(self) -> Self {
+        match self {
+            Self::Pmc => Self::Scav,
+            Self::Scav => Self::Pmc,
+        }
+    }
+
+    /// Shows the extracts and player spawns of this raid type.
+    fn apply_preset(self, overlays: &mut OverlayVisibility) {
+        let pmc = self == Self::Pmc;
+        overlays.spawns = pmc;
+        overlays.scav_spawns = !pmc;
+        overlays.pmc_extracts = pmc;
+        overlays.scav_extracts = !pmc;
+        overlays.shared_extracts = true;
+    }
+}
+
+impl TarkovMapApp {
+    /// Switches the raid mode and applies its overlay preset.
+    pub fn set_raid_mode(&mut self, mode: RaidMode) {
+        if mode != self.raid_mode {
+            self.usage.feature_used(Feature::RaidModeSwitch);
+        }
+        self.raid_mode = mode;
+        mode.apply_preset(&mut self.overlays);
+    }
+
+    pub fn toggle_raid_mode(&mut self) {
+        self.set_raid_mode(self.raid_mode.toggled());
+    }
+
+    /// Renders the PMC/Scav raid switch for the title bar.
+    pub fn show_raid_mode_switch(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(8.0);
+        for mode in [RaidMode::Pmc, RaidMode::Scav] {
+            let text = egui::RichText::new(format!("{} raid", mode.label())).strong();
+            if ui
+                .selectable_label(self.raid_mode == mode, text)
+                .on_hover_text("Switch raid type (R)")
+                .clicked()
+                && self.raid_mode != mode
+            {
+                self.set_raid_mode(mode);
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Raid plans: a named loadout note and key list per map.
+//!
+//! One plan per map can be active. While it is, its loadout and keys show in
+//! their own sidebar section, next to the map and extracts they were planned
+//! for.
+
+use crate::TarkovMapApp;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use tarkov_map::Map;
+
+/// What to bring on a raid on one map.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RaidPlan {
+    pub name: String,
+    /// Normalized name of the map the plan is for.
+    pub map: String,
+    /// Free-form weapon build and loadout notes.
+    pub loadout: String,
+    /// Keys to bring, by name.
+    pub keys: Vec<String>,
+}
+
+/// All raid plans and the active plan of each map.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RaidPlans {
+    plans: Vec<RaidPlan>,
+    /// Name of the active plan, by normalized map name.
+    active: HashMap<String, String>,
+    /// Name typed into the "new plan" field.
+    #[serde(skip)]
+    new_plan: String,
+    /// Key name typed into the "add key" field.
+    #[serde(skip)]
+    new_key: String,
+}
+
+impl RaidPlans {
+    fn active_index(&self, map: &str) -> Option<usize> {
+        let name = self.active.get(map)?;
+        self.plans
+            .iter()
+            .position(|plan| plan.map == map && plan.name == *name)
+    }
+
+    /// Returns the active plan for `map`, if any.
+    pub fn active_plan(&self, map: &str) -> Option<&RaidPlan> {
+        self.plans.get(self.active_index(map)?)
+    }
+
+    /// Renders the plan picker and the "new plan" row for `map`.
+    pub fn picker_ui(&mut self, ui: &mut egui::Ui, map: &Map) {
+        let map_name = &map.normalized_name;
+        let selected = self.active.get(map_name).cloned();
+
+        let mut choice = selected.clone();
+        egui::ComboBox::from_id_salt("raid_plan")
+            .width(ui.available_width())
+            .selected_text(selected.as_deref().unwrap_or("No plan"))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut choice, None, "No plan");
+                for plan in self.plans.iter().filter(|plan| plan.map == *map_name) {
+                    ui.selectable_value(&mut choice, Some(plan.name.clone()), &plan.name);
+                }
+            });
+        if choice != selected {
+            match choice {
+                Some(name) => self.active.insert(map_name.clone(), name),
+                None => self.active.remove(map_name),
+            };
+        }
+
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.new_plan)
+                    .hint_text("Plan name")
+                    .desired_width(110.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let name = self.new_plan.trim().to_owned();
+            let exists = self
+                .plans
+                .iter()
+                .any(|plan| plan.map == *map_name && plan.name == name);
+            if (ui.button("Add").clicked() || submitted) && !name.is_empty() && !exists {
+                self.plans.push(RaidPlan {
+                    name: name.clone(),
+                    map: map_name.clone(),
+                    ..Default::default()
+                });
+                self.active.insert(map_name.clone(), name);
+                self.new_plan.clear();
+            }
+        });
+    }
+
+    /// Renders the loadout note and key list of the active plan for `map`.
+    ///
+    /// Keys of the map's locks are offered as suggestions.
+    pub fn plan_ui(&mut self, ui: &mut egui::Ui, map: &Map) {
+        let Some(idx) = self.active_index(&map.normalized_name) else {
+            return;
+        };
+        let plan = &mut self.plans[idx];
+
+        ui.add(
+            egui::TextEdit::multiline(&mut plan.loadout)
+                .desired_rows(3)
+                .desired_width(f32::INFINITY)
+                .hint_text("e.g. M4A1, M995, Thor, 6B47"),
+        );
+
+        ui.add_space(4.0);
+        ui.label("Keys");
+        let mut remove = None;
+        for (key_idx, key) in plan.keys.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::Label::new(key).truncate());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                        remove = Some(key_idx);
+                    }
+                });
+            });
+        }
+        if let Some(key_idx) = remove {
+            plan.keys.remove(key_idx);
+        }
+
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.new_key)
+                    .hint_text("Key")
+                    .desired_width(110.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let key = self.new_key.trim().to_owned();
+            if (ui.button("Add").clicked() || submitted) && !key.is_empty() {
+                if !plan.keys.contains(&key) {
+                    plan.keys.push(key);
+                }
+                self.new_key.clear();
+            }
+        });
+
+        let lock_keys: BTreeSet<&str> = map
+            .locks
+            .iter()
+            .flatten()
+            .filter_map(|lock| Some(lock.key.as_ref()?.name.as_str()))
+            .filter(|name| !plan.keys.iter().any(|key| key == name))
+            .collect();
+        if !lock_keys.is_empty() {
+            ui.menu_button("Add key from map", |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        for name in lock_keys {
+                            if ui.button(name).clicked() {
+                                plan.keys.push(name.to_owned());
+                                ui.close();
+                            }
+                        }
+                    });
+            });
+        }
+
+        ui.add_space(4.0);
+        if ui.button("Delete Plan").clicked() {
+            self.plans.remove(idx);
+            self.active.remove(&map.normalized_name);
+        }
+    }
+}
+
+impl TarkovMapApp {
+    /// Renders the raid plan sections of the sidebar for the selected map.
+    pub fn show_raid_plan(&mut self, ui: &mut egui::Ui) {
+        let Some(map) = self.maps.get(self.selected_map) else {
+            return;
+        };
+
+        egui::CollapsingHeader::new(egui::RichText::new("Raid Plan").strong())
+            .default_open(false)
+            .show(ui, |ui| {
+                self.raid_plans.picker_ui(ui, map);
+            });
+
+        let Some(plan) = self.raid_plans.active_plan(&map.normalized_name) else {
+            return;
+        };
+        egui::CollapsingHeader::new(
+            egui::RichText::new(format!("Loadout: {}", plan.name)).strong(),
+        )
+        .id_salt("raid_plan_loadout")
+        .default_open(true)
+        .show(ui, |ui| {
+            self.raid_plans.plan_ui(ui, map);
+        });
+    }
+}
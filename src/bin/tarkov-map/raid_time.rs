@@ -0,0 +1,72 @@
+//! In-raid clock, computed from real time the same way tarkov.dev does.
+//!
+//! Tarkov time runs 7x faster than real time, anchored to Moscow time (UTC+3).
+//! The "right" raid time is the "left" one offset by 12 hours.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const TARKOV_TIME_RATIO: u64 = 7;
+const MOSCOW_OFFSET_SECS: u64 = 3 * 60 * 60;
+const DAY_SECS: u64 = 24 * 60 * 60;
+const HALF_DAY_SECS: u64 = 12 * 60 * 60;
+
+/// How often the raid clock needs repainting (one in-game minute is ~8.6 s).
+pub const RAID_TIME_REPAINT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A time of day in Tarkov.
+#[derive(Debug, Clone, Copy)]
+pub struct RaidTime {
+    pub hours: u32,
+    pub minutes: u32,
+}
+
+impl RaidTime {
+    fn from_day_secs(secs: u64) -> Self {
+        let secs = secs % DAY_SECS;
+        Self {
+            hours: (secs / 3600) as u32,
+            minutes: ((secs % 3600) / 60) as u32,
+        }
+    }
+
+    /// Whether this is night time (22:00 to 05:00).
+    pub fn is_night(self) -> bool {
+        self.hours >= 22 || self.hours < 5
+    }
+}
+
+impl std::fmt::Display for RaidTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}", self.hours, self.minutes)
+    }
+}
+
+/// Returns the current `(left, right)` raid times.
+pub fn current_raid_times() -> (RaidTime, RaidTime) {
+    let real_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let left = (MOSCOW_OFFSET_SECS + real_secs * TARKOV_TIME_RATIO) % DAY_SECS;
+    (
+        RaidTime::from_day_secs(left),
+        RaidTime::from_day_secs(left + HALF_DAY_SECS),
+    )
+}
+
+/// Renders the left/right raid clocks, with a moon marking night raids.
+pub fn show_raid_time(ui: &mut eframe::egui::Ui) {
+    let (left, right) = current_raid_times();
+    let format = |time: RaidTime| {
+        if time.is_night() {
+            format!("{time} ☾")
+        } else {
+            time.to_string()
+        }
+    };
+
+    ui.label(format!("Raid time: {} / {}", format(left), format(right)))
+        .on_hover_text("Left and right raid times. Weather is not available from tarkov.dev.");
+    ui.ctx().request_repaint_after(RAID_TIME_REPAINT_INTERVAL);
+}
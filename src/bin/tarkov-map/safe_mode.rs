@@ -0,0 +1,206 @@
+//! Safe mode, for when the settings or the GPU keep the app from working.
+//!
+//! `--safe-mode` starts with default settings, no position tracking or game
+//! log watchers, and only the last selected map loaded. The stored settings
+//! are left alone unless repaired in the Safe Mode window, which shows them
+//! as editable RON.
+//!
+//! A marker file exists while the app runs and is removed on a clean exit.
+//! If it is still there on the next start, the previous run crashed and the
+//! app offers to restart in safe mode.
+
+use crate::{APP_ID, AppSettings, TarkovMapApp};
+use eframe::egui;
+use ron::ser::PrettyConfig;
+use std::fs;
+use std::path::PathBuf;
+
+/// Command-line flag that starts the app in safe mode.
+pub const SAFE_MODE_ARG: &str = "--safe-mode";
+
+const RUNNING_MARKER_FILE: &str = "running";
+
+fn running_marker_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join(RUNNING_MARKER_FILE))
+}
+
+/// Creates the running marker. Returns `true` if it already existed, i.e. the
+/// previous run didn't exit cleanly.
+pub fn mark_running() -> bool {
+    let Some(path) = running_marker_path() else {
+        return false;
+    };
+    let crashed = path.exists();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Err(err) = fs::write(&path, std::process::id().to_string()) {
+        log::warn!("Failed to write {}: {err}", path.display());
+    }
+    crashed
+}
+
+/// Removes the running marker on a clean exit or before a restart.
+///
+/// A marker written by another instance, such as the one a restart started, is
+/// left in place.
+pub fn clear_running_marker() {
+    let Some(path) = running_marker_path() else {
+        return;
+    };
+    let ours = fs::read_to_string(&path).is_ok_and(|pid| pid == std::process::id().to_string());
+    if ours && let Err(err) = fs::remove_file(&path) {
+        log::warn!("Failed to remove {}: {err}", path.display());
+    }
+}
+
+/// Starts a new instance of the app, in or out of safe mode.
+pub fn relaunch(safe_mode: bool) -> std::io::Result<()> {
+    // This instance is closing on purpose, so the new one shouldn't see a crash
+    clear_running_marker();
+    let exe = std::env::current_exe()?;
+    let mut cmd = std::process::Command::new(exe);
+    cmd.args(std::env::args().skip(1).filter(|arg| arg != SAFE_MODE_ARG));
+    if safe_mode {
+        cmd.arg(SAFE_MODE_ARG);
+    }
+    cmd.spawn()?;
+    Ok(())
+}
+
+/// Formats settings RON for editing, pretty-printed if it parses.
+fn pretty_settings(text: &str) -> String {
+    ron::from_str::<AppSettings>(text)
+        .ok()
+        .and_then(|settings| ron::ser::to_string_pretty(&settings, PrettyConfig::default()).ok())
+        .unwrap_or_else(|| text.to_owned())
+}
+
+/// Safe mode state and the settings being repaired.
+#[derive(Default)]
+pub struct SafeMode {
+    pub active: bool,
+    /// The previous run crashed and a restart in safe mode is offered.
+    crash_prompt: bool,
+    /// Stored settings as RON, edited in the Safe Mode window.
+    settings_text: String,
+    settings_error: Option<String>,
+    /// Checked settings to store on exit, replacing the stored ones.
+    pub repaired: Option<String>,
+}
+
+impl SafeMode {
+    /// `stored` is the stored settings RON, if any.
+    pub fn new(active: bool, crashed: bool, stored: Option<String>) -> Self {
+        Self {
+            active,
+            crash_prompt: crashed && !active,
+            settings_text: stored.as_deref().map(pretty_settings).unwrap_or_default(),
+            settings_error: None,
+            repaired: None,
+        }
+    }
+
+    /// Checks the edited settings, remembering them for the exit if they parse.
+    fn check_settings(&mut self) {
+        match ron::from_str::<AppSettings>(&self.settings_text) {
+            Ok(_) => {
+                self.settings_error = None;
+                self.repaired = Some(self.settings_text.clone());
+            }
+            Err(err) => {
+                self.settings_error = Some(err.to_string());
+                self.repaired = None;
+            }
+        }
+    }
+}
+
+impl TarkovMapApp {
+    /// Renders the crash prompt, or the Safe Mode window while in safe mode.
+    pub fn show_safe_mode_window(&mut self, ctx: &egui::Context) {
+        if self.safe_mode.crash_prompt {
+            self.show_crash_prompt(ctx);
+        }
+        if !self.safe_mode.active {
+            return;
+        }
+
+        egui::Window::new("Safe Mode")
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let safe_mode = &mut self.safe_mode;
+                ui.label(
+                    "Running with default settings, without position tracking and with \
+                     only one map. Your settings are unchanged unless you save them here.",
+                );
+                ui.add_space(6.0);
+                ui.strong("Stored settings");
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        let response = ui.add(
+                            egui::TextEdit::multiline(&mut safe_mode.settings_text)
+                                .code_editor()
+                                .desired_rows(12)
+                                .desired_width(f32::INFINITY)
+                                .hint_text("No stored settings"),
+                        );
+                        if response.changed() {
+                            safe_mode.repaired = None;
+                        }
+                    });
+                if let Some(err) = &safe_mode.settings_error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                } else if safe_mode.repaired.is_some() {
+                    ui.weak("Settings are valid and will be saved on exit");
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("Save Settings").clicked() {
+                        safe_mode.check_settings();
+                    }
+                    if ui.button("Reset to Defaults").clicked() {
+                        safe_mode.settings_text = ron::ser::to_string_pretty(
+                            &AppSettings::default(),
+                            PrettyConfig::default(),
+                        )
+                        .unwrap_or_default();
+                        safe_mode.check_settings();
+                    }
+                    if ui.button("Restart Normally").clicked() {
+                        match relaunch(false) {
+                            Ok(()) => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+                            Err(err) => safe_mode.settings_error = Some(err.to_string()),
+                        }
+                    }
+                });
+            });
+    }
+
+    fn show_crash_prompt(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Tarkov Map closed unexpectedly")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("The last session didn't exit cleanly.");
+                ui.label(
+                    "Safe mode starts with default settings and one map, and lets you repair \
+                     the stored settings.",
+                );
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Restart in Safe Mode").clicked() {
+                        match relaunch(true) {
+                            Ok(()) => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+                            Err(err) => log::warn!("Failed to restart in safe mode: {err}"),
+                        }
+                    }
+                    if ui.button("Continue").clicked() {
+                        self.safe_mode.crash_prompt = false;
+                    }
+                });
+            });
+    }
+}
@@ -0,0 +1,201 @@
+//! Scav cooldown timer in the status bar.
+//!
+//! The cooldown is started by hand, or when the game's application log reports
+//! the end of a raid while the raid switch is set to Scav. The end time is
+//! saved with the settings, so the timer keeps running across restarts. A
+//! toast and a taskbar flash announce when the scav is ready again.
+
+use crate::TarkovMapApp;
+use crate::constants::SCAV_COOLDOWN_MINUTES;
+use crate::game_logs::{logs_dir, newest_session_log};
+use crate::raid_mode::RaidMode;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// Time between checks of the newest application log.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Log line written when the player leaves a raid, whether extracted or killed.
+const RAID_ENDED_MARKER: &str = "application|GameEnded";
+
+/// Cooldown length and the running cooldown, if any.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScavTimer {
+    pub cooldown_minutes: u32,
+    /// When the running cooldown ends.
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Start the cooldown when a raid ends in Scav mode.
+    pub start_on_raid_end: bool,
+}
+
+impl Default for ScavTimer {
+    fn default() -> Self {
+        Self {
+            cooldown_minutes: SCAV_COOLDOWN_MINUTES,
+            ends_at: None,
+            start_on_raid_end: true,
+        }
+    }
+}
+
+impl ScavTimer {
+    pub fn start(&mut self) {
+        self.ends_at = Some(Utc::now() + ChronoDuration::minutes(i64::from(self.cooldown_minutes)));
+    }
+
+    pub fn stop(&mut self) {
+        self.ends_at = None;
+    }
+
+    /// Time left on the running cooldown, zero once it has ended.
+    fn remaining(&self) -> Option<ChronoDuration> {
+        self.ends_at
+            .map(|ends_at| (ends_at - Utc::now()).max(ChronoDuration::zero()))
+    }
+}
+
+/// Watches the game's application log for raid ends.
+pub struct RaidEndWatcher {
+    rx: Receiver<()>,
+}
+
+impl RaidEndWatcher {
+    /// Starts polling the game's logs. Returns `None` if the logs folder isn't found.
+    pub fn new(ctx: egui::Context) -> Option<Self> {
+        let logs_dir = logs_dir()?;
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || poll_logs(&logs_dir, &tx, &ctx));
+        Some(Self { rx })
+    }
+
+    /// Returns whether a raid ended since the last call.
+    pub fn poll(&self) -> bool {
+        self.rx.try_iter().count() > 0
+    }
+}
+
+/// Reports raid ends added to the newest application log until the receiver is dropped.
+fn poll_logs(logs_dir: &Path, tx: &Sender<()>, ctx: &egui::Context) {
+    let mut current: Option<PathBuf> = None;
+    // Raids already in the log when it was first seen ended before the app started
+    let mut seen = 0;
+    let mut first = true;
+    loop {
+        if let Some(path) = newest_session_log(logs_dir, "application.log") {
+            let ended = fs::read_to_string(&path)
+                .map(|text| text.matches(RAID_ENDED_MARKER).count())
+                .unwrap_or_default();
+            if current.as_ref() != Some(&path) {
+                seen = if first { ended } else { 0 };
+                current = Some(path);
+            }
+            first = false;
+
+            if ended > seen {
+                seen = ended;
+                if tx.send(()).is_err() {
+                    return;
+                }
+                ctx.request_repaint();
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Formats a duration as `m:ss`.
+fn format_countdown(duration: ChronoDuration) -> String {
+    let secs = duration.num_seconds().max(0);
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+impl TarkovMapApp {
+    /// Starts the cooldown on a logged Scav raid end and announces when it completes.
+    pub fn poll_scav_timer(&mut self, ctx: &egui::Context) {
+        let raid_ended = self
+            .raid_end_watcher
+            .as_ref()
+            .is_some_and(RaidEndWatcher::poll);
+        if raid_ended && self.raid_mode == RaidMode::Scav && self.scav_timer.start_on_raid_end {
+            log::info!("Scav raid ended, starting scav cooldown");
+            self.scav_timer.start();
+        }
+
+        let Some(remaining) = self.scav_timer.remaining() else {
+            return;
+        };
+        if remaining > ChronoDuration::zero() {
+            ctx.request_repaint_after(Duration::from_secs(1));
+            return;
+        }
+        self.scav_timer.stop();
+        self.toasts.add(Toast {
+            kind: ToastKind::Success,
+            text: "Scav cooldown complete".into(),
+            options: ToastOptions::default().duration_in_seconds(10.0),
+            ..Default::default()
+        });
+        ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+            egui::UserAttentionType::Informational,
+        ));
+    }
+
+    /// Renders the scav cooldown, with a context menu to start, stop and configure it.
+    pub fn show_scav_timer(&mut self, ui: &mut egui::Ui) {
+        let timer = &mut self.scav_timer;
+        let text = match timer.remaining() {
+            Some(remaining) => {
+                egui::RichText::new(format!("Scav: {}", format_countdown(remaining)))
+            }
+            None => egui::RichText::new("Scav ready").weak(),
+        };
+        let hover = if self.raid_end_watcher.is_some() {
+            "Click to start or stop the scav cooldown. It also starts when a raid ends in \
+             Scav mode.\nRight-click for settings"
+        } else {
+            "Click to start or stop the scav cooldown\nRight-click for settings"
+        };
+
+        let response = ui
+            .add(egui::Label::new(text).sense(egui::Sense::click()))
+            .on_hover_text(hover);
+        if response.clicked() {
+            if timer.ends_at.is_some() {
+                timer.stop();
+            } else {
+                timer.start();
+            }
+        }
+        response.context_menu(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Cooldown");
+                ui.add(
+                    egui::DragValue::new(&mut timer.cooldown_minutes)
+                        .range(1..=60)
+                        .suffix(" min"),
+                );
+            });
+            ui.checkbox(&mut timer.start_on_raid_end, "Start when a Scav raid ends");
+            ui.separator();
+            if ui.button("Restart").clicked() {
+                timer.start();
+                ui.close();
+            }
+            if ui
+                .add_enabled(timer.ends_at.is_some(), egui::Button::new("Stop"))
+                .clicked()
+            {
+                timer.stop();
+                ui.close();
+            }
+        });
+    }
+}
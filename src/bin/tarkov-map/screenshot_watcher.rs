@@ -3,13 +3,52 @@
 //! Tarkov saves coordinates in screenshot filenames in the format:
 //! `2026-01-07[19-56]_-198.89, 22.74, -345.97_0.32263, 0.47266, -0.18602, 0.79869_15.61 (0).png`
 //!                    ^--- position (x, y, z) ---^  ^--- quaternion (x, y, z, w) ---^
+//!
+//! New screenshots are picked up with a file system watcher. Where that is
+//! unreliable (network or OneDrive-synced Documents folders) the folder is
+//! polled instead, which is also the fallback when the watcher fails to start.
 
+use crate::sessions::RaidHistory;
 use eframe::egui;
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use tarkov_map::rotation::yaw_from_quaternion;
+
+/// Subfolder of the screenshots folder that archived screenshots are moved into.
+const ARCHIVE_DIR_NAME: &str = "archive";
+
+/// Time without new screenshots after which polling slows down.
+const POLL_IDLE_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// Factor the poll interval is multiplied by while idle.
+const POLL_IDLE_FACTOR: u32 = 4;
+
+/// How the screenshots folder is checked for new screenshots.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotPolling {
+    /// Polls the folder even when the file system watcher is available.
+    pub always_poll: bool,
+    /// Seconds between polls.
+    pub interval_secs: u32,
+}
+
+impl Default for ScreenshotPolling {
+    fn default() -> Self {
+        Self {
+            always_poll: false,
+            interval_secs: 2,
+        }
+    }
+}
 
 /// Player position and rotation data extracted from a screenshot filename.
 #[derive(Debug, Clone, Copy)]
@@ -20,21 +59,37 @@ pub struct PlayerPosition {
     pub yaw: f32,
 }
 
+/// Source of new screenshot events.
+enum WatchBackend {
+    /// The watcher must be kept alive for events to fire
+    Notify { _watcher: RecommendedWatcher },
+    /// Background thread listing the folder; stops when the flag is set
+    Poll(Arc<AtomicBool>),
+}
+
 /// Watches the Tarkov screenshots folder for new screenshots and extracts player position.
 pub struct ScreenshotWatcher {
-    /// Receiver for position updates from the file watcher
+    /// Receiver for position updates from the watcher or poller
     position_rx: Receiver<PlayerPosition>,
-    /// The watcher must be kept alive for events to fire
-    _watcher: RecommendedWatcher,
+    backend: WatchBackend,
     /// Current player position (most recent)
     current_position: Option<PlayerPosition>,
 }
 
+impl Drop for ScreenshotWatcher {
+    fn drop(&mut self) {
+        if let WatchBackend::Poll(stop) = &self.backend {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
 impl ScreenshotWatcher {
     /// Creates a new screenshot watcher.
     ///
-    /// Returns `None` if the screenshots folder doesn't exist or watching fails.
-    pub fn new(ctx: egui::Context) -> Option<Self> {
+    /// Falls back to polling if the file system watcher can't be started.
+    /// Returns `None` if the screenshots folder doesn't exist.
+    pub fn new(ctx: egui::Context, polling: ScreenshotPolling) -> Option<Self> {
         let screenshots_path = Self::screenshots_path()?;
 
         if !screenshots_path.exists() {
@@ -61,51 +116,152 @@ impl ScreenshotWatcher {
             );
         }
 
-        // Set up file watcher
-        let tx = position_tx.clone();
-        let ctx_clone = ctx.clone();
+        let watcher = if polling.always_poll {
+            None
+        } else {
+            Self::start_notify(&screenshots_path, position_tx.clone(), ctx.clone())
+                .inspect_err(|err| {
+                    log::warn!("File watcher unavailable, polling screenshots instead: {err}");
+                })
+                .ok()
+        };
+        let backend = match watcher {
+            Some(watcher) => {
+                log::info!(
+                    "Watching screenshots folder: {}",
+                    screenshots_path.display()
+                );
+                WatchBackend::Notify { _watcher: watcher }
+            }
+            None => {
+                log::info!(
+                    "Polling screenshots folder every {}s: {}",
+                    polling.interval_secs,
+                    screenshots_path.display()
+                );
+                WatchBackend::Poll(Self::start_polling(
+                    screenshots_path,
+                    polling.interval_secs,
+                    position_tx,
+                    ctx,
+                ))
+            }
+        };
+
+        Some(Self {
+            position_rx,
+            backend,
+            current_position: initial_position,
+        })
+    }
+
+    /// Returns `true` if the folder is polled rather than watched.
+    pub fn is_polling(&self) -> bool {
+        matches!(self.backend, WatchBackend::Poll(_))
+    }
+
+    /// Starts a file system watcher sending positions from newly created screenshots.
+    fn start_notify(
+        dir: &Path,
+        tx: Sender<PlayerPosition>,
+        ctx: egui::Context,
+    ) -> notify::Result<RecommendedWatcher> {
         let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
             if let Ok(event) = res {
                 // Only handle file creation events
                 if matches!(event.kind, EventKind::Create(_)) {
                     for path in event.paths {
-                        if path.extension().is_some_and(|ext| ext == "png")
-                            && let Some(position) = Self::parse_screenshot_filename(&path)
-                        {
-                            log::info!(
-                                "New player position: [{:.2}, {:.2}, {:.2}], yaw: {:.2}°",
-                                position.position[0],
-                                position.position[1],
-                                position.position[2],
-                                position.yaw.to_degrees()
-                            );
-                            let _ = tx.send(position);
-                            ctx_clone.request_repaint();
+                        if path.extension().is_some_and(|ext| ext == "png") {
+                            Self::send_position(&path, &tx, &ctx);
                         }
                     }
                 }
             }
-        })
-        .ok()?;
+        })?;
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        Ok(watcher)
+    }
+
+    /// Starts a thread listing `dir` for screenshots newer than the last one seen.
+    ///
+    /// Polls every `interval_secs`, slowing down while no new screenshots arrive.
+    fn start_polling(
+        dir: PathBuf,
+        interval_secs: u32,
+        tx: Sender<PlayerPosition>,
+        ctx: egui::Context,
+    ) -> Arc<AtomicBool> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let interval = Duration::from_secs(u64::from(interval_secs.max(1)));
 
-        watcher
-            .watch(&screenshots_path, RecursiveMode::NonRecursive)
-            .ok()?;
+        thread::spawn(move || {
+            let mut newest_seen = Self::newest_modified(&dir).unwrap_or(SystemTime::UNIX_EPOCH);
+            let mut last_new = Instant::now();
 
-        log::info!(
-            "Watching screenshots folder: {}",
-            screenshots_path.display()
-        );
+            while !thread_stop.load(Ordering::Relaxed) {
+                let idle = last_new.elapsed() >= POLL_IDLE_AFTER;
+                thread::sleep(if idle {
+                    interval * POLL_IDLE_FACTOR
+                } else {
+                    interval
+                });
 
-        Some(Self {
-            position_rx,
-            _watcher: watcher,
-            current_position: initial_position,
-        })
+                let Ok(entries) = fs::read_dir(&dir) else {
+                    continue;
+                };
+                let mut new_screenshots: Vec<(SystemTime, PathBuf)> = entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "png"))
+                    .filter_map(|entry| {
+                        Some((entry.metadata().ok()?.modified().ok()?, entry.path()))
+                    })
+                    .filter(|(modified, _)| *modified > newest_seen)
+                    .collect();
+                new_screenshots.sort_by_key(|(modified, _)| *modified);
+
+                // Every screenshot since the last poll, oldest first, so
+                // sessions and trails get the positions in between too
+                for (modified, path) in new_screenshots {
+                    if thread_stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    newest_seen = modified;
+                    last_new = Instant::now();
+                    Self::send_position(&path, &tx, &ctx);
+                }
+            }
+        });
+        stop
+    }
+
+    /// Parses the screenshot at `path` and sends its position, if it has one.
+    fn send_position(path: &Path, tx: &Sender<PlayerPosition>, ctx: &egui::Context) {
+        if let Some(position) = Self::parse_screenshot_filename(path) {
+            log::info!(
+                "New player position: [{:.2}, {:.2}, {:.2}], yaw: {:.2}°",
+                position.position[0],
+                position.position[1],
+                position.position[2],
+                position.yaw.to_degrees()
+            );
+            let _ = tx.send(position);
+            ctx.request_repaint();
+        }
+    }
+
+    /// Returns the modification time of the newest PNG in the given directory.
+    fn newest_modified(dir: &Path) -> Option<SystemTime> {
+        fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "png"))
+            .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+            .max()
     }
 
     /// Returns the path to the Tarkov screenshots folder.
-    fn screenshots_path() -> Option<PathBuf> {
+    pub fn screenshots_path() -> Option<PathBuf> {
         let documents = dirs::document_dir()?;
         Some(documents.join("Escape from Tarkov").join("Screenshots"))
     }
@@ -144,7 +300,7 @@ impl ScreenshotWatcher {
         let qz: f32 = caps.name("qz")?.as_str().parse().ok()?;
         let qw: f32 = caps.name("qw")?.as_str().parse().ok()?;
 
-        let yaw = quaternion_to_yaw(qx, qy, qz, qw);
+        let yaw = yaw_from_quaternion(qx, qy, qz, qw);
 
         Some(PlayerPosition {
             position: [x, y, z],
@@ -152,14 +308,17 @@ impl ScreenshotWatcher {
         })
     }
 
-    /// Polls for new position updates and returns the current position.
-    pub fn poll(&mut self) -> Option<PlayerPosition> {
-        // Drain all pending updates, keeping only the most recent
+    /// The most recent position, including the one found on startup.
+    pub fn current(&self) -> Option<PlayerPosition> {
+        self.current_position
+    }
+
+    /// Returns the positions from screenshots taken since the last poll, oldest first.
+    pub fn poll(&mut self) -> Vec<PlayerPosition> {
+        let mut positions = Vec::new();
         loop {
             match self.position_rx.try_recv() {
-                Ok(position) => {
-                    self.current_position = Some(position);
-                }
+                Ok(position) => positions.push(position),
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => {
                     log::warn!("Screenshot watcher channel disconnected");
@@ -168,19 +327,126 @@ impl ScreenshotWatcher {
             }
         }
 
-        self.current_position
+        if let Some(&position) = positions.last() {
+            self.current_position = Some(position);
+        }
+        positions
+    }
+}
+
+/// What to do with position screenshots once they are old enough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CleanupMode {
+    #[default]
+    Off,
+    Delete,
+    Archive,
+}
+
+/// When a position screenshot is old enough to be cleaned up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CleanupAge {
+    /// After [`ScreenshotCleanup::max_age_minutes`].
+    #[default]
+    Minutes,
+    /// Once it was taken before the last [`ScreenshotCleanup::keep_raids`] raids.
+    Raids,
+}
+
+/// Automatic cleanup policy for parsed position screenshots.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotCleanup {
+    pub mode: CleanupMode,
+    pub age: CleanupAge,
+    /// Screenshots older than this many minutes are cleaned up.
+    pub max_age_minutes: u32,
+    /// Screenshots from before this many of the most recent raids are cleaned up.
+    pub keep_raids: u32,
+}
+
+impl Default for ScreenshotCleanup {
+    fn default() -> Self {
+        Self {
+            mode: CleanupMode::Off,
+            age: CleanupAge::Minutes,
+            max_age_minutes: 60,
+            keep_raids: 3,
+        }
     }
 }
 
-/// Converts a quaternion rotation to yaw angle in radians.
-///
-/// Based on the TarkovMonitor implementation which uses parameter order (x, z, y, w)
-/// meaning y and z are swapped in the formula relative to standard quaternion conventions.
-fn quaternion_to_yaw(x: f32, y: f32, z: f32, w: f32) -> f32 {
-    // TarkovMonitor's formula with their (x, z, y, w) convention:
-    // siny_cosp = 2 * (w * z + x * y) where their z=our y, their y=our z
-    // So we need: 2 * (w * y + x * z)
-    let siny_cosp = 2.0 * (w * y + x * z);
-    let cosy_cosp = 1.0 - 2.0 * (z * z + y * y);
-    f32::atan2(siny_cosp, cosy_cosp)
+impl ScreenshotCleanup {
+    /// Time before which screenshots count as old, or `None` if none do yet.
+    ///
+    /// Raids are the sessions in the [`RaidHistory`], so the raid in progress
+    /// counts as one of the raids kept.
+    fn cutoff(&self) -> Option<SystemTime> {
+        match self.age {
+            CleanupAge::Minutes => SystemTime::now()
+                .checked_sub(Duration::from_secs(u64::from(self.max_age_minutes) * 60)),
+            CleanupAge::Raids => {
+                let history = RaidHistory::load();
+                let visits = history.visits();
+                let oldest_kept = visits.len().checked_sub(self.keep_raids.max(1) as usize)?;
+                Some(visits[oldest_kept].started_at.into())
+            }
+        }
+    }
+
+    /// Deletes or archives old position screenshots, returning how many were handled.
+    ///
+    /// Only files whose names parse as position screenshots are touched; any
+    /// other file in the folder is left alone.
+    pub fn run(&self) -> usize {
+        if self.mode == CleanupMode::Off {
+            return 0;
+        }
+        let Some(dir) = ScreenshotWatcher::screenshots_path() else {
+            return 0;
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return 0;
+        };
+        let Some(cutoff) = self.cutoff() else {
+            return 0;
+        };
+
+        let archive_dir = dir.join(ARCHIVE_DIR_NAME);
+        let mut handled = 0;
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "png")
+                || ScreenshotWatcher::parse_screenshot_filename(&path).is_none()
+            {
+                continue;
+            }
+
+            let is_old = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified < cutoff);
+            if !is_old {
+                continue;
+            }
+
+            let result = match self.mode {
+                CleanupMode::Off => continue,
+                CleanupMode::Delete => fs::remove_file(&path),
+                CleanupMode::Archive => fs::create_dir_all(&archive_dir)
+                    .and_then(|()| fs::rename(&path, archive_dir.join(entry.file_name()))),
+            };
+
+            match result {
+                Ok(()) => handled += 1,
+                Err(err) => log::warn!("Failed to clean up {}: {err}", path.display()),
+            }
+        }
+
+        if handled > 0 {
+            log::info!("Cleaned up {handled} screenshots ({:?})", self.mode);
+        }
+        handled
+    }
 }
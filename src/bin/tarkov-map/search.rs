@@ -0,0 +1,154 @@
+//! Sidebar search over map labels and extracts.
+//!
+//! Names and queries are transliterated to Latin before matching, so Cyrillic
+//! and Latin spellings of the same name find each other. Every translation of
+//! a name is searched, and results are listed in the selected language.
+
+use crate::TarkovMapApp;
+use crate::usage_stats::Feature;
+use eframe::egui;
+use tarkov_map::{Map, Translations};
+
+/// Maximum number of results listed below the search field.
+const MAX_RESULTS: usize = 12;
+
+/// Transliterates Cyrillic letters to Latin (simplified BGN/PCGN).
+fn transliterate(c: char) -> Option<&'static str> {
+    Some(match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' | 'э' => "e",
+        'ё' => "yo",
+        'ж' => "zh",
+        'з' => "z",
+        'и' => "i",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ю' => "yu",
+        'я' => "ya",
+        'ъ' | 'ь' => "",
+        _ => return None,
+    })
+}
+
+/// Lowercases, transliterates, and strips everything but letters and digits.
+fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if let Some(latin) = transliterate(c) {
+            normalized.push_str(latin);
+        } else if c.is_alphanumeric() {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+/// A label or extract matching the search query.
+struct SearchResult {
+    name: String,
+    kind: &'static str,
+    /// Position `[x, z]` in game coordinates.
+    position: [f64; 2],
+}
+
+/// Returns `true` if the normalized `query` is part of `name` or any of its translations.
+fn matches(query: &str, name: &str, translations: Option<&Translations>) -> bool {
+    let translated = translations.into_iter().flat_map(|t| t.values());
+    std::iter::once(name)
+        .chain(translated.map(String::as_str))
+        .any(|text| normalize(text).contains(query))
+}
+
+fn search(map: &Map, query: &str, language: Option<&str>) -> Vec<SearchResult> {
+    let query = normalize(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let labels = map
+        .labels
+        .iter()
+        .flatten()
+        .filter(|label| matches(&query, &label.text, label.translations.as_ref()))
+        .map(|label| SearchResult {
+            name: label.localized_text(language).replace('\n', " "),
+            kind: "Label",
+            position: label.position.into(),
+        });
+    let extracts = map
+        .extracts
+        .iter()
+        .flatten()
+        .filter(|extract| matches(&query, &extract.name, extract.translations.as_ref()))
+        .filter_map(|extract| {
+            Some(SearchResult {
+                name: extract.localized_name(language).to_owned(),
+                kind: "Extract",
+                position: extract.center()?.into(),
+            })
+        });
+
+    labels.chain(extracts).take(MAX_RESULTS).collect()
+}
+
+impl TarkovMapApp {
+    /// Renders the search field and its results for the selected map.
+    pub fn show_search(&mut self, ui: &mut egui::Ui) {
+        ui.add(
+            egui::TextEdit::singleline(&mut self.search_query)
+                .hint_text("Search labels and extracts")
+                .desired_width(f32::INFINITY),
+        );
+
+        let Some(map) = self.selected_map() else {
+            return;
+        };
+        if self.search_query.trim().is_empty() {
+            return;
+        }
+
+        let results = search(map, &self.search_query, self.language.as_deref());
+        if results.is_empty() {
+            ui.weak("No matches");
+            return;
+        }
+
+        for result in results {
+            let response = ui
+                .horizontal(|ui| {
+                    let response = ui.add(
+                        egui::Label::new(&result.name)
+                            .truncate()
+                            .sense(egui::Sense::click()),
+                    );
+                    ui.weak(result.kind);
+                    response
+                })
+                .inner
+                .on_hover_cursor(egui::CursorIcon::PointingHand);
+            if response.clicked() {
+                self.usage.feature_used(Feature::Search);
+                self.focus_position = Some(result.position);
+            }
+        }
+    }
+}
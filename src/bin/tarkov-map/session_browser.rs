@@ -0,0 +1,317 @@
+//! Sessions window for browsing, replaying, and managing recorded raids.
+
+use crate::TarkovMapApp;
+use crate::colors;
+use crate::coordinates::game_to_display;
+use crate::sessions::{RaidSession, StoredSession, load_sessions, prune_sessions, sessions_dir};
+use crate::usage_stats::Feature;
+use chrono::Local;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tarkov_map::Map;
+
+/// Automatic deletion of old session files.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionPrune {
+    pub enabled: bool,
+    pub max_age_days: u32,
+}
+
+impl Default for SessionPrune {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_age_days: 30,
+        }
+    }
+}
+
+impl SessionPrune {
+    /// Deletes sessions older than the configured age, if enabled.
+    pub fn run(self) {
+        if self.enabled {
+            let pruned = prune_sessions(self.max_age_days);
+            if pruned > 0 {
+                log::info!("Pruned {pruned} old raid sessions");
+            }
+        }
+    }
+}
+
+/// State of the open Sessions window.
+#[derive(Default)]
+pub struct SessionBrowser {
+    sessions: Vec<StoredSession>,
+    /// Index of the session being renamed, and the edited name.
+    renaming: Option<(usize, String)>,
+}
+
+impl SessionBrowser {
+    fn load() -> Self {
+        Self {
+            sessions: load_sessions(),
+            renaming: None,
+        }
+    }
+}
+
+/// Formats a byte count as KiB or MiB.
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Action picked for a session row, applied after the list is drawn.
+enum SessionAction {
+    Replay(usize),
+    CopyCsv(usize),
+    StartRename(usize),
+    FinishRename,
+    Delete(usize),
+}
+
+impl TarkovMapApp {
+    /// Opens the Sessions window, pruning old sessions first.
+    pub fn open_session_browser(&mut self) {
+        self.session_prune.run();
+        self.usage.feature_used(Feature::Sessions);
+        self.session_browser = Some(SessionBrowser::load());
+    }
+
+    /// Renders the Sessions window while it is open.
+    pub fn show_session_browser(&mut self, ctx: &egui::Context) {
+        let Some(mut browser) = self.session_browser.take() else {
+            return;
+        };
+
+        let mut open = true;
+        let mut action = None;
+        egui::Window::new("Sessions")
+            .open(&mut open)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                let total: u64 = browser.sessions.iter().map(|s| s.size).sum();
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} sessions, {}",
+                        browser.sessions.len(),
+                        format_size(total)
+                    ));
+                    if ui.small_button("Open Folder").clicked()
+                        && let Some(dir) = sessions_dir()
+                    {
+                        let _ = fs::create_dir_all(&dir);
+                        let _ = open::that(dir);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(
+                        &mut self.session_prune.enabled,
+                        "Delete sessions older than",
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut self.session_prune.max_age_days)
+                            .range(1..=365)
+                            .suffix(" days"),
+                    );
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("sessions")
+                        .num_columns(5)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            let SessionBrowser { sessions, renaming } = &mut browser;
+                            for (idx, stored) in sessions.iter().enumerate() {
+                                self.session_row(ui, idx, &stored.session, renaming, &mut action);
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if let Some(action) = action {
+            self.apply_session_action(ctx, &mut browser, action);
+        }
+        if open {
+            self.session_browser = Some(browser);
+        }
+    }
+
+    fn session_row(
+        &self,
+        ui: &mut egui::Ui,
+        idx: usize,
+        session: &RaidSession,
+        renaming: &mut Option<(usize, String)>,
+        action: &mut Option<SessionAction>,
+    ) {
+        let map_name = self
+            .maps
+            .iter()
+            .find(|map| map.normalized_name == session.map)
+            .map_or(session.map.as_str(), |map| map.name.as_str());
+        let date = session
+            .started_at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string();
+
+        match renaming {
+            Some((renaming, name)) if *renaming == idx => {
+                let response = ui.text_edit_singleline(name);
+                if response.lost_focus() {
+                    *action = Some(SessionAction::FinishRename);
+                }
+            }
+            _ => {
+                ui.label(session.name.as_deref().unwrap_or(&date))
+                    .on_hover_text(&date);
+            }
+        }
+        ui.label(map_name);
+
+        let minutes = (session.ended_at - session.started_at).num_minutes();
+        ui.label(format!("{minutes} min"));
+        ui.label(format!("{:.0} m", session.distance()));
+
+        ui.horizontal(|ui| {
+            let replaying = self
+                .replay
+                .as_ref()
+                .is_some_and(|replay| replay.started_at == session.started_at);
+            if ui
+                .selectable_label(replaying, "Replay")
+                .on_hover_text("Show the recorded path on the map")
+                .clicked()
+            {
+                *action = Some(SessionAction::Replay(idx));
+            }
+            if ui
+                .small_button("CSV")
+                .on_hover_text("Copy positions as CSV")
+                .clicked()
+            {
+                *action = Some(SessionAction::CopyCsv(idx));
+            }
+            if ui.small_button("Rename").clicked() {
+                *action = Some(SessionAction::StartRename(idx));
+            }
+            if ui.small_button("🗑").on_hover_text("Delete").clicked() {
+                *action = Some(SessionAction::Delete(idx));
+            }
+        });
+    }
+
+    fn apply_session_action(
+        &mut self,
+        ctx: &egui::Context,
+        browser: &mut SessionBrowser,
+        action: SessionAction,
+    ) {
+        match action {
+            SessionAction::Replay(idx) => {
+                let session = &browser.sessions[idx].session;
+                let already = self
+                    .replay
+                    .as_ref()
+                    .is_some_and(|replay| replay.started_at == session.started_at);
+                if already {
+                    self.replay = None;
+                    return;
+                }
+                if let Some(map_idx) = self
+                    .maps
+                    .iter()
+                    .position(|map| map.normalized_name == session.map)
+                {
+                    self.select_map(map_idx);
+                }
+                self.replay = Some(session.clone());
+            }
+            SessionAction::CopyCsv(idx) => {
+                ctx.copy_text(browser.sessions[idx].session.to_csv());
+            }
+            SessionAction::StartRename(idx) => {
+                let name = browser.sessions[idx]
+                    .session
+                    .name
+                    .clone()
+                    .unwrap_or_default();
+                browser.renaming = Some((idx, name));
+            }
+            SessionAction::FinishRename => {
+                let Some((idx, name)) = browser.renaming.take() else {
+                    return;
+                };
+                let name = Some(name.trim().to_owned()).filter(|name| !name.is_empty());
+                let session = &mut browser.sessions[idx].session;
+                session.name = name.clone();
+                if let Err(err) = session.save() {
+                    log::warn!("Failed to rename session: {err}");
+                }
+                if let Some(current) = self
+                    .session_recorder
+                    .current_mut()
+                    .filter(|current| current.started_at == session.started_at)
+                {
+                    current.name = name;
+                }
+            }
+            SessionAction::Delete(idx) => {
+                let stored = browser.sessions.remove(idx);
+                if let Err(err) = fs::remove_file(&stored.path) {
+                    log::warn!("Failed to delete {}: {err}", stored.path.display());
+                }
+                self.personal_heatmap_stale = true;
+                if self
+                    .replay
+                    .as_ref()
+                    .is_some_and(|replay| replay.started_at == stored.session.started_at)
+                {
+                    self.replay = None;
+                }
+                browser.renaming = None;
+            }
+        }
+    }
+
+    /// Draws the path of the replayed session, if it was recorded on `map`.
+    pub fn draw_replay(&self, ui: &egui::Ui, map: &Map, map_rect: egui::Rect) {
+        let Some(replay) = self
+            .replay
+            .as_ref()
+            .filter(|r| r.map == map.normalized_name)
+        else {
+            return;
+        };
+
+        let points: Vec<egui::Pos2> = replay
+            .positions
+            .iter()
+            .filter_map(|p| game_to_display(map, map_rect, [p[0], p[2]]))
+            .collect();
+        let painter = ui.painter();
+        let stroke = egui::Stroke::new(2.5, colors::REPLAY_PATH);
+        painter.add(egui::Shape::line(points.clone(), stroke));
+        for (i, point) in points.iter().enumerate() {
+            let radius = if i == 0 || i == points.len() - 1 {
+                5.0
+            } else {
+                3.0
+            };
+            painter.circle(
+                *point,
+                radius,
+                colors::REPLAY_PATH,
+                egui::Stroke::new(1.0, egui::Color32::BLACK),
+            );
+        }
+    }
+}
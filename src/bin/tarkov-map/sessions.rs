@@ -0,0 +1,247 @@
+//! Raid sessions recorded from screenshot positions.
+//!
+//! A session starts with the first position on a map and ends when positions
+//! stop arriving for [`SESSION_GAP_MINUTES`] or arrive on a different map.
+//! Sessions are stored as RON files in `<data dir>/tarkov-map/sessions/`,
+//! next to a small `history.ron` that counts raids per map.
+
+use crate::APP_ID;
+use chrono::{DateTime, TimeDelta, Utc};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Minutes without a new position after which the next one starts a new session.
+const SESSION_GAP_MINUTES: i64 = 45;
+
+const HISTORY_FILE: &str = "history.ron";
+
+/// Errors that can occur when storing sessions.
+#[derive(Error, Debug)]
+pub enum SessionError {
+    #[error("no data directory available")]
+    NoDataDir,
+    #[error("failed to write '{path}': {source}")]
+    Write {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("failed to serialize session: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+/// A single raid, as seen through the screenshots taken during it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaidSession {
+    /// Normalized name of the map.
+    pub map: String,
+    pub started_at: DateTime<Utc>,
+    /// Time of the last recorded position.
+    pub ended_at: DateTime<Utc>,
+    /// Player positions `[x, y, z]` in game coordinates, in order.
+    pub positions: Vec<[f64; 3]>,
+    /// User-given name, shown instead of the date.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl RaidSession {
+    /// Horizontal distance in meters covered between recorded positions.
+    pub fn distance(&self) -> f64 {
+        self.positions
+            .windows(2)
+            .map(|pair| {
+                ((pair[1][0] - pair[0][0]).powi(2) + (pair[1][2] - pair[0][2]).powi(2)).sqrt()
+            })
+            .sum()
+    }
+
+    /// Formats the positions as CSV with an `x,y,z` header.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("x,y,z\n");
+        for [x, y, z] in &self.positions {
+            csv.push_str(&format!("{x},{y},{z}\n"));
+        }
+        csv
+    }
+
+    fn file_name(&self) -> String {
+        format!(
+            "{}-{}.ron",
+            self.started_at.format("%Y%m%d-%H%M%S"),
+            self.map
+        )
+    }
+
+    pub fn save(&self) -> Result<(), SessionError> {
+        let dir = sessions_dir().ok_or(SessionError::NoDataDir)?;
+        write_ron(&dir.join(self.file_name()), self)
+    }
+}
+
+/// A session loaded from disk.
+pub struct StoredSession {
+    pub path: PathBuf,
+    /// File size in bytes.
+    pub size: u64,
+    pub session: RaidSession,
+}
+
+/// Loads all stored sessions, newest first.
+pub fn load_sessions() -> Vec<StoredSession> {
+    let Some(entries) = sessions_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut sessions: Vec<StoredSession> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "ron")
+                && path.file_name().is_some_and(|name| name != HISTORY_FILE)
+        })
+        .filter_map(|path| {
+            let text = fs::read_to_string(&path).ok()?;
+            let session = ron::from_str(&text)
+                .inspect_err(|err| log::warn!("Skipping session {}: {err}", path.display()))
+                .ok()?;
+            Some(StoredSession {
+                size: text.len() as u64,
+                path,
+                session,
+            })
+        })
+        .collect();
+    sessions.sort_by_key(|stored| std::cmp::Reverse(stored.session.started_at));
+    sessions
+}
+
+/// Deletes stored sessions that ended more than `max_age_days` ago.
+///
+/// The raid history is kept. Returns the number of deleted sessions.
+pub fn prune_sessions(max_age_days: u32) -> usize {
+    let cutoff = Utc::now() - TimeDelta::days(i64::from(max_age_days));
+    load_sessions()
+        .into_iter()
+        .filter(|stored| stored.session.ended_at < cutoff)
+        .filter(|stored| {
+            fs::remove_file(&stored.path)
+                .inspect_err(|err| log::warn!("Failed to delete {}: {err}", stored.path.display()))
+                .is_ok()
+        })
+        .count()
+}
+
+/// When a raid on a map started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaidVisit {
+    pub map: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Every raid ever recorded, kept even when session files are deleted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RaidHistory(Vec<RaidVisit>);
+
+impl RaidHistory {
+    /// Loads the history file, or an empty history if there is none.
+    pub fn load() -> Self {
+        sessions_dir()
+            .and_then(|dir| fs::read_to_string(dir.join(HISTORY_FILE)).ok())
+            .and_then(|text| {
+                ron::from_str(&text)
+                    .inspect_err(|err| log::warn!("Failed to parse raid history: {err}"))
+                    .ok()
+            })
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), SessionError> {
+        let dir = sessions_dir().ok_or(SessionError::NoDataDir)?;
+        write_ron(&dir.join(HISTORY_FILE), self)
+    }
+
+    /// All recorded raids, oldest first.
+    pub fn visits(&self) -> &[RaidVisit] {
+        &self.0
+    }
+
+    /// Number of raids recorded on a map.
+    pub fn count(&self, map: &str) -> usize {
+        self.0.iter().filter(|visit| visit.map == map).count()
+    }
+}
+
+/// Groups incoming player positions into raid sessions.
+#[derive(Default)]
+pub struct SessionRecorder {
+    current: Option<RaidSession>,
+}
+
+impl SessionRecorder {
+    /// The session currently being recorded, if any.
+    pub fn current_mut(&mut self) -> Option<&mut RaidSession> {
+        self.current.as_mut()
+    }
+
+    /// Adds a position on `map`, starting a new session when the raid changed.
+    ///
+    /// Returns `true` if a new session was started. New sessions are added to
+    /// `history`. The current session is saved after every position so nothing
+    /// is lost if the app closes mid-raid.
+    pub fn record(
+        &mut self,
+        map: &str,
+        position: [f64; 3],
+        history: &mut RaidHistory,
+    ) -> Result<bool, SessionError> {
+        let now = Utc::now();
+        let gap = TimeDelta::minutes(SESSION_GAP_MINUTES);
+
+        let mut started = false;
+        let session = match &mut self.current {
+            Some(session) if session.map == map && now - session.ended_at < gap => session,
+            current => {
+                started = true;
+                log::info!("Starting raid session on {map}");
+                history.0.push(RaidVisit {
+                    map: map.to_owned(),
+                    started_at: now,
+                });
+                history.save()?;
+                current.insert(RaidSession {
+                    map: map.to_owned(),
+                    started_at: now,
+                    ended_at: now,
+                    positions: Vec::new(),
+                    name: None,
+                })
+            }
+        };
+
+        session.ended_at = now;
+        session.positions.push(position);
+        session.save()?;
+        Ok(started)
+    }
+}
+
+/// Returns the folder sessions are stored in.
+pub fn sessions_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("sessions"))
+}
+
+fn write_ron<T: Serialize>(path: &Path, value: &T) -> Result<(), SessionError> {
+    let write_error = |source| SessionError::Write {
+        path: path.display().to_string(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let text = ron::ser::to_string_pretty(value, PrettyConfig::default())?;
+    fs::write(path, text).map_err(write_error)
+}
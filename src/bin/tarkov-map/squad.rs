@@ -0,0 +1,497 @@
+//! Squad view: followers' viewports mirror a leader's over the local network.
+//!
+//! The leader hosts a session on [`SQUAD_PORT`] and sends its map, view and
+//! floor whenever they change, one JSON line per update. Followers apply each
+//! update until they pan, zoom or switch maps themselves, which pauses
+//! following until they resume it. This lets one person drive the planning
+//! view during a pre-raid briefing.
+//!
+//! Sessions have no authentication, so the leader only listens on its LAN
+//! address unless another address is set. Each follower gets its own writer
+//! thread, and followers that fall behind are dropped.
+
+use crate::TarkovMapApp;
+use eframe::egui;
+use egui_toast::{Toast, ToastKind, ToastOptions};
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, Shutdown, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender, TrySendError};
+use std::thread;
+use std::time::Duration;
+use tarkov_map::MapView;
+
+/// TCP port squad sessions are hosted on.
+pub const SQUAD_PORT: u16 = 47_611;
+
+/// How long joining waits for the leader to answer.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the host thread checks for new followers.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Longest update line a follower accepts; longer lines end the connection.
+const MAX_LINE_BYTES: u64 = 4096;
+
+/// Updates queued for a follower before it counts as stalled and is dropped.
+const FOLLOWER_BACKLOG: usize = 8;
+
+/// Views closer than this are considered equal when detecting local changes.
+const VIEW_EPSILON: f32 = 1e-3;
+
+/// The leader's map and view, as sent to followers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SharedView {
+    /// Normalized name of the leader's map.
+    map: String,
+    view: MapView,
+    /// Name of the leader's selected floor, or `None` for the base map.
+    #[serde(default)]
+    layer: Option<String>,
+}
+
+/// Hosts a squad session, sending view updates to every follower.
+pub struct SquadHost {
+    /// Address the session is hosted on.
+    address: IpAddr,
+    /// Lines for the host thread to send; dropping it stops the thread.
+    tx: Sender<String>,
+    followers: Arc<AtomicUsize>,
+    last_sent: Option<String>,
+}
+
+impl SquadHost {
+    fn start(ctx: egui::Context, address: IpAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind((address, SQUAD_PORT))?;
+        listener.set_nonblocking(true)?;
+        let (tx, rx) = mpsc::channel();
+        let followers = Arc::new(AtomicUsize::new(0));
+        let count = Arc::clone(&followers);
+        thread::spawn(move || run_host(&listener, &rx, &count, &ctx));
+        Ok(Self {
+            address,
+            tx,
+            followers,
+            last_sent: None,
+        })
+    }
+
+    /// Sends `view` to the followers if it changed since the last update.
+    fn send(&mut self, view: &SharedView) {
+        let Ok(mut line) = serde_json::to_string(view) else {
+            return;
+        };
+        line.push('\n');
+        if self.last_sent.as_ref() != Some(&line) {
+            let _ = self.tx.send(line.clone());
+            self.last_sent = Some(line);
+        }
+    }
+
+    fn followers(&self) -> usize {
+        self.followers.load(Ordering::Relaxed)
+    }
+}
+
+/// Accepts followers and forwards view updates until the [`SquadHost`] is dropped.
+fn run_host(
+    listener: &TcpListener,
+    rx: &Receiver<String>,
+    count: &AtomicUsize,
+    ctx: &egui::Context,
+) {
+    // Dropping a follower's sender ends its writer thread
+    let mut followers: Vec<SyncSender<Arc<str>>> = Vec::new();
+    let mut last: Option<Arc<str>> = None;
+    loop {
+        match rx.recv_timeout(ACCEPT_INTERVAL) {
+            Ok(line) => {
+                let line: Arc<str> = line.into();
+                followers.retain(|follower| match follower.try_send(Arc::clone(&line)) {
+                    Ok(()) => true,
+                    Err(TrySendError::Full(_)) => {
+                        log::warn!("Dropping stalled squad follower");
+                        false
+                    }
+                    Err(TrySendError::Disconnected(_)) => false,
+                });
+                last = Some(line);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        while let Ok((stream, addr)) = listener.accept() {
+            let setup = stream
+                .set_nonblocking(false)
+                .and_then(|()| stream.set_write_timeout(Some(Duration::from_secs(1))))
+                .and_then(|()| stream.set_nodelay(true));
+            if let Err(err) = setup {
+                log::warn!("Failed to set up squad follower {addr}: {err}");
+                continue;
+            }
+            let (tx, rx) = mpsc::sync_channel(FOLLOWER_BACKLOG);
+            // Late joiners start from the current view
+            if let Some(line) = &last {
+                let _ = tx.try_send(Arc::clone(line));
+            }
+            thread::spawn(move || run_writer(stream, &rx));
+            log::info!("Squad follower joined from {addr}");
+            followers.push(tx);
+        }
+
+        if count.swap(followers.len(), Ordering::Relaxed) != followers.len() {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// Writes updates to one follower until it disconnects or the host stops.
+fn run_writer(mut stream: TcpStream, rx: &Receiver<Arc<str>>) {
+    for line in rx {
+        if stream.write_all(line.as_bytes()).is_err() {
+            break;
+        }
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+/// Message from the follower's connection thread.
+enum FollowerEvent {
+    /// Connected; the stream is kept to close the connection when leaving.
+    Connected(TcpStream),
+    View(SharedView),
+    Disconnected(String),
+}
+
+/// Follows a leader's view, unless paused by a local change.
+pub struct SquadFollower {
+    leader: String,
+    rx: Receiver<FollowerEvent>,
+    stream: Option<TcpStream>,
+    /// Latest view received from the leader.
+    leader_view: Option<SharedView>,
+    /// `false` while a local pan, zoom or map switch overrides the leader.
+    following: bool,
+    /// View after the last applied update, for detecting local changes.
+    settled: Option<MapView>,
+}
+
+impl SquadFollower {
+    fn join(ctx: egui::Context, leader: &str) -> Self {
+        let address = if leader.contains(':') {
+            leader.to_owned()
+        } else {
+            format!("{leader}:{SQUAD_PORT}")
+        };
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            if let Err(err) = run_follower(&address, &tx, &ctx) {
+                let _ = tx.send(FollowerEvent::Disconnected(err.to_string()));
+            }
+            ctx.request_repaint();
+        });
+        Self {
+            leader: leader.to_owned(),
+            rx,
+            stream: None,
+            leader_view: None,
+            following: true,
+            settled: None,
+        }
+    }
+}
+
+impl Drop for SquadFollower {
+    fn drop(&mut self) {
+        if let Some(stream) = &self.stream {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// Connects to the leader and forwards its view updates until the connection ends.
+fn run_follower(address: &str, tx: &Sender<FollowerEvent>, ctx: &egui::Context) -> io::Result<()> {
+    let addr = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "address not found"))?;
+    let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+    let _ = tx.send(FollowerEvent::Connected(stream.try_clone()?));
+    ctx.request_repaint();
+
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        // Bounded so a misbehaving peer can't make the line grow without end
+        (&mut reader)
+            .take(MAX_LINE_BYTES)
+            .read_until(b'\n', &mut line)?;
+        if line.is_empty() {
+            break;
+        }
+        if line.last() != Some(&b'\n') && line.len() as u64 >= MAX_LINE_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "view update too long",
+            ));
+        }
+        match serde_json::from_slice(&line) {
+            Ok(view) => {
+                if tx.send(FollowerEvent::View(view)).is_err() {
+                    return Ok(());
+                }
+                ctx.request_repaint();
+            }
+            Err(err) => log::warn!("Ignoring squad view update: {err}"),
+        }
+    }
+    let _ = tx.send(FollowerEvent::Disconnected(
+        "the leader ended the session".to_owned(),
+    ));
+    Ok(())
+}
+
+/// This computer's address on the local network, for followers to join.
+fn local_address() -> Option<std::net::IpAddr> {
+    // Connecting a UDP socket sends nothing, but picks the outgoing interface
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("192.0.2.1", 80)).ok()?;
+    Some(socket.local_addr().ok()?.ip())
+}
+
+/// The user's role in a squad session.
+pub enum SquadSession {
+    Leading(SquadHost),
+    Following(SquadFollower),
+}
+
+impl TarkovMapApp {
+    fn squad_toast(&mut self, kind: ToastKind, text: String) {
+        self.toasts.add(Toast {
+            kind,
+            text: text.into(),
+            options: ToastOptions::default().duration_in_seconds(6.0),
+            ..Default::default()
+        });
+    }
+
+    /// Sends the leader's view, or applies the leader's view while following.
+    pub fn poll_squad(&mut self, ctx: &egui::Context) {
+        match &self.squad {
+            None => {}
+            Some(SquadSession::Leading(_)) => {
+                let Some(map) = self.maps.get(self.selected_map) else {
+                    return;
+                };
+                let view = SharedView {
+                    map: map.normalized_name.clone(),
+                    view: self.pending_view.unwrap_or_else(|| self.current_view()),
+                    layer: self.selected_layer_name(),
+                };
+                if let Some(SquadSession::Leading(host)) = &mut self.squad {
+                    host.send(&view);
+                }
+            }
+            Some(SquadSession::Following(_)) => self.poll_follower(ctx),
+        }
+    }
+
+    fn poll_follower(&mut self, ctx: &egui::Context) {
+        let Some(SquadSession::Following(follower)) = &mut self.squad else {
+            return;
+        };
+        let mut update = None;
+        let mut disconnected = None;
+        for event in follower.rx.try_iter() {
+            match event {
+                FollowerEvent::Connected(stream) => follower.stream = Some(stream),
+                FollowerEvent::View(view) => update = Some(view),
+                FollowerEvent::Disconnected(reason) => disconnected = Some(reason),
+            }
+        }
+        let apply = update.is_some() && follower.following;
+        if let Some(view) = update {
+            follower.leader_view = Some(view);
+        }
+        let disconnected =
+            disconnected.map(|reason| format!("Stopped following {}: {reason}", follower.leader));
+
+        if apply {
+            self.apply_leader_view(ctx);
+        }
+        if let Some(text) = disconnected {
+            self.squad = None;
+            self.squad_toast(ToastKind::Warning, text);
+            return;
+        }
+        self.detect_local_override();
+    }
+
+    /// Moves the view to the leader's map and view.
+    fn apply_leader_view(&mut self, ctx: &egui::Context) {
+        let Some(SquadSession::Following(follower)) = &mut self.squad else {
+            return;
+        };
+        let Some(shared) = follower.leader_view.clone() else {
+            return;
+        };
+        follower.following = true;
+        follower.settled = None;
+        if let Some(idx) = self
+            .maps
+            .iter()
+            .position(|map| map.normalized_name == shared.map)
+        {
+            self.select_map(idx);
+        }
+        self.select_layer_by_name(shared.layer.as_deref());
+        self.pending_view = Some(shared.view);
+        // Record the settled view on the next frame
+        ctx.request_repaint();
+    }
+
+    /// Pauses following once the user moves the view away from the leader's.
+    fn detect_local_override(&mut self) {
+        let view = self.current_view();
+        let selected = self.maps.get(self.selected_map);
+        let Some(SquadSession::Following(follower)) = &mut self.squad else {
+            return;
+        };
+        if !follower.following || self.pending_view.is_some() {
+            return;
+        }
+        let Some(shared) = &follower.leader_view else {
+            return;
+        };
+        let on_leader_map = selected.is_some_and(|map| map.normalized_name == shared.map);
+
+        let Some(settled) = follower.settled else {
+            follower.settled = Some(view);
+            return;
+        };
+        let moved = (view.center[0] - settled.center[0]).abs() > VIEW_EPSILON
+            || (view.center[1] - settled.center[1]).abs() > VIEW_EPSILON
+            || (view.zoom - settled.zoom).abs() > VIEW_EPSILON;
+        if moved || !on_leader_map {
+            follower.following = false;
+        }
+    }
+
+    fn host_squad(&mut self, ctx: &egui::Context) {
+        let address = self.squad_bind_address.trim();
+        let address = if address.is_empty() {
+            local_address()
+        } else {
+            address.parse().ok()
+        };
+        let Some(address) = address else {
+            self.squad_toast(
+                ToastKind::Error,
+                "Failed to host: no valid address to listen on".to_owned(),
+            );
+            return;
+        };
+        match SquadHost::start(ctx.clone(), address) {
+            Ok(host) => self.squad = Some(SquadSession::Leading(host)),
+            Err(err) => self.squad_toast(
+                ToastKind::Error,
+                format!("Failed to host on {address}:{SQUAD_PORT}: {err}"),
+            ),
+        }
+    }
+
+    /// Renders the Squad View window while it is open.
+    pub fn show_squad_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.squad_open;
+        egui::Window::new("Squad View")
+            .open(&mut open)
+            .default_width(240.0)
+            .resizable(false)
+            .show(ctx, |ui| match self.squad {
+                None => self.show_squad_setup(ui),
+                Some(SquadSession::Leading(_)) => self.show_squad_leading(ui),
+                Some(SquadSession::Following(_)) => self.show_squad_following(ui),
+            });
+        self.squad_open = open;
+    }
+
+    fn show_squad_setup(&mut self, ui: &mut egui::Ui) {
+        ui.label("Let one person drive the map for everyone, e.g. during a briefing.");
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.squad_bind_address)
+                    .hint_text("LAN address")
+                    .desired_width(140.0),
+            )
+            .on_hover_text("Address to lead from, e.g. 0.0.0.0 for every network");
+            if ui.button("Lead").clicked() {
+                self.host_squad(ui.ctx());
+            }
+        });
+        ui.separator();
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.squad_address)
+                    .hint_text("Leader's address")
+                    .desired_width(140.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let address = self.squad_address.trim().to_owned();
+            if (ui.button("Follow").clicked() || submitted) && !address.is_empty() {
+                let follower = SquadFollower::join(ui.ctx().clone(), &address);
+                self.squad = Some(SquadSession::Following(follower));
+            }
+        });
+    }
+
+    fn show_squad_leading(&mut self, ui: &mut egui::Ui) {
+        let Some(SquadSession::Leading(host)) = &self.squad else {
+            return;
+        };
+        let address = if host.address.is_unspecified() {
+            local_address()
+        } else {
+            Some(host.address)
+        };
+        match address {
+            Some(ip) => ui.label(format!("Others follow {ip}")),
+            None => ui.label(format!(
+                "Others follow this computer's address, port {SQUAD_PORT}"
+            )),
+        };
+        ui.weak(match host.followers() {
+            1 => "1 follower".to_owned(),
+            count => format!("{count} followers"),
+        });
+        if ui.button("Stop Leading").clicked() {
+            self.squad = None;
+        }
+    }
+
+    fn show_squad_following(&mut self, ui: &mut egui::Ui) {
+        let Some(SquadSession::Following(follower)) = &self.squad else {
+            return;
+        };
+        let connected = follower.stream.is_some();
+        let following = follower.following;
+        ui.label(if connected {
+            format!("Following {}", follower.leader)
+        } else {
+            format!("Connecting to {}...", follower.leader)
+        });
+        if !following {
+            ui.weak("Paused while you look around.");
+            if ui.button("Resume Following").clicked() {
+                self.apply_leader_view(ui.ctx());
+            }
+        }
+        if ui.button("Leave").clicked() {
+            self.squad = None;
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Hover details for switch markers, linking them to the extracts and switches they operate.
+
+use crate::TarkovMapApp;
+use crate::colors;
+use crate::coordinates::game_to_display;
+use crate::marker_picking::{MarkerHit, MarkerKind};
+use eframe::egui;
+use tarkov_map::{Map, SwitchTarget};
+
+impl TarkovMapApp {
+    /// Shows a tooltip for the picked switch at `index` and draws dashed lines to the
+    /// extracts and switches it operates.
+    pub fn show_switch_hover(
+        &self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        map: &Map,
+        map_rect: egui::Rect,
+        index: usize,
+    ) {
+        let Some(switch) = map.switches.iter().flatten().nth(index) else {
+            return;
+        };
+        let Some(switch_pos) = game_to_display(map, map_rect, switch.position.xz()) else {
+            return;
+        };
+
+        let stroke = egui::Stroke::new(2.0, colors::SWITCH);
+        let targets = map
+            .switch_extracts(switch)
+            .filter_map(|extract| extract.center())
+            .chain(
+                map.switch_targets(switch)
+                    .map(|target| target.position.xz()),
+            );
+        for target in targets {
+            let Some(target_pos) = game_to_display(map, map_rect, target) else {
+                continue;
+            };
+            ui.painter().extend(egui::Shape::dashed_line(
+                &[switch_pos, target_pos],
+                stroke,
+                8.0,
+                5.0,
+            ));
+            ui.painter().circle_stroke(target_pos, 12.0, stroke);
+        }
+
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            ui.strong(&switch.name);
+            if let Some(switch_type) = &switch.switch_type {
+                ui.label(format!("Type: {switch_type}"));
+            }
+            if let Some(activated_by) = &switch.activated_by {
+                ui.label(format!("Needs {activated_by} first"));
+            }
+            for extract in &switch.extracts {
+                ui.label(format!("Opens {extract}"));
+            }
+            for operation in &switch.operations {
+                if let SwitchTarget::Switch(target) = &operation.target {
+                    let verb = operation.operation.as_deref().unwrap_or("Activates");
+                    ui.label(format!("{verb} {target}"));
+                }
+            }
+            let p = switch.position;
+            ui.weak(self.coordinate_convention.format(p.x, Some(p.y), p.z));
+            let hit = MarkerHit {
+                kind: MarkerKind::Switch,
+                index,
+            };
+            self.show_marker_note(ui, map, hit);
+            self.marker_picking.cycle_hint(ui);
+        });
+    }
+}
@@ -2,15 +2,36 @@
 
 use crate::TarkovMapApp;
 use crate::colors;
+use crate::compare::draw_changes;
 use crate::constants::{SIDEBAR_WIDTH, TITLE_BAR_HEIGHT, ZOOM_MAX, ZOOM_MIN, ZOOM_SPEED};
-use crate::overlays::{draw_extracts, draw_labels, draw_player_marker, draw_spawns};
+use crate::coordinates::{CoordinateConvention, game_to_display};
+use crate::custom_maps::{CustomMapWizard, custom_maps_dir};
+use crate::icon_pack::{available_icon_packs, icon_packs_dir};
+use crate::map_detect::MapDetection;
+use crate::marker_picking::{MarkerHit, MarkerKind};
+use crate::overlays::{OverlayContext, draw_temp_marker};
+use crate::print_export::PaperSize;
+use crate::raid_time::show_raid_time;
+use crate::safe_mode::relaunch;
+use crate::screenshot_watcher::{CleanupAge, CleanupMode};
+use crate::zoom::ZoomAnchor;
 use crate::{APP_TITLE, APP_VERSION};
 use eframe::egui::{self, ViewportCommand};
+use std::fs;
 use tarkov_map::Map;
 
 impl TarkovMapApp {
     /// Handles keyboard shortcuts for zoom and overlay toggles.
     pub fn handle_keyboard_input(&mut self, ctx: &egui::Context) {
+        // Don't treat typing in text fields (notes, bookmark names) as shortcuts
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::F11)) {
+            self.toggle_kiosk(ctx);
+        }
+
         ctx.input(|i| {
             if i.key_pressed(egui::Key::Plus) || i.key_pressed(egui::Key::Equals) {
                 self.zoom = (self.zoom * ZOOM_SPEED).clamp(ZOOM_MIN, ZOOM_MAX);
@@ -24,41 +45,17 @@ impl TarkovMapApp {
             if i.key_pressed(egui::Key::L) {
                 self.overlays.labels = !self.overlays.labels;
             }
+            if i.key_pressed(egui::Key::R) {
+                self.toggle_raid_mode();
+            }
+            self.handle_bookmark_keys(i);
+            if i.key_pressed(egui::Key::Escape) {
+                self.measurement.clear();
+                self.temp_marker = None;
+            }
         });
     }
 
-    /// Renders the bottom status bar with controls hint and map author info.
-    pub fn show_status_bar(&self, ctx: &egui::Context, selected_map: &Option<Map>) {
-        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Scroll: Zoom | Drag: Pan | +/-: Zoom | 0: Fit | L: Labels");
-
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if let Some(map) = selected_map {
-                        if let Some(link) = &map.author_link {
-                            ui.hyperlink_to(map.author.as_deref().unwrap_or("Map author"), link);
-                            ui.label("Map by:");
-                        } else if let Some(author) = &map.author {
-                            ui.label(format!("Map by: {author}"));
-                        }
-                    }
-                });
-            });
-        });
-    }
-
-    /// Renders the left sidebar panel.
-    pub fn show_sidebar(&mut self, ctx: &egui::Context) {
-        egui::SidePanel::left("sidebar")
-            .exact_width(SIDEBAR_WIDTH)
-            .resizable(false)
-            .show(ctx, |ui| {
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    self.show_sidebar_content(ui);
-                });
-            });
-    }
-
     /// Renders the sidebar content: map selector and overlay toggles.
     fn show_sidebar_content(&mut self, ui: &mut egui::Ui) {
         ui.add_space(4.0);
@@ -70,161 +67,88 @@ impl TarkovMapApp {
         if self.maps.is_empty() {
             ui.label("No maps loaded");
         } else {
-            let prev_selected = self.selected_map;
+            let mut clicked = None;
+            let mut hovered = None;
             for (idx, map) in self.maps.iter().enumerate() {
-                if ui
-                    .selectable_label(self.selected_map == idx, &map.name)
-                    .clicked()
-                {
-                    self.selected_map = idx;
+                if self.is_map_variant(idx) {
+                    continue;
+                }
+                let response = ui.selectable_label(
+                    self.selected_map == idx,
+                    map.localized_name(self.language.as_deref()),
+                );
+                if response.clicked() {
+                    clicked = Some(idx);
+                }
+                if response.hovered() {
+                    hovered = Some(idx);
+                }
+                if let Some(variant) = self.show_map_variants(ui, idx) {
+                    clicked = Some(variant);
                 }
             }
 
-            if self.selected_map != prev_selected {
-                self.reset_view();
+            self.prewarm_hovered_map(ui.ctx(), hovered);
+            if let Some(idx) = clicked {
+                self.select_map_keeping_variant_view(idx);
             }
+
+            ui.add_space(4.0);
+            self.show_raid_counter(ui);
         }
 
         ui.add_space(12.0);
 
+        // Search section
+        ui.strong("Search");
+        ui.separator();
+        self.show_search(ui);
+
+        ui.add_space(12.0);
+
         // Overlays section
         ui.strong("Overlays");
         ui.separator();
 
-        Self::overlay_toggle_circle(
-            ui,
-            &mut self.overlays.labels,
-            "Labels",
-            egui::Color32::WHITE,
-        );
-        Self::overlay_toggle_circle(
-            ui,
-            &mut self.overlays.spawns,
-            "PMC Spawns",
-            colors::SPAWN_FILL,
-        );
-        Self::overlay_toggle_rect(
-            ui,
-            &mut self.overlays.pmc_extracts,
-            "PMC Extracts",
-            colors::PMC_EXTRACT_FILL,
-        );
-        Self::overlay_toggle_rect(
-            ui,
-            &mut self.overlays.scav_extracts,
-            "Scav Extracts",
-            colors::SCAV_EXTRACT_FILL,
-        );
-        Self::overlay_toggle_rect(
-            ui,
-            &mut self.overlays.shared_extracts,
-            "Shared Extracts",
-            colors::SHARED_EXTRACT_FILL,
-        );
-        Self::overlay_toggle_triangle(
-            ui,
-            &mut self.overlays.player_marker,
-            "Player Position",
-            colors::PLAYER_MARKER_FILL,
-        );
-    }
+        let map = self.maps.get(self.selected_map);
+        for overlay in &mut self.registered_overlays {
+            overlay.settings_ui(ui, &mut self.overlays, map);
+        }
 
-    /// Renders a triangle-style overlay toggle (for player marker).
-    fn overlay_toggle_triangle(
-        ui: &mut egui::Ui,
-        value: &mut bool,
-        label: &str,
-        color: egui::Color32,
-    ) {
-        ui.horizontal(|ui| {
-            ui.checkbox(value, "");
-            let (rect, icon_response) =
-                ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::click());
-            let center = rect.center();
-            // Draw a small triangle pointing up
-            let size = 5.0;
-            let points = vec![
-                center + egui::vec2(0.0, -size),
-                center + egui::vec2(-size * 0.7, size * 0.5),
-                center + egui::vec2(size * 0.7, size * 0.5),
-            ];
-            ui.painter().add(egui::Shape::convex_polygon(
-                points,
-                color,
-                egui::Stroke::new(1.0, color.gamma_multiply(0.5)),
-            ));
-            let label_response = ui
-                .label(label)
-                .interact(egui::Sense::click())
-                .on_hover_cursor(egui::CursorIcon::PointingHand);
-            if icon_response.clicked() || label_response.clicked() {
-                *value = !*value;
-            }
-        });
-    }
+        ui.add_space(12.0);
 
-    /// Renders a circle-style overlay toggle (for spawns, labels).
-    fn overlay_toggle_circle(
-        ui: &mut egui::Ui,
-        value: &mut bool,
-        label: &str,
-        color: egui::Color32,
-    ) {
-        ui.horizontal(|ui| {
-            ui.checkbox(value, "");
-            let (rect, icon_response) =
-                ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::click());
-            let center = rect.center();
-            ui.painter().circle_filled(center, 5.0, color);
-            ui.painter()
-                .circle_stroke(center, 5.0, egui::Stroke::new(1.0, egui::Color32::GRAY));
-            let label_response = ui
-                .label(label)
-                .interact(egui::Sense::click())
-                .on_hover_cursor(egui::CursorIcon::PointingHand);
-            if icon_response.clicked() || label_response.clicked() {
-                *value = !*value;
-            }
-        });
-    }
+        // Bookmarks section
+        ui.strong("Bookmarks");
+        ui.separator();
+        self.show_bookmarks(ui);
 
-    /// Renders a rectangle-style overlay toggle (for extracts).
-    fn overlay_toggle_rect(ui: &mut egui::Ui, value: &mut bool, label: &str, color: egui::Color32) {
-        ui.horizontal(|ui| {
-            ui.checkbox(value, "");
-            let (rect, icon_response) =
-                ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::click());
-            ui.painter().rect_filled(rect, 2.0, color);
-            ui.painter().rect_stroke(
-                rect,
-                2.0,
-                egui::Stroke::new(1.0, color.gamma_multiply(0.5)),
-                egui::StrokeKind::Inside,
-            );
-            let label_response = ui
-                .label(label)
-                .interact(egui::Sense::click())
-                .on_hover_cursor(egui::CursorIcon::PointingHand);
-            if icon_response.clicked() || label_response.clicked() {
-                *value = !*value;
-            }
-        });
-    }
+        self.show_comparison_summary(ui);
 
-    /// Renders the central panel containing the map view.
-    pub fn show_central_panel(&mut self, ctx: &egui::Context, selected_map: Option<Map>) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let Some(map) = selected_map else {
-                ui.centered_and_justified(|ui| {
-                    ui.label("No map data.\nRun `cargo run --bin fetch_maps` to generate assets.");
-                });
-                return;
-            };
+        ui.add_space(12.0);
 
-            let panel_rect = ui.max_rect();
-            self.show_map(ui, ctx, &map);
-            self.show_zoom_controls(ctx, panel_rect);
-        });
+        // Extracts section
+        egui::CollapsingHeader::new(egui::RichText::new("Extracts").strong())
+            .default_open(false)
+            .show(ui, |ui| {
+                self.show_extract_list(ui);
+            });
+
+        // Checklist section
+        egui::CollapsingHeader::new(egui::RichText::new("Checklist").strong())
+            .default_open(false)
+            .show(ui, |ui| {
+                self.show_checklist(ui);
+            });
+
+        // Raid plan section
+        self.show_raid_plan(ui);
+
+        // Map info section
+        egui::CollapsingHeader::new(egui::RichText::new("Map Info").strong())
+            .default_open(false)
+            .show(ui, |ui| {
+                self.show_map_stats(ui);
+            });
     }
 
     /// Renders the floating zoom controls panel.
@@ -260,7 +184,7 @@ impl TarkovMapApp {
     }
 
     /// Renders the map image and overlays.
-    fn show_map(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context, map: &Map) {
+    pub fn show_map(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context, map: &Map) {
         use crate::assets::AssetLoadState;
 
         let image_path = &map.image_path;
@@ -294,92 +218,130 @@ impl TarkovMapApp {
         // Calculate base scale to fit map in viewport at zoom 1.0
         let fit_scale = (viewport_size.x / logical_size.x).min(viewport_size.y / logical_size.y);
 
-        // Handle zoom
-        let zoomed_this_frame = self.handle_scroll_zoom(ui, viewport_rect);
-        if !zoomed_this_frame {
-            self.handle_slider_zoom();
+        // Apply a pending view (needs the viewport size to compute the pan)
+        if let Some(view) = self.pending_view.take() {
+            self.zoom = view.zoom.clamp(ZOOM_MIN, ZOOM_MAX);
+            self.prev_zoom = self.zoom;
+            let display_size = logical_size * fit_scale * self.zoom;
+            self.pan_offset = egui::vec2(
+                (0.5 - view.center[0]) * display_size.x,
+                (0.5 - view.center[1]) * display_size.y,
+            );
         }
 
+        // Handle zoom
+        let unzoomed_rect = egui::Rect::from_center_size(
+            viewport_rect.center() + self.pan_offset,
+            logical_size * fit_scale * self.prev_zoom,
+        );
+        self.handle_zoom(ui, viewport_rect, unzoomed_rect, map);
+
         // Handle drag panning
         if response.dragged() {
             self.pan_offset += response.drag_delta();
         }
 
         let display_size = logical_size * fit_scale * self.zoom;
+        self.clamp_pan(ui, response.dragged(), viewport_size, display_size);
+        self.last_display_size = display_size;
         let map_center = viewport_rect.center() + self.pan_offset;
-        let map_rect = egui::Rect::from_center_size(map_center, display_size);
-
-        ui.set_clip_rect(viewport_rect);
-
-        // Draw map image
-        ui.painter().image(
-            texture_id,
-            map_rect,
-            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
-            egui::Color32::WHITE,
-        );
+        let mut map_rect = egui::Rect::from_center_size(map_center, display_size);
 
-        // Draw overlays
-        let overlays = self.overlays;
-        if overlays.labels
-            && let Some(labels) = &map.labels
+        // Center the view on a requested game position (e.g. from the extract list)
+        if let Some(focus) = self.focus_position.take()
+            && let Some(pos) = game_to_display(map, map_rect, focus)
         {
-            draw_labels(ui, map_rect, map, labels, self.zoom);
+            let delta = viewport_rect.center() - pos;
+            self.pan_offset += delta;
+            map_rect = map_rect.translate(delta);
         }
 
-        if overlays.spawns
-            && let Some(spawns) = &map.spawns
-        {
-            draw_spawns(ui, map_rect, map, spawns, self.zoom);
-        }
+        ui.set_clip_rect(viewport_rect);
 
-        if let Some(extracts) = &map.extracts {
-            draw_extracts(ui, map_rect, map, extracts, self.zoom, &overlays);
+        // Draw map image, dimmed under the selected layer once it is loaded
+        let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        let layer_texture = self
+            .selected_layer_image()
+            .and_then(|path| self.get_texture(path))
+            .map(|texture| texture.id());
+        let base_tint = match layer_texture {
+            Some(_) => colors::MAP_UNDER_LAYER,
+            None => egui::Color32::WHITE,
+        };
+        ui.painter().image(texture_id, map_rect, uv, base_tint);
+        if let Some(layer_texture) = layer_texture {
+            ui.painter()
+                .image(layer_texture, map_rect, uv, egui::Color32::WHITE);
         }
 
-        // Draw player position marker
-        if overlays.player_marker
-            && let Some(player_pos) = &self.player_position
-        {
-            draw_player_marker(ui, map_rect, map, player_pos, self.zoom);
+        // Draw overlays
+        let overlay_ctx = OverlayContext {
+            map,
+            map_rect,
+            zoom: self.zoom,
+            visibility: &self.overlays,
+            icons: &self.icon_pack,
+            player: self.player_position.as_ref(),
+            raid_mode: self.raid_mode,
+            label_style: self.label_style,
+            language: self.language.as_deref(),
+            carried_items: &self.carried_items,
+            player_height: self
+                .player_position
+                .filter(|_| self.follow_player_height)
+                .map(|player| player.position[1]),
+            personal_heatmap: self
+                .personal_heatmap
+                .as_ref()
+                .filter(|heatmap| heatmap.map == map.normalized_name),
+            completed_quests: &self.completed_quests,
+        };
+        for overlay in &self.registered_overlays {
+            overlay.draw(ui, &overlay_ctx);
         }
-    }
 
-    /// Handles scroll wheel zoom, zooming towards the mouse position.
-    fn handle_scroll_zoom(&mut self, ui: &mut egui::Ui, viewport_rect: egui::Rect) -> bool {
-        let hover_pos = ui.input(|i| i.pointer.hover_pos());
-        let scroll_delta = ui.input(|i| i.raw_scroll_delta.y);
-
-        if scroll_delta == 0.0 || !hover_pos.is_some_and(|p| viewport_rect.contains(p)) {
-            return false;
+        if let Some(diff) = self
+            .comparison
+            .as_ref()
+            .and_then(|c| c.diff.map(&map.normalized_name))
+        {
+            draw_changes(ui, map_rect, map, diff);
         }
 
-        let zoom_factor = if scroll_delta > 0.0 {
-            ZOOM_SPEED
-        } else {
-            1.0 / ZOOM_SPEED
-        };
-        let new_zoom = (self.zoom * zoom_factor).clamp(ZOOM_MIN, ZOOM_MAX);
-
-        // Zoom towards mouse position
-        if let Some(hover) = hover_pos {
-            let viewport_center = viewport_rect.center();
-            let mouse_from_center = hover - viewport_center;
-            let map_point = mouse_from_center - self.pan_offset;
-            let zoom_ratio = new_zoom / self.zoom;
-            let new_map_point = map_point * zoom_ratio;
-            self.pan_offset = mouse_from_center - new_map_point;
+        match self.pick_marker(&response, map, map_rect) {
+            Some(MarkerHit {
+                kind: MarkerKind::Extract,
+                index,
+            }) => self.show_extract_tooltip(&response, map, index),
+            Some(MarkerHit {
+                kind: MarkerKind::Switch,
+                index,
+            }) => self.show_switch_hover(ui, &response, map, map_rect, index),
+            Some(MarkerHit {
+                kind: MarkerKind::Boss,
+                index,
+            }) => self.show_boss_tooltip(&response, map, index),
+            Some(MarkerHit {
+                kind: MarkerKind::Lock,
+                index,
+            }) => self.show_lock_tooltip(&response, map, index),
+            Some(MarkerHit {
+                kind: MarkerKind::Label,
+                index,
+            }) => self.show_label_tooltip(&response, map, index),
+            Some(MarkerHit {
+                kind: MarkerKind::Spawn,
+                index,
+            }) => self.show_spawn_tooltip(&response, map, index),
+            None => {}
         }
+        self.show_cursor_readout(ui, &response, map, map_rect, viewport_rect);
+        self.handle_measure_input(ui, &response, map, map_rect);
+        self.draw_measurement(ui, map, map_rect);
+        self.draw_replay(ui, map, map_rect);
 
-        self.zoom = new_zoom;
-        true
-    }
-
-    /// Handles zoom changes from the slider, adjusting pan to zoom from center.
-    fn handle_slider_zoom(&mut self) {
-        let zoom_ratio = self.zoom / self.prev_zoom;
-        if (zoom_ratio - 1.0).abs() > 0.001 {
-            self.pan_offset *= zoom_ratio;
+        if let Some(marker) = self.temp_marker {
+            draw_temp_marker(ui, map_rect, map, marker, self.zoom);
         }
     }
 
@@ -448,7 +410,7 @@ impl TarkovMapApp {
             )
             .show_inside(ui, |ui| {
                 ui.horizontal(|ui| {
-                    ui.label("Scroll: Zoom | Drag: Pan | +/-: Zoom | 0: Fit | L: Labels");
+                    ui.label("Scroll: Zoom | Drag: Pan | +/-: Zoom | 0: Fit | L: Labels | R: Raid type | 1-9: Bookmarks | Right-click: Measure | Alt+click: Next marker");
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         if let Some(map) = &selected_map {
@@ -461,6 +423,14 @@ impl TarkovMapApp {
                             } else if let Some(author) = &map.author {
                                 ui.label(format!("Map by: {author}"));
                             }
+                            ui.separator();
+                        }
+                        self.show_scav_timer(ui);
+                        ui.separator();
+                        show_raid_time(ui);
+                        if self.data_provenance.fetched_at.is_some() {
+                            ui.separator();
+                            self.show_data_freshness(ui);
                         }
                     });
                 });
@@ -488,6 +458,9 @@ impl TarkovMapApp {
             let panel_rect = ui.max_rect();
             self.show_map(ui, &ctx, &map);
             self.show_zoom_controls(&ctx, panel_rect);
+            self.show_layer_controls(&ctx, panel_rect, &map);
+            self.show_measure_panel(&ctx, panel_rect, &map);
+            self.show_extract_routes(&ctx, panel_rect, &map);
         });
     }
 
@@ -546,6 +519,7 @@ impl TarkovMapApp {
             |ui| {
                 ui.add_space(8.0);
                 self.show_menu_bar(ui);
+                self.show_raid_mode_switch(ui);
             },
         );
 
@@ -571,14 +545,69 @@ impl TarkovMapApp {
                     self.clear_settings_on_close = true;
 
                     // Spawn a new instance of the app before closing
-                    if let Ok(exe_path) = std::env::current_exe() {
-                        let _ = std::process::Command::new(exe_path).spawn();
+                    if let Err(err) = relaunch(false) {
+                        log::warn!("Failed to restart after clearing settings: {err}");
                     }
 
                     ui.ctx().send_viewport_cmd(ViewportCommand::Close);
                     ui.close();
                 }
 
+                ui.menu_button("Screenshot Cleanup", |ui| {
+                    self.show_screenshot_cleanup_menu(ui);
+                });
+                ui.menu_button("Screenshot Detection", |ui| {
+                    self.show_screenshot_polling_menu(ui);
+                });
+
+                if ui
+                    .add_enabled(
+                        self.selected_map().is_some(),
+                        egui::Button::new("Export GeoJSON"),
+                    )
+                    .clicked()
+                {
+                    self.export_geojson();
+                    ui.close();
+                }
+
+                ui.add_enabled_ui(
+                    self.selected_map().is_some() && !self.print_export_running(),
+                    |ui| {
+                        ui.menu_button("Print Export", |ui| {
+                            for paper in PaperSize::ALL {
+                                if ui.button(format!("{} at 300 DPI", paper.label())).clicked() {
+                                    self.start_print_export(ui.ctx(), paper);
+                                    ui.close();
+                                }
+                            }
+                        });
+                    },
+                );
+
+                if ui.button("Data Packs...").clicked() {
+                    self.open_data_packs();
+                    ui.close();
+                }
+
+                ui.menu_button("Custom Maps", |ui| {
+                    if ui.button("Add Custom Map...").clicked() {
+                        self.custom_map_wizard = Some(CustomMapWizard::default());
+                        ui.close();
+                    }
+                    if ui.button("Reload Custom Maps").clicked() {
+                        self.reload_custom_maps();
+                        ui.close();
+                    }
+                    if ui.button("Open Custom Maps Folder").clicked()
+                        && let Some(dir) = custom_maps_dir()
+                    {
+                        let _ = fs::create_dir_all(&dir);
+                        let _ = open::that(dir);
+                        ui.close();
+                    }
+                });
+
                 ui.separator();
 
                 if ui.button("Exit").clicked() {
@@ -587,8 +616,127 @@ impl TarkovMapApp {
                 }
             });
 
+            // View menu
+            ui.menu_button("View", |ui| {
+                if ui.button("Kiosk Mode (F11)").clicked() {
+                    self.toggle_kiosk(ui.ctx());
+                    ui.close();
+                }
+                ui.checkbox(
+                    &mut self.kiosk_settings.start_in_kiosk,
+                    "Start in Kiosk Mode",
+                )
+                .on_hover_text("Opens fullscreen on the monitor kiosk mode was last used on");
+                self.kiosk_monitor_ui(ui);
+
+                ui.separator();
+
+                let mut watch_clipboard = self.clipboard_watcher.is_some();
+                if ui
+                    .checkbox(&mut watch_clipboard, "Watch Clipboard for Coordinates")
+                    .changed()
+                {
+                    self.set_watch_clipboard(ui.ctx(), watch_clipboard);
+                }
+
+                ui.checkbox(&mut self.raid_checklist.open, "Raid Checklist");
+                if ui.button("Raid History").clicked() {
+                    self.raid_history_open = true;
+                    ui.close();
+                }
+                if ui.button("Sessions").clicked() {
+                    self.open_session_browser();
+                    ui.close();
+                }
+                ui.checkbox(&mut self.squad_open, "Squad View");
+
+                ui.menu_button("Detect Map from Position", |ui| {
+                    ui.radio_value(&mut self.map_detection, MapDetection::Off, "Off");
+                    ui.radio_value(&mut self.map_detection, MapDetection::Ask, "Ask first");
+                    ui.radio_value(
+                        &mut self.map_detection,
+                        MapDetection::Auto,
+                        "Switch automatically",
+                    );
+                });
+
+                ui.menu_button("Coordinate Format", |ui| {
+                    let previous = self.coordinate_convention;
+                    for convention in CoordinateConvention::ALL {
+                        ui.radio_value(
+                            &mut self.coordinate_convention,
+                            convention,
+                            convention.label(),
+                        );
+                    }
+                    ui.weak("Used for display, copying and pasting");
+                    // The clipboard watcher reads pairs in the selected format
+                    if self.coordinate_convention != previous && self.clipboard_watcher.is_some() {
+                        self.set_watch_clipboard(ui.ctx(), true);
+                    }
+                });
+
+                ui.menu_button("Zoom Toward", |ui| {
+                    for anchor in ZoomAnchor::ALL {
+                        ui.radio_value(&mut self.zoom_anchor, anchor, anchor.label());
+                    }
+                    ui.weak("Used by +/- and the zoom slider");
+                });
+
+                ui.menu_button("Panning", |ui| {
+                    self.pan_clamp.settings_ui(ui);
+                });
+
+                ui.menu_button("Marker Picking", |ui| {
+                    self.marker_picking.settings_ui(ui);
+                });
+
+                ui.menu_button("Language", |ui| {
+                    self.language_ui(ui);
+                });
+
+                ui.menu_button("Label Style", |ui| {
+                    self.label_style.settings_ui(ui);
+                });
+
+                ui.menu_button("Icon Pack", |ui| {
+                    if ui
+                        .radio(self.icon_pack_name.is_none(), "Built-in")
+                        .clicked()
+                    {
+                        self.set_icon_pack(ui.ctx(), None);
+                        ui.close();
+                    }
+                    for pack in available_icon_packs() {
+                        let selected = self.icon_pack_name.as_deref() == Some(pack.as_str());
+                        if ui.radio(selected, &pack).clicked() {
+                            self.set_icon_pack(ui.ctx(), Some(pack));
+                            ui.close();
+                        }
+                    }
+
+                    ui.separator();
+
+                    if ui.button("Open Icon Pack Folder").clicked() {
+                        if let Some(dir) = icon_packs_dir() {
+                            let _ = std::fs::create_dir_all(&dir);
+                            let _ = open::that(dir);
+                        }
+                        ui.close();
+                    }
+                });
+            });
+
             // Help menu
             ui.menu_button("Help", |ui| {
+                if ui.button("Health Check").clicked() {
+                    self.start_health_check(ui.ctx());
+                    ui.close();
+                }
+                if ui.button("Usage Stats").clicked() {
+                    self.usage_stats_open = true;
+                    ui.close();
+                }
                 if ui.button("GitHub").clicked() {
                     let _ = open::that("https://github.com/teevik/tarkov-map");
                     ui.close();
@@ -597,6 +745,72 @@ impl TarkovMapApp {
         });
     }
 
+    /// Renders the screenshot cleanup policy controls.
+    fn show_screenshot_cleanup_menu(&mut self, ui: &mut egui::Ui) {
+        let cleanup = &mut self.screenshot_cleanup;
+        ui.radio_value(&mut cleanup.mode, CleanupMode::Off, "Off");
+        ui.radio_value(
+            &mut cleanup.mode,
+            CleanupMode::Delete,
+            "Delete old screenshots",
+        );
+        ui.radio_value(
+            &mut cleanup.mode,
+            CleanupMode::Archive,
+            "Move to archive folder",
+        );
+
+        ui.add_enabled_ui(cleanup.mode != CleanupMode::Off, |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut cleanup.age, CleanupAge::Minutes, "Older than");
+                ui.add(
+                    egui::DragValue::new(&mut cleanup.max_age_minutes)
+                        .range(5..=10_080)
+                        .suffix(" min"),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut cleanup.age, CleanupAge::Raids, "Before the last");
+                ui.add(
+                    egui::DragValue::new(&mut cleanup.keep_raids)
+                        .range(1..=100)
+                        .suffix(" raids"),
+                );
+            });
+        });
+        ui.weak("Only position screenshots are touched.");
+    }
+
+    /// Renders the screenshot watching/polling controls.
+    fn show_screenshot_polling_menu(&mut self, ui: &mut egui::Ui) {
+        let polling = &mut self.screenshot_polling;
+        let mut changed = ui
+            .checkbox(&mut polling.always_poll, "Poll folder instead of watching")
+            .on_hover_text("For Documents folders on network drives or OneDrive")
+            .changed();
+        ui.horizontal(|ui| {
+            ui.label("Poll every");
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut polling.interval_secs)
+                        .range(1..=60)
+                        .suffix(" s"),
+                )
+                .changed();
+        });
+
+        let status = match &self.screenshot_watcher {
+            Some(watcher) if watcher.is_polling() => "Polling the screenshots folder",
+            Some(_) => "Watching the screenshots folder",
+            None => "Screenshots folder not found",
+        };
+        ui.weak(status);
+
+        if changed {
+            self.restart_screenshot_watcher(ui.ctx());
+        }
+    }
+
     /// Renders Windows-style window control buttons (minimize, maximize/restore, close).
     fn window_controls(ui: &mut egui::Ui, is_maximized: bool, corner_radius: f32) {
         let button_width = 46.0;
@@ -1,3 +1,4 @@
+use crate::safe_mode::clear_running_marker;
 use eframe::egui;
 use egui_toast::{Toast, ToastKind, ToastOptions, Toasts};
 use std::sync::mpsc;
@@ -287,6 +288,7 @@ fn spawn_update_install(
 }
 
 fn restart_self() -> std::io::Result<()> {
+    clear_running_marker();
     let exe = std::env::current_exe()?;
 
     let mut cmd = std::process::Command::new(exe);
@@ -296,13 +298,14 @@ fn restart_self() -> std::io::Result<()> {
     Ok(())
 }
 
-fn render_action_toast(
+/// Renders a toast with an action button that sends `action` when clicked.
+pub fn render_action_toast<C>(
     ui: &mut egui::Ui,
     toast: &mut Toast,
     icon: egui::WidgetText,
     action_label: &str,
-    action: Command,
-    cmd_tx: &mpsc::Sender<Command>,
+    action: C,
+    cmd_tx: &mpsc::Sender<C>,
 ) -> egui::Response {
     let inner_margin = 10.0;
     let frame = egui::Frame::window(ui.style());
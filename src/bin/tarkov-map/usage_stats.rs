@@ -0,0 +1,266 @@
+//! Opt-in usage statistics, kept only on this machine.
+//!
+//! Nothing is sent anywhere. When enabled, time per map, map opens, overlay
+//! usage and feature use are counted and written to
+//! `<data dir>/tarkov-map/usage.ron`, viewable under Help > Usage Stats.
+
+use crate::APP_ID;
+use crate::TarkovMapApp;
+use crate::overlays::OverlayVisibility;
+use eframe::egui;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Features counted by [`UsageStats::record_feature`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Feature {
+    Measure,
+    Search,
+    Bookmark,
+    ExtractList,
+    KioskMode,
+    RaidModeSwitch,
+    Sessions,
+    Comparison,
+    Photos,
+}
+
+impl Feature {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Measure => "Measure tool",
+            Self::Search => "Search",
+            Self::Bookmark => "Bookmarks",
+            Self::ExtractList => "Extract list",
+            Self::KioskMode => "Kiosk mode",
+            Self::RaidModeSwitch => "Raid type switch",
+            Self::Sessions => "Sessions window",
+            Self::Comparison => "Data comparison",
+            Self::Photos => "Photo attachments",
+        }
+    }
+}
+
+/// Accumulated usage counters.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageStats {
+    /// Seconds the app was open on each map, by normalized name.
+    pub map_seconds: BTreeMap<String, f64>,
+    /// Times each map was selected.
+    pub map_opens: BTreeMap<String, u32>,
+    /// Seconds each overlay was enabled.
+    pub overlay_seconds: BTreeMap<String, f64>,
+    pub features: BTreeMap<Feature, u32>,
+}
+
+fn usage_stats_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("usage.ron"))
+}
+
+/// Overlay names and whether each is enabled.
+fn enabled_overlays(visibility: &OverlayVisibility) -> [(&'static str, bool); 9] {
+    [
+        ("Labels", visibility.labels),
+        ("PMC Spawns", visibility.spawns),
+        ("Scav Spawns", visibility.scav_spawns),
+        ("AI Spawns", visibility.ai_spawns),
+        ("PMC Extracts", visibility.pmc_extracts),
+        ("Scav Extracts", visibility.scav_extracts),
+        ("Shared Extracts", visibility.shared_extracts),
+        ("Player Position", visibility.player_marker),
+        ("Elevation", visibility.elevation),
+    ]
+}
+
+/// Formats seconds as e.g. `3 h 12 min`.
+fn format_duration(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).round() as u64;
+    match minutes {
+        0..60 => format!("{minutes} min"),
+        _ => format!("{} h {} min", minutes / 60, minutes % 60),
+    }
+}
+
+impl UsageStats {
+    /// Loads the stored statistics, or empty ones if there are none.
+    pub fn load() -> Self {
+        usage_stats_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| {
+                ron::from_str(&text)
+                    .inspect_err(|err| log::warn!("Failed to parse usage stats: {err}"))
+                    .ok()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let Some(path) = usage_stats_path() else {
+            return;
+        };
+        let result = ron::ser::to_string_pretty(self, PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+                }
+                fs::write(&path, text).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            log::warn!("Failed to save usage stats: {err}");
+        }
+    }
+
+    pub fn record_map_open(&mut self, map: &str) {
+        *self.map_opens.entry(map.to_owned()).or_default() += 1;
+    }
+
+    pub fn record_feature(&mut self, feature: Feature) {
+        *self.features.entry(feature).or_default() += 1;
+    }
+}
+
+/// Usage tracking state: the statistics and when they were last advanced.
+pub struct UsageTracker {
+    pub enabled: bool,
+    pub stats: UsageStats,
+    last_tick: Instant,
+}
+
+impl UsageTracker {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            stats: if enabled {
+                UsageStats::load()
+            } else {
+                UsageStats::default()
+            },
+            last_tick: Instant::now(),
+        }
+    }
+
+    /// Counts a map being selected, if tracking is enabled.
+    pub fn map_opened(&mut self, map: &str) {
+        if self.enabled {
+            self.stats.record_map_open(map);
+        }
+    }
+
+    /// Counts a feature being used, if tracking is enabled.
+    pub fn feature_used(&mut self, feature: Feature) {
+        if self.enabled {
+            self.stats.record_feature(feature);
+        }
+    }
+
+    /// Adds the time since the last tick to the current map and enabled overlays.
+    fn tick(&mut self, map: Option<&str>, visibility: &OverlayVisibility) {
+        let seconds = self.last_tick.elapsed().as_secs_f64();
+        self.last_tick = Instant::now();
+        if !self.enabled {
+            return;
+        }
+
+        if let Some(map) = map {
+            *self.stats.map_seconds.entry(map.to_owned()).or_default() += seconds;
+        }
+        for (name, enabled) in enabled_overlays(visibility) {
+            if enabled {
+                *self
+                    .stats
+                    .overlay_seconds
+                    .entry(name.to_owned())
+                    .or_default() += seconds;
+            }
+        }
+    }
+}
+
+impl TarkovMapApp {
+    /// Advances usage time tracking. Called once per frame.
+    pub fn track_usage(&mut self) {
+        let map = self.selected_map().map(|map| map.normalized_name.clone());
+        self.usage.tick(map.as_deref(), &self.overlays);
+    }
+
+    /// Renders the Usage Stats window while it is open.
+    pub fn show_usage_stats_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.usage_stats_open;
+        egui::Window::new("Usage Stats")
+            .open(&mut open)
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                if ui
+                    .checkbox(&mut self.usage.enabled, "Track usage on this computer")
+                    .on_hover_text("Statistics are stored locally and never sent anywhere")
+                    .changed()
+                    && self.usage.enabled
+                {
+                    self.usage.stats = UsageStats::load();
+                }
+                ui.separator();
+
+                let stats = &self.usage.stats;
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.strong("Maps");
+                    let mut maps: Vec<_> = stats.map_seconds.iter().collect();
+                    maps.sort_by(|a, b| b.1.total_cmp(a.1));
+                    egui::Grid::new("usage_maps").striped(true).show(ui, |ui| {
+                        for (map, seconds) in maps {
+                            let name = self
+                                .maps
+                                .iter()
+                                .find(|m| &m.normalized_name == map)
+                                .map_or(map.as_str(), |m| m.name.as_str());
+                            let opens = stats.map_opens.get(map).copied().unwrap_or(0);
+                            ui.label(name);
+                            ui.label(format_duration(*seconds));
+                            ui.label(format!("{opens} opens"));
+                            ui.end_row();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                    ui.strong("Overlays");
+                    let mut overlays: Vec<_> = stats.overlay_seconds.iter().collect();
+                    overlays.sort_by(|a, b| b.1.total_cmp(a.1));
+                    egui::Grid::new("usage_overlays")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (overlay, seconds) in overlays {
+                                ui.label(overlay);
+                                ui.label(format_duration(*seconds));
+                                ui.end_row();
+                            }
+                        });
+
+                    ui.add_space(8.0);
+                    ui.strong("Features");
+                    let mut features: Vec<_> = stats.features.iter().collect();
+                    features.sort_by(|a, b| b.1.cmp(a.1));
+                    egui::Grid::new("usage_features")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (feature, count) in features {
+                                ui.label(feature.label());
+                                ui.label(format!("{count}×"));
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                ui.separator();
+                if ui.button("Reset").clicked() {
+                    self.usage.stats = UsageStats::default();
+                    self.usage.stats.save();
+                }
+            });
+        self.usage_stats_open = open;
+    }
+}
@@ -0,0 +1,100 @@
+//! Zooming around an anchor point that stays fixed on screen.
+//!
+//! Scroll zoom always anchors at the pointer. Keyboard and slider zoom anchor
+//! at the point chosen in View > Zoom Toward.
+
+use crate::TarkovMapApp;
+use crate::constants::{ZOOM_MAX, ZOOM_MIN, ZOOM_SPEED};
+use crate::coordinates::game_to_display;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use tarkov_map::Map;
+
+/// Point that keyboard and slider zoom keep fixed on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ZoomAnchor {
+    #[default]
+    Center,
+    Cursor,
+    /// The player marker, or the center when the position is unknown.
+    Player,
+}
+
+impl ZoomAnchor {
+    pub const ALL: [Self; 3] = [Self::Center, Self::Cursor, Self::Player];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Center => "View center",
+            Self::Cursor => "Cursor",
+            Self::Player => "Player marker",
+        }
+    }
+}
+
+impl TarkovMapApp {
+    /// Sets the zoom level, adjusting the pan so `anchor` (a screen position) stays put.
+    fn apply_zoom(&mut self, new_zoom: f32, anchor: egui::Pos2, viewport_rect: egui::Rect) {
+        let new_zoom = new_zoom.clamp(ZOOM_MIN, ZOOM_MAX);
+        let anchor_from_center = anchor - viewport_rect.center();
+        let map_point = anchor_from_center - self.pan_offset;
+        self.pan_offset = anchor_from_center - map_point * (new_zoom / self.zoom);
+        self.zoom = new_zoom;
+    }
+
+    /// Screen position keyboard and slider zoom should keep fixed.
+    fn zoom_anchor(
+        &self,
+        ui: &egui::Ui,
+        viewport_rect: egui::Rect,
+        map_rect: egui::Rect,
+        map: &Map,
+    ) -> egui::Pos2 {
+        let anchor = match self.zoom_anchor {
+            ZoomAnchor::Center => None,
+            ZoomAnchor::Cursor => ui.input(|i| i.pointer.hover_pos()),
+            ZoomAnchor::Player => self.player_position.and_then(|player| {
+                game_to_display(map, map_rect, [player.position[0], player.position[2]])
+            }),
+        };
+        anchor
+            .filter(|pos| viewport_rect.contains(*pos))
+            .unwrap_or(viewport_rect.center())
+    }
+
+    /// Applies zoom from scrolling or, failing that, from the keyboard or slider.
+    ///
+    /// `map_rect` is the map's rectangle before zooming.
+    pub fn handle_zoom(
+        &mut self,
+        ui: &egui::Ui,
+        viewport_rect: egui::Rect,
+        map_rect: egui::Rect,
+        map: &Map,
+    ) {
+        let hover_pos = ui
+            .input(|i| i.pointer.hover_pos())
+            .filter(|pos| viewport_rect.contains(*pos));
+        let scroll_delta = ui.input(|i| i.raw_scroll_delta.y);
+
+        // Keyboard and slider set the zoom directly; replay the change around the anchor
+        let requested_zoom = self.zoom;
+        self.zoom = self.prev_zoom;
+
+        if let Some(hover) = hover_pos
+            && scroll_delta != 0.0
+        {
+            let zoom_factor = if scroll_delta > 0.0 {
+                ZOOM_SPEED
+            } else {
+                1.0 / ZOOM_SPEED
+            };
+            self.apply_zoom(requested_zoom * zoom_factor, hover, viewport_rect);
+        } else if (requested_zoom / self.zoom - 1.0).abs() > 0.001 {
+            let anchor = self.zoom_anchor(ui, viewport_rect, map_rect, map);
+            self.apply_zoom(requested_zoom, anchor, viewport_rect);
+        } else {
+            self.zoom = requested_zoom;
+        }
+    }
+}
@@ -0,0 +1,389 @@
+//! Builders for constructing maps by hand, e.g. for tests or custom map packs.
+//!
+//! Every optional field starts out empty, so only what the map actually has
+//! needs to be set. [`MapBuilder::build`] runs [`Map::validate`] and refuses
+//! maps that would display incorrectly.
+
+use crate::{
+    Extent, ExtentBound, Extract, ExtractItem, Faction, Label, Layer, Map, MapView, Position2,
+    Position3, Spawn, TilePyramid, ValidationWarning,
+};
+use thiserror::Error;
+
+/// Errors that can occur when building a map.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum BuildError {
+    #[error("map has no normalized name")]
+    MissingNormalizedName,
+    #[error("map '{map}' is invalid: {}", join_warnings(.warnings))]
+    Invalid {
+        map: String,
+        warnings: Vec<ValidationWarning>,
+    },
+}
+
+fn join_warnings(warnings: &[ValidationWarning]) -> String {
+    warnings
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Appends `item` to an optional list, creating the list if needed.
+fn push<T>(list: &mut Option<Vec<T>>, item: T) {
+    list.get_or_insert_with(Vec::new).push(item);
+}
+
+/// Builder for a [`Map`].
+///
+/// ```
+/// use tarkov_map::{ExtractBuilder, Faction, MapBuilder};
+///
+/// let map = MapBuilder::new("test")
+///     .name("Test")
+///     .image("test.png", [1000.0, 500.0])
+///     .rotation(180.0)
+///     .bounds([100.0, 0.0], [0.0, 50.0])
+///     .extract(
+///         ExtractBuilder::new("Gate", Faction::Pmc)
+///             .position([50.0, 0.0, 25.0])
+///             .build(),
+///     )
+///     .build()
+///     .unwrap();
+/// // The logical size defaults to the image size
+/// assert_eq!(map.logical_size, [1000.0, 500.0]);
+/// assert!(map.contains([50.0, 25.0]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MapBuilder {
+    map: Map,
+}
+
+impl MapBuilder {
+    pub fn new(normalized_name: impl Into<String>) -> Self {
+        Self {
+            map: Map {
+                normalized_name: normalized_name.into(),
+                name: String::new(),
+                translations: None,
+                image_path: String::new(),
+                image_size: [0.0, 0.0],
+                tiles: None,
+                logical_size: [0.0, 0.0],
+                alt_maps: None,
+                author: None,
+                author_link: None,
+                transform: None,
+                coordinate_rotation: None,
+                bounds: None,
+                default_view: None,
+                height_range: None,
+                layers: None,
+                labels: None,
+                spawns: None,
+                extracts: None,
+                spawn_clusters: None,
+                hazards: None,
+                locks: None,
+                loot_containers: None,
+                bosses: None,
+                stationary_weapons: None,
+                switches: None,
+                transits: None,
+                tasks: None,
+            },
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.map.name = name.into();
+        self
+    }
+
+    /// Sets the map image and its size in pixels.
+    pub fn image(mut self, path: impl Into<String>, size: [f32; 2]) -> Self {
+        self.map.image_path = path.into();
+        self.map.image_size = size;
+        self
+    }
+
+    /// Sets the tile pyramid of the map image.
+    pub fn tiles(mut self, tiles: TilePyramid) -> Self {
+        self.map.tiles = Some(tiles);
+        self
+    }
+
+    /// Sets the size in game units. Defaults to the image size.
+    pub fn logical_size(mut self, size: [f32; 2]) -> Self {
+        self.map.logical_size = size;
+        self
+    }
+
+    pub fn alt_map(mut self, key: impl Into<String>) -> Self {
+        push(&mut self.map.alt_maps, key.into());
+        self
+    }
+
+    pub fn author(mut self, name: impl Into<String>, link: Option<String>) -> Self {
+        self.map.author = Some(name.into());
+        self.map.author_link = link;
+        self
+    }
+
+    /// Sets the transform `[scaleX, translateX, scaleY, translateY]` of 270° maps.
+    pub fn transform(mut self, transform: [f64; 4]) -> Self {
+        self.map.transform = Some(transform);
+        self
+    }
+
+    /// Sets the coordinate rotation in degrees.
+    pub fn rotation(mut self, degrees: f64) -> Self {
+        self.map.coordinate_rotation = Some(degrees);
+        self
+    }
+
+    /// Sets the bounds from two opposite corners `[x, z]`, in the order used by `maps.ron`.
+    pub fn bounds(mut self, a: [f64; 2], b: [f64; 2]) -> Self {
+        self.map.bounds = Some([a, b]);
+        self
+    }
+
+    pub fn default_view(mut self, view: MapView) -> Self {
+        self.map.default_view = Some(view);
+        self
+    }
+
+    pub fn height_range(mut self, min: f64, max: f64) -> Self {
+        self.map.height_range = Some([min, max]);
+        self
+    }
+
+    pub fn layer(mut self, layer: Layer) -> Self {
+        push(&mut self.map.layers, layer);
+        self
+    }
+
+    pub fn label(mut self, label: Label) -> Self {
+        push(&mut self.map.labels, label);
+        self
+    }
+
+    pub fn spawn(mut self, spawn: Spawn) -> Self {
+        push(&mut self.map.spawns, spawn);
+        self
+    }
+
+    pub fn extract(mut self, extract: Extract) -> Self {
+        push(&mut self.map.extracts, extract);
+        self
+    }
+
+    /// Builds the map, failing if it has no normalized name or doesn't validate.
+    pub fn build(mut self) -> Result<Map, BuildError> {
+        if self.map.normalized_name.is_empty() {
+            return Err(BuildError::MissingNormalizedName);
+        }
+        if self.map.logical_size == [0.0, 0.0] {
+            self.map.logical_size = self.map.image_size;
+        }
+
+        let warnings = self.map.validate();
+        if !warnings.is_empty() {
+            return Err(BuildError::Invalid {
+                map: self.map.normalized_name,
+                warnings,
+            });
+        }
+        Ok(self.map)
+    }
+}
+
+/// Builder for a [`Layer`].
+///
+/// ```
+/// use tarkov_map::LayerBuilder;
+///
+/// let layer = LayerBuilder::new("Underground")
+///     .image_path("underground.png")
+///     .extent([-10.0, -2.0], [])
+///     .build();
+/// assert_eq!(layer.extents[0].height, [-10.0, -2.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayerBuilder {
+    layer: Layer,
+}
+
+impl LayerBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            layer: Layer {
+                name: name.into(),
+                svg_layer: None,
+                tile_path: None,
+                image_path: None,
+                tiles: None,
+                show: false,
+                extents: Vec::new(),
+            },
+        }
+    }
+
+    pub fn svg_layer(mut self, id: impl Into<String>) -> Self {
+        self.layer.svg_layer = Some(id.into());
+        self
+    }
+
+    pub fn tile_path(mut self, template: impl Into<String>) -> Self {
+        self.layer.tile_path = Some(template.into());
+        self
+    }
+
+    pub fn image_path(mut self, path: impl Into<String>) -> Self {
+        self.layer.image_path = Some(path.into());
+        self
+    }
+
+    pub fn tiles(mut self, tiles: TilePyramid) -> Self {
+        self.layer.tiles = Some(tiles);
+        self
+    }
+
+    /// Shows the layer by default.
+    pub fn show(mut self) -> Self {
+        self.layer.show = true;
+        self
+    }
+
+    /// Adds an extent for the height range `[min, max]`, limited to `bounds` if any are given.
+    pub fn extent(
+        mut self,
+        height: [f64; 2],
+        bounds: impl IntoIterator<Item = ExtentBound>,
+    ) -> Self {
+        let bounds: Vec<ExtentBound> = bounds.into_iter().collect();
+        self.layer.extents.push(Extent {
+            height,
+            bounds: (!bounds.is_empty()).then_some(bounds),
+        });
+        self
+    }
+
+    /// Builds the layer. Its extents are validated with the map it is added to.
+    pub fn build(self) -> Layer {
+        self.layer
+    }
+}
+
+/// Builder for a [`Label`].
+#[derive(Debug, Clone)]
+pub struct LabelBuilder {
+    label: Label,
+}
+
+impl LabelBuilder {
+    pub fn new(text: impl Into<String>, position: impl Into<Position2>) -> Self {
+        Self {
+            label: Label {
+                position: position.into(),
+                text: text.into(),
+                translations: None,
+                rotation: None,
+                size: None,
+                top: None,
+                bottom: None,
+            },
+        }
+    }
+
+    /// Sets the rotation in degrees.
+    pub fn rotation(mut self, degrees: f64) -> Self {
+        self.label.rotation = Some(degrees);
+        self
+    }
+
+    pub fn size(mut self, size: i32) -> Self {
+        self.label.size = Some(size);
+        self
+    }
+
+    /// Limits visibility to heights between `bottom` and `top`.
+    pub fn heights(mut self, bottom: f64, top: f64) -> Self {
+        self.label.bottom = Some(bottom);
+        self.label.top = Some(top);
+        self
+    }
+
+    pub fn build(self) -> Label {
+        self.label
+    }
+}
+
+/// Builder for an [`Extract`].
+#[derive(Debug, Clone)]
+pub struct ExtractBuilder {
+    extract: Extract,
+}
+
+impl ExtractBuilder {
+    pub fn new(name: impl Into<String>, faction: Faction) -> Self {
+        Self {
+            extract: Extract {
+                name: name.into(),
+                faction,
+                position: None,
+                outline: None,
+                top: None,
+                bottom: None,
+                required_item: None,
+                switches: None,
+                coop: false,
+                translations: None,
+            },
+        }
+    }
+
+    pub fn position(mut self, position: impl Into<Position3>) -> Self {
+        self.extract.position = Some(position.into());
+        self
+    }
+
+    /// Sets the activation zone outline `[x, z]` and its height bounds.
+    pub fn outline(
+        mut self,
+        outline: impl IntoIterator<Item = impl Into<Position2>>,
+        bottom: Option<f64>,
+        top: Option<f64>,
+    ) -> Self {
+        self.extract.outline = Some(outline.into_iter().map(Into::into).collect());
+        self.extract.bottom = bottom;
+        self.extract.top = top;
+        self
+    }
+
+    pub fn required_item(mut self, name: impl Into<String>, count: u32) -> Self {
+        self.extract.required_item = Some(ExtractItem {
+            name: name.into(),
+            count,
+        });
+        self
+    }
+
+    /// Adds a switch that must be activated before the extract opens.
+    pub fn switch(mut self, name: impl Into<String>) -> Self {
+        push(&mut self.extract.switches, name.into());
+        self
+    }
+
+    /// Marks the extract as needing a PMC and a scav to extract together.
+    pub fn coop(mut self) -> Self {
+        self.extract.coop = true;
+        self
+    }
+
+    pub fn build(self) -> Extract {
+        self.extract
+    }
+}
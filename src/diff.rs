@@ -0,0 +1,334 @@
+//! Comparison of two map datasets, e.g. before and after a game patch.
+//!
+//! ```
+//! use tarkov_map::{LabelBuilder, MapBuilder, TarkovMaps, diff::diff};
+//!
+//! let map = |name: &str, label_x: f64| {
+//!     MapBuilder::new(name)
+//!         .image("map.png", [100.0, 100.0])
+//!         .bounds([100.0, 0.0], [0.0, 100.0])
+//!         .label(LabelBuilder::new("Dorms", [label_x, 50.0]).build())
+//!         .build()
+//!         .unwrap()
+//! };
+//! let old: TarkovMaps = vec![map("customs", 40.0)];
+//! let new: TarkovMaps = vec![map("customs", 60.0), map("labs", 50.0)];
+//!
+//! let changes = diff(&old, &new);
+//! assert_eq!(changes.added_maps, ["labs"]);
+//! assert_eq!(changes.map("customs").unwrap().counts().moved, 1);
+//! assert_eq!(changes.to_string(), "Added maps: labs\ncustoms: labels 1 moved\n");
+//! ```
+
+use crate::{Extract, Label, Map, Position3, Spawn, TarkovMaps};
+use std::fmt;
+
+/// Extracts and labels that moved less than this many meters are considered unchanged.
+const MOVE_TOLERANCE: f64 = 0.5;
+
+/// Spawns within this many meters of an old spawn are considered the same spawn.
+const SPAWN_MATCH_TOLERANCE: f64 = 1.0;
+
+/// A change to a single map feature.
+#[derive(Debug, Clone)]
+pub enum FeatureChange<T> {
+    /// The feature only exists in the new dataset.
+    Added(T),
+    /// The feature only exists in the old dataset.
+    Removed(T),
+    /// The feature exists in both datasets at different positions.
+    Moved { old: T, new: T },
+}
+
+/// Number of added, removed, and moved features.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChangeCounts {
+    pub added: usize,
+    pub removed: usize,
+    pub moved: usize,
+}
+
+impl ChangeCounts {
+    /// Counts the changes in `changes`.
+    pub fn of<T>(changes: &[FeatureChange<T>]) -> Self {
+        let mut counts = Self::default();
+        for change in changes {
+            match change {
+                FeatureChange::Added(_) => counts.added += 1,
+                FeatureChange::Removed(_) => counts.removed += 1,
+                FeatureChange::Moved { .. } => counts.moved += 1,
+            }
+        }
+        counts
+    }
+
+    pub fn total(self) -> usize {
+        self.added + self.removed + self.moved
+    }
+}
+
+impl std::ops::Add for ChangeCounts {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            added: self.added + other.added,
+            removed: self.removed + other.removed,
+            moved: self.moved + other.moved,
+        }
+    }
+}
+
+/// Formats the non-zero counts, e.g. "2 added, 1 moved".
+impl fmt::Display for ChangeCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = [
+            (self.added, "added"),
+            (self.removed, "removed"),
+            (self.moved, "moved"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{count} {what}"))
+        .collect();
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// Changes to the features of a single map.
+#[derive(Debug, Clone, Default)]
+pub struct MapDiff {
+    /// Normalized name of the map.
+    pub normalized_name: String,
+
+    /// Added, removed, and moved extracts (matched by name and faction).
+    pub extracts: Vec<FeatureChange<Extract>>,
+
+    /// Added and removed spawns (matched by position).
+    pub spawns: Vec<FeatureChange<Spawn>>,
+
+    /// Added, removed, and moved labels (matched by text).
+    pub labels: Vec<FeatureChange<Label>>,
+}
+
+impl MapDiff {
+    /// Returns `true` if nothing changed on this map.
+    pub fn is_empty(&self) -> bool {
+        self.extracts.is_empty() && self.spawns.is_empty() && self.labels.is_empty()
+    }
+
+    /// Counts the changes to all features of the map.
+    pub fn counts(&self) -> ChangeCounts {
+        ChangeCounts::of(&self.extracts)
+            + ChangeCounts::of(&self.spawns)
+            + ChangeCounts::of(&self.labels)
+    }
+}
+
+/// Formats the changes as one line, e.g. "customs: extracts 1 added; spawns 2 removed".
+impl fmt::Display for MapDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds: Vec<String> = [
+            ("extracts", ChangeCounts::of(&self.extracts)),
+            ("spawns", ChangeCounts::of(&self.spawns)),
+            ("labels", ChangeCounts::of(&self.labels)),
+        ]
+        .into_iter()
+        .filter(|(_, counts)| counts.total() > 0)
+        .map(|(kind, counts)| format!("{kind} {counts}"))
+        .collect();
+        write!(f, "{}: {}", self.normalized_name, kinds.join("; "))
+    }
+}
+
+/// Differences between two map datasets.
+#[derive(Debug, Clone, Default)]
+pub struct MapsDiff {
+    /// Maps only present in the new dataset.
+    pub added_maps: Vec<String>,
+
+    /// Maps only present in the old dataset.
+    pub removed_maps: Vec<String>,
+
+    /// Per-map feature changes for maps present in both datasets.
+    pub maps: Vec<MapDiff>,
+}
+
+impl MapsDiff {
+    /// Returns the feature changes for a map, if it has any.
+    pub fn map(&self, normalized_name: &str) -> Option<&MapDiff> {
+        self.maps
+            .iter()
+            .find(|diff| diff.normalized_name == normalized_name)
+    }
+
+    /// Returns `true` if the datasets are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.added_maps.is_empty() && self.removed_maps.is_empty() && self.maps.is_empty()
+    }
+
+    /// Counts the feature changes on all maps present in both datasets.
+    pub fn counts(&self) -> ChangeCounts {
+        self.maps
+            .iter()
+            .fold(ChangeCounts::default(), |sum, diff| sum + diff.counts())
+    }
+}
+
+/// Formats the changes one line per added/removed map list and changed map.
+impl fmt::Display for MapsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.added_maps.is_empty() {
+            writeln!(f, "Added maps: {}", self.added_maps.join(", "))?;
+        }
+        if !self.removed_maps.is_empty() {
+            writeln!(f, "Removed maps: {}", self.removed_maps.join(", "))?;
+        }
+        for diff in &self.maps {
+            writeln!(f, "{diff}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two map datasets.
+pub fn diff(old: &TarkovMaps, new: &TarkovMaps) -> MapsDiff {
+    let find = |maps: &'_ TarkovMaps, name: &str| -> Option<usize> {
+        maps.iter().position(|m| m.normalized_name == name)
+    };
+
+    let added_maps = new
+        .iter()
+        .filter(|m| find(old, &m.normalized_name).is_none())
+        .map(|m| m.normalized_name.clone())
+        .collect();
+
+    let removed_maps = old
+        .iter()
+        .filter(|m| find(new, &m.normalized_name).is_none())
+        .map(|m| m.normalized_name.clone())
+        .collect();
+
+    let maps = new
+        .iter()
+        .filter_map(|new_map| {
+            let old_map = &old[find(old, &new_map.normalized_name)?];
+            let diff = diff_map(old_map, new_map);
+            (!diff.is_empty()).then_some(diff)
+        })
+        .collect();
+
+    MapsDiff {
+        added_maps,
+        removed_maps,
+        maps,
+    }
+}
+
+/// Compares the features of two versions of the same map.
+pub fn diff_map(old: &Map, new: &Map) -> MapDiff {
+    MapDiff {
+        normalized_name: new.normalized_name.clone(),
+        extracts: diff_extracts(
+            old.extracts.as_deref().unwrap_or_default(),
+            new.extracts.as_deref().unwrap_or_default(),
+        ),
+        spawns: diff_spawns(
+            old.spawns.as_deref().unwrap_or_default(),
+            new.spawns.as_deref().unwrap_or_default(),
+        ),
+        labels: diff_labels(
+            old.labels.as_deref().unwrap_or_default(),
+            new.labels.as_deref().unwrap_or_default(),
+        ),
+    }
+}
+
+/// Horizontal distance between two positions.
+fn distance(a: Position3, b: Position3) -> f64 {
+    a.xz().distance(b.xz())
+}
+
+fn diff_extracts(old: &[Extract], new: &[Extract]) -> Vec<FeatureChange<Extract>> {
+    let same = |a: &Extract, b: &Extract| a.name == b.name && a.faction == b.faction;
+    let mut changes = Vec::new();
+
+    for new_extract in new {
+        match old
+            .iter()
+            .find(|old_extract| same(old_extract, new_extract))
+        {
+            None => changes.push(FeatureChange::Added(new_extract.clone())),
+            Some(old_extract) => {
+                let moved = match (old_extract.center(), new_extract.center()) {
+                    (Some(a), Some(b)) => a.distance(b) > MOVE_TOLERANCE,
+                    (None, None) => false,
+                    _ => true,
+                };
+                if moved {
+                    changes.push(FeatureChange::Moved {
+                        old: old_extract.clone(),
+                        new: new_extract.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    changes.extend(
+        old.iter()
+            .filter(|old_extract| !new.iter().any(|new_extract| same(old_extract, new_extract)))
+            .cloned()
+            .map(FeatureChange::Removed),
+    );
+
+    changes
+}
+
+fn diff_labels(old: &[Label], new: &[Label]) -> Vec<FeatureChange<Label>> {
+    let mut changes = Vec::new();
+
+    for new_label in new {
+        match old
+            .iter()
+            .find(|old_label| old_label.text == new_label.text)
+        {
+            None => changes.push(FeatureChange::Added(new_label.clone())),
+            Some(old_label) => {
+                if old_label.position.distance(new_label.position) > MOVE_TOLERANCE {
+                    changes.push(FeatureChange::Moved {
+                        old: old_label.clone(),
+                        new: new_label.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    changes.extend(
+        old.iter()
+            .filter(|old_label| !new.iter().any(|new_label| new_label.text == old_label.text))
+            .cloned()
+            .map(FeatureChange::Removed),
+    );
+
+    changes
+}
+
+fn diff_spawns(old: &[Spawn], new: &[Spawn]) -> Vec<FeatureChange<Spawn>> {
+    let near = |a: &Spawn, b: &Spawn| distance(a.position, b.position) <= SPAWN_MATCH_TOLERANCE;
+
+    let added = new
+        .iter()
+        .filter(|new_spawn| !old.iter().any(|old_spawn| near(old_spawn, new_spawn)))
+        .cloned()
+        .map(FeatureChange::Added);
+
+    let removed = old
+        .iter()
+        .filter(|old_spawn| !new.iter().any(|new_spawn| near(old_spawn, new_spawn)))
+        .cloned()
+        .map(FeatureChange::Removed);
+
+    added.chain(removed).collect()
+}
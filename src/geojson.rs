@@ -0,0 +1,308 @@
+//! GeoJSON conversion of map features, for GIS tools such as QGIS.
+//!
+//! Spawns, extracts and labels become `Point` features in game coordinates,
+//! with `[x, z]` as the horizontal position and height `y` as the third
+//! coordinate when known. Each feature's `kind` property tells the three
+//! apart; the remaining properties mirror the fields in `maps.ron`, including
+//! an extract's zone `outline`. Extracts with only a zone are placed at its
+//! center, without a height.
+//!
+//! [`Map::to_geojson_annotated`] adds user annotations such as notes and photos
+//! to each feature's properties. They are ignored when reading features back.
+
+use crate::{Extract, ExtractItem, Faction, Label, Map, Position2, Position3, Spawn};
+use crate::{SpawnCategory, SpawnSide};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use thiserror::Error;
+
+/// Errors that can occur when reading GeoJSON.
+#[derive(Error, Debug)]
+pub enum GeoJsonError {
+    #[error("invalid GeoJSON: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("feature {index}: {message}")]
+    Feature { index: usize, message: String },
+}
+
+/// Labels, spawns and extracts read from GeoJSON.
+#[derive(Debug, Clone, Default)]
+pub struct MapFeatures {
+    pub labels: Vec<Label>,
+    pub spawns: Vec<Spawn>,
+    pub extracts: Vec<Extract>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum GeoJson {
+    FeatureCollection { features: Vec<Feature> },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Feature {
+    Feature {
+        geometry: Geometry,
+        properties: serde_json::Value,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Geometry {
+    Point { coordinates: Vec<f64> },
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Properties {
+    #[serde(rename_all = "camelCase")]
+    Label {
+        text: String,
+        rotation: Option<f64>,
+        size: Option<i32>,
+        top: Option<f64>,
+        bottom: Option<f64>,
+    },
+    Spawn {
+        sides: Vec<SpawnSide>,
+        categories: Vec<SpawnCategory>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Extract {
+        name: String,
+        faction: Faction,
+        required_item: Option<ExtractItem>,
+        switches: Option<Vec<String>>,
+        #[serde(default)]
+        coop: bool,
+        outline: Option<Vec<Position2>>,
+        top: Option<f64>,
+        bottom: Option<f64>,
+    },
+}
+
+/// A label, spawn or extract being exported, passed to [`Map::to_geojson_annotated`].
+#[derive(Debug, Clone, Copy)]
+pub enum MarkerRef<'a> {
+    Label(&'a Label),
+    Spawn(&'a Spawn),
+    Extract(&'a Extract),
+}
+
+/// User annotations written into an exported feature's properties.
+#[derive(Debug, Clone, Default)]
+pub struct Annotations {
+    /// Free-text note, exported as the `note` property.
+    pub note: Option<String>,
+    /// File names of attached photos, exported as the `photos` property when not empty.
+    pub photos: Vec<String>,
+}
+
+fn feature(coordinates: Vec<f64>, properties: Properties, annotations: Annotations) -> Feature {
+    let mut properties = serde_json::to_value(properties).expect("properties serialize to JSON");
+    if let Some(object) = properties.as_object_mut() {
+        if let Some(note) = annotations.note {
+            object.insert("note".to_owned(), note.into());
+        }
+        if !annotations.photos.is_empty() {
+            object.insert("photos".to_owned(), annotations.photos.into());
+        }
+    }
+    Feature::Feature {
+        geometry: Geometry::Point { coordinates },
+        properties,
+    }
+}
+
+impl Map {
+    /// Converts the map's labels, spawns and extracts to a GeoJSON `FeatureCollection`.
+    ///
+    /// Extracts without a position or a zone are left out.
+    ///
+    /// ```
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test",
+    /// #     extracts: Some([
+    /// #         (name: "ZB-1011", faction: "pmc", position: Some((1.0, 2.0, 3.0))),
+    /// #         (name: "Zone", faction: "pmc", outline: Some([(0.0, 0.0), (4.0, 0.0), (4.0, 2.0)])),
+    /// #     ]),
+    /// # )"#).unwrap();
+    /// let json = map.to_geojson();
+    /// let features = tarkov_map::geojson::MapFeatures::from_geojson(&json).unwrap();
+    /// let extracts = map.extracts.unwrap();
+    /// assert_eq!(features.extracts[0].name, "ZB-1011");
+    /// assert_eq!(features.extracts[0].position, extracts[0].position);
+    /// assert_eq!(features.extracts[1].position, None);
+    /// assert_eq!(features.extracts[1].outline, extracts[1].outline);
+    /// ```
+    pub fn to_geojson(&self) -> String {
+        self.to_geojson_annotated(|_| Annotations::default())
+    }
+
+    /// Like [`to_geojson`](Self::to_geojson), adding the annotations `annotate`
+    /// returns for each marker to its feature's properties.
+    ///
+    /// ```
+    /// use tarkov_map::geojson::{Annotations, MapFeatures, MarkerRef};
+    ///
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test",
+    /// #     extracts: Some([(name: "ZB-1011", faction: "pmc", position: Some((1.0, 2.0, 3.0)))]),
+    /// # )"#).unwrap();
+    /// let json = map.to_geojson_annotated(|marker| Annotations {
+    ///     note: match marker {
+    ///         MarkerRef::Extract(extract) => Some(format!("{} needs a flare", extract.name)),
+    ///         _ => None,
+    ///     },
+    ///     photos: vec!["entrance.png".to_owned()],
+    /// });
+    /// assert!(json.contains(r#""note": "ZB-1011 needs a flare""#));
+    /// assert!(json.contains(r#""entrance.png""#));
+    /// assert_eq!(MapFeatures::from_geojson(&json).unwrap().extracts.len(), 1);
+    /// ```
+    pub fn to_geojson_annotated(&self, annotate: impl Fn(MarkerRef) -> Annotations) -> String {
+        let labels = self.labels.iter().flatten().map(|label| {
+            feature(
+                vec![label.position.x, label.position.z],
+                Properties::Label {
+                    text: label.text.clone(),
+                    rotation: label.rotation,
+                    size: label.size,
+                    top: label.top,
+                    bottom: label.bottom,
+                },
+                annotate(MarkerRef::Label(label)),
+            )
+        });
+        let spawns = self.spawns.iter().flatten().map(|spawn| {
+            let Position3 { x, y, z } = spawn.position;
+            feature(
+                vec![x, z, y],
+                Properties::Spawn {
+                    sides: spawn.sides.clone(),
+                    categories: spawn.categories.clone(),
+                },
+                annotate(MarkerRef::Spawn(spawn)),
+            )
+        });
+        let extracts = self.extracts.iter().flatten().filter_map(|extract| {
+            let coordinates = match extract.position {
+                Some(Position3 { x, y, z }) => vec![x, z, y],
+                None => {
+                    let center = extract.center()?;
+                    vec![center.x, center.z]
+                }
+            };
+            Some(feature(
+                coordinates,
+                Properties::Extract {
+                    name: extract.name.clone(),
+                    faction: extract.faction.clone(),
+                    required_item: extract.required_item.clone(),
+                    switches: extract.switches.clone(),
+                    coop: extract.coop,
+                    outline: extract.outline.clone(),
+                    top: extract.top,
+                    bottom: extract.bottom,
+                },
+                annotate(MarkerRef::Extract(extract)),
+            ))
+        });
+
+        let collection = GeoJson::FeatureCollection {
+            features: labels.chain(spawns).chain(extracts).collect(),
+        };
+        serde_json::to_string_pretty(&collection).expect("GeoJSON serializes")
+    }
+}
+
+impl MapFeatures {
+    /// Reads features written by [`Map::to_geojson`], or edited copies of them.
+    ///
+    /// Points without a height get a height of 0, except extracts with a zone,
+    /// which are read back without a position.
+    pub fn from_geojson(text: &str) -> Result<Self, GeoJsonError> {
+        let GeoJson::FeatureCollection { features } = serde_json::from_str(text)?;
+
+        let mut result = Self::default();
+        for (
+            index,
+            Feature::Feature {
+                geometry,
+                properties,
+            },
+        ) in features.into_iter().enumerate()
+        {
+            let feature_error = |message: String| GeoJsonError::Feature { index, message };
+            let Geometry::Point { coordinates } = geometry;
+            let (x, z, height) = match coordinates[..] {
+                [x, z] => (x, z, None),
+                [x, z, y, ..] => (x, z, Some(y)),
+                _ => return Err(feature_error("point needs at least two coordinates".into())),
+            };
+            let properties =
+                serde_json::from_value(properties).map_err(|e| feature_error(e.to_string()))?;
+
+            match properties {
+                Properties::Label {
+                    text,
+                    rotation,
+                    size,
+                    top,
+                    bottom,
+                } => result.labels.push(Label {
+                    position: Position2::new(x, z),
+                    text,
+                    rotation,
+                    size,
+                    top,
+                    bottom,
+                    translations: None,
+                }),
+                Properties::Spawn { sides, categories } => result.spawns.push(Spawn {
+                    position: Position3::new(x, height.unwrap_or_default(), z),
+                    sides,
+                    categories,
+                }),
+                Properties::Extract {
+                    name,
+                    faction,
+                    required_item,
+                    switches,
+                    coop,
+                    outline,
+                    top,
+                    bottom,
+                } => result.extracts.push(Extract {
+                    name,
+                    faction,
+                    // A zone's center without a height stands in for a missing position
+                    position: match (height, &outline) {
+                        (None, Some(_)) => None,
+                        _ => Some(Position3::new(x, height.unwrap_or_default(), z)),
+                    },
+                    outline,
+                    top,
+                    bottom,
+                    required_item,
+                    switches,
+                    coop,
+                    translations: None,
+                }),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Total number of features.
+    pub fn len(&self) -> usize {
+        self.labels.len() + self.spawns.len() + self.extracts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -3,9 +3,30 @@
 //! This crate defines the core types used to represent interactive maps from
 //! the tarkov-dev project, including map metadata, layers, labels, spawn points,
 //! and extraction points.
+//!
+//! The viewer and `fetch_maps` need the default `app` feature. Without it, the
+//! library only depends on serde and chrono and builds for
+//! `wasm32-unknown-unknown`, so a web viewer can share the data model and
+//! coordinate math.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
+use std::collections::{BTreeMap, VecDeque};
+
+pub mod builder;
+pub mod diff;
+pub mod geojson;
+pub mod merge;
+pub mod position;
+pub mod rotation;
+pub mod validate;
+
+pub use builder::{BuildError, ExtractBuilder, LabelBuilder, LayerBuilder, MapBuilder};
+pub use diff::{ChangeCounts, FeatureChange, MapDiff, MapsDiff};
+pub use merge::MergeConflict;
+pub use position::{GameBounds, Position2, Position3};
+pub use validate::ValidationWarning;
 
 /// An interactive map for a Tarkov location.
 ///
@@ -19,17 +40,33 @@ pub struct Map {
     pub normalized_name: String,
 
     /// Human-readable display name (e.g., "Customs").
+    ///
+    /// May be empty in data packs that only add markers to an existing map.
+    #[serde(default)]
     pub name: String,
 
+    /// [`name`](Self::name) in other languages.
+    #[serde(default)]
+    pub translations: Option<Translations>,
+
     /// Path to the pre-rendered high-resolution PNG image.
+    ///
+    /// May be empty in data packs that only add markers to an existing map.
+    #[serde(default)]
     pub image_path: String,
 
     /// Original image dimensions `[width, height]` in pixels.
+    #[serde(default)]
     pub image_size: [f32; 2],
 
+    /// Tile pyramid of the map image, for viewers that stream visible tiles.
+    #[serde(default)]
+    pub tiles: Option<TilePyramid>,
+
     /// Logical dimensions `[width, height]` in game units (meters).
     ///
     /// Used for consistent zoom scaling across maps.
+    #[serde(default)]
     pub logical_size: [f32; 2],
 
     /// Alternative map keys that share this map.
@@ -63,6 +100,12 @@ pub struct Map {
     #[serde(default)]
     pub bounds: Option<[[f64; 2]; 2]>,
 
+    /// Initial view framing the playable area of the map.
+    ///
+    /// When absent, the viewer fits the whole image.
+    #[serde(default)]
+    pub default_view: Option<MapView>,
+
     /// Default height range `[min, max]` for layer visibility.
     #[serde(default)]
     pub height_range: Option<[f64; 2]>,
@@ -75,13 +118,656 @@ pub struct Map {
     #[serde(default)]
     pub labels: Option<Vec<Label>>,
 
-    /// PMC spawn points.
+    /// Spawn points of PMCs, player Scavs and bots, told apart by their
+    /// [`sides`](Spawn::sides) and [`categories`](Spawn::categories).
     #[serde(default)]
     pub spawns: Option<Vec<Spawn>>,
 
     /// Extraction points.
     #[serde(default)]
     pub extracts: Option<Vec<Extract>>,
+
+    /// Named groups of nearby spawn points.
+    #[serde(default)]
+    pub spawn_clusters: Option<Vec<SpawnCluster>>,
+
+    /// Hazardous areas (minefields, sniper zones).
+    #[serde(default)]
+    pub hazards: Option<Vec<Hazard>>,
+
+    /// Locked doors and containers.
+    #[serde(default)]
+    pub locks: Option<Vec<Lock>>,
+
+    /// Lootable containers (safes, jackets, weapon boxes, caches).
+    #[serde(default)]
+    pub loot_containers: Option<Vec<LootContainer>>,
+
+    /// Bosses that can spawn on the map.
+    #[serde(default)]
+    pub bosses: Option<Vec<BossSpawn>>,
+
+    /// Mounted weapons (machine guns, grenade launchers).
+    #[serde(default)]
+    pub stationary_weapons: Option<Vec<StationaryWeapon>>,
+
+    /// Switches and levers, such as power switches and extract levers.
+    #[serde(default)]
+    pub switches: Option<Vec<Switch>>,
+
+    /// Transits to other maps.
+    #[serde(default)]
+    pub transits: Option<Vec<Transit>>,
+
+    /// Tasks with objectives on this map, listing only those objectives.
+    #[serde(default)]
+    pub tasks: Option<Vec<Task>>,
+}
+
+impl Map {
+    /// Returns the map name in `language`, or the English name if it has no translation.
+    pub fn localized_name(&self, language: Option<&str>) -> &str {
+        localized(&self.name, self.translations.as_ref(), language)
+    }
+
+    /// Returns the map bounds as a rectangle in game coordinates.
+    ///
+    /// ```
+    /// # use tarkov_map::Position2;
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test",
+    /// #     bounds: Some(((100.0, 0.0), (0.0, 100.0))),
+    /// # )"#).unwrap();
+    /// let bounds = map.game_bounds().unwrap();
+    /// assert_eq!(bounds.min, Position2::new(0.0, 0.0));
+    /// assert_eq!(bounds.max, Position2::new(100.0, 100.0));
+    /// ```
+    pub fn game_bounds(&self) -> Option<GameBounds> {
+        let [a, b] = self.bounds?;
+        Some(GameBounds::from_corners(a.into(), b.into()))
+    }
+
+    /// Returns `true` if game coordinates `[x, z]` lie within the map bounds.
+    ///
+    /// Maps without bounds contain no positions.
+    ///
+    /// ```
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test",
+    /// #     bounds: Some(((100.0, 0.0), (0.0, 100.0))),
+    /// # )"#).unwrap();
+    /// assert!(map.contains([25.0, 75.0]));
+    /// assert!(!map.contains([-25.0, 75.0]));
+    /// ```
+    pub fn contains(&self, game_pos: impl Into<Position2>) -> bool {
+        self.game_bounds()
+            .is_some_and(|bounds| bounds.contains(game_pos))
+    }
+
+    /// Projects game coordinates `[x, z]` onto the map image.
+    ///
+    /// Returns the position as fractions `[x, y]` of the image size, with
+    /// `[0, 0]` at the top-left corner, or `None` if the map has no bounds.
+    /// Follows the tarkov-dev implementation: game coordinates are rotated by
+    /// `coordinate_rotation`, then mapped onto the image using the rotated
+    /// bounds. Rotated maps with a `transform` (Labs, Labyrinth) map through the
+    /// transform instead, which accounts for the SVG margins.
+    ///
+    /// ```
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test", name: "Test", imagePath: "test.png",
+    /// #     imageSize: (100.0, 100.0), logicalSize: (100.0, 100.0),
+    /// #     coordinateRotation: Some(0.0),
+    /// #     bounds: Some(((100.0, 0.0), (0.0, 100.0))),
+    /// # )"#).unwrap();
+    /// // Bounds span 0..100 on both axes, with no rotation
+    /// assert_eq!(map.project([25.0, 75.0]), Some([0.25, 0.25]));
+    /// ```
+    pub fn project(&self, game_pos: impl Into<Position2>) -> Option<[f64; 2]> {
+        let bounds = self.bounds?;
+        let rotation = self.coordinate_rotation.unwrap_or(0.0);
+
+        let game_pos = game_pos.into();
+        let (rotated_x, rotated_y) = rotate_point(game_pos.x, game_pos.z, rotation);
+
+        if rotation == 270.0
+            && let Some(transform) = self.transform
+        {
+            let scale_x = transform[0];
+            let margin_x = transform[1];
+            let scale_y = -transform[2]; // Negated per tarkov-dev convention
+            let margin_y = transform[3];
+
+            let svg_x = scale_x * rotated_x + margin_x;
+            let svg_y = scale_y * rotated_y + margin_y;
+
+            return Some([
+                svg_x / f64::from(self.image_size[0]),
+                svg_y / f64::from(self.image_size[1]),
+            ]);
+        }
+
+        let [min_x, max_x, min_y, max_y] = rotated_extent(bounds, rotation);
+        Some([
+            (rotated_x - min_x) / (max_x - min_x),
+            (max_y - rotated_y) / (max_y - min_y), // Y inverted
+        ])
+    }
+
+    /// Converts a position on the map image back to game coordinates `[x, z]`.
+    ///
+    /// The inverse of [`Map::project`]: `frac` is given as fractions `[x, y]`
+    /// of the image size, with `[0, 0]` at the top-left corner. Returns `None`
+    /// if the map has no bounds.
+    ///
+    /// ```
+    /// # use tarkov_map::Position2;
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test", name: "Test", imagePath: "test.png",
+    /// #     imageSize: (100.0, 100.0), logicalSize: (100.0, 100.0),
+    /// #     coordinateRotation: Some(0.0),
+    /// #     bounds: Some(((100.0, 0.0), (0.0, 100.0))),
+    /// # )"#).unwrap();
+    /// assert_eq!(map.unproject([0.25, 0.25]), Some(Position2::new(25.0, 75.0)));
+    ///
+    /// // Rotated maps with a transform round-trip through the transform
+    /// # let labs: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "labs", name: "Labs", imagePath: "labs.png",
+    /// #     imageSize: (200.0, 100.0), logicalSize: (200.0, 100.0),
+    /// #     coordinateRotation: Some(270.0),
+    /// #     transform: Some((2.0, 10.0, 2.0, 5.0)),
+    /// #     bounds: Some(((100.0, 0.0), (0.0, 50.0))),
+    /// # )"#).unwrap();
+    /// let frac = labs.project([40.0, 20.0]).unwrap();
+    /// let pos = labs.unproject(frac).unwrap();
+    /// assert!((pos.x - 40.0).abs() < 1e-9 && (pos.z - 20.0).abs() < 1e-9);
+    /// ```
+    pub fn unproject(&self, frac: [f64; 2]) -> Option<Position2> {
+        let bounds = self.bounds?;
+        let rotation = self.coordinate_rotation.unwrap_or(0.0);
+        let [frac_x, frac_y] = frac;
+
+        let (rotated_x, rotated_y) = if rotation == 270.0
+            && let Some(transform) = self.transform
+        {
+            let svg_x = frac_x * f64::from(self.image_size[0]);
+            let svg_y = frac_y * f64::from(self.image_size[1]);
+            (
+                (svg_x - transform[1]) / transform[0],
+                (svg_y - transform[3]) / -transform[2], // Negated per tarkov-dev convention
+            )
+        } else {
+            let [min_x, max_x, min_y, max_y] = rotated_extent(bounds, rotation);
+            (
+                min_x + frac_x * (max_x - min_x),
+                max_y - frac_y * (max_y - min_y), // Y inverted
+            )
+        };
+
+        let (x, z) = rotate_point(rotated_x, rotated_y, -rotation);
+        Some(Position2 { x, z })
+    }
+
+    /// Horizontal distance in meters between two game positions `[x, z]`.
+    ///
+    /// Game units are meters; height is ignored, as on the map.
+    ///
+    /// ```
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(normalizedName: "test")"#).unwrap();
+    /// assert_eq!(map.meters_between([0.0, 0.0], [30.0, 40.0]), 50.0);
+    /// ```
+    pub fn meters_between(&self, a: impl Into<Position2>, b: impl Into<Position2>) -> f64 {
+        a.into().distance(b.into())
+    }
+
+    /// Display pixels per game meter when the map is drawn `display_width` pixels wide.
+    ///
+    /// Measured along the game x axis, with the display height following the
+    /// map's logical size. Returns `None` if the map has no bounds or size.
+    ///
+    /// ```
+    /// // Customs is drawn at 1 pixel per meter at its logical size
+    /// # let customs: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "customs", name: "Customs", imagePath: "customs.png",
+    /// #     imageSize: (1062.0, 535.0), logicalSize: (1070.0, 544.0),
+    /// #     coordinateRotation: Some(180.0),
+    /// #     bounds: Some(((698.0, -307.0), (-372.0, 237.0))),
+    /// # )"#).unwrap();
+    /// let scale = customs.pixels_per_meter(1070.0).unwrap();
+    /// assert!((scale - 1.0).abs() < 1e-9);
+    /// // and at twice the scale when zoomed in 2x
+    /// let scale = customs.pixels_per_meter(2140.0).unwrap();
+    /// assert!((scale - 2.0).abs() < 1e-9);
+    ///
+    /// // A 100 m ruler on a 500 px wide map spanning 0..250 m
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test", name: "Test", imagePath: "test.png",
+    /// #     imageSize: (100.0, 100.0), logicalSize: (100.0, 100.0),
+    /// #     bounds: Some(((250.0, 0.0), (0.0, 250.0))),
+    /// # )"#).unwrap();
+    /// let ruler = map.pixels_per_meter(500.0).unwrap() * 100.0;
+    /// assert!((ruler - 200.0).abs() < 1e-9);
+    /// ```
+    pub fn pixels_per_meter(&self, display_width: f32) -> Option<f64> {
+        let [[x, z], _] = self.bounds?;
+        let [width, height] = self.logical_size.map(f64::from);
+        if width <= 0.0 {
+            return None;
+        }
+        let display_width = f64::from(display_width);
+        let display_height = display_width * height / width;
+
+        let [x1, y1] = self.project([x, z])?;
+        let [x2, y2] = self.project([x + 100.0, z])?;
+        let pixels = ((x2 - x1) * display_width).hypot((y2 - y1) * display_height) / 100.0;
+        (pixels > 0.0).then_some(pixels)
+    }
+
+    /// Index of the first layer active for a player at `height` and game position `[x, z]`.
+    ///
+    /// `None` means the player is on the base map.
+    pub fn active_layer(&self, height: f64, game_pos: impl Into<Position2>) -> Option<usize> {
+        let game_pos = game_pos.into();
+        self.layers
+            .iter()
+            .flatten()
+            .position(|layer| layer.active_for(height, game_pos))
+    }
+
+    /// Returns the first extract named `name`.
+    ///
+    /// ```
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test",
+    /// #     extracts: Some([(name: "ZB-1011", faction: "pmc")]),
+    /// # )"#).unwrap();
+    /// assert!(map.extract_by_name("ZB-1011").is_some());
+    /// assert!(map.extract_by_name("Crossroads").is_none());
+    /// ```
+    pub fn extract_by_name(&self, name: &str) -> Option<&Extract> {
+        self.extracts
+            .iter()
+            .flatten()
+            .find(|extract| extract.name == name)
+    }
+
+    /// Returns the extracts that `switch` opens, in the order it lists them.
+    pub fn switch_extracts<'a>(&'a self, switch: &'a Switch) -> impl Iterator<Item = &'a Extract> {
+        switch
+            .extracts
+            .iter()
+            .filter_map(|name| self.extract_by_name(name))
+    }
+
+    /// Returns the switch named `name`.
+    pub fn switch_by_name(&self, name: &str) -> Option<&Switch> {
+        self.switches
+            .iter()
+            .flatten()
+            .find(|switch| switch.name == name)
+    }
+
+    /// Returns the switches that `switch` operates on.
+    pub fn switch_targets<'a>(&'a self, switch: &'a Switch) -> impl Iterator<Item = &'a Switch> {
+        switch
+            .operations
+            .iter()
+            .filter_map(|operation| match &operation.target {
+                SwitchTarget::Switch(name) => self.switch_by_name(name),
+                SwitchTarget::Extract(_) => None,
+            })
+    }
+
+    /// Returns the transits leading to the map `destination`, by normalized name.
+    pub fn transits_to<'a>(&'a self, destination: &'a str) -> impl Iterator<Item = &'a Transit> {
+        self.transits
+            .iter()
+            .flatten()
+            .filter(move |transit| transit.destination == destination)
+    }
+
+    /// Returns the task with ID `id` and the tasks on this map it requires,
+    /// in the order they have to be done.
+    ///
+    /// Prerequisites on other maps are left out, but the chain still follows
+    /// them to earlier tasks on this map.
+    ///
+    /// ```
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test",
+    /// #     tasks: Some([
+    /// #         (id: "c", name: "Third", objectives: [], prerequisites: ["a", "b"]),
+    /// #         (id: "a", name: "First", objectives: []),
+    /// #         (id: "d", name: "Other", objectives: []),
+    /// #     ]),
+    /// # )"#).unwrap();
+    /// let names: Vec<_> = map.task_chain("c").iter().map(|task| task.name.as_str()).collect();
+    /// assert_eq!(names, ["First", "Third"]);
+    /// ```
+    pub fn task_chain(&self, id: &str) -> Vec<&Task> {
+        let tasks = self.tasks.as_deref().unwrap_or_default();
+        let Some(task) = tasks.iter().find(|task| task.id == id) else {
+            return Vec::new();
+        };
+        let chain: Vec<&Task> = tasks.iter().filter(|other| task.requires(other)).collect();
+
+        // Kahn's algorithm over the prerequisites within the chain, keeping
+        // the data order between tasks that don't depend on each other
+        let mut pending: Vec<usize> = chain
+            .iter()
+            .map(|task| chain.iter().filter(|other| task.requires(other)).count())
+            .collect();
+        let mut ready: VecDeque<usize> = (0..chain.len()).filter(|&i| pending[i] == 0).collect();
+        let mut ordered = Vec::with_capacity(chain.len() + 1);
+        while let Some(done) = ready.pop_front() {
+            ordered.push(chain[done]);
+            for (i, other) in chain.iter().enumerate() {
+                if other.requires(chain[done]) {
+                    pending[i] -= 1;
+                    if pending[i] == 0 {
+                        ready.push_back(i);
+                    }
+                }
+            }
+        }
+        // Tasks in a prerequisite cycle have no order, keep them in data order
+        ordered.extend(
+            (0..chain.len())
+                .filter(|&i| pending[i] > 0)
+                .map(|i| chain[i]),
+        );
+
+        ordered.push(task);
+        ordered
+    }
+
+    /// Returns the extracts usable by `faction`'s own side, not including shared extracts.
+    pub fn extracts_for_faction<'a>(
+        &'a self,
+        faction: &'a Faction,
+    ) -> impl Iterator<Item = &'a Extract> {
+        self.extracts
+            .iter()
+            .flatten()
+            .filter(move |extract| extract.faction == *faction)
+    }
+
+    /// Returns the spawns matching `side` and `category`, where `None` matches any.
+    ///
+    /// Spawns for [`SpawnSide::All`] match every side.
+    pub fn spawns_filtered<'a>(
+        &'a self,
+        side: Option<&'a SpawnSide>,
+        category: Option<&'a SpawnCategory>,
+    ) -> impl Iterator<Item = &'a Spawn> {
+        self.spawns.iter().flatten().filter(move |spawn| {
+            side.is_none_or(|side| spawn.has_side(side))
+                && category.is_none_or(|category| spawn.has_category(category))
+        })
+    }
+
+    /// Groups PMC spawn points into named clusters.
+    ///
+    /// Only spawns where PMC players start a raid are clustered. Spawns closer
+    /// than `radius` meters (horizontally) to any other spawn in a cluster join
+    /// that cluster. Each cluster is named after the nearest map label, falling
+    /// back to a numbered name when no label is close.
+    pub fn compute_spawn_clusters(&self, radius: f64) -> Vec<SpawnCluster> {
+        let points: Vec<Position2> = self
+            .spawns_filtered(Some(&SpawnSide::Pmc), Some(&SpawnCategory::Player))
+            .map(|s| s.position.xz())
+            .collect();
+        if points.is_empty() {
+            return Vec::new();
+        }
+
+        // Single-linkage clustering via union-find
+        let mut parent: Vec<usize> = (0..points.len()).collect();
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                if points[i].distance(points[j]) <= radius {
+                    let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                    parent[a] = b;
+                }
+            }
+        }
+
+        let mut groups: Vec<(usize, Vec<Position2>)> = Vec::new();
+        for (i, point) in points.iter().enumerate() {
+            let root = find(&mut parent, i);
+            match groups.iter_mut().find(|(r, _)| *r == root) {
+                Some((_, members)) => members.push(*point),
+                None => groups.push((root, vec![*point])),
+            }
+        }
+
+        let labels = self.labels.as_deref().unwrap_or_default();
+
+        groups
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (_, members))| {
+                let count = members.len();
+                let position = Position2::new(
+                    members.iter().map(|p| p.x).sum::<f64>() / count as f64,
+                    members.iter().map(|p| p.z).sum::<f64>() / count as f64,
+                );
+
+                let name = labels
+                    .iter()
+                    .map(|label| (label, label.position.distance(position)))
+                    .filter(|(_, d)| *d <= radius * SPAWN_CLUSTER_LABEL_RANGE)
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(label, _)| format!("{} spawns", label.text.replace('\n', " ")))
+                    .unwrap_or_else(|| format!("Spawn group {}", idx + 1));
+
+                SpawnCluster {
+                    name,
+                    position,
+                    count,
+                }
+            })
+            .collect()
+    }
+
+    /// Computes the initial view framing the playable area of the map.
+    ///
+    /// Projects the corners of the map bounds onto the image with
+    /// [`project`](Self::project), the projection markers are placed with.
+    /// Returns `None` when the bounds cannot be projected, fall outside the
+    /// image, or already fill most of its area, as they do for maps whose image
+    /// is fitted to the bounds.
+    pub fn compute_default_view(&self) -> Option<MapView> {
+        let [[x1, z1], [x2, z2]] = self.bounds?;
+
+        let (mut min_x, mut max_x) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut min_y, mut max_y) = (f64::INFINITY, f64::NEG_INFINITY);
+        for corner in [[x1, z1], [x1, z2], [x2, z1], [x2, z2]] {
+            let [frac_x, frac_y] = self.project(corner)?;
+            min_x = min_x.min(frac_x);
+            max_x = max_x.max(frac_x);
+            min_y = min_y.min(frac_y);
+            max_y = max_y.max(frac_y);
+        }
+
+        let inside = |min: f64, max: f64| min >= -0.01 && max <= 1.01 && max > min;
+        if !inside(min_x, max_x) || !inside(min_y, max_y) {
+            return None;
+        }
+
+        let (width, height) = (
+            max_x.min(1.0) - min_x.max(0.0),
+            max_y.min(1.0) - min_y.max(0.0),
+        );
+        if width * height > DEFAULT_VIEW_MAX_COVERAGE {
+            return None;
+        }
+
+        Some(MapView {
+            center: [
+                ((min_x + max_x) / 2.0) as f32,
+                ((min_y + max_y) / 2.0) as f32,
+            ],
+            zoom: (1.0 / width.max(height)) as f32,
+        })
+    }
+}
+
+/// Playable areas covering more than this fraction of the image area use the fit view.
+const DEFAULT_VIEW_MAX_COVERAGE: f64 = 0.9;
+
+/// Maximum distance in meters between spawns in the same cluster.
+pub const SPAWN_CLUSTER_RADIUS: f64 = 40.0;
+
+/// How far (as a multiple of the cluster radius) a label may be from a cluster to name it.
+const SPAWN_CLUSTER_LABEL_RANGE: f64 = 3.0;
+
+/// Rotates a 2D point by the given angle (in degrees).
+pub fn rotate_point(x: f64, y: f64, angle_deg: f64) -> (f64, f64) {
+    if angle_deg == 0.0 {
+        return (x, y);
+    }
+    let angle_rad = angle_deg.to_radians();
+    let (sin, cos) = angle_rad.sin_cos();
+    (x * cos - y * sin, x * sin + y * cos)
+}
+
+/// Extent `[min_x, max_x, min_y, max_y]` of map bounds after rotation.
+fn rotated_extent(bounds: [[f64; 2]; 2], rotation: f64) -> [f64; 4] {
+    let corners = [
+        (bounds[0][0], bounds[0][1]), // (maxX, minY)
+        (bounds[0][0], bounds[1][1]), // (maxX, maxY)
+        (bounds[1][0], bounds[0][1]), // (minX, minY)
+        (bounds[1][0], bounds[1][1]), // (minX, maxY)
+    ];
+
+    corners.iter().fold(
+        [
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ],
+        |[min_x, max_x, min_y, max_y], &(x, y)| {
+            let (x, y) = rotate_point(x, y, rotation);
+            [min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y)]
+        },
+    )
+}
+
+/// A camera view over the map image.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapView {
+    /// View center as a fraction `[x, y]` of the image size (0.0 to 1.0).
+    pub center: [f32; 2],
+
+    /// Zoom level relative to fitting the whole image (1.0 = fit).
+    pub zoom: f32,
+}
+
+/// A local tile pyramid of a map or layer image, written by
+/// `fetch_maps --keep-tiles`.
+///
+/// Zoom level `z` splits the image into `2^z` by `2^z` tiles; at
+/// [`max_zoom`](Self::max_zoom) they add up to the full-resolution image.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TilePyramid {
+    /// Tile path with `{z}`, `{x}` and `{y}` placeholders, relative like image paths.
+    pub path_template: String,
+
+    /// Width and height of a tile in pixels.
+    pub tile_size: u32,
+
+    /// Coarsest zoom level available.
+    pub min_zoom: u32,
+
+    /// Finest zoom level available.
+    pub max_zoom: u32,
+}
+
+impl TilePyramid {
+    /// Returns the path of tile `(x, y)` at zoom level `z`.
+    ///
+    /// ```
+    /// # use tarkov_map::TilePyramid;
+    /// let tiles = TilePyramid {
+    ///     path_template: "maps/woods/{z}/{x}/{y}.png".into(),
+    ///     tile_size: 256,
+    ///     min_zoom: 1,
+    ///     max_zoom: 4,
+    /// };
+    /// assert_eq!(tiles.tile_path(2, 3, 1), "maps/woods/2/3/1.png");
+    /// ```
+    pub fn tile_path(&self, z: u32, x: u32, y: u32) -> String {
+        self.path_template
+            .replace("{z}", &z.to_string())
+            .replace("{x}", &x.to_string())
+            .replace("{y}", &y.to_string())
+    }
+
+    /// Returns the number of tiles along each axis at zoom level `z`.
+    pub fn tiles_per_axis(z: u32) -> u32 {
+        1 << z
+    }
+
+    /// Returns the coarsest zoom level that still has at least one tile pixel
+    /// per screen pixel, when the full-resolution image is drawn at `scale`
+    /// screen pixels per image pixel.
+    ///
+    /// ```
+    /// # use tarkov_map::TilePyramid;
+    /// # let tiles = TilePyramid {
+    /// #     path_template: "{z}/{x}/{y}.png".into(), tile_size: 256, min_zoom: 1, max_zoom: 4,
+    /// # };
+    /// assert_eq!(tiles.zoom_for_scale(1.0), 4);
+    /// assert_eq!(tiles.zoom_for_scale(0.25), 2);
+    /// assert_eq!(tiles.zoom_for_scale(0.3), 3);
+    /// assert_eq!(tiles.zoom_for_scale(0.01), 1);
+    /// ```
+    pub fn zoom_for_scale(&self, scale: f32) -> u32 {
+        let levels_below_max = (-scale.log2()).floor().max(0.0) as u32;
+        self.max_zoom
+            .saturating_sub(levels_below_max)
+            .max(self.min_zoom)
+    }
+
+    /// Returns the tiles `(x, y)` at zoom level `z` that overlap the part of
+    /// the image between `min` and `max`, given as fractions `[x, y]` of the
+    /// image size.
+    ///
+    /// ```
+    /// # use tarkov_map::TilePyramid;
+    /// # let tiles = TilePyramid {
+    /// #     path_template: "{z}/{x}/{y}.png".into(), tile_size: 256, min_zoom: 1, max_zoom: 4,
+    /// # };
+    /// let visible: Vec<_> = tiles.visible_tiles(2, [0.3, 0.0], [0.6, 0.2]).collect();
+    /// assert_eq!(visible, [(1, 0), (2, 0)]);
+    /// ```
+    pub fn visible_tiles(
+        &self,
+        z: u32,
+        min: [f32; 2],
+        max: [f32; 2],
+    ) -> impl Iterator<Item = (u32, u32)> + use<> {
+        let count = Self::tiles_per_axis(z);
+        let range = |axis: usize| {
+            let first = (min[axis].clamp(0.0, 1.0) * count as f32).floor() as u32;
+            let last = ((max[axis].clamp(0.0, 1.0) * count as f32).ceil() as u32).min(count);
+            first..last
+        };
+        let (xs, ys) = (range(0), range(1));
+        ys.flat_map(move |y| xs.clone().map(move |x| (x, y)))
+    }
 }
 
 /// A map layer representing a floor level or area.
@@ -100,6 +786,15 @@ pub struct Layer {
     #[serde(default)]
     pub tile_path: Option<String>,
 
+    /// Path to the pre-rendered layer image, the same size as the map image
+    /// and transparent outside the layer.
+    #[serde(default)]
+    pub image_path: Option<String>,
+
+    /// Tile pyramid of the layer image.
+    #[serde(default)]
+    pub tiles: Option<TilePyramid>,
+
     /// Whether this layer is visible by default.
     #[serde(default)]
     pub show: bool,
@@ -109,6 +804,31 @@ pub struct Layer {
     pub extents: Vec<Extent>,
 }
 
+impl Layer {
+    /// Returns `true` if a player at `height` and game position `[x, z]` is
+    /// inside one of the layer's extents.
+    ///
+    /// ```
+    /// use tarkov_map::{ExtentBound, LayerBuilder};
+    ///
+    /// let basement = LayerBuilder::new("Basement")
+    ///     .extent(
+    ///         [-10.0, -2.0],
+    ///         [ExtentBound { point1: [0.0, 0.0], point2: [50.0, 50.0], name: "West".into() }],
+    ///     )
+    ///     .build();
+    /// assert!(basement.active_for(-5.0, [20.0, 30.0]));
+    /// assert!(!basement.active_for(0.0, [20.0, 30.0]));
+    /// assert!(!basement.active_for(-5.0, [80.0, 30.0]));
+    /// ```
+    pub fn active_for(&self, height: f64, game_pos: impl Into<Position2>) -> bool {
+        let game_pos = game_pos.into();
+        self.extents
+            .iter()
+            .any(|extent| extent.contains(height, game_pos))
+    }
+}
+
 /// Defines visibility conditions for a layer based on height and bounds.
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,17 +855,42 @@ pub struct ExtentBound {
     pub name: String,
 }
 
+impl Extent {
+    /// Returns `true` if `height` is within the extent's height range and
+    /// `[x, z]` within its bounds. Extents without bounds cover the whole map.
+    pub fn contains(&self, height: f64, game_pos: impl Into<Position2>) -> bool {
+        let [min, max] = self.height;
+        let game_pos = game_pos.into();
+        (min..=max).contains(&height)
+            && self
+                .bounds
+                .as_ref()
+                .is_none_or(|bounds| bounds.iter().any(|bound| bound.contains(game_pos)))
+    }
+}
+
+impl ExtentBound {
+    /// Returns `true` if `[x, z]` lies within the rectangle spanned by the two corners.
+    pub fn contains(&self, game_pos: impl Into<Position2>) -> bool {
+        GameBounds::from_corners(self.point1.into(), self.point2.into()).contains(game_pos)
+    }
+}
+
 /// A text label/annotation on the map.
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Label {
-    /// Position `[x, y]` in game coordinates.
-    pub position: [f64; 2],
+    /// Position in game coordinates.
+    pub position: Position2,
 
     /// Label text content.
     pub text: String,
 
+    /// [`text`](Self::text) in other languages.
+    #[serde(default)]
+    pub translations: Option<Translations>,
+
     /// Rotation angle in degrees.
     #[serde(default)]
     pub rotation: Option<f64>,
@@ -163,18 +908,67 @@ pub struct Label {
     pub bottom: Option<f64>,
 }
 
+impl Label {
+    /// Returns `true` if the label applies at `height`, i.e. between
+    /// [`bottom`](Self::bottom) and [`top`](Self::top) where set.
+    ///
+    /// ```
+    /// use tarkov_map::LabelBuilder;
+    ///
+    /// let label = LabelBuilder::new("Bunker", [0.0, 0.0]).heights(-8.0, -1.0).build();
+    /// assert!(label.visible_at(-4.0));
+    /// assert!(!label.visible_at(3.0));
+    /// assert!(LabelBuilder::new("Dorms", [0.0, 0.0]).build().visible_at(3.0));
+    /// ```
+    pub fn visible_at(&self, height: f64) -> bool {
+        self.bottom.is_none_or(|bottom| height >= bottom)
+            && self.top.is_none_or(|top| height <= top)
+    }
+
+    /// Returns the label text in `language`, or the English text if it has no translation.
+    pub fn localized_text(&self, language: Option<&str>) -> &str {
+        localized(&self.text, self.translations.as_ref(), language)
+    }
+}
+
 /// A spawn point on the map.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Spawn {
-    /// Position `[x, y, z]` in game coordinates.
-    pub position: [f64; 3],
+    /// Position in game coordinates.
+    pub position: Position3,
+
+    /// Spawn sides.
+    pub sides: Vec<SpawnSide>,
+
+    /// Spawn categories.
+    pub categories: Vec<SpawnCategory>,
+}
+
+impl Spawn {
+    /// Returns `true` if `side` can spawn here, either directly or through [`SpawnSide::All`].
+    pub fn has_side(&self, side: &SpawnSide) -> bool {
+        self.sides.iter().any(|s| s == side || *s == SpawnSide::All)
+    }
+
+    /// Returns `true` if the spawn is used for `category`.
+    pub fn has_category(&self, category: &SpawnCategory) -> bool {
+        self.categories.contains(category)
+    }
+}
+
+/// A named group of nearby spawn points.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnCluster {
+    /// Display name (e.g., "Crossroads spawns").
+    pub name: String,
 
-    /// Spawn sides (e.g., "pmc", "scav", "all").
-    pub sides: Vec<String>,
+    /// Cluster centroid in game coordinates.
+    pub position: Position2,
 
-    /// Spawn categories (e.g., "player", "bot").
-    pub categories: Vec<String>,
+    /// Number of spawn points in the cluster.
+    pub count: usize,
 }
 
 /// An extraction point on the map.
@@ -186,14 +980,875 @@ pub struct Extract {
     pub name: String,
 
     /// Faction that can use this extract.
+    pub faction: Faction,
+
+    /// Position in game coordinates.
+    #[serde(default)]
+    pub position: Option<Position3>,
+
+    /// Outline of the activation zone in game coordinates `[x, z]`.
+    ///
+    /// When absent, only the marker at `position` is shown.
+    #[serde(default)]
+    pub outline: Option<Vec<Position2>>,
+
+    /// Upper height bound of the activation zone.
+    #[serde(default)]
+    pub top: Option<f64>,
+
+    /// Lower height bound of the activation zone.
+    #[serde(default)]
+    pub bottom: Option<f64>,
+
+    /// Item that must be handed over to use the extract (e.g., roubles for a car).
+    #[serde(default)]
+    pub required_item: Option<ExtractItem>,
+
+    /// Names of switches that must be activated before the extract opens.
+    #[serde(default)]
+    pub switches: Option<Vec<String>>,
+
+    /// Whether a PMC and a scav must extract together.
+    #[serde(default)]
+    pub coop: bool,
+
+    /// [`name`](Self::name) in other languages.
+    #[serde(default)]
+    pub translations: Option<Translations>,
+}
+
+impl Extract {
+    /// Returns the extract name in `language`, or the English name if it has no translation.
+    pub fn localized_name(&self, language: Option<&str>) -> &str {
+        localized(&self.name, self.translations.as_ref(), language)
+    }
+
+    /// Where the extract is marked: its [`position`](Self::position), or the
+    /// center of its [`outline`](Self::outline) when it only has a zone.
+    ///
+    /// ```
+    /// # use tarkov_map::Position2;
+    /// # let extract: tarkov_map::Extract = ron::from_str(r#"(
+    /// #     name: "Zone", faction: "pmc",
+    /// #     outline: Some([(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0)]),
+    /// # )"#).unwrap();
+    /// assert_eq!(extract.center(), Some(Position2::new(2.0, 1.0)));
+    /// ```
+    pub fn center(&self) -> Option<Position2> {
+        if let Some(position) = self.position {
+            return Some(position.xz());
+        }
+        self.outline.as_deref().and_then(outline_center)
+    }
+}
+
+/// A switch or lever on the map, such as a power switch or an extract lever.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Switch {
+    /// Switch name (e.g., "Power switch"), as listed in [`Extract::switches`].
+    pub name: String,
+
+    /// Kind of switch as named by tarkov.dev (e.g., "Lever", "Switch").
+    #[serde(default)]
+    pub switch_type: Option<String>,
+
+    /// Position in game coordinates.
+    pub position: Position3,
+
+    /// Names of the extracts this switch opens.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extracts: Vec<String>,
+
+    /// Name of the switch that has to be used before this one works.
+    #[serde(default)]
+    pub activated_by: Option<String>,
+
+    /// What using the switch does to other switches and extracts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operations: Vec<SwitchOperation>,
+}
+
+/// An effect of using a [`Switch`].
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchOperation {
+    /// Operation as named by tarkov.dev (e.g., "Unlock", "Open").
+    #[serde(default)]
+    pub operation: Option<String>,
+
+    /// What the operation acts on.
+    pub target: SwitchTarget,
+}
+
+/// The switch or extract a [`SwitchOperation`] acts on, by name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SwitchTarget {
+    Switch(String),
+    Extract(String),
+}
+
+/// A hazardous area, such as a minefield or a zone covered by scav snipers.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hazard {
+    /// Hazard name (e.g., "Minefield").
+    pub name: String,
+
+    /// Kind of hazard.
+    pub hazard_type: HazardType,
+
+    /// Center of the hazard in game coordinates.
+    #[serde(default)]
+    pub position: Option<Position3>,
+
+    /// Outline of the hazardous area in game coordinates `[x, z]`.
     ///
-    /// Values: "pmc", "scav", or "shared".
-    pub faction: String,
+    /// When absent, the hazard is shown as a marker at `position`.
+    #[serde(default)]
+    pub outline: Option<Vec<Position2>>,
 
-    /// Position `[x, y, z]` in game coordinates.
+    /// Upper height bound of the area.
     #[serde(default)]
-    pub position: Option<[f64; 3]>,
+    pub top: Option<f64>,
+
+    /// Lower height bound of the area.
+    #[serde(default)]
+    pub bottom: Option<f64>,
+}
+
+/// A locked door or container, opened with a key.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lock {
+    /// What is locked.
+    pub lock_type: LockType,
+
+    /// Key that opens the lock, if known.
+    #[serde(default)]
+    pub key: Option<LockKey>,
+
+    /// Position in game coordinates.
+    pub position: Position3,
+
+    /// Whether the lock only opens once the power is on.
+    #[serde(default)]
+    pub needs_power: bool,
+}
+
+/// The key that opens a [`Lock`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockKey {
+    /// tarkov.dev item ID.
+    pub id: String,
+
+    /// Item display name (e.g., "Dorm room 314 marked key").
+    pub name: String,
+
+    /// Short item name (e.g., "314 marked").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+}
+
+impl LockKey {
+    /// Returns the short name, or the full name if there is none.
+    pub fn label(&self) -> &str {
+        self.short_name.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// A lootable container, such as a safe, jacket or weapon box.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LootContainer {
+    /// Container type as tarkov.dev's normalized name (e.g., "safe", "weapon-box-6x3").
+    pub container_type: String,
+
+    /// Display name (e.g., "Safe").
+    pub name: String,
+
+    /// Position in game coordinates.
+    pub position: Position3,
+
+    /// Whether this is a regular container or a hidden stash.
+    #[serde(default)]
+    pub category: ContainerCategory,
+}
+
+/// A boss that can spawn on a map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BossSpawn {
+    /// Boss display name (e.g., "Reshala").
+    pub name: String,
+
+    /// Chance of the boss spawning in a raid, from 0 to 1.
+    pub spawn_chance: f64,
+
+    /// Possible spawn positions in game coordinates.
+    pub positions: Vec<Position3>,
+
+    /// Named zones the boss spawns in, with the chance of each.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<BossSpawnLocation>,
+}
+
+/// A zone a boss can spawn in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BossSpawnLocation {
+    /// Zone display name (e.g., "Dorms").
+    pub name: String,
+
+    /// Chance of the boss picking this zone when it spawns, from 0 to 1.
+    pub chance: f64,
+}
+
+/// A mounted weapon, such as a heavy machine gun or an automatic grenade launcher.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StationaryWeapon {
+    /// Display name (e.g., "AGS-30 30x29mm automatic grenade launcher").
+    pub name: String,
+
+    /// Short display name (e.g., "AGS-30").
+    #[serde(default)]
+    pub short_name: Option<String>,
+
+    /// Position in game coordinates.
+    pub position: Position3,
+}
+
+/// A transit point that takes the player to another map mid-raid.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transit {
+    /// Normalized name of the destination map (e.g., "woods").
+    pub destination: String,
+
+    /// Description as shown in game (e.g., "Transit to Woods").
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Requirements for using the transit, such as a quest or a fee.
+    #[serde(default)]
+    pub conditions: Option<String>,
+
+    /// Position in game coordinates.
+    #[serde(default)]
+    pub position: Option<Position3>,
+
+    /// Outline of the activation zone in game coordinates `[x, z]`.
+    #[serde(default)]
+    pub outline: Option<Vec<Position2>>,
+
+    /// Upper height bound of the activation zone.
+    #[serde(default)]
+    pub top: Option<f64>,
+
+    /// Lower height bound of the activation zone.
+    #[serde(default)]
+    pub bottom: Option<f64>,
+}
+
+/// A task (quest) with objectives on a map.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    /// tarkov.dev task ID, the same ID the game's logs use for the quest.
+    pub id: String,
+
+    /// Display name (e.g., "Delivery from the Past").
+    pub name: String,
+
+    /// Name of the trader giving the task (e.g., "Prapor").
+    #[serde(default)]
+    pub trader: Option<String>,
+
+    /// Objectives with places on the map.
+    pub objectives: Vec<TaskObjective>,
+
+    /// IDs of the tasks to finish before this one, directly or through other
+    /// tasks, on any map.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prerequisites: Vec<String>,
+}
+
+impl Task {
+    /// Returns whether `other` has to be finished before this task.
+    pub fn requires(&self, other: &Task) -> bool {
+        self.prerequisites.contains(&other.id)
+    }
+}
+
+/// A task objective, with the places on the map it involves.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskObjective {
+    /// tarkov.dev objective ID.
+    #[serde(default)]
+    pub id: Option<String>,
+
+    /// Description as shown in game (e.g., "Stash the package in the Tarcone Director's office").
+    pub description: String,
+
+    /// Whether the objective is optional.
+    #[serde(default)]
+    pub optional: bool,
+
+    /// Zones to visit or mark, or possible positions of a quest item.
+    pub zones: Vec<TaskZone>,
+}
+
+/// A place on the map that a task objective involves.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskZone {
+    /// Position in game coordinates.
+    #[serde(default)]
+    pub position: Option<Position3>,
+
+    /// Outline of the zone in game coordinates `[x, z]`.
+    #[serde(default)]
+    pub outline: Option<Vec<Position2>>,
+
+    /// Upper height bound of the zone.
+    #[serde(default)]
+    pub top: Option<f64>,
+
+    /// Lower height bound of the zone.
+    #[serde(default)]
+    pub bottom: Option<f64>,
+}
+
+impl TaskZone {
+    /// Where the zone is marked: its [`position`](Self::position), or the
+    /// center of its [`outline`](Self::outline).
+    pub fn center(&self) -> Option<Position2> {
+        if let Some(position) = self.position {
+            return Some(position.xz());
+        }
+        self.outline.as_deref().and_then(outline_center)
+    }
+}
+
+/// A marker placed by the user, such as a stash, a camping spot or a route point.
+///
+/// Not part of `maps.ron`: the viewer, exporters and sharing tools store
+/// these separately, keyed to a map by its normalized name.
+///
+/// ```
+/// use tarkov_map::{UserMarker, UserMarkerIcon};
+///
+/// let marker = UserMarker::new("customs", [120.0, -45.5])
+///     .with_label("Stash")
+///     .with_icon(UserMarkerIcon::Loot)
+///     .with_color([255, 200, 0]);
+/// let text = ron::to_string(&marker).unwrap();
+/// assert_eq!(ron::from_str::<UserMarker>(&text).unwrap(), marker);
+///
+/// // Only the position and map are required
+/// let minimal: UserMarker = ron::from_str(r#"(map: "woods", position: (10.0, 20.0))"#).unwrap();
+/// assert_eq!(minimal.icon, UserMarkerIcon::Pin);
+/// ```
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMarker {
+    /// Normalized name of the map the marker is on (e.g., "customs").
+    pub map: String,
+
+    /// Position in game coordinates.
+    pub position: Position2,
+
+    /// Text shown next to the marker. May be empty.
+    #[serde(default)]
+    pub label: String,
+
+    /// Icon the marker is drawn with.
+    #[serde(default)]
+    pub icon: UserMarkerIcon,
+
+    /// Marker color as sRGB `[r, g, b]`, or `None` for the icon's default color.
+    #[serde(default)]
+    pub color: Option<[u8; 3]>,
+}
+
+impl UserMarker {
+    /// Creates an unlabeled pin on the map with normalized name `map`.
+    pub fn new(map: impl Into<String>, position: impl Into<Position2>) -> Self {
+        Self {
+            map: map.into(),
+            position: position.into(),
+            label: String::new(),
+            icon: UserMarkerIcon::default(),
+            color: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn with_icon(mut self, icon: UserMarkerIcon) -> Self {
+        self.icon = icon;
+        self
+    }
+
+    pub fn with_color(mut self, color: [u8; 3]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Whether the marker belongs on `map`.
+    pub fn is_on(&self, map: &Map) -> bool {
+        self.map == map.normalized_name
+    }
+}
+
+/// Defines an enum of known lowercase string values with an `Other` fallback.
+///
+/// Values are matched case-insensitively and serialized as strings. Unknown
+/// values are kept in `Other` so they survive a round trip.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident { $($(#[$variant_meta:meta])* $variant:ident => $value:literal),* $(,)? }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            Other(String),
+        }
+
+        impl $name {
+            /// Serialized name of the value.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Other(other) => other,
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                match value.to_lowercase().as_str() {
+                    $($value => Self::$variant,)*
+                    _ => Self::Other(value),
+                }
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                match value {
+                    $name::Other(other) => other,
+                    value => value.as_str().to_owned(),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+string_enum! {
+    /// Faction that can use an extraction point.
+    ///
+    /// Serialized as a lowercase string ("pmc", "scav", "shared").
+    pub enum Faction {
+        Pmc => "pmc",
+        Scav => "scav",
+        Shared => "shared",
+    }
+}
+
+string_enum! {
+    /// Side a spawn point is used by.
+    ///
+    /// Serialized as a lowercase string ("pmc", "scav", "all").
+    pub enum SpawnSide {
+        Pmc => "pmc",
+        Scav => "scav",
+        All => "all",
+    }
+}
+
+string_enum! {
+    /// Kind of character a spawn point is used for.
+    ///
+    /// Serialized as a lowercase string ("player", "bot", "boss", "sniper").
+    pub enum SpawnCategory {
+        Player => "player",
+        Bot => "bot",
+        Boss => "boss",
+        Sniper => "sniper",
+    }
+}
+
+string_enum! {
+    /// Kind of hazardous area.
+    ///
+    /// Serialized as a lowercase string ("minefield", "sniper").
+    pub enum HazardType {
+        Minefield => "minefield",
+        Sniper => "sniper",
+    }
+}
+
+string_enum! {
+    /// Kind of [`LootContainer`].
+    ///
+    /// Serialized as a lowercase string ("loot", "stash").
+    #[derive(Default)]
+    pub enum ContainerCategory {
+        /// Containers in plain sight, such as safes, jackets and weapon boxes.
+        #[default]
+        Loot => "loot",
+        /// Buried barrels and ground caches, hidden and off the usual paths.
+        Stash => "stash",
+    }
+}
+
+string_enum! {
+    /// Kind of lock.
+    ///
+    /// Serialized as a lowercase string ("door", "container", "trunk").
+    pub enum LockType {
+        Door => "door",
+        Container => "container",
+        Trunk => "trunk",
+    }
+}
+
+string_enum! {
+    /// Icon of a [`UserMarker`].
+    ///
+    /// Serialized as a lowercase string ("pin", "loot", "key", "danger",
+    /// "quest", "extract").
+    #[derive(Default)]
+    pub enum UserMarkerIcon {
+        #[default]
+        Pin => "pin",
+        Loot => "loot",
+        Key => "key",
+        Danger => "danger",
+        Quest => "quest",
+        Extract => "extract",
+    }
+}
+
+/// Translated text by tarkov.dev language code (e.g., "de", "ru").
+pub type Translations = BTreeMap<String, String>;
+
+/// Returns `text` in `language`, falling back to `text` itself (English).
+fn localized<'a>(
+    text: &'a str,
+    translations: Option<&'a Translations>,
+    language: Option<&str>,
+) -> &'a str {
+    language
+        .and_then(|language| translations?.get(language))
+        .map_or(text, String::as_str)
+}
+
+/// Mean of the outline's vertices, or `None` for an empty outline.
+fn outline_center(outline: &[Position2]) -> Option<Position2> {
+    if outline.is_empty() {
+        return None;
+    }
+    let count = outline.len() as f64;
+    let (x, z) = outline
+        .iter()
+        .fold((0.0, 0.0), |(x, z), point| (x + point.x, z + point.z));
+    Some(Position2::new(x / count, z / count))
+}
+
+/// An item requirement of an extraction point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractItem {
+    /// Item display name (e.g., "Roubles").
+    pub name: String,
+
+    /// Number of items required.
+    pub count: u32,
 }
 
 /// Collection of all Tarkov maps.
 pub type TarkovMaps = Vec<Map>;
+
+/// JSON conversion of a map list, for tooling that doesn't read RON.
+///
+/// The JSON mirrors `maps.ron`: field names are camelCase and positions are
+/// plain arrays.
+///
+/// ```
+/// use tarkov_map::{MapsJson, TarkovMaps};
+///
+/// let maps = TarkovMaps::from_json_str(r#"[{"normalizedName": "customs", "name": "Customs"}]"#)
+///     .unwrap();
+/// assert_eq!(maps[0].name, "Customs");
+///
+/// let json = maps.to_json_string().unwrap();
+/// assert_eq!(TarkovMaps::from_json_str(&json).unwrap()[0].normalized_name, "customs");
+/// ```
+pub trait MapsJson: Sized {
+    /// Parses a JSON array of maps.
+    fn from_json_str(json: &str) -> Result<Self, serde_json::Error>;
+
+    /// Writes the maps as a pretty-printed JSON array.
+    fn to_json_string(&self) -> Result<String, serde_json::Error>;
+}
+
+impl MapsJson for TarkovMaps {
+    fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Schema version of the `maps.ron` files written by `fetch_maps`.
+///
+/// Bump when a data model change can't read files written before it, and
+/// handle the older version in the viewer's loader. Files from before
+/// versioning hold a bare list of maps and count as version 0.
+pub const MAPS_SCHEMA_VERSION: u32 = 1;
+
+/// Top-level contents of a `maps.ron` file.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapsFile {
+    /// [`MAPS_SCHEMA_VERSION`] at the time the file was written.
+    pub schema_version: u32,
+
+    /// When `fetch_maps` wrote the file.
+    #[serde(default)]
+    pub fetched_at: Option<DateTime<Utc>>,
+
+    /// tarkov-dev commit the map layout (`maps.json`) was read from.
+    #[serde(default)]
+    pub source_commit: Option<String>,
+
+    pub maps: TarkovMaps,
+}
+
+/// File name of the [`DataManifest`] that `fetch_maps` writes next to `maps.ron`.
+pub const DATA_MANIFEST_FILE: &str = "manifest.ron";
+
+/// Version and checksums of the files in a map data set.
+///
+/// Lets a viewer show which data it ships and an updater download only the
+/// files that changed.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataManifest {
+    /// Fetch date, followed by the abbreviated tarkov-dev commit if known
+    /// (e.g. `2026-01-31+1a2b3c4`).
+    pub version: String,
+
+    /// When `fetch_maps` wrote the data.
+    #[serde(default)]
+    pub fetched_at: Option<DateTime<Utc>>,
+
+    /// tarkov-dev commit the map layout was read from.
+    #[serde(default)]
+    pub source_commit: Option<String>,
+
+    /// `maps.ron` and the images it refers to, sorted by path.
+    pub files: Vec<ManifestFile>,
+}
+
+/// A file listed in a [`DataManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestFile {
+    /// Path relative to the directory of the manifest, with `/` separators.
+    pub path: String,
+
+    /// Size in bytes.
+    pub size: u64,
+
+    /// Lowercase hex SHA-256 digest of the contents.
+    pub sha256: String,
+}
+
+impl DataManifest {
+    /// Returns the files that are new or differ from those in `installed`.
+    ///
+    /// ```
+    /// # use tarkov_map::{DataManifest, ManifestFile};
+    /// let file = |path: &str, sha256: &str| ManifestFile {
+    ///     path: path.into(),
+    ///     size: 1,
+    ///     sha256: sha256.into(),
+    /// };
+    /// let manifest = |files| DataManifest {
+    ///     version: "2026-01-31".into(),
+    ///     fetched_at: None,
+    ///     source_commit: None,
+    ///     files,
+    /// };
+    /// let installed = manifest(vec![file("maps.ron", "aa"), file("maps/woods.png", "bb")]);
+    /// let latest = manifest(vec![
+    ///     file("maps.ron", "cc"),
+    ///     file("maps/woods.png", "bb"),
+    ///     file("maps/customs.png", "dd"),
+    /// ]);
+    ///
+    /// let changed: Vec<&str> = latest
+    ///     .changed_files(&installed)
+    ///     .map(|file| file.path.as_str())
+    ///     .collect();
+    /// assert_eq!(changed, ["maps.ron", "maps/customs.png"]);
+    /// ```
+    pub fn changed_files<'a>(
+        &'a self,
+        installed: &'a DataManifest,
+    ) -> impl Iterator<Item = &'a ManifestFile> {
+        self.files.iter().filter(|file| {
+            !installed
+                .files
+                .iter()
+                .any(|other| other.path == file.path && other.sha256 == file.sha256)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(ron: &str) -> Map {
+        ron::from_str(ron).unwrap()
+    }
+
+    /// A map from the bundled `maps.ron`.
+    fn bundled_map(normalized_name: &str) -> Map {
+        let file: MapsFile = ron::from_str(include_str!("../assets/maps.ron")).unwrap();
+        file.maps
+            .into_iter()
+            .find(|map| map.normalized_name == normalized_name)
+            .unwrap()
+    }
+
+    #[track_caller]
+    fn assert_projects(map: &Map, game: [f64; 2], expected: [f64; 2]) {
+        let [x, y] = map.project(game).unwrap();
+        assert!(
+            (x - expected[0]).abs() < 1e-3 && (y - expected[1]).abs() < 1e-3,
+            "{} projects {game:?} to [{x:.4}, {y:.4}], expected {expected:?}",
+            map.normalized_name
+        );
+        let back = map.unproject([x, y]).unwrap();
+        assert!((back.x - game[0]).abs() < 1e-6 && (back.z - game[1]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn customs_is_rotated_180_degrees() {
+        let customs = bundled_map("customs");
+        // Bounds corners land on the image corners, west on the left
+        assert_projects(&customs, [698.0, -307.0], [0.0, 0.0]);
+        assert_projects(&customs, [-372.0, 237.0], [1.0, 1.0]);
+        // ZB-1011 on the western edge, Crossroads on the eastern one
+        assert_projects(&customs, [621.496, -128.605], [0.0715, 0.3279]);
+        assert_projects(&customs, [-334.804, -87.979], [0.9652, 0.4026]);
+    }
+
+    #[test]
+    fn labs_projects_through_transform() {
+        let labs = bundled_map("the-lab");
+        // Main Elevator and Cargo Elevator
+        assert_projects(&labs, [-282.304, -334.896], [0.5065, 0.1793]);
+        assert_projects(&labs, [-112.152, -408.64], [0.2644, 0.7383]);
+        // The bounds don't reach the image edges, the transform adds margins
+        assert_projects(&labs, [-80.0, -477.0], [0.0396, 0.844]);
+    }
+
+    #[test]
+    fn factory_is_rotated_90_degrees() {
+        let factory = bundled_map("factory");
+        // Gate 3 near the top-left corner, Cellars at the top right
+        assert_projects(&factory, [58.709, 60.868], [0.0495, 0.1395]);
+        assert_projects(&factory, [73.894, -29.082], [0.7315, 0.0351]);
+    }
+
+    #[test]
+    fn default_view_of_map_fitted_to_bounds_is_fit() {
+        // The transform of tile maps like Customs is in Leaflet units, not
+        // image pixels, and must not be used: the image spans the bounds
+        assert!(bundled_map("customs").compute_default_view().is_none());
+    }
+
+    #[test]
+    fn default_view_frames_labs() {
+        // Labs' bounds span 93% of its image's width but only 68% of its height
+        let view = bundled_map("the-lab").compute_default_view().unwrap();
+        assert!((view.center[0] - 0.506).abs() < 1e-3);
+        assert!((view.center[1] - 0.504).abs() < 1e-3);
+        assert!((view.zoom - 1.072).abs() < 1e-3);
+    }
+
+    #[test]
+    fn default_view_frames_small_playable_area() {
+        // The Labs drawing on a canvas twice its size
+        let mut labs = bundled_map("the-lab");
+        labs.image_size = [350.0, 350.0];
+        let view = labs.compute_default_view().unwrap();
+        assert!((view.center[0] - 0.253).abs() < 1e-3);
+        assert!((view.center[1] - 0.252).abs() < 1e-3);
+        assert!((view.zoom - 2.14).abs() < 1e-2);
+    }
+
+    #[test]
+    fn task_chain_orders_by_dependency_not_prerequisite_count() {
+        // "Second" has one prerequisite but comes after "First", which has
+        // two, one of them on another map
+        let map = map(r#"(
+            normalizedName: "test",
+            tasks: Some([
+                (id: "last", name: "Last", objectives: [], prerequisites: ["second", "first", "other"]),
+                (id: "second", name: "Second", objectives: [], prerequisites: ["first"]),
+                (id: "first", name: "First", objectives: [], prerequisites: ["elsewhere", "other"]),
+                (id: "other", name: "Other", objectives: []),
+            ]),
+        )"#);
+        let names: Vec<_> = map
+            .task_chain("last")
+            .iter()
+            .map(|task| task.name.as_str())
+            .collect();
+        assert_eq!(names, ["Other", "First", "Second", "Last"]);
+    }
+
+    #[test]
+    fn bundled_maps_validate() {
+        let text = include_str!("../assets/maps.ron");
+        let file: MapsFile = ron::from_str(text).unwrap();
+        for map in &file.maps {
+            let warnings = map.validate();
+            assert!(warnings.is_empty(), "{}: {warnings:?}", map.normalized_name);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Merging of map datasets, e.g. a community marker pack on top of the official data.
+
+use crate::{
+    BossSpawn, Extract, Hazard, Label, Lock, LootContainer, Map, StationaryWeapon, Switch,
+    TarkovMaps, Task, Transit,
+};
+
+/// Data in the base dataset that was replaced by the merged dataset.
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    /// Normalized name of the map.
+    pub map: String,
+
+    /// Description of the replaced data (e.g., "extract 'ZB-1011'", "bounds").
+    pub feature: String,
+}
+
+/// Merges `overlay` into `base`.
+///
+/// Maps only present in `overlay` are added. Maps present in both are merged
+/// with [`merge_map`]. Returns the data in `base` that `overlay` replaced.
+pub fn merge(base: &mut TarkovMaps, overlay: TarkovMaps) -> Vec<MergeConflict> {
+    let mut conflicts = Vec::new();
+    for map in overlay {
+        match base
+            .iter_mut()
+            .find(|m| m.normalized_name == map.normalized_name)
+        {
+            Some(existing) => conflicts.extend(merge_map(existing, map)),
+            None => base.push(map),
+        }
+    }
+    conflicts
+}
+
+/// Merges `overlay` into `base`, two versions of the same map.
+///
+/// - Non-empty names and image paths, and present optional fields, replace
+///   those in `base`. Replacing an existing value is a conflict. Replacing the
+///   image also drops the tile pyramid of `base`.
+/// - Translations of the map name are added to those in `base`.
+/// - Labels, spawns, extracts, hazards, locks, loot containers, bosses,
+///   stationary weapons, switches, transits, tasks and layers are appended.
+///   Labels with the same text, extracts with the same name and faction,
+///   hazards with the same name and type, locks, loot containers and
+///   stationary weapons at the same position, transits to the same map at the
+///   same position, tasks with the same ID, and bosses, switches and layers
+///   with the same name replace those in `base`, which is a conflict.
+/// - Spawn clusters are taken from `overlay` when present.
+pub fn merge_map(base: &mut Map, overlay: Map) -> Vec<MergeConflict> {
+    let map = base.normalized_name.clone();
+    let mut conflicts = Vec::new();
+    let mut conflict = |feature: String| {
+        conflicts.push(MergeConflict {
+            map: map.clone(),
+            feature,
+        });
+    };
+
+    if !overlay.name.is_empty() {
+        base.name = overlay.name;
+    }
+    if let Some(translations) = overlay.translations {
+        base.translations
+            .get_or_insert_default()
+            .extend(translations);
+    }
+    if !overlay.image_path.is_empty() {
+        if base.image_path != overlay.image_path {
+            conflict("image".to_owned());
+        }
+        base.image_path = overlay.image_path;
+        base.image_size = overlay.image_size;
+        base.logical_size = overlay.logical_size;
+        // The tile pyramid belongs to the replaced image
+        base.tiles = None;
+    }
+
+    macro_rules! replace_option {
+        ($field:ident, $name:literal) => {
+            if let Some(value) = overlay.$field {
+                if base.$field.is_some() {
+                    conflict($name.to_owned());
+                }
+                base.$field = Some(value);
+            }
+        };
+    }
+    replace_option!(tiles, "tile pyramid");
+    replace_option!(alt_maps, "alternative maps");
+    replace_option!(author, "author");
+    replace_option!(author_link, "author link");
+    replace_option!(transform, "transform");
+    replace_option!(coordinate_rotation, "coordinate rotation");
+    replace_option!(bounds, "bounds");
+    replace_option!(default_view, "default view");
+    replace_option!(height_range, "height range");
+    replace_option!(spawn_clusters, "spawn clusters");
+
+    merge_list(
+        &mut base.labels,
+        overlay.labels,
+        |a: &Label, b: &Label| a.text == b.text,
+        |label| format!("label '{}'", label.text.replace('\n', " ")),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.extracts,
+        overlay.extracts,
+        |a: &Extract, b: &Extract| a.name == b.name && a.faction == b.faction,
+        |extract| format!("extract '{}'", extract.name),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.hazards,
+        overlay.hazards,
+        |a: &Hazard, b: &Hazard| a.name == b.name && a.hazard_type == b.hazard_type,
+        |hazard| format!("hazard '{}'", hazard.name),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.locks,
+        overlay.locks,
+        |a: &Lock, b: &Lock| a.position == b.position,
+        |lock| match &lock.key {
+            Some(key) => format!("lock for '{}'", key.name),
+            None => format!("{} lock", lock.lock_type),
+        },
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.loot_containers,
+        overlay.loot_containers,
+        |a: &LootContainer, b: &LootContainer| a.position == b.position,
+        |container| format!("{} container", container.name),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.bosses,
+        overlay.bosses,
+        |a: &BossSpawn, b: &BossSpawn| a.name == b.name,
+        |boss| format!("boss '{}'", boss.name),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.stationary_weapons,
+        overlay.stationary_weapons,
+        |a: &StationaryWeapon, b: &StationaryWeapon| a.position == b.position,
+        |weapon| format!("stationary weapon '{}'", weapon.name),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.switches,
+        overlay.switches,
+        |a: &Switch, b: &Switch| a.name == b.name,
+        |switch| format!("switch '{}'", switch.name),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.transits,
+        overlay.transits,
+        |a: &Transit, b: &Transit| a.destination == b.destination && a.position == b.position,
+        |transit| format!("transit to '{}'", transit.destination),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.tasks,
+        overlay.tasks,
+        |a: &Task, b: &Task| a.id == b.id,
+        |task| format!("task '{}'", task.name),
+        &mut conflict,
+    );
+    merge_list(
+        &mut base.layers,
+        overlay.layers,
+        |a, b| a.name == b.name,
+        |layer| format!("layer '{}'", layer.name),
+        &mut conflict,
+    );
+    if let Some(spawns) = overlay.spawns {
+        base.spawns.get_or_insert_default().extend(spawns);
+    }
+
+    conflicts
+}
+
+/// Appends `overlay` items to `base`, replacing items that are the `same`.
+fn merge_list<T>(
+    base: &mut Option<Vec<T>>,
+    overlay: Option<Vec<T>>,
+    same: impl Fn(&T, &T) -> bool,
+    describe: impl Fn(&T) -> String,
+    conflict: &mut impl FnMut(String),
+) {
+    let Some(overlay) = overlay else {
+        return;
+    };
+    let base = base.get_or_insert_default();
+    for item in overlay {
+        match base.iter_mut().find(|existing| same(existing, &item)) {
+            Some(existing) => {
+                conflict(describe(existing));
+                *existing = item;
+            }
+            None => base.push(item),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Positions in game coordinates.
+//!
+//! Both types serialize as plain arrays (`[x, z]` and `[x, y, z]`), the format
+//! used by `maps.ron` and tarkov-dev.
+
+use serde::{Deserialize, Serialize};
+
+/// A position `[x, z]` on the ground plane, in game coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "[f64; 2]", into = "[f64; 2]")]
+pub struct Position2 {
+    pub x: f64,
+    pub z: f64,
+}
+
+impl Position2 {
+    pub const fn new(x: f64, z: f64) -> Self {
+        Self { x, z }
+    }
+
+    /// Distance to `other` in meters.
+    ///
+    /// ```
+    /// # use tarkov_map::Position2;
+    /// assert_eq!(Position2::new(0.0, 0.0).distance(Position2::new(3.0, 4.0)), 5.0);
+    /// ```
+    pub fn distance(self, other: Self) -> f64 {
+        (self.x - other.x).hypot(self.z - other.z)
+    }
+
+    /// Interpolates linearly towards `other`, where `t = 0` is `self` and `t = 1` is `other`.
+    ///
+    /// ```
+    /// # use tarkov_map::Position2;
+    /// let mid = Position2::new(0.0, 10.0).lerp(Position2::new(4.0, 20.0), 0.5);
+    /// assert_eq!(mid, Position2::new(2.0, 15.0));
+    /// ```
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        Self {
+            x: self.x + (other.x - self.x) * t,
+            z: self.z + (other.z - self.z) * t,
+        }
+    }
+}
+
+impl From<[f64; 2]> for Position2 {
+    fn from([x, z]: [f64; 2]) -> Self {
+        Self { x, z }
+    }
+}
+
+impl From<Position2> for [f64; 2] {
+    fn from(position: Position2) -> Self {
+        [position.x, position.z]
+    }
+}
+
+/// A position `[x, y, z]` in game coordinates, where `y` is height.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "[f64; 3]", into = "[f64; 3]")]
+pub struct Position3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Position3 {
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Projects the position onto the ground plane, dropping the height.
+    ///
+    /// ```
+    /// # use tarkov_map::{Position2, Position3};
+    /// assert_eq!(Position3::new(1.0, 2.0, 3.0).xz(), Position2::new(1.0, 3.0));
+    /// ```
+    pub fn xz(self) -> Position2 {
+        Position2::new(self.x, self.z)
+    }
+
+    /// Straight-line distance to `other` in meters, including height.
+    ///
+    /// ```
+    /// # use tarkov_map::Position3;
+    /// let a = Position3::new(0.0, 0.0, 0.0);
+    /// assert_eq!(a.distance(Position3::new(2.0, 3.0, 6.0)), 7.0);
+    /// ```
+    pub fn distance(self, other: Self) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2))
+            .sqrt()
+    }
+
+    /// Interpolates linearly towards `other`, where `t = 0` is `self` and `t = 1` is `other`.
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        Self {
+            x: self.x + (other.x - self.x) * t,
+            y: self.y + (other.y - self.y) * t,
+            z: self.z + (other.z - self.z) * t,
+        }
+    }
+}
+
+impl From<[f64; 3]> for Position3 {
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl From<Position3> for [f64; 3] {
+    fn from(position: Position3) -> Self {
+        [position.x, position.y, position.z]
+    }
+}
+
+/// An axis-aligned rectangle on the ground plane, in game coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameBounds {
+    pub min: Position2,
+    pub max: Position2,
+}
+
+impl GameBounds {
+    /// Bounds spanning two opposite corners, in any order.
+    ///
+    /// ```
+    /// # use tarkov_map::{GameBounds, Position2};
+    /// let bounds = GameBounds::from_corners(Position2::new(10.0, -5.0), Position2::new(-10.0, 5.0));
+    /// assert_eq!(bounds.min, Position2::new(-10.0, -5.0));
+    /// assert_eq!(bounds.max, Position2::new(10.0, 5.0));
+    /// ```
+    pub fn from_corners(a: Position2, b: Position2) -> Self {
+        Self {
+            min: Position2::new(a.x.min(b.x), a.z.min(b.z)),
+            max: Position2::new(a.x.max(b.x), a.z.max(b.z)),
+        }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.z - self.min.z
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+
+    /// Returns `true` if `position` lies inside the bounds or on their edge.
+    ///
+    /// ```
+    /// # use tarkov_map::{GameBounds, Position2};
+    /// let bounds = GameBounds::from_corners(Position2::new(0.0, 0.0), Position2::new(100.0, 50.0));
+    /// assert!(bounds.contains(Position2::new(100.0, 25.0)));
+    /// assert!(!bounds.contains(Position2::new(25.0, 60.0)));
+    /// ```
+    pub fn contains(&self, position: impl Into<Position2>) -> bool {
+        let position = position.into();
+        (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.z..=self.max.z).contains(&position.z)
+    }
+
+    /// Grows the bounds by `margin` meters on every side.
+    pub fn expand(self, margin: f64) -> Self {
+        Self {
+            min: Position2::new(self.min.x - margin, self.min.z - margin),
+            max: Position2::new(self.max.x + margin, self.max.z + margin),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Player rotation math for position feeds.
+//!
+//! Tarkov reports rotations as Unity quaternions, e.g. in the `_QX, QY, QZ, QW_`
+//! part of position screenshot names. These helpers turn them into a facing
+//! direction on the map, so any position feeder draws the same arrow as the
+//! viewer.
+
+/// Converts a rotation quaternion, in the component order Tarkov writes it,
+/// to a yaw angle in radians.
+///
+/// Follows the TarkovMonitor implementation, which passes the components as
+/// `(x, z, y, w)`: compared to the textbook yaw formula, `y` and `z` are
+/// swapped. A yaw of 0 faces north (+z) and angles increase clockwise.
+///
+/// ```
+/// use tarkov_map::rotation::yaw_from_quaternion;
+///
+/// // A real screenshot, "2026-01-07[19-56]_-198.89, 22.74, -345.97_0.32263,
+/// // 0.47266, -0.18602, 0.79869_15.61 (0).png", faces north-east
+/// let yaw = yaw_from_quaternion(0.32263, 0.47266, -0.18602, 0.79869);
+/// assert!((yaw.to_degrees() - 52.69).abs() < 0.01);
+///
+/// // Level rotations about the vertical axis only: a quarter turn faces east,
+/// // an eighth turn the other way north-west
+/// let yaw = yaw_from_quaternion(0.0, 0.70711, 0.0, 0.70711);
+/// assert!((yaw.to_degrees() - 90.0).abs() < 0.01);
+/// let yaw = yaw_from_quaternion(0.0, -0.38268, 0.0, 0.92388);
+/// assert!((yaw.to_degrees() + 45.0).abs() < 0.01);
+/// ```
+pub fn yaw_from_quaternion(x: f32, y: f32, z: f32, w: f32) -> f32 {
+    // TarkovMonitor's siny_cosp = 2 * (w * z + x * y), with their z being our y
+    let siny_cosp = 2.0 * (w * y + x * z);
+    let cosy_cosp = 1.0 - 2.0 * (z * z + y * y);
+    f32::atan2(siny_cosp, cosy_cosp)
+}
+
+/// Adjusts a game yaw (radians) for a map's `coordinate_rotation` (degrees).
+///
+/// The result is the facing direction on the map image, in radians clockwise
+/// from straight up.
+///
+/// ```
+/// use tarkov_map::rotation::map_yaw;
+///
+/// // Facing east on a map drawn rotated by 90°, so east is up on the image
+/// let yaw = map_yaw(90f32.to_radians(), 90.0);
+/// assert!(yaw.abs() < 1e-6);
+/// ```
+pub fn map_yaw(yaw: f32, coordinate_rotation: f64) -> f32 {
+    yaw - (coordinate_rotation as f32).to_radians()
+}
@@ -0,0 +1,161 @@
+//! Integrity checks for map data, run before shipping or displaying a dataset.
+
+use crate::{Map, Position2};
+use std::fmt;
+
+/// Markers outside the bounds by up to this fraction of the bounds' size are
+/// still accepted. Some spawns and extracts lie just past the drawn area.
+const BOUNDS_TOLERANCE: f64 = 0.1;
+
+/// A problem found in a map by [`Map::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationWarning {
+    /// The logical size has a zero or negative dimension.
+    InvalidLogicalSize([f32; 2]),
+
+    /// The map is rotated 270° but has no transform to project markers with.
+    MissingTransform,
+
+    /// A label lies outside the map bounds.
+    LabelOutOfBounds { text: String, position: Position2 },
+
+    /// A spawn lies outside the map bounds.
+    SpawnOutOfBounds { position: Position2 },
+
+    /// An extract lies outside the map bounds.
+    ExtractOutOfBounds { name: String, position: Position2 },
+
+    /// A layer extent's height range is inverted (`min > max`).
+    InvertedExtentHeight { layer: String, height: [f64; 2] },
+
+    /// A layer extent bound encloses no area.
+    EmptyExtentBound { layer: String, bound: String },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLogicalSize([w, h]) => write!(f, "invalid logical size {w} × {h}"),
+            Self::MissingTransform => write!(f, "rotated 270° without a transform"),
+            Self::LabelOutOfBounds { text, position } => write!(
+                f,
+                "label '{}' out of bounds at [{:.1}, {:.1}]",
+                text.replace('\n', " "),
+                position.x,
+                position.z
+            ),
+            Self::SpawnOutOfBounds { position } => write!(
+                f,
+                "spawn out of bounds at [{:.1}, {:.1}]",
+                position.x, position.z
+            ),
+            Self::ExtractOutOfBounds { name, position } => write!(
+                f,
+                "extract '{name}' out of bounds at [{:.1}, {:.1}]",
+                position.x, position.z
+            ),
+            Self::InvertedExtentHeight { layer, height } => write!(
+                f,
+                "layer '{layer}' has inverted height range [{}, {}]",
+                height[0], height[1]
+            ),
+            Self::EmptyExtentBound { layer, bound } => {
+                write!(f, "layer '{layer}' bound '{bound}' encloses no area")
+            }
+        }
+    }
+}
+
+/// Returns `true` if `position` lies within `bounds`, allowing [`BOUNDS_TOLERANCE`].
+fn within_bounds(bounds: [[f64; 2]; 2], position: Position2) -> bool {
+    let [[x1, z1], [x2, z2]] = bounds;
+    let within = |value: f64, a: f64, b: f64| {
+        let tolerance = (a - b).abs() * BOUNDS_TOLERANCE;
+        value >= a.min(b) - tolerance && value <= a.max(b) + tolerance
+    };
+    within(position.x, x1, x2) && within(position.z, z1, z2)
+}
+
+impl Map {
+    /// Checks the map for data that would display incorrectly.
+    ///
+    /// Returns an empty list if the map is well-formed. Bounds checks are
+    /// skipped for maps without bounds.
+    ///
+    /// ```
+    /// # use tarkov_map::ValidationWarning;
+    /// # let map: tarkov_map::Map = ron::from_str(r#"(
+    /// #     normalizedName: "test", name: "Test", imagePath: "test.png",
+    /// #     imageSize: (100.0, 100.0), logicalSize: (100.0, 0.0),
+    /// #     coordinateRotation: Some(270.0),
+    /// # )"#).unwrap();
+    /// assert_eq!(
+    ///     map.validate(),
+    ///     [
+    ///         ValidationWarning::InvalidLogicalSize([100.0, 0.0]),
+    ///         ValidationWarning::MissingTransform,
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+
+        if self.logical_size.iter().any(|size| *size <= 0.0) {
+            warnings.push(ValidationWarning::InvalidLogicalSize(self.logical_size));
+        }
+        if self.coordinate_rotation == Some(270.0) && self.transform.is_none() {
+            warnings.push(ValidationWarning::MissingTransform);
+        }
+
+        if let Some(bounds) = self.bounds {
+            let outside = |position: Position2| !within_bounds(bounds, position);
+
+            for label in self.labels.iter().flatten() {
+                if outside(label.position) {
+                    warnings.push(ValidationWarning::LabelOutOfBounds {
+                        text: label.text.clone(),
+                        position: label.position,
+                    });
+                }
+            }
+            for spawn in self.spawns.iter().flatten() {
+                if outside(spawn.position.xz()) {
+                    warnings.push(ValidationWarning::SpawnOutOfBounds {
+                        position: spawn.position.xz(),
+                    });
+                }
+            }
+            for extract in self.extracts.iter().flatten() {
+                if let Some(position) = extract.center()
+                    && outside(position)
+                {
+                    warnings.push(ValidationWarning::ExtractOutOfBounds {
+                        name: extract.name.clone(),
+                        position,
+                    });
+                }
+            }
+        }
+
+        for layer in self.layers.iter().flatten() {
+            for extent in &layer.extents {
+                if extent.height[0] > extent.height[1] {
+                    warnings.push(ValidationWarning::InvertedExtentHeight {
+                        layer: layer.name.clone(),
+                        height: extent.height,
+                    });
+                }
+                for bound in extent.bounds.iter().flatten() {
+                    if bound.point1[0] == bound.point2[0] || bound.point1[1] == bound.point2[1] {
+                        warnings.push(ValidationWarning::EmptyExtentBound {
+                            layer: layer.name.clone(),
+                            bound: bound.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        warnings
+    }
+}